@@ -5,7 +5,35 @@ pub use documented_item::*;
 pub use item_type::*;
 pub use module::*;
 
+use crate::{html, DocumentationError};
+use std::path::Path;
+
 /// Represents a compiled project's entire documentation.
 pub struct Documentation {
-    modules: Vec<Module>,
+    pub(crate) modules: Vec<Module>,
+}
+
+impl Documentation {
+    /// The documented modules of every package, sorted by name, so that each module directly
+    /// follows its parent.
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// Renders the documentation as static HTML pages in `out_dir`: an `index.html` listing the
+    /// packages, and an `index.html` for each module in a directory named after its path, such as
+    /// `std/chain/index.html`. The pages only link to each other and to the scripts and the style
+    /// sheet written along with them, so they can be read offline.
+    pub fn write_html(&self, out_dir: &Path) -> Result<(), DocumentationError> {
+        html::write(self, out_dir)
+    }
+}
+
+/// The first paragraph of `documentation`, which summarizes it.
+fn first_paragraph(documentation: &str) -> &str {
+    let documentation = documentation.trim_start();
+    match documentation.find("\n\n") {
+        Some(end) => &documentation[..end],
+        None => documentation,
+    }
 }
@@ -1,7 +1,55 @@
-use super::ItemType;
+use super::{first_paragraph, ItemType};
 
 /// Represents an item that has been documented.
 pub struct DocumentedItem {
-    item_type: ItemType,
-    documentation: String,
+    pub(crate) item_type: ItemType,
+    pub(crate) name: String,
+    /// The declaration of the item on a single line, such as `pub fn add(a: u64, b: u64) -> u64`.
+    pub(crate) signature: String,
+    /// The doc comments written above the item, without their leading `///`.
+    pub(crate) documentation: String,
+    /// The fields of a struct, the variants of an enum, or the methods of a type, trait or ABI.
+    pub(crate) members: Vec<DocumentedItem>,
+}
+
+impl DocumentedItem {
+    /// The type of the item.
+    pub fn item_type(&self) -> ItemType {
+        self.item_type
+    }
+
+    /// The name of the item.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The declaration of the item on a single line, with its types resolved.
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// The doc comments written above the item, without their leading `///`.
+    pub fn documentation(&self) -> &str {
+        &self.documentation
+    }
+
+    /// The items documented within this one.
+    pub fn members(&self) -> &[DocumentedItem] {
+        &self.members
+    }
+
+    /// The first paragraph of the documentation of the item.
+    pub fn summary(&self) -> &str {
+        first_paragraph(&self.documentation)
+    }
+
+    /// The anchor of the item within the page of its module, such as `struct.Point`, or
+    /// `struct.Point.field.x` for a member.
+    pub(crate) fn anchor(&self, parent: Option<&DocumentedItem>) -> String {
+        let anchor = format!("{}.{}", self.item_type.as_str(), self.name);
+        match parent {
+            Some(parent) => format!("{}.{}", parent.anchor(None), anchor),
+            None => anchor,
+        }
+    }
 }
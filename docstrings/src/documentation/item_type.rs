@@ -1,4 +1,5 @@
 /// The type of the item being documented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ItemType {
     /// A Sway function, starting with `fn`, that is _not_ a method, abi method, or interface surface item.
     Function,
@@ -6,4 +7,50 @@ pub enum ItemType {
     Method,
     /// A Sway struct, denoted with `struct`.
     Struct,
+    /// A field of a struct.
+    Field,
+    /// A Sway enum, denoted with `enum`.
+    Enum,
+    /// A variant of an enum.
+    Variant,
+    /// A Sway trait, denoted with `trait`.
+    Trait,
+    /// A Sway ABI, denoted with `abi`.
+    Abi,
+    /// A Sway constant, denoted with `const`.
+    Constant,
+    /// A Sway type alias, denoted with `type`.
+    TypeAlias,
+}
+
+impl ItemType {
+    /// The name of this type of item in the anchors of the rendered documentation, such as
+    /// `struct` in `#struct.Point`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemType::Function => "fn",
+            ItemType::Method => "method",
+            ItemType::Struct => "struct",
+            ItemType::Field => "field",
+            ItemType::Enum => "enum",
+            ItemType::Variant => "variant",
+            ItemType::Trait => "trait",
+            ItemType::Abi => "abi",
+            ItemType::Constant => "const",
+            ItemType::TypeAlias => "type",
+        }
+    }
+
+    /// Whether a type written in a signature can name an item of this type, so that it links to
+    /// it.
+    pub fn is_type(&self) -> bool {
+        matches!(
+            self,
+            ItemType::Struct
+                | ItemType::Enum
+                | ItemType::Trait
+                | ItemType::Abi
+                | ItemType::TypeAlias
+        )
+    }
 }
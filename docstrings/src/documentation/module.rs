@@ -1,4 +1,4 @@
-use super::DocumentedItem;
+use super::{first_paragraph, DocumentedItem};
 /// A sway module is a name, where the strings represent the name of the module
 /// and parent modules.
 /// e.g. if module `root` contains a submodule `foo`, this would be
@@ -9,7 +9,38 @@ pub struct Module {
     /// The name of a module.
     /// e.g. if module `root` contains a submodule `foo`, this would be
     /// vec!["root", "foo"]
-    name: Vec<String>,
+    /// The root module of a package is named after the package.
+    pub(crate) name: Vec<String>,
+    /// The `//!` doc comments of the module's file, without their leading `//!`.
+    pub(crate) documentation: String,
     /// The documented items contained in this module.
-    documented_items: Vec<DocumentedItem>,
+    pub(crate) documented_items: Vec<DocumentedItem>,
+}
+
+impl Module {
+    /// The name of the module along with the names of its parent modules, starting with the
+    /// package it is in.
+    pub fn name(&self) -> &[String] {
+        &self.name
+    }
+
+    /// The `//!` doc comments of the module's file.
+    pub fn documentation(&self) -> &str {
+        &self.documentation
+    }
+
+    /// The first paragraph of the documentation of the module.
+    pub fn summary(&self) -> &str {
+        first_paragraph(&self.documentation)
+    }
+
+    /// The documented items contained in this module.
+    pub fn documented_items(&self) -> &[DocumentedItem] {
+        &self.documented_items
+    }
+
+    /// The path of the module, such as `std::chain`.
+    pub fn path(&self) -> String {
+        self.name.join("::")
+    }
 }
@@ -1,20 +1,122 @@
-mod source_code_module;
 use crate::*;
-pub use source_code_module::*;
+use std::collections::VecDeque;
+use sway_core::{module_items, submodules, ModuleItem, NamespaceRef, SymbolKind};
 
 /// A documentation parser and generator.
+///
+/// Packages are documented from their type checked namespaces, in the order they were compiled
+/// in, so that the modules of a package's dependencies, which are reachable from its namespace,
+/// are already documented under the dependency's name.
+#[derive(Default)]
 pub struct Documenter {
-    modules: Vec<SourceCodeModule>,
+    modules: Vec<Module>,
+    documented: Vec<NamespaceRef>,
 }
 
 impl Documenter {
-    /// Given input sway source code, generates [Documention] for it.
-    pub fn generate_documentation(input: &str) -> Result<Documentation, DocumentationError> {
-        todo!("{}", input)
+    /// Create a new [Documenter], with no package documented yet.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    /// Create a new [Documenter] from a mapping of module names to their source code.
-    pub fn new(_raw: impl Into<Vec<SourceCodeModule>>) -> Self {
-        todo!()
+    /// Documents the package `name`, whose root module is `namespace`, along with the modules it
+    /// declares with `dep`. Private items are only documented if `include_private` is set.
+    pub fn document_package(&mut self, name: &str, namespace: NamespaceRef, include_private: bool) {
+        // modules reachable by several paths are named by the shortest
+        let mut modules = VecDeque::from(vec![(vec![name.to_string()], namespace)]);
+        while let Some((module_name, module)) = modules.pop_front() {
+            if self.documented.contains(&module) {
+                continue;
+            }
+            self.documented.push(module);
+
+            let items = module_items(module);
+            let documentation = items.first().map(module_doc_comment).unwrap_or_default();
+            let documented_items = items
+                .iter()
+                .filter(|item| include_private || item.is_public)
+                .map(|item| documented_item(item, false))
+                .collect();
+            for (submodule_name, submodule) in submodules(module) {
+                let mut submodule_path = module_name.clone();
+                submodule_path.push(submodule_name);
+                modules.push_back((submodule_path, submodule));
+            }
+            self.modules.push(Module {
+                name: module_name,
+                documentation,
+                documented_items,
+            });
+        }
+    }
+
+    /// The documentation of every package documented so far.
+    pub fn finish(mut self) -> Documentation {
+        self.modules.sort_by(|a, b| a.name.cmp(&b.name));
+        Documentation {
+            modules: self.modules,
+        }
     }
 }
+
+fn documented_item(item: &ModuleItem, is_member: bool) -> DocumentedItem {
+    let item_type = match item.kind {
+        SymbolKind::Function if is_member => ItemType::Method,
+        SymbolKind::Function => ItemType::Function,
+        SymbolKind::Struct => ItemType::Struct,
+        SymbolKind::Field => ItemType::Field,
+        SymbolKind::Enum => ItemType::Enum,
+        SymbolKind::Variant => ItemType::Variant,
+        SymbolKind::Trait => ItemType::Trait,
+        SymbolKind::Abi => ItemType::Abi,
+        SymbolKind::TypeAlias => ItemType::TypeAlias,
+        SymbolKind::Constant | SymbolKind::Variable | SymbolKind::Parameter => ItemType::Constant,
+    };
+    DocumentedItem {
+        item_type,
+        name: item.name.as_str().to_string(),
+        signature: item.signature.clone(),
+        documentation: doc_comment(item),
+        members: item
+            .members
+            .iter()
+            .map(|member| documented_item(member, true))
+            .collect(),
+    }
+}
+
+/// The `///` doc comments on the lines above the declaration of `item`. Attributes, such as
+/// `#[storage(read)]`, may be written between them and the declaration.
+fn doc_comment(item: &ModuleItem) -> String {
+    let span = item.name.span();
+    let before = &span.input()[..span.start()];
+    let mut lines = before.lines().rev();
+    // the line the declaration starts on
+    if !before.ends_with('\n') {
+        lines.next();
+    }
+    let mut doc_lines = lines
+        .map(str::trim)
+        .filter(|line| !line.starts_with("#["))
+        .map_while(|line| line.strip_prefix("///"))
+        .map(strip_leading_space)
+        .collect::<Vec<_>>();
+    doc_lines.reverse();
+    doc_lines.join("\n")
+}
+
+/// The `//!` doc comments of the file `item` is declared in, which document its module.
+fn module_doc_comment(item: &ModuleItem) -> String {
+    item.name
+        .span()
+        .input()
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("//!"))
+        .map(strip_leading_space)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn strip_leading_space(line: &str) -> &str {
+    line.strip_prefix(' ').unwrap_or(line)
+}
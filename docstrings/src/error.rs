@@ -1,2 +1,15 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
 /// An error generated while documenting Sway code.
-pub struct DocumentationError {}
+#[derive(Debug, Error)]
+pub enum DocumentationError {
+    /// A file of the rendered documentation could not be written.
+    #[error("could not write {}: {}", .path.display(), .source)]
+    Write {
+        /// The file, or directory, that could not be written.
+        path: PathBuf,
+        /// Why it could not be written.
+        source: std::io::Error,
+    },
+}
@@ -0,0 +1,453 @@
+//! Rendering of [Documentation] as static HTML pages, one per module.
+use crate::*;
+use serde_json::{json, Value};
+use std::{collections::HashMap, fs, path::Path};
+
+const STYLE: &str = include_str!("static/style.css");
+const SEARCH: &str = include_str!("static/search.js");
+
+/// The sections of the page of a module, in the order they appear in.
+const SECTIONS: &[(ItemType, &str)] = &[
+    (ItemType::Abi, "ABIs"),
+    (ItemType::Trait, "Traits"),
+    (ItemType::Struct, "Structs"),
+    (ItemType::Enum, "Enums"),
+    (ItemType::TypeAlias, "Type Aliases"),
+    (ItemType::Function, "Functions"),
+    (ItemType::Constant, "Constants"),
+];
+
+pub(crate) fn write(
+    documentation: &Documentation,
+    out_dir: &Path,
+) -> Result<(), DocumentationError> {
+    let links = Links::new(documentation);
+    write_file(&out_dir.join("style.css"), STYLE)?;
+    write_file(&out_dir.join("search.js"), SEARCH)?;
+    write_file(
+        &out_dir.join("search-index.js"),
+        &search_index(documentation),
+    )?;
+    write_file(&out_dir.join("index.html"), &index_page(documentation))?;
+    for index in 0..documentation.modules.len() {
+        let module = &documentation.modules[index];
+        write_file(
+            &out_dir.join(module_url(module)),
+            &module_page(documentation, &links, index),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), DocumentationError> {
+    let write = || {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)
+    };
+    write().map_err(|source| DocumentationError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// The path of the page of `module` from the root of the documentation.
+fn module_url(module: &Module) -> String {
+    format!("{}/index.html", module.name.join("/"))
+}
+
+/// The items the types written in signatures link to.
+struct Links<'a> {
+    modules: &'a [Module],
+    /// The items which are types, by name, each with the index of the module declaring it.
+    types: HashMap<&'a str, Vec<(usize, &'a DocumentedItem)>>,
+}
+
+impl<'a> Links<'a> {
+    fn new(documentation: &'a Documentation) -> Self {
+        let mut types: HashMap<_, Vec<_>> = HashMap::new();
+        for (index, module) in documentation.modules.iter().enumerate() {
+            for item in &module.documented_items {
+                if item.item_type.is_type() {
+                    types
+                        .entry(item.name.as_str())
+                        .or_default()
+                        .push((index, item));
+                }
+            }
+        }
+        Links {
+            modules: &documentation.modules,
+            types,
+        }
+    }
+
+    /// The URL, from the root of the documentation, of the type `name` written in the module at
+    /// `from`: the one declared in the same module if there is one, else the one declared in the
+    /// same package, else the only one by that name.
+    fn resolve(&self, name: &str, from: usize) -> Option<String> {
+        let types = self.types.get(name)?;
+        let package = &self.modules[from].name[0];
+        let (module, item) = types
+            .iter()
+            .find(|(module, _)| *module == from)
+            .or_else(|| {
+                types
+                    .iter()
+                    .find(|(module, _)| self.modules[*module].name[0] == *package)
+            })
+            .or(match types.as_slice() {
+                [only] => Some(only),
+                _ => None,
+            })?;
+        Some(format!(
+            "{}#{}",
+            module_url(&self.modules[*module]),
+            item.anchor(None)
+        ))
+    }
+}
+
+/// A page of the documentation. `root` is the relative path from the page to the root of the
+/// documentation, which the scripts and the style sheet are in.
+fn page(title: &str, root: &str, sidebar: &str, content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="stylesheet" href="{root}style.css">
+</head>
+<body>
+<nav class="sidebar">
+{sidebar}
+</nav>
+<main>
+<input id="search" type="search" placeholder="Search the documentation..." autocomplete="off">
+<div id="search-results"></div>
+<div id="content">
+{content}
+</div>
+</main>
+<script>var rootPath = "{root}";</script>
+<script src="{root}search-index.js"></script>
+<script src="{root}search.js"></script>
+</body>
+</html>
+"#,
+        title = escape(title),
+        root = root,
+        sidebar = sidebar,
+        content = content,
+    )
+}
+
+/// The list of packages in the sidebar of every page.
+fn packages_sidebar(documentation: &Documentation, root: &str) -> String {
+    let packages = documentation
+        .modules
+        .iter()
+        .filter(|module| module.name.len() == 1)
+        .map(|module| {
+            format!(
+                r#"<li><a href="{}{}">{}</a></li>"#,
+                root,
+                module_url(module),
+                escape(&module.name[0])
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<h3><a href="{}index.html">Packages</a></h3>
+<ul>{}</ul>"#,
+        root, packages
+    )
+}
+
+fn index_page(documentation: &Documentation) -> String {
+    let rows = documentation
+        .modules
+        .iter()
+        .filter(|module| module.name.len() == 1)
+        .map(|module| {
+            format!(
+                r#"<tr><td><a href="{}">{}</a></td><td>{}</td></tr>"#,
+                module_url(module),
+                escape(&module.name[0]),
+                render_inline(module.summary())
+            )
+        })
+        .collect::<String>();
+    let content = format!("<h1>Packages</h1>\n<table>{}</table>", rows);
+    page(
+        "Packages",
+        "",
+        &packages_sidebar(documentation, ""),
+        &content,
+    )
+}
+
+fn module_page(documentation: &Documentation, links: &Links, index: usize) -> String {
+    let module = &documentation.modules[index];
+    let root = "../".repeat(module.name.len());
+    let submodules = documentation
+        .modules
+        .iter()
+        .filter(|other| {
+            other.name.len() == module.name.len() + 1 && other.name.starts_with(&module.name)
+        })
+        .collect::<Vec<_>>();
+
+    let breadcrumbs = (1..=module.name.len())
+        .map(|len| {
+            format!(
+                r#"<a href="{}{}/index.html">{}</a>"#,
+                root,
+                module.name[..len].join("/"),
+                escape(&module.name[len - 1])
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("::");
+    let kind = if module.name.len() == 1 {
+        "Package"
+    } else {
+        "Module"
+    };
+    let mut content = format!(
+        "<h1>{} {}</h1>\n{}",
+        kind,
+        breadcrumbs,
+        docblock(&module.documentation)
+    );
+    let mut sidebar = packages_sidebar(documentation, &root);
+
+    if !submodules.is_empty() {
+        content.push_str("<h2 id=\"modules\">Modules</h2>\n<table>");
+        sidebar.push_str("\n<h3><a href=\"#modules\">Modules</a></h3>\n<ul>");
+        for submodule in submodules {
+            let url = format!("{}{}", root, module_url(submodule));
+            let name = escape(submodule.name.last().expect("modules are named"));
+            content.push_str(&format!(
+                r#"<tr><td><a href="{}">{}</a></td><td>{}</td></tr>"#,
+                url,
+                name,
+                render_inline(submodule.summary())
+            ));
+            sidebar.push_str(&format!(r#"<li><a href="{}">{}</a></li>"#, url, name));
+        }
+        content.push_str("</table>\n");
+        sidebar.push_str("</ul>");
+    }
+
+    for (item_type, heading) in SECTIONS {
+        let items = module
+            .documented_items
+            .iter()
+            .filter(|item| item.item_type == *item_type)
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            continue;
+        }
+        let section = item_type.as_str();
+        content.push_str(&format!("<h2 id=\"{}\">{}</h2>\n", section, heading));
+        sidebar.push_str(&format!(
+            "\n<h3><a href=\"#{}\">{}</a></h3>\n<ul>",
+            section, heading
+        ));
+        for item in items {
+            content.push_str(&render_item(item, links, index, &root));
+            sidebar.push_str(&format!(
+                r##"<li><a href="#{}">{}</a></li>"##,
+                item.anchor(None),
+                escape(&item.name)
+            ));
+        }
+        sidebar.push_str("</ul>");
+    }
+
+    page(&module.path(), &root, &sidebar, &content)
+}
+
+fn render_item(item: &DocumentedItem, links: &Links, from: usize, root: &str) -> String {
+    let mut html = format!(
+        "<section id=\"{}\">\n<pre class=\"signature\"><code>{}</code></pre>\n{}",
+        item.anchor(None),
+        render_signature(&item.signature, Some(&item.name), links, from, root),
+        docblock(&item.documentation)
+    );
+    if !item.members.is_empty() {
+        html.push_str("<div class=\"members\">\n");
+        for member in &item.members {
+            html.push_str(&format!(
+                "<div class=\"member\" id=\"{}\"><code>{}</code>\n{}</div>\n",
+                member.anchor(Some(item)),
+                render_signature(&member.signature, None, links, from, root),
+                docblock(&member.documentation)
+            ));
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+/// The signature `signature` with each type named in it linked to the item declaring it. The
+/// first occurrence of `own_name`, the name of the item being declared, is left alone.
+fn render_signature(
+    signature: &str,
+    own_name: Option<&str>,
+    links: &Links,
+    from: usize,
+    root: &str,
+) -> String {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut own_name = own_name;
+    let mut html = String::new();
+    let mut rest = signature;
+    while let Some(start) = rest.find(is_ident_char) {
+        html.push_str(&escape(&rest[..start]));
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+        let (word, after) = rest.split_at(end);
+        rest = after;
+
+        if own_name == Some(word) {
+            own_name = None;
+            html.push_str(word);
+            continue;
+        }
+        // the names of parameters, fields and variants are followed by their type
+        let after = after.trim_start();
+        let is_typed_name = after.starts_with(':') && !after.starts_with("::");
+        match links.resolve(word, from).filter(|_| !is_typed_name) {
+            Some(url) => html.push_str(&format!(r#"<a href="{}{}">{}</a>"#, root, url, word)),
+            None => html.push_str(word),
+        }
+    }
+    html.push_str(&escape(rest));
+    html
+}
+
+/// The block of a page holding `documentation`, if there is any.
+fn docblock(documentation: &str) -> String {
+    if documentation.trim().is_empty() {
+        return String::new();
+    }
+    format!(
+        "<div class=\"docblock\">\n{}</div>\n",
+        render_docs(documentation)
+    )
+}
+
+/// Doc comments as HTML. Paragraphs are separated by blank lines, lines starting with `#` are
+/// headings, code blocks are fenced with ```, and `code` is inline code.
+fn render_docs(documentation: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut code_block: Option<Vec<&str>> = None;
+    for line in documentation.lines() {
+        if let Some(code) = &mut code_block {
+            if line.trim_start().starts_with("```") {
+                html.push_str(&format!(
+                    "<pre><code>{}</code></pre>\n",
+                    escape(&code.join("\n"))
+                ));
+                code_block = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            end_paragraph(&mut html, &mut paragraph);
+            code_block = Some(vec![]);
+        } else if trimmed.starts_with('#') {
+            end_paragraph(&mut html, &mut paragraph);
+            html.push_str(&format!(
+                "<h4>{}</h4>\n",
+                render_inline(trimmed.trim_start_matches('#').trim())
+            ));
+        } else if trimmed.is_empty() {
+            end_paragraph(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    end_paragraph(&mut html, &mut paragraph);
+    if let Some(code) = code_block {
+        html.push_str(&format!(
+            "<pre><code>{}</code></pre>\n",
+            escape(&code.join("\n"))
+        ));
+    }
+    html
+}
+
+fn end_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    // the lines of lists are kept apart
+    let is_list = paragraph.iter().any(|line| {
+        line.starts_with("- ")
+            || line.starts_with("* ")
+            || matches!(line.split_once(". "), Some((number, _)) if number.parse::<u32>().is_ok())
+    });
+    let separator = if is_list { "<br>\n" } else { "\n" };
+    let lines = paragraph
+        .iter()
+        .map(|line| render_inline(line))
+        .collect::<Vec<_>>();
+    html.push_str(&format!("<p>{}</p>\n", lines.join(separator)));
+    paragraph.clear();
+}
+
+/// `text` as HTML, with the parts between backticks as inline code.
+fn render_inline(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                format!("<code>{}</code>", escape(part))
+            } else {
+                escape(part)
+            }
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The script defining `searchIndex`, the modules and items the search box looks through.
+fn search_index(documentation: &Documentation) -> String {
+    let mut entries = vec![];
+    for module in &documentation.modules {
+        entries.push(json!({
+            "name": module.name.last(),
+            "kind": "mod",
+            "path": module.path(),
+            "url": module_url(module),
+            "summary": module.summary().replace('\n', " "),
+        }));
+        for item in &module.documented_items {
+            entries.push(json!({
+                "name": item.name,
+                "kind": item.item_type.as_str(),
+                "path": format!("{}::{}", module.path(), item.name),
+                "url": format!("{}#{}", module_url(module), item.anchor(None)),
+                "summary": item.summary().replace('\n', " "),
+            }));
+        }
+    }
+    format!("var searchIndex = {};\n", Value::Array(entries))
+}
@@ -0,0 +1,42 @@
+// Filters the entries of `searchIndex`, defined in search-index.js, by the text typed in the search
+// box, and shows them in place of the content of the page. `rootPath` is set by each page to the
+// relative path of the root of the documentation.
+(function () {
+    var input = document.getElementById("search");
+    var results = document.getElementById("search-results");
+    var content = document.getElementById("content");
+
+    function escape(text) {
+        return text
+            .replace(/&/g, "&amp;")
+            .replace(/</g, "&lt;")
+            .replace(/>/g, "&gt;")
+            .replace(/"/g, "&quot;");
+    }
+
+    input.addEventListener("input", function () {
+        var query = input.value.trim().toLowerCase();
+        if (query === "") {
+            results.innerHTML = "";
+            content.style.display = "";
+            return;
+        }
+        var matches = searchIndex.filter(function (entry) {
+            return entry.path.toLowerCase().indexOf(query) !== -1;
+        });
+        // exact names first, then the shortest paths
+        matches.sort(function (a, b) {
+            var exact = (b.name.toLowerCase() === query) - (a.name.toLowerCase() === query);
+            return exact !== 0 ? exact : a.path.length - b.path.length;
+        });
+        content.style.display = "none";
+        if (matches.length === 0) {
+            results.innerHTML = "<p>No results.</p>";
+            return;
+        }
+        results.innerHTML = "<table>" + matches.slice(0, 100).map(function (entry) {
+            return "<tr><td>" + entry.kind + "</td><td><a href=\"" + rootPath + entry.url + "\">" +
+                escape(entry.path) + "</a></td><td>" + escape(entry.summary) + "</td></tr>";
+        }).join("") + "</table>";
+    });
+})();
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use sway_utils::constants;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum Dependency {
+    /// In the simple format, only a version is specified, eg.
+    /// `package = "<version>"`
+    Simple(String),
+    /// The simple format is equivalent to a detailed dependency
+    /// specifying only a version, eg.
+    /// `package = { version = "<version>" }`
+    Detailed(DependencyDetails),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DependencyDetails {
+    pub version: Option<String>,
+    pub path: Option<String>,
+    pub git: Option<String>,
+    pub branch: Option<String>,
+}
+
+/// The directory a dependency hosted on GitHub is downloaded to.
+/// A given dependency `dep` is stored under `~/.forc/dep/default/$owner-$repo-$hash`.
+/// If a branch is specified, it will go in `~/.forc/dep/$branch/$owner-$repo-$hash.
+/// If a version is specified, it will go in `~/.forc/dep/$version/$owner-$repo-$hash.
+/// Version takes precedence over branch reference.
+pub fn github_dep_install_dir(
+    dep_name: &str,
+    branch: &Option<String>,
+    version: &Option<String>,
+) -> Result<PathBuf, String> {
+    let home_dir = match dirs::home_dir() {
+        None => return Err("Couldn't find home directory (`~/`)".into()),
+        Some(p) => p.to_str().unwrap().to_owned(),
+    };
+
+    // hash the dep name into a number to avoid bad characters
+    let mut s = DefaultHasher::new();
+    dep_name.hash(&mut s);
+    let hashed_dep_name = s.finish().to_string();
+
+    // Version tag takes precedence over branch reference.
+    let out_dir = match &version {
+        Some(v) => PathBuf::from(format!(
+            "{}/{}/{}/{}",
+            home_dir,
+            constants::FORC_DEPENDENCIES_DIRECTORY,
+            hashed_dep_name,
+            v
+        )),
+        // If no version specified, check if a branch was specified
+        None => match &branch {
+            Some(b) => PathBuf::from(format!(
+                "{}/{}/{}/{}",
+                home_dir,
+                constants::FORC_DEPENDENCIES_DIRECTORY,
+                hashed_dep_name,
+                b
+            )),
+            // If no version and no branch, use default
+            None => PathBuf::from(format!(
+                "{}/{}/{}/default",
+                home_dir,
+                constants::FORC_DEPENDENCIES_DIRECTORY,
+                hashed_dep_name
+            )),
+        },
+    };
+    Ok(out_dir)
+}
+
+/// Returns the path of a dependency that has already been downloaded to `out_dir`, if any.
+pub fn find_installed_dep(out_dir: &Path) -> Option<PathBuf> {
+    // If the path to that dependency at that branch/version already exists and there's a
+    // directory inside of it, this directory should be the installation path.
+    fs::read_dir(out_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir())
+}
+
+/// Returns the path of the download of `commit` in `out_dir`, if it has been downloaded. GitHub
+/// names the directory of a download after the repository and the commit, shortened, as in
+/// `$owner-$repo-$hash`.
+pub fn find_installed_commit(out_dir: &Path, commit: &str) -> Option<PathBuf> {
+    fs::read_dir(out_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .find(|path| {
+            let hash = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.rsplit('-').next());
+            matches!(hash, Some(hash) if !hash.is_empty() && commit.starts_with(hash))
+        })
+}
@@ -0,0 +1,461 @@
+use crate::dependency::{Dependency, DependencyDetails};
+use crate::lock::Lock;
+use crate::manifest::{read_manifest, Manifest};
+use crate::workspace::Workspace;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use sway_utils::find_manifest_dir;
+
+/// A package, or the members of a workspace, and every package they depend on, directly or not,
+/// each appearing once however many packages depend on it.
+///
+/// Building the graph checks that it can be compiled: it has no cycles, and no package is
+/// depended on at two different versions. The packages can then be compiled in [compile_order],
+/// each after all of its dependencies.
+///
+/// [compile_order]: PackageGraph::compile_order
+#[derive(Debug)]
+pub struct PackageGraph {
+    packages: Vec<Package>,
+    /// The packages the graph was built for.
+    roots: Vec<usize>,
+    compile_order: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub struct Package {
+    pub manifest_dir: PathBuf,
+    pub manifest: Manifest,
+    pub source: Source,
+    /// The packages this one depends on, by index in the graph, with the names it gives them.
+    pub dependencies: Vec<(String, usize)>,
+}
+
+/// Where a package in a graph was found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// A package the graph was built for.
+    Root,
+    /// A package depended on by path.
+    Path,
+    /// A package downloaded from GitHub, at the commit locked for it.
+    Git {
+        git: String,
+        branch: Option<String>,
+        version: Option<String>,
+        commit: String,
+    },
+}
+
+impl PackageGraph {
+    /// Builds the graph of the package in `manifest_dir`. Its dependencies fetched from GitHub must
+    /// have been downloaded at the commits locked for them in `lock`.
+    pub fn load(manifest_dir: &Path, lock: &Lock) -> Result<Self, String> {
+        let mut builder = GraphBuilder::new(lock);
+        let root = builder.add_package(manifest_dir, Source::Root, None)?;
+        builder.finish(vec![root])
+    }
+
+    /// Builds the graph of the members of `workspace`, which share the lock `lock`.
+    pub fn load_workspace(workspace: &Workspace, lock: &Lock) -> Result<Self, String> {
+        let mut builder = GraphBuilder::new(lock);
+        let mut roots = vec![];
+        for member_dir in &workspace.members {
+            let root = builder.add_package(member_dir, Source::Root, None)?;
+            // a member other members depend on is found before it is reached in the list
+            builder.packages[root].source = Source::Root;
+            roots.push(root);
+        }
+        builder.finish(roots)
+    }
+
+    /// The first package the graph was built for.
+    pub fn root(&self) -> &Package {
+        &self.packages[self.roots[0]]
+    }
+
+    /// The indices of the packages the graph was built for: the package, or the members of the
+    /// workspace, in the order they are listed.
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    /// The indices of the packages of the graph, with every package after those it depends on.
+    /// When the graph was built for a single package, it comes last.
+    pub fn compile_order(&self) -> &[usize] {
+        &self.compile_order
+    }
+
+    /// The indices of the packages the package at `index` depends on, directly or not, in compile
+    /// order.
+    pub fn dependencies_of(&self, index: usize) -> Vec<usize> {
+        let mut needed = vec![false; self.packages.len()];
+        let mut stack = vec![index];
+        while let Some(current) = stack.pop() {
+            for (_, dependency) in &self.packages[current].dependencies {
+                if !needed[*dependency] {
+                    needed[*dependency] = true;
+                    stack.push(*dependency);
+                }
+            }
+        }
+        self.compile_order
+            .iter()
+            .copied()
+            .filter(|index| needed[*index])
+            .collect()
+    }
+
+    /// Whether the package at `index` is one the graph was built for.
+    pub fn is_root(&self, index: usize) -> bool {
+        self.roots.contains(&index)
+    }
+}
+
+impl Package {
+    pub fn name(&self) -> &str {
+        &self.manifest.project.name
+    }
+}
+
+/// Describes where a package was found, for errors.
+fn describe(source: &Source, manifest_dir: &Path) -> String {
+    match source {
+        Source::Root | Source::Path => format!("at {}", manifest_dir.display()),
+        Source::Git {
+            git,
+            branch,
+            version,
+            commit,
+        } => {
+            let commit = &commit[..commit.len().min(7)];
+            match version.as_ref().or(branch.as_ref()) {
+                Some(reference) => format!("from {} at {} ({})", git, reference, commit),
+                None => format!("from {} ({})", git, commit),
+            }
+        }
+    }
+}
+
+struct GraphBuilder<'lock> {
+    lock: &'lock Lock,
+    packages: Vec<Package>,
+    /// The index of the package in each manifest directory, so that a package depended on by
+    /// several others is only added once.
+    indices: HashMap<PathBuf, usize>,
+    /// The name of the package that first depended on each package, for errors.
+    required_by: Vec<Option<String>>,
+}
+
+impl<'lock> GraphBuilder<'lock> {
+    fn new(lock: &'lock Lock) -> Self {
+        GraphBuilder {
+            lock,
+            packages: vec![],
+            indices: HashMap::new(),
+            required_by: vec![],
+        }
+    }
+
+    fn finish(self, roots: Vec<usize>) -> Result<PackageGraph, String> {
+        let compile_order = compile_order(&self.packages, &roots)?;
+        Ok(PackageGraph {
+            packages: self.packages,
+            roots,
+            compile_order,
+        })
+    }
+
+    /// Adds the package in `manifest_dir`, and the packages it depends on, to the graph, unless it
+    /// is in it already, and returns its index.
+    fn add_package(
+        &mut self,
+        manifest_dir: &Path,
+        source: Source,
+        required_by: Option<&str>,
+    ) -> Result<usize, String> {
+        // the same package can be reached through different relative paths
+        let manifest_dir = manifest_dir
+            .canonicalize()
+            .unwrap_or_else(|_| manifest_dir.to_path_buf());
+        if let Some(index) = self.indices.get(&manifest_dir) {
+            return Ok(*index);
+        }
+
+        let manifest = read_manifest(&manifest_dir)?;
+        let name = manifest.project.name.clone();
+        if let Some(other) = self.packages.iter().position(|p| p.name() == name) {
+            return Err(format!(
+                "Package `{}` is depended on at two different versions: {}, required by `{}`, \
+                 and {}, required by `{}`. Every package depending on it must use the same one.",
+                name,
+                describe(
+                    &self.packages[other].source,
+                    &self.packages[other].manifest_dir
+                ),
+                self.required_by[other].as_deref().unwrap_or_default(),
+                describe(&source, &manifest_dir),
+                required_by.unwrap_or_default(),
+            ));
+        }
+
+        let index = self.packages.len();
+        self.indices.insert(manifest_dir.clone(), index);
+        self.packages.push(Package {
+            manifest_dir: manifest_dir.clone(),
+            manifest,
+            source,
+            dependencies: vec![],
+        });
+        self.required_by.push(required_by.map(str::to_string));
+
+        let mut dependencies = vec![];
+        for (dep_name, dependency) in self.packages[index].manifest.dependencies.iter().flatten() {
+            let (dep_path, dep_source) =
+                self.find_dependency(&manifest_dir, dep_name, dependency)?;
+            dependencies.push((dep_name.clone(), dep_path, dep_source));
+        }
+        for (dep_name, dep_path, dep_source) in dependencies {
+            let dep_manifest_dir = find_manifest_dir(&dep_path)
+                .ok_or_else(|| format!("Manifest not found for dependency {:?}.", dep_path))?;
+            let dep_index = self.add_package(&dep_manifest_dir, dep_source, Some(&name))?;
+            self.packages[index]
+                .dependencies
+                .push((dep_name, dep_index));
+        }
+        Ok(index)
+    }
+
+    /// Where the dependency `dep_name` of the package in `manifest_dir` is, and where it comes
+    /// from.
+    fn find_dependency(
+        &self,
+        manifest_dir: &Path,
+        dep_name: &str,
+        dependency: &Dependency,
+    ) -> Result<(PathBuf, Source), String> {
+        let details = match dependency {
+            Dependency::Simple(..) => {
+                return Err(
+                    "Not yet implemented: Simple version-spec dependencies require a registry."
+                        .into(),
+                )
+            }
+            Dependency::Detailed(details) => details,
+        };
+        match details {
+            DependencyDetails {
+                git: Some(git),
+                branch,
+                version,
+                ..
+            } => {
+                let not_fetched = || {
+                    format!(
+                        "Dependency {:?} has not been downloaded at the commit locked for it. \
+                         Run `forc build` to fetch it.",
+                        dep_name
+                    )
+                };
+                let locked = self
+                    .lock
+                    .find_git(dep_name, details)
+                    .ok_or_else(not_fetched)?;
+                let path = self
+                    .lock
+                    .installed_path(dep_name, details)
+                    .ok_or_else(not_fetched)?;
+                let source = Source::Git {
+                    git: git.clone(),
+                    branch: branch.clone(),
+                    version: version.clone(),
+                    commit: locked.commit.clone().unwrap_or_default(),
+                };
+                Ok((path, source))
+            }
+            // dependency paths are relative to the manifest of the package depending on them
+            DependencyDetails {
+                path: Some(path), ..
+            } => Ok((manifest_dir.join(path), Source::Path)),
+            _ => Err(format!(
+                "Dependency {:?} has neither a path nor a git repository.",
+                dep_name
+            )),
+        }
+    }
+}
+
+/// Orders the packages so that each comes after the packages it depends on, by walking the graph
+/// depth first from each of the roots, or fails if the packages depend on each other in a cycle.
+fn compile_order(packages: &[Package], roots: &[usize]) -> Result<Vec<usize>, String> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        NotVisited,
+        InProgress,
+        Done,
+    }
+
+    fn visit(
+        index: usize,
+        packages: &[Package],
+        visits: &mut [Visit],
+        path: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), String> {
+        match visits[index] {
+            Visit::Done => return Ok(()),
+            Visit::InProgress => {
+                let start = path.iter().position(|i| *i == index).unwrap_or_default();
+                let cycle = path[start..]
+                    .iter()
+                    .chain(std::iter::once(&index))
+                    .map(|i| format!("`{}`", packages[*i].name()))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                return Err(format!("Dependency cycle detected: {}.", cycle));
+            }
+            Visit::NotVisited => (),
+        }
+        visits[index] = Visit::InProgress;
+        path.push(index);
+        for (_, dep_index) in &packages[index].dependencies {
+            visit(*dep_index, packages, visits, path, order)?;
+        }
+        path.pop();
+        visits[index] = Visit::Done;
+        order.push(index);
+        Ok(())
+    }
+
+    let mut visits = vec![Visit::NotVisited; packages.len()];
+    let mut order = vec![];
+    for root in roots {
+        visit(*root, packages, &mut visits, &mut vec![], &mut order)?;
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Writes a package named `name` to `dir`, depending by path on each of `dependencies`, given
+    /// as `(name, path)`.
+    fn write_package(dir: &Path, name: &str, dependencies: &[(&str, &str)]) {
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let mut manifest = format!(
+            "[project]\nauthor = \"Fuel Labs\"\nlicense = \"Apache-2.0\"\nname = \"{}\"\n\n\
+             [dependencies]\n",
+            name
+        );
+        for (dep_name, path) in dependencies {
+            manifest.push_str(&format!("{} = {{ path = \"{}\" }}\n", dep_name, path));
+        }
+        fs::write(dir.join("Forc.toml"), manifest).unwrap();
+    }
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("forc-pkg-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn shared_dependencies_are_compiled_once_and_first() {
+        let dir = temp_dir("graph-shared");
+        write_package(
+            &dir.join("app"),
+            "app",
+            &[("lib", "../lib"), ("std", "../std")],
+        );
+        write_package(&dir.join("lib"), "lib", &[("std", "../std")]);
+        write_package(&dir.join("std"), "std", &[]);
+
+        let graph = PackageGraph::load(&dir.join("app"), &Lock::default()).unwrap();
+        assert_eq!(graph.packages().len(), 3);
+        let names = graph
+            .compile_order()
+            .iter()
+            .map(|index| graph.packages()[*index].name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["std", "lib", "app"]);
+        assert!(graph.is_root(*graph.compile_order().last().unwrap()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn workspace_members_are_compiled_after_the_members_they_depend_on() {
+        let dir = temp_dir("graph-workspace");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Forc.toml"),
+            "[workspace]\nmembers = [\"script\", \"contract\", \"abi\"]\n",
+        )
+        .unwrap();
+        write_package(&dir.join("script"), "script", &[("abi", "../abi")]);
+        write_package(&dir.join("contract"), "contract", &[("abi", "../abi")]);
+        write_package(&dir.join("abi"), "abi", &[("std", "../std")]);
+        write_package(&dir.join("std"), "std", &[]);
+
+        let workspace = Workspace::load(&dir).unwrap().unwrap();
+        let graph = PackageGraph::load_workspace(&workspace, &Lock::default()).unwrap();
+        let names = graph
+            .compile_order()
+            .iter()
+            .map(|index| graph.packages()[*index].name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["std", "abi", "script", "contract"]);
+        let roots = graph
+            .roots()
+            .iter()
+            .map(|index| graph.packages()[*index].name())
+            .collect::<Vec<_>>();
+        assert_eq!(roots, ["script", "contract", "abi"]);
+        assert!(graph
+            .roots()
+            .iter()
+            .all(|index| graph.packages()[*index].source == Source::Root));
+        let script_dependencies = graph
+            .dependencies_of(graph.roots()[0])
+            .iter()
+            .map(|index| graph.packages()[*index].name())
+            .collect::<Vec<_>>();
+        assert_eq!(script_dependencies, ["std", "abi"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cycles_are_errors() {
+        let dir = temp_dir("graph-cycle");
+        write_package(&dir.join("app"), "app", &[("a", "../a")]);
+        write_package(&dir.join("a"), "a", &[("b", "../b")]);
+        write_package(&dir.join("b"), "b", &[("a", "../a")]);
+
+        let error = PackageGraph::load(&dir.join("app"), &Lock::default()).unwrap_err();
+        assert_eq!(error, "Dependency cycle detected: `a` -> `b` -> `a`.");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn two_versions_of_a_package_are_errors() {
+        let dir = temp_dir("graph-conflict");
+        write_package(
+            &dir.join("app"),
+            "app",
+            &[("lib", "../lib"), ("std", "../std")],
+        );
+        write_package(&dir.join("lib"), "lib", &[("std", "../std2")]);
+        write_package(&dir.join("std"), "std", &[]);
+        write_package(&dir.join("std2"), "std", &[]);
+
+        let error = PackageGraph::load(&dir.join("app"), &Lock::default()).unwrap_err();
+        assert!(error.starts_with("Package `std` is depended on at two different versions"));
+        assert!(error.contains("required by `app`") && error.contains("required by `lib`"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
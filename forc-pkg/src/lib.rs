@@ -0,0 +1,16 @@
+//! The layout of a Sway package on disk: its `Forc.toml` manifest, its `Forc.lock` lock file,
+//! where its dependencies are found and the graph they form, and the workspaces packages can be
+//! grouped in. Shared by `forc` and the language server.
+pub mod dependency;
+pub mod graph;
+pub mod lock;
+pub mod manifest;
+pub mod workspace;
+
+pub use dependency::{Dependency, DependencyDetails};
+pub use graph::PackageGraph;
+pub use lock::{Lock, LockedPackage};
+pub use manifest::{
+    find_file_name, find_main_path, get_main_file, read_manifest, BuildProfile, Manifest,
+};
+pub use workspace::Workspace;
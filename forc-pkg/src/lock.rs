@@ -0,0 +1,313 @@
+use crate::dependency::{find_installed_commit, github_dep_install_dir, DependencyDetails};
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sway_utils::constants;
+
+/// The exact sources of the dependencies of a package, kept in a `Forc.lock` file next to its
+/// manifest so that every build of the package uses the same code.
+///
+/// Packages fetched from git are pinned to a commit and to a hash of their contents. Packages
+/// found by path are only listed along with their path, since their code is part of the project
+/// already.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Lock {
+    #[serde(default, rename = "package", skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<LockedPackage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedPackage {
+    pub name: String,
+    /// The directory of a package found by path, relative to the lock file, with `/` separating
+    /// its components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// The repository the package was fetched from, for packages hosted on GitHub.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    /// The branch asked for in the manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// The version tag asked for in the manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The commit the branch or tag pointed to when the package was first fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// The hash of the package's files at that commit, see [checksum_dir].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// The names of the packages this one depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+const LOCK_FILE_HEADER: &str = "# This file is automatically generated by forc.\n\
+                                # It is not intended for manual editing.\n";
+
+impl Lock {
+    /// Reads the lock file of the package in `manifest_dir`. A package without one has an empty
+    /// lock.
+    pub fn read(manifest_dir: &Path) -> Result<Self, String> {
+        let lock_path = manifest_dir.join(constants::LOCK_FILE_NAME);
+        if !lock_path.exists() {
+            return Ok(Lock::default());
+        }
+        let lock = fs::read_to_string(&lock_path)
+            .map_err(|e| format!("failed to read lock file at {:?}: {}", lock_path, e))?;
+        toml::from_str(&lock).map_err(|e| format!("Error parsing lock file: {}.", e))
+    }
+
+    /// Writes the lock file of the package in `manifest_dir`.
+    pub fn write(&self, manifest_dir: &Path) -> Result<(), String> {
+        let lock_path = manifest_dir.join(constants::LOCK_FILE_NAME);
+        let lock = toml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(&lock_path, format!("{}\n{}", LOCK_FILE_HEADER, lock))
+            .map_err(|e| format!("failed to write lock file at {:?}: {}", lock_path, e))
+    }
+
+    /// The locked version of the git dependency `name`, with the source given in `details`.
+    pub fn find_git(&self, name: &str, details: &DependencyDetails) -> Option<&LockedPackage> {
+        self.packages.iter().find(|package| {
+            package.name == name
+                && package.git == details.git
+                && package.branch == details.branch
+                && package.version == details.version
+        })
+    }
+
+    /// Whether a package with the same name and source as `package` is locked already.
+    pub fn contains(&self, package: &LockedPackage) -> bool {
+        self.packages
+            .iter()
+            .any(|locked| locked.is_same_source(package))
+    }
+
+    /// Sorts the packages so that the lock file doesn't change when nothing in it has.
+    pub fn sort(&mut self) {
+        self.packages.sort_by(|a, b| {
+            (&a.name, &a.path, &a.git, &a.branch, &a.version)
+                .cmp(&(&b.name, &b.path, &b.git, &b.branch, &b.version))
+        });
+    }
+
+    /// The directory the locked version of the git dependency `name` has been downloaded to, if
+    /// it has been.
+    pub fn installed_path(&self, name: &str, details: &DependencyDetails) -> Option<PathBuf> {
+        let locked = self.find_git(name, details)?;
+        let fully_qualified_dep_name = format!("{}-{}", name, details.git.as_ref()?);
+        let out_dir =
+            github_dep_install_dir(&fully_qualified_dep_name, &details.branch, &details.version)
+                .ok()?;
+        find_installed_commit(&out_dir, locked.commit.as_ref()?)
+    }
+}
+
+impl LockedPackage {
+    /// A package found by path in `dir`, or the package the lock file in `lock_dir` belongs to.
+    pub fn local(name: &str, dir: &Path, lock_dir: &Path) -> Self {
+        LockedPackage {
+            path: Some(relative_path(dir, lock_dir)),
+            ..LockedPackage::named(name)
+        }
+    }
+
+    fn named(name: &str) -> Self {
+        LockedPackage {
+            name: name.to_string(),
+            path: None,
+            git: None,
+            branch: None,
+            version: None,
+            commit: None,
+            checksum: None,
+            dependencies: vec![],
+        }
+    }
+
+    /// A package fetched from git at `commit`, whose files hash to `checksum`.
+    pub fn git(name: &str, details: &DependencyDetails, commit: String, checksum: String) -> Self {
+        LockedPackage {
+            git: details.git.clone(),
+            branch: details.branch.clone(),
+            version: details.version.clone(),
+            commit: Some(commit),
+            checksum: Some(checksum),
+            ..LockedPackage::named(name)
+        }
+    }
+
+    pub fn is_same_source(&self, other: &LockedPackage) -> bool {
+        self.name == other.name
+            && self.path == other.path
+            && self.git == other.git
+            && self.branch == other.branch
+            && self.version == other.version
+    }
+
+    /// The commit, shortened the way GitHub shows it.
+    pub fn short_commit(&self) -> Option<&str> {
+        let commit = self.commit.as_ref()?;
+        Some(&commit[..commit.len().min(7)])
+    }
+}
+
+/// The directory of the lock file used by the package or workspace in `manifest_dir`, along with
+/// the manifest directories of the packages sharing it: the package alone, or every member of the
+/// workspace.
+pub fn find_lock_dir(manifest_dir: &Path) -> Result<(PathBuf, Vec<PathBuf>), String> {
+    // the members of a workspace share the lock file at its root
+    let workspace = match Workspace::load(manifest_dir)? {
+        Some(workspace) => Some(workspace),
+        None => Workspace::find(manifest_dir)?,
+    };
+    Ok(match workspace {
+        Some(workspace) => (workspace.dir, workspace.members),
+        None => (manifest_dir.to_path_buf(), vec![manifest_dir.to_path_buf()]),
+    })
+}
+
+/// The path of `dir` relative to `base`, which is the same on every machine the project is
+/// checked out on, as long as `dir` is part of the project.
+fn relative_path(dir: &Path, base: &Path) -> String {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+    let common = dir
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let components = base
+        .components()
+        .skip(common)
+        .map(|_| "..".to_string())
+        .chain(
+            dir.components()
+                .skip(common)
+                .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+        )
+        .collect::<Vec<_>>();
+    if components.is_empty() {
+        ".".into()
+    } else {
+        components.join("/")
+    }
+}
+
+/// Hashes the files in `dir`, along with their paths relative to it, so that a package fetched
+/// again can be checked to have exactly the same contents.
+pub fn checksum_dir(dir: &Path) -> Result<String, String> {
+    let mut files = vec![];
+    collect_files(dir, &mut files).map_err(|e| format!("failed to read {:?}: {}", dir, e))?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative_path = file.strip_prefix(dir).unwrap_or(&file);
+        // `/` separates the components on every platform, so the hash is the same everywhere
+        let relative_path = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let contents = fs::read(&file).map_err(|e| format!("failed to read {:?}: {}", file, e))?;
+        hasher.update(relative_path.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(git: &str, branch: Option<&str>) -> DependencyDetails {
+        DependencyDetails {
+            version: None,
+            path: None,
+            git: Some(git.into()),
+            branch: branch.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn lock_round_trips_through_toml() {
+        let std = details("https://github.com/FuelLabs/sway-lib-std", Some("master"));
+        let dir = std::env::temp_dir();
+        let mut app = LockedPackage::local("app", &dir, &dir);
+        app.dependencies = vec!["std".into()];
+        assert_eq!(app.path.as_deref(), Some("."));
+        let mut lock = Lock {
+            packages: vec![
+                LockedPackage::git("std", &std, "e6940e4".repeat(5), "ab".repeat(32)),
+                app,
+            ],
+        };
+        lock.sort();
+        assert_eq!(lock.packages[0].name, "app");
+
+        let text = toml::to_string(&lock).unwrap();
+        assert!(text.contains("[[package]]"));
+        assert_eq!(toml::from_str::<Lock>(&text).unwrap(), lock);
+
+        let locked = lock.find_git("std", &std).unwrap();
+        assert_eq!(locked.short_commit(), Some("e6940e4"));
+        // another branch of the same repository isn't the same package
+        assert!(lock
+            .find_git("std", &details(&std.git.clone().unwrap(), None))
+            .is_none());
+    }
+
+    #[test]
+    fn packages_found_by_path_are_told_apart_by_it() {
+        let lock_dir = Path::new("/project");
+        let member = LockedPackage::local("utils", &lock_dir.join("utils"), lock_dir);
+        let vendored = LockedPackage::local("utils", &lock_dir.join("vendor/utils"), lock_dir);
+        assert_eq!(member.path.as_deref(), Some("utils"));
+        assert_eq!(vendored.path.as_deref(), Some("vendor/utils"));
+        assert!(!member.is_same_source(&vendored));
+
+        let outside = LockedPackage::local("shared", Path::new("/shared"), lock_dir);
+        assert_eq!(outside.path.as_deref(), Some("../shared"));
+    }
+
+    #[test]
+    fn checksum_covers_names_and_contents() {
+        let dir = std::env::temp_dir().join(format!("forc-pkg-checksum-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Forc.toml"), "[project]\n").unwrap();
+        fs::write(dir.join("src").join("lib.sw"), "library lib;\n").unwrap();
+        let checksum = checksum_dir(&dir).unwrap();
+        assert_eq!(checksum, checksum_dir(&dir).unwrap());
+
+        fs::write(dir.join("src").join("lib.sw"), "library lib2;\n").unwrap();
+        let changed = checksum_dir(&dir).unwrap();
+        assert_ne!(checksum, changed);
+
+        fs::rename(
+            dir.join("src").join("lib.sw"),
+            dir.join("src").join("main.sw"),
+        )
+        .unwrap();
+        assert_ne!(changed, checksum_dir(&dir).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
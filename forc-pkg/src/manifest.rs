@@ -0,0 +1,217 @@
+use crate::dependency::Dependency;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use sway_utils::constants::{self, DEFAULT_NODE_URL};
+
+// using https://github.com/rust-lang/cargo/blob/master/src/cargo/util/toml/mod.rs as the source of
+// implementation strategy
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Manifest {
+    pub project: Project,
+    pub network: Option<Network>,
+    pub dependencies: Option<BTreeMap<String, Dependency>>,
+    pub profile: Option<Profiles>,
+}
+
+impl Manifest {
+    /// The release build profile if `release` is set, or the debug one, with the settings the
+    /// manifest doesn't give taken from the profile's defaults.
+    pub fn build_profile(&self, release: bool) -> BuildProfile {
+        let profiles = self.profile.as_ref();
+        let (default, settings) = if release {
+            (
+                BuildProfile::release(),
+                profiles.and_then(|p| p.release.as_ref()),
+            )
+        } else {
+            (
+                BuildProfile::debug(),
+                profiles.and_then(|p| p.debug.as_ref()),
+            )
+        };
+        match settings {
+            Some(settings) => BuildProfile {
+                name: default.name,
+                use_ir: settings.use_ir.unwrap_or(default.use_ir),
+                optimize: settings.optimize.unwrap_or(default.optimize),
+                checked_arithmetic: settings
+                    .checked_arithmetic
+                    .unwrap_or(default.checked_arithmetic),
+                source_map: settings.source_map.unwrap_or(default.source_map),
+            },
+            None => default,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Project {
+    pub author: String,
+    pub name: String,
+    pub organization: Option<String>,
+    pub license: String,
+    #[serde(default = "default_entry")]
+    pub entry: String,
+}
+
+fn default_entry() -> String {
+    "main.sw".into()
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Network {
+    #[serde(default = "default_url")]
+    pub url: String,
+}
+
+fn default_url() -> String {
+    DEFAULT_NODE_URL.into()
+}
+
+/// The `[profile.debug]` and `[profile.release]` sections of a manifest.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Profiles {
+    pub debug: Option<ProfileSettings>,
+    pub release: Option<ProfileSettings>,
+}
+
+/// The settings a manifest gives for a build profile. Those left out keep their default.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProfileSettings {
+    pub use_ir: Option<bool>,
+    pub optimize: Option<bool>,
+    pub checked_arithmetic: Option<bool>,
+    pub source_map: Option<bool>,
+}
+
+/// How a package is built, and where its artifacts go: `out/<name>/` next to the manifest, or to
+/// the workspace manifest for a member of a workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildProfile {
+    pub name: &'static str,
+    /// Compile through the IR pipeline.
+    pub use_ir: bool,
+    /// Run the optimisation passes of the IR pipeline that aren't needed to generate code.
+    pub optimize: bool,
+    /// Revert when arithmetic overflows, instead of wrapping around.
+    pub checked_arithmetic: bool,
+    /// Write the source map of the bytecode along with it.
+    pub source_map: bool,
+}
+
+impl BuildProfile {
+    pub fn debug() -> Self {
+        BuildProfile {
+            name: "debug",
+            use_ir: false,
+            optimize: false,
+            checked_arithmetic: true,
+            source_map: true,
+        }
+    }
+
+    /// Optimised builds without a source map. Arithmetic is still checked for overflow, as in
+    /// debug builds, unless the manifest sets `checked-arithmetic = false` for the profile.
+    pub fn release() -> Self {
+        BuildProfile {
+            name: "release",
+            use_ir: false,
+            optimize: true,
+            checked_arithmetic: true,
+            source_map: false,
+        }
+    }
+}
+
+pub fn find_main_path(manifest_dir: &Path, manifest: &Manifest) -> PathBuf {
+    let mut code_dir = manifest_dir.to_path_buf();
+    code_dir.push(constants::SRC_DIR);
+    code_dir.push(&manifest.project.entry);
+    code_dir
+}
+
+pub fn find_file_name<'sc>(manifest_dir: &Path, main_path: &'sc Path) -> Result<&'sc Path, String> {
+    let mut file_path = manifest_dir.to_path_buf();
+    file_path.pop();
+    let file_name = match main_path.strip_prefix(file_path.clone()) {
+        Ok(o) => o,
+        Err(err) => return Err(err.to_string()),
+    };
+    Ok(file_name)
+}
+
+pub fn read_manifest(manifest_dir: &Path) -> Result<Manifest, String> {
+    let manifest_path = {
+        let mut man = PathBuf::from(manifest_dir);
+        man.push(constants::MANIFEST_FILE_NAME);
+        man
+    };
+    let manifest_path_str = format!("{:?}", manifest_path);
+    let manifest = match std::fs::read_to_string(manifest_path) {
+        Ok(o) => o,
+        Err(e) => {
+            return Err(format!(
+                "failed to read manifest at {:?}: {}",
+                manifest_path_str, e
+            ))
+        }
+    };
+    match toml::from_str(&manifest) {
+        Ok(o) => Ok(o),
+        Err(e) => Err(format!("Error parsing manifest: {}.", e)),
+    }
+}
+
+pub fn get_main_file(manifest_of_dep: &Manifest, manifest_dir: &Path) -> Result<Arc<str>, String> {
+    let main_path = {
+        let mut code_dir = PathBuf::from(manifest_dir);
+        code_dir.push(constants::SRC_DIR);
+        code_dir.push(&manifest_of_dep.project.entry);
+        code_dir
+    };
+
+    // some hackery to get around lifetimes for now, until the AST returns a non-lifetime-bound AST
+    let main_file = std::fs::read_to_string(&main_path).map_err(|e| e.to_string())?;
+    let main_file = Arc::from(main_file);
+    Ok(main_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_fall_back_to_their_defaults() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[project]
+author = "user"
+license = "Apache-2.0"
+name = "app"
+
+[profile.release]
+use-ir = true
+checked-arithmetic = false
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.build_profile(false), BuildProfile::debug());
+        assert_eq!(
+            manifest.build_profile(true),
+            BuildProfile {
+                use_ir: true,
+                checked_arithmetic: false,
+                ..BuildProfile::release()
+            }
+        );
+    }
+}
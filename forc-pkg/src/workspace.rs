@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use sway_utils::constants;
+
+/// Packages built together, listed in a `Forc.toml` with a `[workspace]` section instead of a
+/// `[project]` one:
+///
+/// ```toml
+/// [workspace]
+/// members = ["my_abi", "my_contract", "my_script"]
+/// ```
+///
+/// The members share a single `Forc.lock`, next to the workspace manifest, and a dependency of
+/// several of them is only compiled once. Members may depend on each other by path.
+#[derive(Debug)]
+pub struct Workspace {
+    /// The directory of the workspace manifest.
+    pub dir: PathBuf,
+    /// The manifest directories of the members, in the order they are listed.
+    pub members: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct WorkspaceManifest {
+    workspace: WorkspaceSection,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct WorkspaceSection {
+    /// The directories of the members, relative to the workspace manifest.
+    members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Reads the manifest in `dir` as that of a workspace, or returns `None` if it is the
+    /// manifest of a package.
+    pub fn load(dir: &Path) -> Result<Option<Self>, String> {
+        let manifest_path = dir.join(constants::MANIFEST_FILE_NAME);
+        let manifest = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("failed to read manifest at {:?}: {}", manifest_path, e))?;
+        let value: toml::Value =
+            toml::from_str(&manifest).map_err(|e| format!("Error parsing manifest: {}.", e))?;
+        match (value.get("workspace"), value.get("project")) {
+            (None, _) => return Ok(None),
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "The manifest at {:?} has both a [workspace] and a [project] section. A \
+                     workspace can't be a package itself; move the package to a directory of \
+                     its own and list it as a member.",
+                    manifest_path
+                ))
+            }
+            (Some(_), None) => (),
+        }
+        let manifest: WorkspaceManifest = value
+            .try_into()
+            .map_err(|e| format!("Error parsing manifest: {}.", e))?;
+
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut members = vec![];
+        for member in &manifest.workspace.members {
+            let member_dir = dir.join(member);
+            if !member_dir.join(constants::MANIFEST_FILE_NAME).exists() {
+                return Err(format!(
+                    "Workspace member {:?} has no `{}`.",
+                    member,
+                    constants::MANIFEST_FILE_NAME
+                ));
+            }
+            let member_dir = member_dir.canonicalize().unwrap_or(member_dir);
+            if Workspace::load(&member_dir)?.is_some() {
+                return Err(format!(
+                    "Workspace member {:?} is a workspace itself. Workspaces can't be nested.",
+                    member
+                ));
+            }
+            members.push(member_dir);
+        }
+        Ok(Some(Workspace { dir, members }))
+    }
+
+    /// The workspace the package in `manifest_dir` is a member of, if any, found in the
+    /// directories above it.
+    pub fn find(manifest_dir: &Path) -> Result<Option<Self>, String> {
+        let manifest_dir = manifest_dir
+            .canonicalize()
+            .unwrap_or_else(|_| manifest_dir.to_path_buf());
+        for dir in manifest_dir.ancestors().skip(1) {
+            if !dir.join(constants::MANIFEST_FILE_NAME).exists() {
+                continue;
+            }
+            if let Some(workspace) = Workspace::load(dir)? {
+                if workspace.members.contains(&manifest_dir) {
+                    return Ok(Some(workspace));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn members_find_their_workspace() {
+        let dir = std::env::temp_dir().join(format!("forc-pkg-workspace-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for member in ["abi", "contract", "other"] {
+            fs::create_dir_all(dir.join(member)).unwrap();
+            fs::write(
+                dir.join(member).join("Forc.toml"),
+                format!("[project]\nname = \"{}\"\n", member),
+            )
+            .unwrap();
+        }
+        fs::write(
+            dir.join("Forc.toml"),
+            "[workspace]\nmembers = [\"contract\", \"abi\"]\n",
+        )
+        .unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        let workspace = Workspace::load(&dir).unwrap().unwrap();
+        assert_eq!(workspace.members, [dir.join("contract"), dir.join("abi")]);
+        assert!(Workspace::load(&dir.join("abi")).unwrap().is_none());
+
+        let found = Workspace::find(&dir.join("abi")).unwrap().unwrap();
+        assert_eq!(found.dir, dir);
+        // a package in the directory of a workspace isn't part of it unless it is listed
+        assert!(Workspace::find(&dir.join("other")).unwrap().is_none());
+
+        fs::write(
+            dir.join("Forc.toml"),
+            "[workspace]\nmembers = [\"abi\", \"missing\"]\n",
+        )
+        .unwrap();
+        let error = Workspace::load(&dir).unwrap_err();
+        assert!(error.contains("\"missing\""));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
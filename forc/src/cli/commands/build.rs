@@ -25,6 +25,10 @@ pub struct Command {
     /// If set, outputs source file mapping in JSON format
     #[structopt(short = "g", long)]
     pub debug_outfile: Option<String>,
+    /// Build with the release profile instead of the debug one. Artifacts are written to
+    /// `out/release` instead of `out/debug`.
+    #[structopt(long)]
+    pub release: bool,
     /// Offline mode, prevents Forc from using the network when managing dependencies.
     /// Meaning it will only try to use previously downloaded dependencies.
     #[structopt(long = "offline")]
@@ -35,6 +39,6 @@ pub struct Command {
 }
 
 pub(crate) fn exec(command: Command) -> Result<(), String> {
-    forc_build::build(command)?;
+    forc_build::build_all(command)?;
     Ok(())
 }
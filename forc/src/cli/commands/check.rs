@@ -0,0 +1,47 @@
+use crate::ops::forc_check;
+use std::str::FromStr;
+use structopt::{self, StructOpt};
+
+/// Check the current or target project for errors, without compiling it to bytecode.
+///
+/// The project and its dependencies are parsed, type checked and go through control flow
+/// analysis, which is where every error and warning is found.
+#[derive(Debug, StructOpt)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[structopt(short, long)]
+    pub path: Option<String>,
+    /// How errors and warnings are printed: `human`, or `json` for one JSON object per line on
+    /// the standard output.
+    #[structopt(long, default_value = "human", possible_values = &["human", "json"])]
+    pub message_format: MessageFormat,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[structopt(long = "offline")]
+    pub offline_mode: bool,
+    /// Silent mode. Don't output any warnings or errors to the command line.
+    #[structopt(long = "silent", short = "s")]
+    pub silent_mode: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageFormat {
+    Human,
+    Json,
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!("unknown message format `{}`", s)),
+        }
+    }
+}
+
+pub(crate) fn exec(command: Command) -> Result<(), String> {
+    forc_check::check(command)
+}
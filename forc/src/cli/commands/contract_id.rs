@@ -0,0 +1,39 @@
+use crate::ops::forc_contract_id;
+use fuel_tx::{Bytes32, Salt};
+use structopt::{self, StructOpt};
+
+/// Compute the ID of a contract project without deploying it.
+/// Builds the contract and prints the ID it would be deployed with, given its salt and the root of
+/// its initial storage.
+#[derive(Debug, StructOpt)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[structopt(short, long)]
+    pub path: Option<String>,
+    /// The salt of the deployment, as hex. Defaults to zero, the salt used by `forc deploy`.
+    #[structopt(long)]
+    pub salt: Option<Salt>,
+    /// The root of the initial storage of the contract, as hex. Defaults to the root of an empty
+    /// storage, the storage deployed by `forc deploy`.
+    #[structopt(long)]
+    pub state_root: Option<Bytes32>,
+    /// Whether to compile using the IR pipeline.
+    #[structopt(long)]
+    pub use_ir: bool,
+    /// Build with the release profile instead of the debug one.
+    #[structopt(long)]
+    pub release: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[structopt(long = "offline")]
+    pub offline_mode: bool,
+    /// Silent mode. Don't output any warnings or errors to the command line.
+    #[structopt(long = "silent", short = "s")]
+    pub silent_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<(), String> {
+    let contract_id = forc_contract_id::contract_id(command).map_err(|e| e.message)?;
+    println!("0x{:x}", contract_id);
+    Ok(())
+}
@@ -26,6 +26,9 @@ pub struct Command {
     /// If set, outputs source file mapping in JSON format
     #[structopt(short = "g", long)]
     pub debug_outfile: Option<String>,
+    /// Build with the release profile instead of the debug one.
+    #[structopt(long)]
+    pub release: bool,
     /// Offline mode, prevents Forc from using the network when managing dependencies.
     /// Meaning it will only try to use previously downloaded dependencies.
     #[structopt(long = "offline")]
@@ -0,0 +1,28 @@
+use crate::ops::forc_doc;
+use structopt::{self, StructOpt};
+
+/// Build the documentation of the current or target project, and of its dependencies.
+///
+/// The `///` doc comments of the project's items are rendered, along with their signatures, as
+/// static HTML pages in `out/doc`, one per module, which can be read and searched offline.
+#[derive(Debug, StructOpt)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[structopt(short, long)]
+    pub path: Option<String>,
+    /// Document the private items of libraries too. Every item of a script, contract or predicate
+    /// is always documented.
+    #[structopt(long)]
+    pub document_private_items: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[structopt(long = "offline")]
+    pub offline_mode: bool,
+    /// Silent mode. Don't output any warnings or errors to the command line.
+    #[structopt(long = "silent", short = "s")]
+    pub silent_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<(), String> {
+    forc_doc::doc(command)
+}
@@ -1,13 +1,21 @@
-use crate::ops::forc_init;
+use crate::ops::forc_init::{self, Template};
 use structopt::{self, StructOpt};
 
 /// Create a new Forc project.
 #[derive(Debug, StructOpt)]
 pub(crate) struct Command {
     project_name: String,
+    /// The kind of project to create: `contract`, `script`, `predicate` or `library`. The path to
+    /// a directory can be given instead, which is copied as the new project, replacing
+    /// `{{project_name}}` with the name of the project in the names and contents of its files.
+    #[structopt(long, default_value = "script")]
+    template: Template,
 }
 
 pub(crate) fn exec(command: Command) -> Result<(), String> {
-    let project_name = command.project_name;
-    forc_init::init_new_project(project_name).map_err(|e| e.to_string())
+    let Command {
+        project_name,
+        template,
+    } = command;
+    forc_init::init_new_project(project_name, template).map_err(|e| e.to_string())
 }
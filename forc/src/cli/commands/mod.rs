@@ -1,12 +1,16 @@
 pub mod addr2line;
 pub mod build;
+pub mod check;
+pub mod contract_id;
 pub mod deploy;
+pub mod doc;
 pub mod explorer;
 pub mod format;
 pub mod init;
 pub mod json_abi;
 pub mod lsp;
 pub mod parse_bytecode;
+pub mod predicate_root;
 pub mod run;
 pub mod test;
 pub mod update;
@@ -0,0 +1,31 @@
+use crate::ops::forc_predicate_root;
+use structopt::{self, StructOpt};
+
+/// Compute the address of a predicate project.
+/// Builds the predicate and prints the address of the coins it owns, which is derived from its
+/// bytecode.
+#[derive(Debug, StructOpt)]
+pub struct Command {
+    /// Path to the project, if not specified, current working directory will be used.
+    #[structopt(short, long)]
+    pub path: Option<String>,
+    /// Whether to compile using the IR pipeline.
+    #[structopt(long)]
+    pub use_ir: bool,
+    /// Build with the release profile instead of the debug one.
+    #[structopt(long)]
+    pub release: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[structopt(long = "offline")]
+    pub offline_mode: bool,
+    /// Silent mode. Don't output any warnings or errors to the command line.
+    #[structopt(long = "silent", short = "s")]
+    pub silent_mode: bool,
+}
+
+pub(crate) fn exec(command: Command) -> Result<(), String> {
+    let address = forc_predicate_root::predicate_root(command).map_err(|e| e.message)?;
+    println!("0x{:x}", address);
+    Ok(())
+}
@@ -2,7 +2,8 @@ use crate::ops::forc_run;
 use structopt::{self, StructOpt};
 
 /// Run script project.
-/// Crafts a script transaction then sends it to a running node.
+/// Crafts a script transaction then sends it to a running node, or runs it locally with
+/// `--dry-run`.
 #[derive(Debug, StructOpt)]
 pub struct Command {
     /// Hex string of data to input to script.
@@ -17,7 +18,8 @@ pub struct Command {
     #[structopt(long)]
     pub use_ir: bool,
 
-    /// Only craft transaction and print it out.
+    /// Run the script in a local, in-memory VM instead of sending it to a node, and print its
+    /// receipts, return value and the gas it used.
     #[structopt(long)]
     pub dry_run: bool,
 
@@ -49,6 +51,9 @@ pub struct Command {
     /// If set, outputs source file mapping in JSON format
     #[structopt(short = "g", long)]
     pub debug_outfile: Option<String>,
+    /// Build with the release profile instead of the debug one.
+    #[structopt(long)]
+    pub release: bool,
 
     /// Silent mode. Don't output any warnings or errors to the command line.
     #[structopt(long = "silent", short = "s")]
@@ -61,6 +66,11 @@ pub struct Command {
     /// 32-byte contract ID that will be called during the transaction.
     #[structopt(long = "contract")]
     pub contract: Option<Vec<String>>,
+
+    /// Paths to contract projects to compile and deploy to the VM of a dry run before the
+    /// script is run, so that it can call them.
+    #[structopt(long = "preload-contract", requires = "dry-run")]
+    pub preload_contracts: Option<Vec<String>>,
 }
 
 pub(crate) async fn exec(command: Command) -> Result<(), String> {
@@ -1,50 +1,31 @@
-use std::io::{BufRead, BufReader};
-use std::process::Command as ProcessCommand;
-use std::process::Stdio;
-use std::thread;
+use crate::ops::forc_test;
 use structopt::{self, StructOpt};
 
-/// Run Rust-based tests on current project.
-/// As of now, `forc test` is a simple wrapper on
-/// `cargo test`; `forc init` also creates a rust
-/// package under your project, named `tests`.
-/// You can opt to either run these Rust tests by
-/// using `forc test` or going inside the package
-/// and using `cargo test`.
+/// Run the tests of the current or target project.
+///
+/// Every function marked `#[test]` is compiled on its own, as the entry point of a
+/// script, and run in a local Fuel VM. A test passes unless it reverts. Projects
+/// created by `forc init` also have a Rust test harness, in the `tests` package,
+/// which is run afterwards with `cargo test`.
 #[derive(Debug, StructOpt)]
-pub(crate) struct Command {
+pub struct Command {
     /// If specified, only run tests containing this string in their names
     pub test_name: Option<String>,
+    /// Path to the project, if not specified, current working directory will be used.
+    #[structopt(short, long)]
+    pub path: Option<String>,
+    /// Whether to compile the tests using the IR pipeline.
+    #[structopt(long)]
+    pub use_ir: bool,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[structopt(long = "offline")]
+    pub offline_mode: bool,
+    /// Silent mode. Don't output any warnings or errors to the command line.
+    #[structopt(long = "silent", short = "s")]
+    pub silent_mode: bool,
 }
 
 pub(crate) fn exec(command: Command) -> Result<(), String> {
-    // Cargo args setup
-    let mut args: Vec<String> = vec!["test".into()];
-    if let Some(name) = command.test_name {
-        args.push(name);
-    };
-    args.push("--color".into());
-    args.push("always".into());
-    args.push("--".into());
-    args.push("--nocapture".into());
-
-    let mut child = ProcessCommand::new("cargo")
-        .args(args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .unwrap();
-
-    let out = BufReader::new(child.stdout.take().unwrap());
-    let err = BufReader::new(child.stderr.take().unwrap());
-
-    // Reading stderr on a separate thread so we keep things non-blocking
-    let thread = thread::spawn(move || {
-        err.lines().for_each(|line| println!("{}", line.unwrap()));
-    });
-
-    out.lines().for_each(|line| println!("{}", line.unwrap()));
-    thread.join().unwrap();
-
-    Ok(())
+    forc_test::test(command)
 }
@@ -1,7 +1,8 @@
 use crate::ops::forc_update;
 use structopt::{self, StructOpt};
 
-/// Update dependencies in the Forc dependencies directory.
+/// Update the commits of the dependencies locked in `Forc.lock`, downloading them to the Forc
+/// dependencies directory.
 #[derive(Debug, StructOpt)]
 pub struct Command {
     /// Path to the project, if not specified, current working directory will be used.
@@ -9,7 +10,7 @@ pub struct Command {
     pub path: Option<String>,
 
     /// Dependency to be updated.
-    /// If not set, all dependencies will be updated.
+    /// If not set, all dependencies will be updated, along with their own dependencies.
     #[structopt(short = "d")]
     pub target_dependency: Option<String>,
 
@@ -2,21 +2,25 @@ use structopt::StructOpt;
 
 mod commands;
 use self::commands::{
-    addr2line, build, deploy, explorer, format, init, json_abi, lsp, parse_bytecode, run, test,
-    update,
+    addr2line, build, check, contract_id, deploy, doc, explorer, format, init, json_abi, lsp,
+    parse_bytecode, predicate_root, run, test, update,
 };
 
 use addr2line::Command as Addr2LineCommand;
 pub use build::Command as BuildCommand;
+pub use check::{Command as CheckCommand, MessageFormat};
+pub use contract_id::Command as ContractIdCommand;
 pub use deploy::Command as DeployCommand;
+pub use doc::Command as DocCommand;
 pub use explorer::Command as ExplorerCommand;
 pub use format::Command as FormatCommand;
 use init::Command as InitCommand;
 pub use json_abi::Command as JsonAbiCommand;
 use lsp::Command as LspCommand;
 use parse_bytecode::Command as ParseBytecodeCommand;
+pub use predicate_root::Command as PredicateRootCommand;
 pub use run::Command as RunCommand;
-use test::Command as TestCommand;
+pub use test::Command as TestCommand;
 pub use update::Command as UpdateCommand;
 
 #[derive(Debug, StructOpt)]
@@ -32,12 +36,16 @@ enum Forc {
     #[structopt(name = "addr2line")]
     Addr2Line(Addr2LineCommand),
     Build(BuildCommand),
+    Check(CheckCommand),
+    ContractId(ContractIdCommand),
     Deploy(DeployCommand),
+    Doc(DocCommand),
     Explorer(ExplorerCommand),
     #[structopt(name = "fmt")]
     Format(FormatCommand),
     Init(InitCommand),
     ParseBytecode(ParseBytecodeCommand),
+    PredicateRoot(PredicateRootCommand),
     Run(RunCommand),
     Test(TestCommand),
     Update(UpdateCommand),
@@ -50,11 +58,15 @@ pub(crate) async fn run_cli() -> Result<(), String> {
     match opt.command {
         Forc::Addr2Line(command) => addr2line::exec(command),
         Forc::Build(command) => build::exec(command),
+        Forc::Check(command) => check::exec(command),
+        Forc::ContractId(command) => contract_id::exec(command),
         Forc::Deploy(command) => deploy::exec(command).await,
+        Forc::Doc(command) => doc::exec(command),
         Forc::Explorer(command) => explorer::exec(command).await,
         Forc::Format(command) => format::exec(command),
         Forc::Init(command) => init::exec(command),
         Forc::ParseBytecode(command) => parse_bytecode::exec(command),
+        Forc::PredicateRoot(command) => predicate_root::exec(command),
         Forc::Run(command) => run::exec(command).await,
         Forc::Test(command) => test::exec(command),
         Forc::Update(command) => update::exec(command).await,
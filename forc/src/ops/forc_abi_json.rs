@@ -1,25 +1,24 @@
-use crate::utils::dependency::{Dependency, DependencyDetails};
 use crate::{
     cli::JsonAbiCommand,
     utils::dependency,
-    utils::helpers::{
-        find_file_name, find_main_path, get_main_file, print_on_failure, print_on_success,
-        read_manifest,
-    },
+    utils::helpers::{print_on_failure, print_on_success},
+};
+use forc_pkg::{
+    find_file_name, find_main_path, get_main_file, read_manifest, PackageGraph, Workspace,
 };
 
 use sway_types::{Function, JsonABI};
 use sway_utils::{find_manifest_dir, MANIFEST_FILE_NAME};
 
 use anyhow::Result;
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use sway_core::{
-    create_module, BuildConfig, CompileAstResult, NamespaceRef, NamespaceWrapper, TreeType,
-    TypedParseTree,
+    create_module, parse, BuildConfig, CompileAstResult, NamespaceRef, NamespaceWrapper, TreeType,
+    TypeEngine, TypedParseTree,
 };
 
 pub fn build(command: JsonAbiCommand) -> Result<Value, String> {
@@ -47,68 +46,54 @@ pub fn build(command: JsonAbiCommand) -> Result<Value, String> {
             ))
         }
     };
-    let mut manifest = read_manifest(&manifest_dir)?;
-    let main_path = find_main_path(&manifest_dir, &manifest);
-    let file_name = find_file_name(&manifest_dir, &main_path)?;
+    let output_json = match Workspace::load(&manifest_dir)? {
+        Some(workspace) => build_workspace(&workspace, offline_mode, silent_mode)?,
+        None => build_package(&manifest_dir, offline_mode, silent_mode)?,
+    };
 
-    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
-        file_name.to_owned(),
-        manifest_dir.clone(),
-    );
-    let mut dependency_graph = HashMap::new();
-    let mut json_abi = vec![];
+    if let Some(outfile) = json_outfile {
+        let file = File::create(outfile).map_err(|e| e.to_string())?;
+        serde_json::to_writer(&file, &output_json).map_err(|e| e.to_string())?;
+    } else {
+        println!("{}", output_json);
+    }
 
-    let namespace = create_module();
-    if let Some(ref mut deps) = manifest.dependencies {
-        for (dependency_name, dependency_details) in deps.iter_mut() {
-            // Check if dependency is a git-based dependency.
-            let dep = match dependency_details {
-                Dependency::Simple(..) => {
-                    return Err(
-                        "Not yet implemented: Simple version-spec dependencies require a registry."
-                            .into(),
-                    );
-                }
-                Dependency::Detailed(dep_details) => dep_details,
-            };
+    Ok(output_json)
+}
 
-            // Download a non-local dependency if the `git` property is set in this dependency.
-            if let Some(git) = &dep.git {
-                let fully_qualified_dep_name = format!("{}-{}", dependency_name, git);
-                let downloaded_dep_path = match dependency::download_github_dep(
-                    &fully_qualified_dep_name,
-                    git,
-                    &dep.branch,
-                    &dep.version,
-                    offline_mode.into(),
-                ) {
-                    Ok(path) => path,
-                    Err(e) => {
-                        return Err(format!(
-                            "Couldn't download dependency ({:?}): {:?}",
-                            dependency_name, e
-                        ))
-                    }
-                };
+fn build_package(
+    manifest_dir: &Path,
+    offline_mode: bool,
+    silent_mode: bool,
+) -> Result<Value, String> {
+    let manifest = read_manifest(manifest_dir)?;
+    let lock = dependency::fetch_and_lock(manifest_dir, offline_mode).map_err(|e| e.to_string())?;
+    let main_path = find_main_path(manifest_dir, &manifest);
+    let file_name = find_file_name(manifest_dir, &main_path)?;
 
-                // Mutate this dependency's path to hold the newly downloaded dependency's path.
-                dep.path = Some(downloaded_dep_path);
-            }
+    // the package and its dependencies share a namespace, so their types must share an engine,
+    // which is entered for the ABIs to be generated from them
+    let type_engine = TypeEngine::new();
+    let _type_engine = type_engine.enter();
+    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+        file_name.to_owned(),
+        manifest_dir.to_path_buf(),
+    )
+    .type_engine(type_engine.clone());
+    let mut dependency_graph = HashMap::new();
 
-            json_abi.append(&mut compile_dependency_lib(
-                &this_dir,
-                dependency_name,
-                dependency_details,
-                namespace,
-                &mut dependency_graph,
-                silent_mode,
-                offline_mode,
-            )?);
-        }
-    }
+    let graph = PackageGraph::load(manifest_dir, &lock)?;
+    let (namespace, mut json_abi) = compile_dependencies(
+        &graph,
+        graph.roots()[0],
+        &mut HashMap::new(),
+        &mut dependency_graph,
+        &type_engine,
+        silent_mode,
+    )?;
 
     // now, compile this program with all of its dependencies
-    let main_file = get_main_file(&manifest, &manifest_dir)?;
+    let main_file = get_main_file(&manifest, manifest_dir)?;
 
     let mut res = compile(
         main_file,
@@ -120,137 +105,126 @@ pub fn build(command: JsonAbiCommand) -> Result<Value, String> {
     )?;
     json_abi.append(&mut res);
 
-    let output_json = json!(json_abi);
-
-    if let Some(outfile) = json_outfile {
-        let file = File::create(outfile).map_err(|e| e.to_string())?;
-        serde_json::to_writer(&file, &output_json).map_err(|e| e.to_string())?;
-    } else {
-        println!("{}", output_json);
-    }
-
-    Ok(output_json)
+    Ok(json!(json_abi))
 }
 
-/// Takes a dependency and returns a namespace of exported things from that dependency
-/// trait implementations are included as well
-fn compile_dependency_lib<'manifest>(
-    project_file_path: &Path,
-    dependency_name: &'manifest str,
-    dependency_lib: &mut Dependency,
-    namespace: NamespaceRef,
-    dependency_graph: &mut HashMap<String, HashSet<String>>,
-    silent_mode: bool,
+/// Generates the ABIs of the members of `workspace` that aren't libraries, as an object with a
+/// field for each.
+fn build_workspace(
+    workspace: &Workspace,
     offline_mode: bool,
-) -> Result<Vec<Function>, String> {
-    let mut details = match dependency_lib {
-        Dependency::Simple(..) => {
-            return Err(
-                "Not yet implemented: Simple version-spec dependencies require a registry.".into(),
-            )
-        }
-        Dependency::Detailed(ref mut details) => details,
-    };
-    // Download a non-local dependency if the `git` property is set in this dependency.
-    if let Some(ref git) = details.git {
-        // the qualified name of the dependency includes its source and some metadata to prevent
-        // conflating dependencies from different sources
-        let fully_qualified_dep_name = format!("{}-{}", dependency_name, git);
-        let downloaded_dep_path = match dependency::download_github_dep(
-            &fully_qualified_dep_name,
-            git,
-            &details.branch,
-            &details.version,
-            offline_mode.into(),
-        ) {
-            Ok(path) => path,
-            Err(e) => {
-                return Err(format!(
-                    "Couldn't download dependency ({:?}): {:?}",
-                    dependency_name, e
-                ))
-            }
-        };
+    silent_mode: bool,
+) -> Result<Value, String> {
+    let lock =
+        dependency::fetch_and_lock(&workspace.dir, offline_mode).map_err(|e| e.to_string())?;
+    let graph = PackageGraph::load_workspace(workspace, &lock)?;
 
-        // Mutate this dependency's path to hold the newly downloaded dependency's path.
-        details.path = Some(downloaded_dep_path);
-    }
-    let dep_path = match dependency_lib {
-        Dependency::Simple(..) => {
-            return Err(
-                "Not yet implemented: Simple version-spec dependencies require a registry.".into(),
-            )
+    // the members share their dependencies, so their types must share an engine
+    let type_engine = TypeEngine::new();
+    let _type_engine = type_engine.enter();
+    let mut dependency_graph = HashMap::new();
+    let mut compiled = HashMap::new();
+    let mut output_json = Map::new();
+    for &index in graph.compile_order() {
+        if !graph.is_root(index) {
+            continue;
         }
-        Dependency::Detailed(DependencyDetails { path, .. }) => path,
-    };
-
-    let dep_path =
-        match dep_path {
-            Some(p) => p,
-            None => return Err(
-                "Only simple path imports are supported right now. Please supply a path relative \
-                 to the manifest file."
-                    .into(),
-            ),
-        };
-
-    // dependency paths are relative to the path of the project being compiled
-    let mut project_path = PathBuf::from(project_file_path);
-    project_path.push(dep_path);
-
-    // compile the dependencies of this dependency
-    // this should detect circular dependencies
-    let manifest_dir = match find_manifest_dir(&project_path) {
-        Some(o) => o,
-        None => {
-            return Err(format!(
-                "Manifest not found for dependency {:?}.",
-                project_path
-            ))
+        let package = &graph.packages()[index];
+        let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
+        // libraries are compiled along with the members depending on them
+        let is_library = matches!(
+            parse(main_file.clone(), None).value,
+            Some(parse_tree) if matches!(parse_tree.tree_type, TreeType::Library { .. })
+        );
+        if is_library {
+            continue;
         }
-    };
-    let mut manifest_of_dep = read_manifest(&manifest_dir)?;
-    let main_path = find_main_path(&manifest_dir, &manifest_of_dep);
-    let file_name = find_file_name(&manifest_dir, &main_path)?;
 
-    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
-        file_name.to_owned(),
-        manifest_dir.clone(),
-    );
-
-    let dep_namespace = create_module();
-    // The part below here is just a massive shortcut to get the standard library working
-    if let Some(ref mut deps) = manifest_of_dep.dependencies {
-        for ref mut dep in deps {
-            // to do this properly, iterate over list of dependencies make sure there are no
-            // circular dependencies
-            compile_dependency_lib(
-                &manifest_dir,
-                dep.0,
-                dep.1,
-                dep_namespace,
-                dependency_graph,
-                silent_mode,
-                offline_mode,
-            )?;
-        }
+        let (namespace, mut json_abi) = compile_dependencies(
+            &graph,
+            index,
+            &mut compiled,
+            &mut dependency_graph,
+            &type_engine,
+            silent_mode,
+        )?;
+        let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+        let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            file_name.to_owned(),
+            package.manifest_dir.clone(),
+        )
+        .type_engine(type_engine.clone());
+        let mut res = compile(
+            main_file,
+            package.name(),
+            namespace,
+            build_config,
+            &mut dependency_graph,
+            silent_mode,
+        )?;
+        json_abi.append(&mut res);
+        output_json.insert(package.name().to_string(), json!(json_abi));
     }
+    Ok(Value::Object(output_json))
+}
 
-    let main_file = get_main_file(&manifest_of_dep, &manifest_dir)?;
+/// Compiles the packages of `graph` the package at `root` depends on, each once and after the
+/// packages it depends on, and returns the namespace `root` is compiled in, holding them, along
+/// with the ABIs of its own dependencies. Trait implementations are included as well. Packages
+/// already in `compiled`, which those compiled are added to, aren't compiled again.
+fn compile_dependencies(
+    graph: &PackageGraph,
+    root: usize,
+    compiled: &mut HashMap<usize, (NamespaceRef, JsonABI)>,
+    dependency_graph: &mut HashMap<String, HashSet<String>>,
+    type_engine: &TypeEngine,
+    silent_mode: bool,
+) -> Result<(NamespaceRef, JsonABI), String> {
+    for index in graph.dependencies_of(root) {
+        if compiled.contains_key(&index) {
+            continue;
+        }
+        let package = &graph.packages()[index];
+        let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+        let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            file_name.to_path_buf(),
+            package.manifest_dir.clone(),
+        )
+        .type_engine(type_engine.clone());
+        let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
 
-    let (compiled, json_abi) = compile_library(
-        main_file,
-        &manifest_of_dep.project.name,
-        dep_namespace,
-        build_config,
-        dependency_graph,
-        silent_mode,
-    )?;
+        let compiled_package = compile_library(
+            main_file,
+            package.name(),
+            package_namespace(graph, index, compiled),
+            build_config,
+            dependency_graph,
+            silent_mode,
+        )?;
+        compiled.insert(index, compiled_package);
+    }
 
-    namespace.insert_module_ref(dependency_name.to_string(), compiled);
+    let json_abi = graph.packages()[root]
+        .dependencies
+        .iter()
+        .flat_map(|(_, dependency)| compiled[dependency].1.clone())
+        .collect();
+    Ok((package_namespace(graph, root, compiled), json_abi))
+}
 
-    // nothing is returned from this method since it mutates the hashmaps it was given
-    Ok(json_abi)
+/// The namespace the package at `index` is compiled in, holding its compiled dependencies.
+fn package_namespace(
+    graph: &PackageGraph,
+    index: usize,
+    compiled: &HashMap<usize, (NamespaceRef, JsonABI)>,
+) -> NamespaceRef {
+    let namespace = create_module();
+    for (dependency_name, dependency) in &graph.packages()[index].dependencies {
+        namespace.insert_module_ref(dependency_name.clone(), compiled[dependency].0);
+    }
+    namespace
 }
 
 fn compile_library(
@@ -1,12 +1,13 @@
-use crate::utils::dependency::{Dependency, DependencyDetails};
-use crate::utils::helpers::{find_file_name, find_main_path};
 use crate::{
     cli::BuildCommand,
     utils::dependency,
-    utils::helpers::{
-        get_main_file, print_on_failure, print_on_success, print_on_success_library, read_manifest,
-    },
+    utils::helpers::{print_on_failure, print_on_success, print_on_success_library},
 };
+use forc_pkg::{
+    find_file_name, find_main_path, get_main_file, lock::find_lock_dir, read_manifest,
+    BuildProfile, PackageGraph, Workspace,
+};
+use serde_json::json;
 use std::fs::{self, File};
 use std::io::Write;
 use std::sync::Arc;
@@ -15,21 +16,75 @@ use sway_utils::{constants, find_manifest_dir, MANIFEST_FILE_NAME};
 
 use sway_core::{
     create_module, source_map::SourceMap, BuildConfig, BytecodeCompilationResult,
-    CompilationResult, NamespaceRef, NamespaceWrapper,
+    CompilationResult, NamespaceRef, NamespaceWrapper, TypeEngine,
 };
 
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use sway_types::JsonABI;
 
+/// Builds the package in the directory of `command`, or the one it is in, and returns its
+/// bytecode. A workspace has no bytecode of its own, so building one is an error naming its
+/// members; [build_all] builds every one of them.
 pub fn build(command: BuildCommand) -> Result<Vec<u8>, String> {
-    // find manifest directory, even if in subdirectory
+    let manifest_dir = find_command_manifest_dir(&command)?;
+    if let Some(workspace) = Workspace::load(&manifest_dir)? {
+        let members = workspace
+            .members
+            .iter()
+            .map(|member| {
+                member
+                    .strip_prefix(&workspace.dir)
+                    .unwrap_or(member)
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(format!(
+            "`{}` is a workspace, which has no bytecode of its own. Pass the path of the member \
+             to build instead, one of: {}",
+            workspace.dir.display(),
+            members
+        ));
+    }
+    build_package(manifest_dir, command)
+}
+
+/// Builds every member of the workspace in the directory of `command`, or the package there, and
+/// returns the bytecode of each along with the name of its package.
+pub fn build_all(command: BuildCommand) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let manifest_dir = find_command_manifest_dir(&command)?;
+    match Workspace::load(&manifest_dir)? {
+        Some(workspace) => build_workspace(&workspace, command),
+        None => {
+            let name = read_manifest(&manifest_dir)?.project.name;
+            Ok(vec![(name, build_package(manifest_dir, command)?)])
+        }
+    }
+}
+
+/// The directory of the manifest of the package `command` builds, even if it is run in a
+/// subdirectory of it.
+fn find_command_manifest_dir(command: &BuildCommand) -> Result<PathBuf, String> {
     let this_dir = if let Some(ref path) = command.path {
         PathBuf::from(path)
     } else {
         std::env::current_dir().map_err(|e| format!("{:?}", e))?
     };
 
+    match find_manifest_dir(&this_dir) {
+        Some(dir) => Ok(dir),
+        None => Err(format!(
+            "could not find `{}` in `{}` or any parent directory",
+            MANIFEST_FILE_NAME,
+            this_dir.display(),
+        )),
+    }
+}
+
+fn build_package(manifest_dir: PathBuf, command: BuildCommand) -> Result<Vec<u8>, String> {
     let BuildCommand {
         binary_outfile,
         use_ir,
@@ -37,22 +92,16 @@ pub fn build(command: BuildCommand) -> Result<Vec<u8>, String> {
         print_finalized_asm,
         print_intermediate_asm,
         print_ir,
+        release,
         offline_mode,
         silent_mode,
         ..
     } = command;
-    let manifest_dir = match find_manifest_dir(&this_dir) {
-        Some(dir) => dir,
-        None => {
-            return Err(format!(
-                "could not find `{}` in `{}` or any parent directory",
-                MANIFEST_FILE_NAME,
-                this_dir.display(),
-            ))
-        }
-    };
 
-    let mut manifest = read_manifest(&manifest_dir)?;
+    let manifest = read_manifest(&manifest_dir)?;
+    let profile = manifest.build_profile(release);
+    let lock =
+        dependency::fetch_and_lock(&manifest_dir, offline_mode).map_err(|e| e.to_string())?;
 
     let main_path = {
         let mut code_dir = manifest_dir.clone();
@@ -67,43 +116,35 @@ pub fn build(command: BuildCommand) -> Result<Vec<u8>, String> {
         Err(err) => return Err(err.to_string()),
     };
 
+    // the package and its dependencies share a namespace, so their types must share an engine
+    let type_engine = TypeEngine::new();
     let build_config = BuildConfig::root_from_file_name_and_manifest_path(
         file_name.to_path_buf(),
         manifest_dir.clone(),
     )
-    .use_ir(use_ir || print_ir) // --print-ir implies --use-ir.
+    .use_ir(use_ir || print_ir || profile.use_ir) // --print-ir implies --use-ir.
     .print_finalized_asm(print_finalized_asm)
     .print_intermediate_asm(print_intermediate_asm)
-    .print_ir(print_ir);
+    .print_ir(print_ir)
+    .optimize(profile.optimize)
+    .checked_arithmetic(profile.checked_arithmetic)
+    .type_engine(type_engine.clone());
 
     let mut dependency_graph = HashMap::new();
-    let namespace = create_module();
-
     let mut source_map = SourceMap::new();
 
-    if let Some(ref mut deps) = manifest.dependencies {
-        for (dependency_name, dependency_details) in deps.iter_mut() {
-            compile_dependency_lib(
-                &this_dir,
-                dependency_name,
-                dependency_details,
-                namespace,
-                &mut dependency_graph,
-                silent_mode,
-                offline_mode,
-            )?;
-
-            source_map.insert_dependency(match dependency_details {
-                Dependency::Simple(..) => {
-                    todo!("simple deps (compile_dependency_lib should have errored on this)");
-                }
-                Dependency::Detailed(DependencyDetails { path, .. }) => path
-                    .as_ref()
-                    .expect("compile_dependency_lib should have set this")
-                    .clone(),
-            });
-        }
+    let graph = PackageGraph::load(&manifest_dir, &lock)?;
+    for index in graph.dependencies_of(graph.roots()[0]) {
+        source_map.insert_dependency(&graph.packages()[index].manifest_dir);
     }
+    let namespace = compile_dependencies(
+        &graph,
+        graph.roots()[0],
+        &mut HashMap::new(),
+        &mut dependency_graph,
+        &type_engine,
+        silent_mode,
+    )?;
 
     // now, compile this program with all of its dependencies
     let main_file = get_main_file(&manifest, &manifest_dir)?;
@@ -117,6 +158,17 @@ pub fn build(command: BuildCommand) -> Result<Vec<u8>, String> {
         &mut source_map,
         silent_mode,
     )?;
+    if let Some(program) = &main {
+        let (out_root, _) = find_lock_dir(&manifest_dir)?;
+        write_artifacts(
+            &out_root,
+            &manifest.project.name,
+            program,
+            &source_map,
+            &profile,
+        )?;
+    }
+    let main = main.map(|program| program.bytes).unwrap_or_default();
 
     if let Some(outfile) = binary_outfile {
         let mut file = File::create(outfile).map_err(|e| e.to_string())?;
@@ -136,123 +188,200 @@ pub fn build(command: BuildCommand) -> Result<Vec<u8>, String> {
     Ok(main)
 }
 
-/// Takes a dependency and returns a namespace of exported things from that dependency
-/// trait implementations are included as well
-fn compile_dependency_lib<'manifest>(
-    project_file_path: &Path,
-    dependency_name: &'manifest str,
-    dependency_lib: &mut Dependency,
-    namespace: NamespaceRef,
-    dependency_graph: &mut HashMap<String, HashSet<String>>,
-    silent_mode: bool,
-    offline_mode: bool,
-) -> Result<(), String> {
-    let mut details = match dependency_lib {
-        Dependency::Simple(..) => {
-            return Err(
-                "Not yet implemented: Simple version-spec dependencies require a registry.".into(),
-            )
-        }
-        Dependency::Detailed(ref mut details) => details,
-    };
-    // Download a non-local dependency if the `git` property is set in this dependency.
-    if let Some(ref git) = details.git {
-        // the qualified name of the dependency includes its source and some metadata to prevent
-        // conflating dependencies from different sources
-        let fully_qualified_dep_name = format!("{}-{}", dependency_name, git);
-        let downloaded_dep_path = match dependency::download_github_dep(
-            &fully_qualified_dep_name,
-            git,
-            &details.branch,
-            &details.version,
-            offline_mode.into(),
-        ) {
-            Ok(path) => path,
-            Err(e) => {
-                return Err(format!(
-                    "Couldn't download dependency ({:?}): {:?}",
-                    dependency_name, e
-                ))
-            }
-        };
-
-        // Mutate this dependency's path to hold the newly downloaded dependency's path.
-        details.path = Some(downloaded_dep_path);
+/// Builds every member of `workspace`, each after the members it depends on, and returns their
+/// bytecode. The libraries the members depend on, members or not, are compiled once and shared by
+/// all of them.
+fn build_workspace(
+    workspace: &Workspace,
+    command: BuildCommand,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let BuildCommand {
+        binary_outfile,
+        use_ir,
+        debug_outfile,
+        print_finalized_asm,
+        print_intermediate_asm,
+        print_ir,
+        release,
+        offline_mode,
+        silent_mode,
+        ..
+    } = command;
+    if binary_outfile.is_some() || debug_outfile.is_some() {
+        return Err(
+            "`-o` and `-g` can't be used when building a workspace, build its members one at a \
+             time instead"
+                .into(),
+        );
     }
-    let dep_path = match dependency_lib {
-        Dependency::Simple(..) => {
-            return Err(
-                "Not yet implemented: Simple version-spec dependencies require a registry.".into(),
-            )
-        }
-        Dependency::Detailed(DependencyDetails { path, .. }) => path,
-    };
 
-    let dep_path =
-        match dep_path {
-            Some(p) => p,
-            None => return Err(
-                "Only simple path imports are supported right now. Please supply a path relative \
-                 to the manifest file."
-                    .into(),
-            ),
-        };
-
-    // dependency paths are relative to the path of the project being compiled
-    let mut project_path = PathBuf::from(project_file_path);
-    project_path.push(dep_path);
-
-    // compile the dependencies of this dependency
-    // this should detect circular dependencies
-    let manifest_dir = match find_manifest_dir(&project_path) {
-        Some(o) => o,
-        None => {
-            return Err(format!(
-                "Manifest not found for dependency {:?}.",
-                project_path
-            ))
-        }
-    };
-    let mut manifest_of_dep = read_manifest(&manifest_dir)?;
-    let main_path = find_main_path(&manifest_dir, &manifest_of_dep);
-    let file_name = find_file_name(&manifest_dir, &main_path)?;
+    let lock =
+        dependency::fetch_and_lock(&workspace.dir, offline_mode).map_err(|e| e.to_string())?;
+    let graph = PackageGraph::load_workspace(workspace, &lock)?;
 
-    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
-        file_name.to_path_buf(),
-        manifest_dir.clone(),
-    );
+    // the members share their dependencies, so their types must share an engine
+    let type_engine = TypeEngine::new();
+    let mut dependency_graph = HashMap::new();
+    let mut compiled = HashMap::new();
+    let mut members = vec![];
+    for &index in graph.compile_order() {
+        // a member other members depend on is compiled, as a library, along with them
+        let is_dependency = graph
+            .roots()
+            .iter()
+            .any(|&root| graph.dependencies_of(root).contains(&index));
+        if !graph.is_root(index) || is_dependency {
+            continue;
+        }
 
-    let dep_namespace = create_module();
-    if let Some(ref mut deps) = manifest_of_dep.dependencies {
-        for (dependency_name, ref mut dependency_lib) in deps {
-            // to do this properly, iterate over list of dependencies make sure there are no
-            // circular dependencies
-            compile_dependency_lib(
-                &manifest_dir,
-                dependency_name,
-                dependency_lib,
-                dep_namespace,
-                dependency_graph,
-                silent_mode,
-                offline_mode,
+        let package = &graph.packages()[index];
+        let profile = package.manifest.build_profile(release);
+        let namespace = compile_dependencies(
+            &graph,
+            index,
+            &mut compiled,
+            &mut dependency_graph,
+            &type_engine,
+            silent_mode,
+        )?;
+        let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+        let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            file_name.to_path_buf(),
+            package.manifest_dir.clone(),
+        )
+        .use_ir(use_ir || print_ir || profile.use_ir) // --print-ir implies --use-ir.
+        .print_finalized_asm(print_finalized_asm)
+        .print_intermediate_asm(print_intermediate_asm)
+        .print_ir(print_ir)
+        .optimize(profile.optimize)
+        .checked_arithmetic(profile.checked_arithmetic)
+        .type_engine(type_engine.clone());
+        let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
+
+        let mut source_map = SourceMap::new();
+        for index in graph.dependencies_of(index) {
+            source_map.insert_dependency(&graph.packages()[index].manifest_dir);
+        }
+        let main = compile(
+            main_file,
+            package.name(),
+            namespace,
+            build_config,
+            &mut dependency_graph,
+            &mut source_map,
+            silent_mode,
+        )?;
+        if let Some(program) = &main {
+            // the members of a workspace share its `out` directory
+            write_artifacts(
+                &workspace.dir,
+                package.name(),
+                program,
+                &source_map,
+                &profile,
             )?;
         }
+        let main = main.map(|program| program.bytes).unwrap_or_default();
+        println!("  Bytecode size is {} bytes.", main.len());
+        members.push((package.name().to_string(), main));
     }
+    Ok(members)
+}
 
-    let main_file = get_main_file(&manifest_of_dep, &manifest_dir)?;
+/// Compiles the packages of `graph` the package at `root` depends on, each once and after the
+/// packages it depends on, and returns the namespace `root` is compiled in, holding them.
+/// Trait implementations are included as well. Packages already in `compiled`, which those
+/// compiled are added to, aren't compiled again.
+pub(crate) fn compile_dependencies(
+    graph: &PackageGraph,
+    root: usize,
+    compiled: &mut HashMap<usize, NamespaceRef>,
+    dependency_graph: &mut HashMap<String, HashSet<String>>,
+    type_engine: &TypeEngine,
+    silent_mode: bool,
+) -> Result<NamespaceRef, String> {
+    for index in graph.dependencies_of(root) {
+        if compiled.contains_key(&index) {
+            continue;
+        }
+        let package = &graph.packages()[index];
+        let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+        let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            file_name.to_path_buf(),
+            package.manifest_dir.clone(),
+        )
+        .type_engine(type_engine.clone());
+        let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
+
+        let namespace = compile_library(
+            main_file,
+            package.name(),
+            package_namespace(graph, index, compiled),
+            build_config,
+            dependency_graph,
+            silent_mode,
+        )?;
+        compiled.insert(index, namespace);
+    }
+    Ok(package_namespace(graph, root, compiled))
+}
 
-    let compiled = compile_library(
-        main_file,
-        &manifest_of_dep.project.name,
-        dep_namespace,
-        build_config,
-        dependency_graph,
-        silent_mode,
-    )?;
+/// The namespace the package at `index` is compiled in, holding its compiled dependencies.
+fn package_namespace(
+    graph: &PackageGraph,
+    index: usize,
+    compiled: &HashMap<usize, NamespaceRef>,
+) -> NamespaceRef {
+    let namespace = create_module();
+    for (dependency_name, dependency) in &graph.packages()[index].dependencies {
+        namespace.insert_module_ref(dependency_name.clone(), compiled[dependency]);
+    }
+    namespace
+}
 
-    namespace.insert_module_ref(dependency_name.to_string(), compiled);
+/// A program compiled to bytecode, with what its artifacts are made of.
+struct CompiledProgram {
+    bytes: Vec<u8>,
+    tree_type: TreeType,
+    json_abi: JsonABI,
+}
 
-    // nothing is returned from this method since it mutates the hashmaps it was given
+/// Writes the artifacts of the program `name` to `out/<profile>/` in `out_root`: its bytecode, its
+/// JSON ABI and, if the profile asks for it, its source map. The storage layout of a contract is
+/// written too.
+fn write_artifacts(
+    out_root: &Path,
+    name: &str,
+    program: &CompiledProgram,
+    source_map: &SourceMap,
+    profile: &BuildProfile,
+) -> Result<(), String> {
+    let out_dir = out_root.join(constants::OUT_DIR).join(profile.name);
+    fs::create_dir_all(&out_dir).map_err(|e| e.to_string())?;
+
+    fs::write(out_dir.join(format!("{}.bin", name)), &program.bytes).map_err(|e| e.to_string())?;
+    let json_abi = serde_json::to_vec(&program.json_abi).expect("JSON serialization failed");
+    fs::write(out_dir.join(format!("{}-abi.json", name)), json_abi).map_err(|e| e.to_string())?;
+    if profile.source_map {
+        let source_map = serde_json::to_vec(source_map).expect("JSON serialization failed");
+        fs::write(
+            out_dir.join(format!("{}-source-map.json", name)),
+            source_map,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    if program.tree_type == TreeType::Contract {
+        // storage declarations aren't supported by the compiler yet, so no contract has any
+        // storage slots to lay out
+        let storage_layout = json!([]);
+        fs::write(
+            out_dir.join(format!("{}-storage-layout.json", name)),
+            storage_layout.to_string(),
+        )
+        .map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
@@ -295,7 +424,7 @@ fn compile(
     dependency_graph: &mut HashMap<String, HashSet<String>>,
     source_map: &mut SourceMap,
     silent_mode: bool,
-) -> Result<Vec<u8>, String> {
+) -> Result<Option<CompiledProgram>, String> {
     let res = sway_core::compile_to_bytecode(
         source,
         namespace,
@@ -305,13 +434,22 @@ fn compile(
     );
 
     match res {
-        BytecodeCompilationResult::Success { bytes, warnings } => {
-            print_on_success(silent_mode, proj_name, warnings, TreeType::Script {});
-            Ok(bytes)
+        BytecodeCompilationResult::Success {
+            bytes,
+            tree_type,
+            json_abi,
+            warnings,
+        } => {
+            print_on_success(silent_mode, proj_name, warnings, tree_type.clone());
+            Ok(Some(CompiledProgram {
+                bytes,
+                tree_type,
+                json_abi,
+            }))
         }
         BytecodeCompilationResult::Library { warnings } => {
             print_on_success_library(silent_mode, proj_name, warnings);
-            Ok(vec![])
+            Ok(None)
         }
         BytecodeCompilationResult::Failure { errors, warnings } => {
             print_on_failure(silent_mode, warnings, errors);
@@ -330,7 +468,7 @@ fn compile_to_asm(
 ) -> Result<FinalizedAsm, String> {
     let res = sway_core::compile_to_asm(source, namespace, build_config, dependency_graph);
     match res {
-        CompilationResult::Success { asm, warnings } => {
+        CompilationResult::Success { asm, warnings, .. } => {
             print_on_success(silent_mode, proj_name, warnings, TreeType::Script {});
             Ok(asm)
         }
@@ -0,0 +1,180 @@
+use crate::{
+    cli::{CheckCommand, MessageFormat},
+    utils::dependency,
+    utils::helpers::{print_on_failure, print_on_success},
+};
+use forc_pkg::{find_file_name, find_main_path, get_main_file, PackageGraph, Workspace};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use sway_core::{
+    create_module, error::LineCol, BuildConfig, CompileAstResult, CompileError, CompileWarning,
+    NamespaceWrapper, TypeEngine,
+};
+use sway_utils::{find_manifest_dir, MANIFEST_FILE_NAME};
+
+/// Type checks the project, or every member of the workspace, along with their dependencies, and
+/// reports the errors and warnings found. Nothing is compiled past control flow analysis.
+pub fn check(command: CheckCommand) -> Result<(), String> {
+    // find manifest directory, even if in subdirectory
+    let this_dir = if let Some(ref path) = command.path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir().map_err(|e| format!("{:?}", e))?
+    };
+
+    let CheckCommand {
+        message_format,
+        offline_mode,
+        silent_mode,
+        ..
+    } = command;
+
+    let manifest_dir = match find_manifest_dir(&this_dir) {
+        Some(dir) => dir,
+        None => {
+            return Err(format!(
+                "could not find `{}` in `{}` or any parent directory",
+                MANIFEST_FILE_NAME,
+                this_dir.display(),
+            ))
+        }
+    };
+
+    let lock =
+        dependency::fetch_and_lock(&manifest_dir, offline_mode).map_err(|e| e.to_string())?;
+    let graph = match Workspace::load(&manifest_dir)? {
+        Some(workspace) => PackageGraph::load_workspace(&workspace, &lock)?,
+        None => PackageGraph::load(&manifest_dir, &lock)?,
+    };
+
+    // the packages share a namespace, so their types must share an engine
+    let type_engine = TypeEngine::new();
+    let _type_engine = type_engine.enter();
+    let mut dependency_graph = HashMap::new();
+    let mut checked = HashMap::new();
+    let mut failed = vec![];
+    for &index in graph.compile_order() {
+        let package = &graph.packages()[index];
+        // the errors of a package depending on one that failed would only follow from those
+        if package
+            .dependencies
+            .iter()
+            .any(|(_, dependency)| !checked.contains_key(dependency))
+        {
+            failed.push(package.name().to_string());
+            continue;
+        }
+        let namespace = create_module();
+        for (dependency_name, dependency) in &package.dependencies {
+            namespace.insert_module_ref(dependency_name.clone(), checked[dependency]);
+        }
+
+        let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+        let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            file_name.to_path_buf(),
+            package.manifest_dir.clone(),
+        )
+        .type_engine(type_engine.clone());
+        let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
+
+        match sway_core::compile_to_ast(main_file, namespace, &build_config, &mut dependency_graph)
+        {
+            CompileAstResult::Success {
+                parse_tree,
+                tree_type,
+                warnings,
+            } => {
+                match message_format {
+                    MessageFormat::Human => {
+                        print_on_success(silent_mode, package.name(), warnings, tree_type)
+                    }
+                    MessageFormat::Json => {
+                        print_diagnostics(&package.manifest_dir, package.name(), &warnings, &[])
+                    }
+                }
+                checked.insert(index, parse_tree.get_namespace_ref());
+            }
+            CompileAstResult::Failure { warnings, errors } => {
+                match message_format {
+                    MessageFormat::Human => print_on_failure(silent_mode, warnings, errors),
+                    MessageFormat::Json => {
+                        print_diagnostics(&package.manifest_dir, package.name(), &warnings, &errors)
+                    }
+                }
+                failed.push(package.name().to_string());
+            }
+        }
+    }
+
+    if message_format == MessageFormat::Json {
+        println!(
+            "{}",
+            json!({ "reason": "check-finished", "success": failed.is_empty() })
+        );
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to check {}", failed.join(", ")))
+    }
+}
+
+/// Prints each of the errors and warnings found in the package `package`, as a JSON object on a
+/// line of its own.
+fn print_diagnostics(
+    manifest_dir: &Path,
+    package: &str,
+    warnings: &[CompileWarning],
+    errors: &[CompileError],
+) {
+    for warning in warnings {
+        let message = diagnostic(
+            manifest_dir,
+            package,
+            "warning",
+            warning.to_friendly_warning_string(),
+            warning.path(),
+            warning.line_col(),
+        );
+        println!("{}", message);
+    }
+    for error in errors {
+        let message = diagnostic(
+            manifest_dir,
+            package,
+            "error",
+            error.to_friendly_error_string(),
+            error.path(),
+            error.line_col(),
+        );
+        println!("{}", message);
+    }
+}
+
+fn diagnostic(
+    manifest_dir: &Path,
+    package: &str,
+    level: &str,
+    message: String,
+    path: String,
+    (start, end): (LineCol, LineCol),
+) -> Value {
+    // the paths of the package's own files are relative to the directory it is in
+    let mut path = PathBuf::from(path);
+    if path.is_relative() {
+        if let Some(parent) = manifest_dir.parent() {
+            path = parent.join(path);
+        }
+    }
+    json!({
+        "reason": "diagnostic",
+        "package": package,
+        "level": level,
+        "message": message,
+        "path": path,
+        "start": { "line": start.line, "column": start.col },
+        "end": { "line": end.line, "column": end.col },
+    })
+}
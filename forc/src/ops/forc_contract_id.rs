@@ -0,0 +1,82 @@
+use fuel_tx::{ContractId, Salt};
+use fuel_vm::prelude::*;
+use sway_core::{parse, TreeType};
+
+use crate::cli::{BuildCommand, ContractIdCommand};
+use crate::ops::forc_build;
+use crate::utils::cli_error::CliError;
+
+use forc_pkg::{get_main_file, read_manifest};
+use std::path::PathBuf;
+use sway_utils::{constants::*, find_manifest_dir};
+
+/// Builds the contract and computes the ID it would be deployed with.
+pub fn contract_id(command: ContractIdCommand) -> Result<ContractId, CliError> {
+    let curr_dir = if let Some(ref path) = command.path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir()?
+    };
+
+    let ContractIdCommand {
+        path,
+        salt,
+        state_root,
+        use_ir,
+        release,
+        offline_mode,
+        silent_mode,
+    } = command;
+
+    match find_manifest_dir(&curr_dir) {
+        Some(manifest_dir) => {
+            let manifest = read_manifest(&manifest_dir)?;
+            let project_name = &manifest.project.name;
+            let main_file = get_main_file(&manifest, &manifest_dir)?;
+
+            // parse the main file and check is it a contract
+            let parsed_result = parse(main_file, None);
+            match parsed_result.value {
+                Some(parse_tree) => match parse_tree.tree_type {
+                    TreeType::Contract => {
+                        let build_command = BuildCommand {
+                            path,
+                            use_ir,
+                            print_finalized_asm: false,
+                            print_intermediate_asm: false,
+                            print_ir: false,
+                            binary_outfile: None,
+                            debug_outfile: None,
+                            release,
+                            offline_mode,
+                            silent_mode,
+                        };
+
+                        let compiled_contract = forc_build::build(build_command)?;
+                        let salt = salt.unwrap_or_else(|| Salt::new([0; 32]));
+                        let state_root = state_root.unwrap_or_else(Contract::default_state_root);
+                        let contract = Contract::from(compiled_contract);
+                        Ok(contract.id(&salt, &contract.root(), &state_root))
+                    }
+                    TreeType::Script => Err(CliError::wrong_sway_type(
+                        project_name,
+                        SWAY_CONTRACT,
+                        SWAY_SCRIPT,
+                    )),
+                    TreeType::Predicate => Err(CliError::wrong_sway_type(
+                        project_name,
+                        SWAY_CONTRACT,
+                        SWAY_PREDICATE,
+                    )),
+                    TreeType::Library { .. } => Err(CliError::wrong_sway_type(
+                        project_name,
+                        SWAY_CONTRACT,
+                        SWAY_LIBRARY,
+                    )),
+                },
+                None => Err(CliError::parsing_failed(project_name, parsed_result.errors)),
+            }
+        }
+        None => Err(CliError::manifest_file_missing(curr_dir)),
+    }
+}
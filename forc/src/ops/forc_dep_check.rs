@@ -1,6 +1,7 @@
-use crate::utils::{dependency, helpers::read_manifest};
+use crate::utils::dependency;
 use anyhow::{anyhow, Result};
 use dirs::home_dir;
+use forc_pkg::{read_manifest, DependencyDetails};
 use semver::Version;
 use std::{
     path::{Path, PathBuf},
@@ -54,10 +55,7 @@ pub async fn check(path: Option<String>, target_dependency: Option<String>) -> R
     }
 }
 
-async fn check_dependency(
-    dependency_name: &str,
-    dep: &dependency::DependencyDetails,
-) -> Result<()> {
+async fn check_dependency(dependency_name: &str, dep: &DependencyDetails) -> Result<()> {
     let home_dir = match home_dir() {
         None => return Err(anyhow!("Couldn't find home directory (`~/`)")),
         Some(p) => p.to_str().unwrap().to_owned(),
@@ -116,7 +114,7 @@ async fn check_untagged_dependency(
     git_repo: &str,
     target_directory: &Path,
     dependency_name: &str,
-    dep: &dependency::DependencyDetails,
+    dep: &DependencyDetails,
 ) -> Result<()> {
     let current = dependency::get_current_dependency_version(target_directory)?;
 
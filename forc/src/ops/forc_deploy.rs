@@ -7,8 +7,7 @@ use crate::cli::{BuildCommand, DeployCommand};
 use crate::ops::forc_build;
 use crate::utils::cli_error::CliError;
 
-use crate::utils::helpers;
-use helpers::{get_main_file, read_manifest};
+use forc_pkg::{get_main_file, read_manifest};
 use std::path::PathBuf;
 use sway_utils::{constants::*, find_manifest_dir};
 
@@ -27,6 +26,7 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId, CliEr
         print_ir,
         binary_outfile,
         debug_outfile,
+        release,
         offline_mode,
         silent_mode,
     } = command;
@@ -51,6 +51,7 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId, CliEr
                             binary_outfile,
                             offline_mode,
                             debug_outfile,
+                            release,
                             silent_mode,
                         };
 
@@ -99,7 +100,7 @@ pub async fn deploy(command: DeployCommand) -> Result<fuel_tx::ContractId, CliEr
     }
 }
 
-fn create_contract_tx(
+pub(crate) fn create_contract_tx(
     compiled_contract: Vec<u8>,
     inputs: Vec<Input>,
     outputs: Vec<Output>,
@@ -0,0 +1,110 @@
+use crate::{
+    cli::DocCommand,
+    utils::dependency,
+    utils::helpers::{print_on_failure, print_on_success, println_green_err},
+};
+use docstrings::Documenter;
+use forc_pkg::{
+    find_file_name, find_main_path, get_main_file, lock::find_lock_dir, PackageGraph, Workspace,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use sway_core::{
+    create_module, BuildConfig, CompileAstResult, NamespaceWrapper, TreeType, TypeEngine,
+};
+use sway_utils::{constants, find_manifest_dir, MANIFEST_FILE_NAME};
+
+/// Type checks the project, or every member of the workspace, along with their dependencies, and
+/// renders the documentation of all of them to `out/doc`.
+pub fn doc(command: DocCommand) -> Result<(), String> {
+    // find manifest directory, even if in subdirectory
+    let this_dir = if let Some(ref path) = command.path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir().map_err(|e| format!("{:?}", e))?
+    };
+
+    let DocCommand {
+        document_private_items,
+        offline_mode,
+        silent_mode,
+        ..
+    } = command;
+
+    let manifest_dir = match find_manifest_dir(&this_dir) {
+        Some(dir) => dir,
+        None => {
+            return Err(format!(
+                "could not find `{}` in `{}` or any parent directory",
+                MANIFEST_FILE_NAME,
+                this_dir.display(),
+            ))
+        }
+    };
+
+    let lock =
+        dependency::fetch_and_lock(&manifest_dir, offline_mode).map_err(|e| e.to_string())?;
+    let graph = match Workspace::load(&manifest_dir)? {
+        Some(workspace) => PackageGraph::load_workspace(&workspace, &lock)?,
+        None => PackageGraph::load(&manifest_dir, &lock)?,
+    };
+
+    // the packages share a namespace, so their types must share an engine
+    let type_engine = TypeEngine::new();
+    let _type_engine = type_engine.enter();
+    let mut dependency_graph = HashMap::new();
+    let mut compiled = HashMap::new();
+    // dependencies are documented first, so that the modules of a package which are those of its
+    // dependencies are documented under their own package
+    let mut documenter = Documenter::new();
+    for &index in graph.compile_order() {
+        let package = &graph.packages()[index];
+        let namespace = create_module();
+        for (dependency_name, dependency) in &package.dependencies {
+            namespace.insert_module_ref(dependency_name.clone(), compiled[dependency]);
+        }
+
+        let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+        let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            file_name.to_path_buf(),
+            package.manifest_dir.clone(),
+        )
+        .type_engine(type_engine.clone());
+        let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
+
+        match sway_core::compile_to_ast(main_file, namespace, &build_config, &mut dependency_graph)
+        {
+            CompileAstResult::Success {
+                parse_tree,
+                tree_type,
+                warnings,
+            } => {
+                // only libraries can be imported from, so only their public items are of use to
+                // other packages
+                let include_private =
+                    document_private_items || !matches!(tree_type, TreeType::Library { .. });
+                print_on_success(silent_mode, package.name(), warnings, tree_type);
+                let namespace = parse_tree.get_namespace_ref();
+                documenter.document_package(package.name(), namespace, include_private);
+                compiled.insert(index, namespace);
+            }
+            CompileAstResult::Failure { warnings, errors } => {
+                print_on_failure(silent_mode, warnings, errors);
+                return Err(format!("Failed to document {}", package.name()));
+            }
+        }
+    }
+
+    let (out_root, _) = find_lock_dir(&manifest_dir)?;
+    let out_dir = out_root.join(constants::OUT_DIR).join("doc");
+    documenter
+        .finish()
+        .write_html(&out_dir)
+        .map_err(|e| e.to_string())?;
+    let _ = println_green_err(&format!(
+        "  Documentation written to {}",
+        out_dir.join("index.html").display()
+    ));
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::cli::{BuildCommand, FormatCommand};
 use crate::ops::forc_build;
 use crate::utils::helpers::{println_green, println_red};
+use forc_pkg::Workspace;
 use prettydiff::{basic::DiffOp, diff_lines};
 use std::default::Default;
 use std::{fmt, fs, io, path::Path, sync::Arc};
@@ -17,11 +18,12 @@ pub fn format(command: FormatCommand) -> Result<(), FormatError> {
         print_ir: false,
         binary_outfile: None,
         debug_outfile: None,
+        release: false,
         offline_mode: false,
         silent_mode: false,
     };
 
-    match forc_build::build(build_command) {
+    match forc_build::build_all(build_command) {
         // build is successful, continue to formatting
         Ok(_) => format_after_build(command),
 
@@ -35,9 +37,21 @@ fn format_after_build(command: FormatCommand) -> Result<(), FormatError> {
 
     match find_manifest_dir(&curr_dir) {
         Some(path) => {
-            let mut manifest_file = path.clone();
-            manifest_file.push(constants::MANIFEST_FILE_NAME);
-            let files = get_sway_files(path);
+            let mut manifest_files = vec![path.join(constants::MANIFEST_FILE_NAME)];
+            // the members of a workspace are formatted along with it
+            let package_dirs = match Workspace::load(&path)? {
+                Some(workspace) => {
+                    manifest_files.extend(
+                        workspace
+                            .members
+                            .iter()
+                            .map(|dir| dir.join(constants::MANIFEST_FILE_NAME)),
+                    );
+                    workspace.members
+                }
+                None => vec![path],
+            };
+            let files = package_dirs.into_iter().flat_map(get_sway_files);
             let mut contains_edits = false;
 
             for file in files {
@@ -65,8 +79,12 @@ fn format_after_build(command: FormatCommand) -> Result<(), FormatError> {
                     }
                 }
             }
-            // format manifest using taplo formatter
-            if let Ok(file_content) = fs::read_to_string(&manifest_file) {
+            // format manifests using taplo formatter
+            for manifest_file in manifest_files {
+                let file_content = match fs::read_to_string(&manifest_file) {
+                    Ok(file_content) => file_content,
+                    Err(_) => continue,
+                };
                 let taplo_alphabetize = taplo_fmt::Options {
                     reorder_keys: true,
                     ..Default::default()
@@ -76,10 +94,10 @@ fn format_after_build(command: FormatCommand) -> Result<(), FormatError> {
                     format_file(&manifest_file, &formatted_content)?;
                 } else if formatted_content != file_content {
                     contains_edits = true;
-                    eprintln!("\nManifest Forc.toml improperly formatted");
+                    eprintln!("\nManifest {:?} improperly formatted", manifest_file);
                     display_file_diff(&file_content, &formatted_content)?;
                 } else {
-                    println!("\nManifest Forc.toml properly formatted")
+                    println!("\nManifest {:?} properly formatted", manifest_file)
                 }
             }
 
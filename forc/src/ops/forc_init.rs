@@ -1,32 +1,89 @@
 use crate::utils::defaults;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use sway_utils::constants;
 
-pub(crate) fn init_new_project(project_name: String) -> Result<(), Box<dyn std::error::Error>> {
+/// What a new project is created from.
+#[derive(Debug)]
+pub(crate) enum Template {
+    Contract,
+    Script,
+    Predicate,
+    Library,
+    /// A directory whose files are copied into the new project, with `{{project_name}}` replaced
+    /// by the name of the project in their names and contents.
+    Path(PathBuf),
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "contract" => Ok(Template::Contract),
+            "script" => Ok(Template::Script),
+            "predicate" => Ok(Template::Predicate),
+            "library" => Ok(Template::Library),
+            path if Path::new(path).is_dir() => Ok(Template::Path(PathBuf::from(path))),
+            _ => Err(format!(
+                "unknown template `{}`, expected `contract`, `script`, `predicate`, `library` or \
+                the path to a template directory",
+                s
+            )),
+        }
+    }
+}
+
+/// The placeholder replaced by the name of the project in the files of a template directory.
+const PROJECT_NAME_PLACEHOLDER: &str = "{{project_name}}";
+
+/// The directories of a template which are not part of it, but built from or tracking it.
+const IGNORED_TEMPLATE_DIRS: &[&str] = &[".git", constants::OUT_DIR, "target"];
+
+pub(crate) fn init_new_project(
+    project_name: String,
+    template: Template,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (entry, program) = match template {
+        Template::Contract => ("main.sw", defaults::default_contract()),
+        Template::Script => ("main.sw", defaults::default_program()),
+        Template::Predicate => ("main.sw", defaults::default_predicate()),
+        Template::Library => (
+            "lib.sw",
+            defaults::default_library(&project_name.replace('-', "_")),
+        ),
+        Template::Path(template_dir) => {
+            return init_from_template(&project_name, &template_dir, Path::new(&project_name));
+        }
+    };
+
     // Make a new directory for the project
     fs::create_dir_all(format!("{}/src", project_name))?;
 
-    // Make directory for tests
-    fs::create_dir_all(format!("{}/tests", project_name))?;
-
     // Insert default manifest file
     fs::write(
         format!("{}/{}", project_name, constants::MANIFEST_FILE_NAME),
-        defaults::default_manifest(&project_name),
+        defaults::default_manifest(&project_name, entry),
     )?;
 
+    // Insert default entry point
+    fs::write(format!("{}/src/{}", project_name, entry), program)?;
+
+    // Libraries can't be deployed, so there is nothing for Rust-based tests to call
+    if entry == "lib.sw" {
+        return Ok(());
+    }
+
+    // Make directory for tests
+    fs::create_dir_all(format!("{}/tests", project_name))?;
+
     // Insert default test manifest file
     fs::write(
         format!("{}/{}", project_name, constants::TEST_MANIFEST_FILE_NAME),
         defaults::default_tests_manifest(&project_name),
     )?;
 
-    // Insert default main function
-    fs::write(
-        format!("{}/src/main.sw", project_name),
-        defaults::default_program(),
-    )?;
-
     // Insert default test function
     fs::write(
         format!("{}/tests/harness.rs", project_name),
@@ -35,3 +92,95 @@ pub(crate) fn init_new_project(project_name: String) -> Result<(), Box<dyn std::
 
     Ok(())
 }
+
+/// Creates the project `project_name` in `project_dir` as a copy of the template directory
+/// `template_dir`. The project can be created inside of the template, such as with
+/// `forc init --template . inner`, in which case it isn't copied into itself.
+fn init_from_template(
+    project_name: &str,
+    template_dir: &Path,
+    project_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !template_dir.join(constants::MANIFEST_FILE_NAME).is_file() {
+        return Err(format!(
+            "template `{}` has no `{}`",
+            template_dir.display(),
+            constants::MANIFEST_FILE_NAME
+        )
+        .into());
+    }
+    let template_dir = template_dir.canonicalize()?;
+    fs::create_dir_all(project_dir)?;
+    let project_dir = project_dir.canonicalize()?;
+    if project_dir == template_dir {
+        return Err(format!(
+            "the project can't be created in its own template `{}`",
+            template_dir.display()
+        )
+        .into());
+    }
+    copy_template_dir(&template_dir, &project_dir, &project_dir, project_name)
+}
+
+/// Copies the template directory `from` to `to`, leaving out `project_dir` if it is inside of it.
+fn copy_template_dir(
+    from: &Path,
+    to: &Path,
+    project_dir: &Path,
+    project_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        let target = to.join(file_name.replace(PROJECT_NAME_PLACEHOLDER, project_name));
+        if path.is_dir() {
+            if !IGNORED_TEMPLATE_DIRS.contains(&file_name.as_str()) && path != project_dir {
+                copy_template_dir(&path, &target, project_dir, project_name)?;
+            }
+            continue;
+        }
+        // the lock file is written again by the first build of the new project
+        if file_name == constants::LOCK_FILE_NAME {
+            continue;
+        }
+        match String::from_utf8(fs::read(&path)?) {
+            Ok(contents) => fs::write(
+                target,
+                contents.replace(PROJECT_NAME_PLACEHOLDER, project_name),
+            )?,
+            Err(bytes) => fs::write(target, bytes.into_bytes())?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_inside_their_template_are_not_copied_into_themselves() {
+        let template_dir = std::env::temp_dir().join(format!("forc-init-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&template_dir);
+        fs::create_dir_all(template_dir.join("src")).unwrap();
+        fs::write(
+            template_dir.join(constants::MANIFEST_FILE_NAME),
+            "[project]\nname = \"{{project_name}}\"\n",
+        )
+        .unwrap();
+        fs::write(template_dir.join("src/main.sw"), "script;\n").unwrap();
+
+        let project_dir = template_dir.join("inner");
+        init_from_template("inner", &template_dir, &project_dir).unwrap();
+        let manifest = fs::read_to_string(project_dir.join(constants::MANIFEST_FILE_NAME));
+        let copied_itself = project_dir.join("inner").exists();
+        let into_template = init_from_template("outer", &template_dir, &template_dir);
+        fs::remove_dir_all(&template_dir).unwrap();
+
+        assert_eq!(manifest.unwrap(), "[project]\nname = \"inner\"\n");
+        assert!(!copied_itself);
+        assert!(into_template.is_err());
+    }
+}
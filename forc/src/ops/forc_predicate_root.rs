@@ -0,0 +1,88 @@
+use fuel_tx::{crypto::Hasher, Address, ContractId};
+use fuel_vm::prelude::*;
+use sway_core::{parse, TreeType};
+
+use crate::cli::{BuildCommand, PredicateRootCommand};
+use crate::ops::forc_build;
+use crate::utils::cli_error::CliError;
+
+use forc_pkg::{get_main_file, read_manifest};
+use std::path::PathBuf;
+use sway_utils::{constants::*, find_manifest_dir};
+
+/// Builds the predicate and computes the address of the coins it owns.
+pub fn predicate_root(command: PredicateRootCommand) -> Result<Address, CliError> {
+    let curr_dir = if let Some(ref path) = command.path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir()?
+    };
+
+    let PredicateRootCommand {
+        path,
+        use_ir,
+        release,
+        offline_mode,
+        silent_mode,
+    } = command;
+
+    match find_manifest_dir(&curr_dir) {
+        Some(manifest_dir) => {
+            let manifest = read_manifest(&manifest_dir)?;
+            let project_name = &manifest.project.name;
+            let main_file = get_main_file(&manifest, &manifest_dir)?;
+
+            // parse the main file and check is it a predicate
+            let parsed_result = parse(main_file, None);
+            match parsed_result.value {
+                Some(parse_tree) => match parse_tree.tree_type {
+                    TreeType::Predicate => {
+                        let build_command = BuildCommand {
+                            path,
+                            use_ir,
+                            print_finalized_asm: false,
+                            print_intermediate_asm: false,
+                            print_ir: false,
+                            binary_outfile: None,
+                            debug_outfile: None,
+                            release,
+                            offline_mode,
+                            silent_mode,
+                        };
+
+                        let compiled_predicate = forc_build::build(build_command)?;
+                        Ok(predicate_owner(compiled_predicate))
+                    }
+                    TreeType::Script => Err(CliError::wrong_sway_type(
+                        project_name,
+                        SWAY_PREDICATE,
+                        SWAY_SCRIPT,
+                    )),
+                    TreeType::Contract => Err(CliError::wrong_sway_type(
+                        project_name,
+                        SWAY_PREDICATE,
+                        SWAY_CONTRACT,
+                    )),
+                    TreeType::Library { .. } => Err(CliError::wrong_sway_type(
+                        project_name,
+                        SWAY_PREDICATE,
+                        SWAY_LIBRARY,
+                    )),
+                },
+                None => Err(CliError::parsing_failed(project_name, parsed_result.errors)),
+            }
+        }
+        None => Err(CliError::manifest_file_missing(curr_dir)),
+    }
+}
+
+/// The owner of the coins a predicate can spend: the hash of the contract ID seed and the root of
+/// the predicate's code, computed as the root of a contract's code is.
+///
+/// <https://github.com/FuelLabs/fuel-specs/blob/master/specs/protocol/identifiers.md>
+fn predicate_owner(compiled_predicate: Vec<u8>) -> Address {
+    let mut hasher = Hasher::default();
+    hasher.input(ContractId::SEED);
+    hasher.input(Contract::from(compiled_predicate).root());
+    Address::from(*hasher.digest())
+}
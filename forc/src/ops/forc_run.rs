@@ -1,5 +1,7 @@
+use fuel_asm::InstructionResult;
 use fuel_gql_client::client::FuelClient;
-use fuel_tx::Transaction;
+use fuel_tx::{Receipt, Transaction};
+use fuel_vm::prelude::*;
 use futures::TryFutureExt;
 
 use std::path::PathBuf;
@@ -8,11 +10,10 @@ use sway_core::{parse, TreeType};
 use tokio::process::Child;
 
 use crate::cli::{BuildCommand, RunCommand};
-use crate::ops::forc_build;
+use crate::ops::{forc_build, forc_deploy};
 use crate::utils::cli_error::CliError;
 
-use crate::utils::helpers;
-use helpers::{get_main_file, read_manifest};
+use forc_pkg::{get_main_file, read_manifest};
 use sway_utils::{constants::*, find_manifest_dir};
 
 pub async fn run(command: RunCommand) -> Result<(), CliError> {
@@ -45,12 +46,28 @@ pub async fn run(command: RunCommand) -> Result<(), CliError> {
                             print_ir: command.print_ir,
                             binary_outfile: command.binary_outfile,
                             debug_outfile: command.debug_outfile,
+                            release: command.release,
                             offline_mode: false,
                             silent_mode: command.silent_mode,
                         };
 
                         let compiled_script = forc_build::build(build_command)?;
-                        let contracts = command.contract.unwrap_or_default();
+                        let mut contracts = command.contract.unwrap_or_default();
+
+                        // the preloaded contracts are inputs of the script, for it to call them
+                        let mut deployments = vec![];
+                        for contract_path in command.preload_contracts.unwrap_or_default() {
+                            let compiled_contract = compile_contract(
+                                contract_path,
+                                command.use_ir,
+                                command.release,
+                                command.silent_mode,
+                            )?;
+                            let (tx, contract_id) =
+                                forc_deploy::create_contract_tx(compiled_contract, vec![], vec![]);
+                            contracts.push(format!("0x{:x}", contract_id));
+                            deployments.push(tx);
+                        }
                         let (inputs, outputs) = get_tx_inputs_and_outputs(contracts);
 
                         let tx = create_tx_with_script_and_data(
@@ -61,8 +78,7 @@ pub async fn run(command: RunCommand) -> Result<(), CliError> {
                         );
 
                         if command.dry_run {
-                            println!("{:?}", tx);
-                            Ok(())
+                            run_in_vm(deployments, tx, command.pretty_print)
                         } else {
                             let node_url = match &manifest.network {
                                 Some(network) => &network.url,
@@ -103,6 +119,108 @@ pub async fn run(command: RunCommand) -> Result<(), CliError> {
     }
 }
 
+/// Compiles the contract project at `path`, to be deployed to the VM of a dry run.
+fn compile_contract(
+    path: String,
+    use_ir: bool,
+    release: bool,
+    silent_mode: bool,
+) -> Result<Vec<u8>, CliError> {
+    let path_dir = PathBuf::from(&path);
+    let manifest_dir = match find_manifest_dir(&path_dir) {
+        Some(manifest_dir) => manifest_dir,
+        None => return Err(CliError::manifest_file_missing(path_dir)),
+    };
+    let manifest = read_manifest(&manifest_dir)?;
+    let project_name = &manifest.project.name;
+    let main_file = get_main_file(&manifest, &manifest_dir)?;
+
+    let parsed_result = parse(main_file, None);
+    match parsed_result.value {
+        Some(parse_tree) => match parse_tree.tree_type {
+            TreeType::Contract => {
+                let build_command = BuildCommand {
+                    path: Some(path),
+                    use_ir,
+                    print_finalized_asm: false,
+                    print_intermediate_asm: false,
+                    print_ir: false,
+                    binary_outfile: None,
+                    debug_outfile: None,
+                    release,
+                    offline_mode: false,
+                    silent_mode,
+                };
+                Ok(forc_build::build(build_command)?)
+            }
+            TreeType::Script => Err(CliError::wrong_sway_type(
+                project_name,
+                SWAY_CONTRACT,
+                SWAY_SCRIPT,
+            )),
+            TreeType::Predicate => Err(CliError::wrong_sway_type(
+                project_name,
+                SWAY_CONTRACT,
+                SWAY_PREDICATE,
+            )),
+            TreeType::Library { .. } => Err(CliError::wrong_sway_type(
+                project_name,
+                SWAY_CONTRACT,
+                SWAY_LIBRARY,
+            )),
+        },
+        None => Err(CliError::parsing_failed(project_name, parsed_result.errors)),
+    }
+}
+
+/// Runs the script transaction `tx` in a VM with its own storage in memory, once the contracts
+/// created by the `deployments` transactions are deployed to it, and prints what the script did.
+fn run_in_vm(
+    deployments: Vec<Transaction>,
+    tx: Transaction,
+    pretty_print: bool,
+) -> Result<(), CliError> {
+    let mut vm = Interpreter::with_storage(MemoryStorage::default());
+    for deployment in deployments {
+        vm.transact(deployment)
+            .map_err(|e| format!("Failed to deploy contract: {:?}", e))?;
+    }
+    let state = vm
+        .transact(tx)
+        .map_err(|e| format!("Failed to run script: {:?}", e))?;
+
+    let receipts = state.receipts();
+    if pretty_print {
+        println!("{:#?}", receipts);
+    } else {
+        println!("{:?}", receipts);
+    }
+
+    // the script's own result comes after those of the contract calls it made
+    let mut result = None;
+    let mut gas_used = 0;
+    for receipt in receipts {
+        match receipt {
+            Receipt::Return { val, .. } => result = Some(format!("Returned {}", val)),
+            Receipt::ReturnData { data, .. } => {
+                result = Some(format!("Returned 0x{}", hex::encode(data)))
+            }
+            Receipt::Revert { ra, .. } => result = Some(format!("Reverted with {}", ra)),
+            Receipt::Panic { reason, .. } => {
+                let reason = *InstructionResult::from(*reason).reason();
+                result = Some(format!("Panicked: {:?}", reason))
+            }
+            Receipt::ScriptResult { gas_used: gas, .. } => gas_used = *gas,
+            _ => (),
+        }
+    }
+    if let Some(result) = result {
+        println!("{}", result);
+    }
+    println!("Gas used: {}", gas_used);
+    Ok(())
+}
+
 async fn try_send_tx(
     node_url: &str,
     tx: &Transaction,
@@ -0,0 +1,341 @@
+use crate::{
+    cli::TestCommand,
+    ops::forc_build::compile_dependencies,
+    utils::dependency,
+    utils::helpers::{print_on_failure, print_on_success, println_green, println_red},
+};
+use forc_pkg::{find_file_name, find_main_path, get_main_file, PackageGraph, Workspace};
+use fuel_asm::InstructionResult;
+use fuel_tx::{Receipt, Transaction};
+use fuel_vm::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command as ProcessCommand, Stdio};
+use std::thread;
+use sway_core::{
+    source_map::SourceMap, BuildConfig, CompiledTest, NamespaceRef, TestCompilationResult,
+    TreeType, TypeEngine,
+};
+use sway_utils::{constants, find_manifest_dir, MANIFEST_FILE_NAME};
+
+/// How a test ended.
+enum Outcome {
+    Passed,
+    /// The test reverted, or the VM panicked, at the byte offset `pc` of its bytecode.
+    Failed {
+        reason: String,
+        pc: Option<u64>,
+    },
+}
+
+struct TestResult {
+    name: String,
+    outcome: Outcome,
+    gas_used: u64,
+}
+
+pub fn test(command: TestCommand) -> Result<(), String> {
+    // find manifest directory, even if in subdirectory
+    let this_dir = if let Some(ref path) = command.path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir().map_err(|e| format!("{:?}", e))?
+    };
+
+    let TestCommand {
+        test_name,
+        use_ir,
+        offline_mode,
+        silent_mode,
+        ..
+    } = command;
+
+    let manifest_dir = match find_manifest_dir(&this_dir) {
+        Some(dir) => dir,
+        None => {
+            return Err(format!(
+                "could not find `{}` in `{}` or any parent directory",
+                MANIFEST_FILE_NAME,
+                this_dir.display(),
+            ))
+        }
+    };
+
+    let lock =
+        dependency::fetch_and_lock(&manifest_dir, offline_mode).map_err(|e| e.to_string())?;
+    let (graph, package_dirs) = match Workspace::load(&manifest_dir)? {
+        Some(workspace) => (
+            PackageGraph::load_workspace(&workspace, &lock)?,
+            workspace.members,
+        ),
+        None => (
+            PackageGraph::load(&manifest_dir, &lock)?,
+            vec![manifest_dir],
+        ),
+    };
+
+    // the members of a workspace share their dependencies, so their types must share an engine
+    let type_engine = TypeEngine::new();
+    let mut dependency_graph = HashMap::new();
+    let mut compiled = HashMap::new();
+    let mut failures = vec![];
+    for &index in graph.compile_order() {
+        if !graph.is_root(index) {
+            continue;
+        }
+        // the tests of every member are run, even if those of another fail
+        if let Err(e) = run_sway_tests(
+            &graph,
+            index,
+            &mut compiled,
+            &mut dependency_graph,
+            &type_engine,
+            test_name.as_deref(),
+            use_ir,
+            silent_mode,
+        ) {
+            failures.push(e);
+        }
+    }
+    if !failures.is_empty() {
+        return Err(failures.join("\n"));
+    }
+
+    // projects made by `forc init` also have a Rust test harness
+    for manifest_dir in package_dirs {
+        if manifest_dir
+            .join(constants::TEST_MANIFEST_FILE_NAME)
+            .exists()
+        {
+            run_cargo_tests(&manifest_dir, test_name.clone())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Compiles each of the `#[test]` functions of the package at `root` on its own, as the entry
+/// point of a script, and runs it in a VM in memory. A test passes unless it reverts.
+/// The dependencies of the package are compiled unless they are in `compiled` already.
+#[allow(clippy::too_many_arguments)]
+fn run_sway_tests(
+    graph: &PackageGraph,
+    root: usize,
+    compiled: &mut HashMap<usize, NamespaceRef>,
+    dependency_graph: &mut HashMap<String, HashSet<String>>,
+    type_engine: &TypeEngine,
+    test_name: Option<&str>,
+    use_ir: bool,
+    silent_mode: bool,
+) -> Result<(), String> {
+    let package = &graph.packages()[root];
+    let (manifest_dir, manifest) = (&package.manifest_dir, &package.manifest);
+    let main_path = find_main_path(manifest_dir, manifest);
+    let file_name = find_file_name(manifest_dir, &main_path)?;
+
+    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+        file_name.to_path_buf(),
+        manifest_dir.to_path_buf(),
+    )
+    .use_ir(use_ir)
+    .type_engine(type_engine.clone());
+
+    let namespace = compile_dependencies(
+        graph,
+        root,
+        compiled,
+        dependency_graph,
+        type_engine,
+        silent_mode,
+    )?;
+
+    let main_file = get_main_file(manifest, manifest_dir)?;
+    let tests = match sway_core::compile_tests_to_bytecode(
+        main_file,
+        namespace,
+        build_config,
+        dependency_graph,
+    ) {
+        TestCompilationResult::Success { tests, warnings } => {
+            print_on_success(
+                silent_mode,
+                &manifest.project.name,
+                warnings,
+                TreeType::Script,
+            );
+            tests
+        }
+        TestCompilationResult::Failure { warnings, errors } => {
+            print_on_failure(silent_mode, warnings, errors);
+            return Err(format!("Failed to compile {}", manifest.project.name));
+        }
+    };
+
+    let total = tests.len();
+    let mut tests = tests
+        .into_iter()
+        .filter(|test| match test_name {
+            Some(filter) => test.name.contains(filter),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+    let filtered_out = total - tests.len();
+
+    println!(
+        "\nrunning {} {}",
+        tests.len(),
+        if tests.len() == 1 { "test" } else { "tests" }
+    );
+    let mut results = vec![];
+    for test in &mut tests {
+        for index in graph.dependencies_of(root) {
+            test.source_map
+                .insert_dependency(&graph.packages()[index].manifest_dir);
+        }
+        let result = run_test(test)?;
+        print!("test {} ... ", result.name);
+        let _ = match result.outcome {
+            Outcome::Passed => println_green(&format!("ok (gas {})", result.gas_used)),
+            Outcome::Failed { .. } => println_red(&format!("FAILED (gas {})", result.gas_used)),
+        };
+        results.push(result);
+    }
+
+    let failures = tests
+        .iter()
+        .zip(&results)
+        .filter_map(|(test, result)| match &result.outcome {
+            Outcome::Failed { reason, pc } => Some((test, reason, pc)),
+            Outcome::Passed => None,
+        })
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (test, reason, pc) in &failures {
+            let location = pc
+                .and_then(|pc| source_location(manifest_dir, &test.source_map, pc))
+                .map(|location| format!(" at {}", location))
+                .unwrap_or_default();
+            println!("    {}: {}{}", test.name, reason, location);
+        }
+    }
+
+    let passed = results.len() - failures.len();
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} filtered out\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        failures.len(),
+        filtered_out,
+    );
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of the tests of {} failed",
+            failures.len(),
+            manifest.project.name
+        ))
+    }
+}
+
+fn run_test(test: &CompiledTest) -> Result<TestResult, String> {
+    let tx = Transaction::script(
+        0,
+        fuel_tx::consts::MAX_GAS_PER_TX,
+        0,
+        0,
+        test.bytecode.clone(),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+    );
+    let mut vm = Interpreter::with_storage(MemoryStorage::default());
+    let state = vm
+        .transact(tx)
+        .map_err(|e| format!("Failed to run test {}: {:?}", test.name, e))?;
+
+    let mut gas_used = 0;
+    let mut outcome = Outcome::Passed;
+    for receipt in state.receipts() {
+        match receipt {
+            Receipt::ScriptResult { gas_used: gas, .. } => gas_used = *gas,
+            Receipt::Revert { ra, pc, is, .. } => {
+                outcome = Outcome::Failed {
+                    reason: format!("reverted with {}", ra),
+                    pc: Some(pc - is),
+                }
+            }
+            Receipt::Panic { reason, pc, is, .. } => {
+                outcome = Outcome::Failed {
+                    reason: format!("panicked: {:?}", InstructionResult::from(*reason).reason()),
+                    pc: Some(pc - is),
+                }
+            }
+            _ => (),
+        }
+    }
+    if let (ProgramState::Revert(value), Outcome::Passed) = (state.state(), &outcome) {
+        outcome = Outcome::Failed {
+            reason: format!("reverted with {}", value),
+            pc: None,
+        };
+    }
+
+    Ok(TestResult {
+        name: test.name.clone(),
+        outcome,
+        gas_used,
+    })
+}
+
+/// The `path:line:column` of the source the instruction at byte offset `pc` of a test's bytecode
+/// was compiled from.
+fn source_location(manifest_dir: &Path, source_map: &SourceMap, pc: u64) -> Option<String> {
+    let (mut path, range) = source_map.addr_to_span(pc as usize / 4)?;
+    // the paths of the project's own files are relative to the directory it is in
+    if path.is_relative() {
+        path = manifest_dir.parent()?.join(path);
+    }
+    let source = std::fs::read_to_string(&path).ok()?;
+    let before = source.get(..range.start)?;
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map(|ix| ix + 1).unwrap_or(0) + 1;
+    Some(format!("{}:{}:{}", path.display(), line, column))
+}
+
+/// Runs the Rust tests of the project with `cargo test`.
+fn run_cargo_tests(manifest_dir: &Path, test_name: Option<String>) -> Result<(), String> {
+    // Cargo args setup
+    let mut args: Vec<String> = vec!["test".into()];
+    if let Some(name) = test_name {
+        args.push(name);
+    };
+    args.push("--color".into());
+    args.push("always".into());
+    args.push("--".into());
+    args.push("--nocapture".into());
+
+    let mut child = ProcessCommand::new("cargo")
+        .args(args)
+        .current_dir(manifest_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run `cargo test`: {}", e))?;
+
+    let out = BufReader::new(child.stdout.take().unwrap());
+    let err = BufReader::new(child.stderr.take().unwrap());
+
+    // Reading stderr on a separate thread so we keep things non-blocking
+    let thread = thread::spawn(move || {
+        err.lines().for_each(|line| println!("{}", line.unwrap()));
+    });
+
+    out.lines().for_each(|line| println!("{}", line.unwrap()));
+    thread.join().unwrap();
+
+    Ok(())
+}
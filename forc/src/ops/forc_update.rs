@@ -1,23 +1,19 @@
-use crate::{
-    cli::UpdateCommand,
-    ops::forc_dep_check,
-    utils::{dependency, helpers::read_manifest},
-};
+use crate::{cli::UpdateCommand, ops::forc_dep_check, utils::dependency};
 use anyhow::{anyhow, Result};
-use dirs::home_dir;
-use std::{path::PathBuf, str};
-use sway_utils::{
-    constants::{self},
-    find_manifest_dir,
-};
+use forc_pkg::{lock::find_lock_dir, Lock};
+use std::path::PathBuf;
+use sway_utils::{constants, find_manifest_dir};
 
-/// Forc update will update the contents inside the Forc dependencies directory.
+/// Forc update moves the GitHub-based dependencies locked in a project's `Forc.lock` to the
+/// latest commit of the branch or tag asked for in its `Forc.toml`, downloading them, and
+/// rewrites the lock file. It is the only command that changes commits already in the lock file;
+/// `forc build` only adds the dependencies that are missing from it.
 /// If a dependency `d` is passed as parameter, it will only try and update that specific dependency.
-/// Otherwise, it will try and update all GitHub-based dependencies in a project's `Forc.toml`.
-/// It won't automatically update dependencies that have a version specified, if you have
-/// specified a version for a dependency and want to update it you should, instead,
-/// run `forc update --check` to check for updates for all GitHub-based dependencies, and if
-/// a new version is detected and return, manually update your `Forc.toml` with this new version.
+/// Otherwise, it will try and update all GitHub-based dependencies, including those of dependencies.
+/// A dependency with a version specified only moves if its tag does, to update it to another
+/// version you should, instead, run `forc update --check` to check for updates for all
+/// GitHub-based dependencies, and if a new version is detected, manually update your `Forc.toml`
+/// with this new version.
 pub async fn update(command: UpdateCommand) -> Result<()> {
     if command.check {
         return forc_dep_check::check(command.path, command.target_dependency).await;
@@ -45,61 +41,73 @@ pub async fn update(command: UpdateCommand) -> Result<()> {
         }
     };
 
-    let mut manifest = read_manifest(&manifest_dir).unwrap();
+    let (lock_dir, package_dirs) = find_lock_dir(&manifest_dir).map_err(|e| anyhow!(e))?;
+    let lock = Lock::read(&lock_dir).map_err(|e| anyhow!(e))?;
 
-    let dependencies = dependency::get_detailed_dependencies(&mut manifest);
-
-    match target_dependency {
+    // Forget the commits of the dependencies to update, so that they are fetched at the latest
+    // one again.
+    let mut unlocked = lock.clone();
+    match &target_dependency {
         // Target dependency (`-d`) specified
-        Some(target_dep) => match dependencies.get(&target_dep) {
-            Some(dep) => Ok(update_dependency(&target_dep, dep).await?),
-            None => return Err(anyhow!("dependency {} not found", target_dep)),
-        },
-        // No target dependency specified, try and update all dependencies
-        None => {
-            for (dependency_name, dep) in dependencies {
-                update_dependency(&dependency_name, dep).await?;
+        Some(target_dep) => {
+            if !lock
+                .packages
+                .iter()
+                .any(|package| package.name == *target_dep)
+            {
+                return Err(anyhow!(
+                    "dependency {} not found in {}",
+                    target_dep,
+                    constants::LOCK_FILE_NAME
+                ));
             }
-            Ok(())
+            unlocked
+                .packages
+                .retain(|package| package.name != *target_dep || package.git.is_none());
         }
+        // No target dependency specified, try and update all dependencies
+        None => unlocked.packages.retain(|package| package.git.is_none()),
     }
-}
-
-async fn update_dependency(
-    dependency_name: &str,
-    dep: &dependency::DependencyDetails,
-) -> Result<()> {
-    let home_dir = match home_dir() {
-        None => return Err(anyhow!("Couldn't find home directory (`~/`)")),
-        Some(p) => p.to_str().unwrap().to_owned(),
-    };
-
-    // Currently we only handle updates on github-based dependencies
-    if let Some(git) = &dep.git {
-        match &dep.version {
-            // Automatically updating a dependency that has a tag/version specified in `Forc.toml`
-            // would mean to update the `Forc.toml` file, which I believe isn't a very
-            // nice behavior. Instead, if a tag/version is specified, the user should
-            // lookup for a desired version and manually specify it in `Forc.toml`.
-            Some(version) => println!("Ignoring update for {} at version {}: Forc update not implemented for dependencies with specified tag. To update to another tag, change the tag in `Forc.toml` and run the build command.", dependency_name, version),
-            None => {
-                let target_directory = match &dep.branch {
-                    Some(b) => PathBuf::from(format!("{}/{}/{}/{}", home_dir, constants::FORC_DEPENDENCIES_DIRECTORY, dependency_name, &b)),
-                    None => PathBuf::from(format!("{}/{}/{}/default", home_dir, constants::FORC_DEPENDENCIES_DIRECTORY, dependency_name)),
-                };
-
-                let current = dependency::get_current_dependency_version(&target_directory)?;
 
-                let latest_hash = dependency::get_latest_commit_sha(git, &dep.branch).await?;
+    let new_lock =
+        dependency::fetch_dependencies(&lock_dir, &package_dirs, &unlocked, false.into())?;
 
-                if current.hash == latest_hash {
-                      println!("{} is up-to-date", dependency_name);
-                } else {
-                    dependency::replace_dep_version(&target_directory, git, dep)?;
-                    println!("{}: {} -> {}", dependency_name, current.hash, latest_hash);
-                }
+    for package in new_lock
+        .packages
+        .iter()
+        .filter(|package| package.git.is_some())
+    {
+        let is_target = match &target_dependency {
+            Some(target_dep) => package.name == *target_dep,
+            None => true,
+        };
+        if !is_target {
+            continue;
+        }
+        let old = lock
+            .packages
+            .iter()
+            .find(|locked| locked.is_same_source(package));
+        match old {
+            Some(old) if old.commit == package.commit => {
+                println!("{} is up-to-date", package.name)
             }
+            Some(old) => println!(
+                "{}: {} -> {}",
+                package.name,
+                old.short_commit().unwrap_or_default(),
+                package.short_commit().unwrap_or_default()
+            ),
+            None => println!(
+                "{}: locked at {}",
+                package.name,
+                package.short_commit().unwrap_or_default()
+            ),
         }
     }
+
+    if new_lock != lock {
+        new_lock.write(&lock_dir).map_err(|e| anyhow!(e))?;
+    }
     Ok(())
 }
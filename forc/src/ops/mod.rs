@@ -1,9 +1,14 @@
 pub mod forc_abi_json;
 pub mod forc_build;
+pub mod forc_check;
+pub mod forc_contract_id;
 pub mod forc_dep_check;
 pub mod forc_deploy;
+pub mod forc_doc;
 pub mod forc_explorer;
 pub mod forc_fmt;
 pub mod forc_init;
+pub mod forc_predicate_root;
 pub mod forc_run;
+pub mod forc_test;
 pub mod forc_update;
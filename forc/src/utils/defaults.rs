@@ -1,12 +1,12 @@
 /// We intentionally don't construct this using [serde]'s default deserialization so we get
 /// the chance to insert some helpful comments and nicer formatting.
-pub(crate) fn default_manifest(project_name: &str) -> String {
+pub(crate) fn default_manifest(project_name: &str, entry: &str) -> String {
     let real_name = whoami::realname();
 
     format!(
         r#"[project]
 author = "{real_name}"
-entry = "main.sw"
+entry = "{entry}"
 license = "Apache-2.0"
 name = "{project_name}"
 
@@ -59,6 +59,51 @@ fn main() {
     .into()
 }
 
+pub(crate) fn default_contract() -> String {
+    r#"contract;
+
+abi MyContract {
+    fn test_function(gas_: u64, amount_: u64, asset_id_: b256, input: u64) -> u64;
+}
+
+impl MyContract for Contract {
+    fn test_function(gas_: u64, amount_: u64, asset_id_: b256, input: u64) -> u64 {
+        input
+    }
+}
+"#
+    .into()
+}
+
+pub(crate) fn default_predicate() -> String {
+    r#"predicate;
+
+fn main() -> bool {
+    true
+}
+"#
+    .into()
+}
+
+/// A library must be named by an identifier, which the name of the project may not be.
+pub(crate) fn default_library(library_name: &str) -> String {
+    format!(
+        r#"library {library_name};
+
+/// A point on the plane.
+pub struct Point {{
+    x: u64,
+    y: u64,
+}}
+
+/// The point at `x`, `y`.
+pub fn point(x: u64, y: u64) -> Point {{
+    Point {{ x: x, y: y }}
+}}
+"#
+    )
+}
+
 pub(crate) fn default_test_program() -> String {
     r#"
 
@@ -69,3 +114,24 @@ async fn harness() {
 "#
     .into()
 }
+
+#[test]
+fn parse_default_manifest() {
+    println!(
+        "{:#?}",
+        toml::from_str::<forc_pkg::Manifest>(&default_manifest("test_proj", "main.sw")).unwrap()
+    )
+}
+
+#[test]
+fn parse_default_programs() {
+    for program in [
+        default_contract(),
+        default_program(),
+        default_predicate(),
+        default_library("test_proj"),
+    ] {
+        let result = sway_core::parse(program.into(), None);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+}
@@ -1,10 +1,11 @@
-use crate::utils::manifest::Manifest;
 use anyhow::{anyhow, bail, Context, Result};
-use dirs::home_dir;
 use flate2::read::GzDecoder;
+use forc_pkg::{
+    dependency::{find_installed_commit, github_dep_install_dir},
+    lock::{checksum_dir, find_lock_dir},
+    read_manifest, Dependency, DependencyDetails, Lock, LockedPackage, Manifest,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
 use std::io::Read;
 use std::{
     collections::HashMap,
@@ -12,31 +13,12 @@ use std::{
     io::Cursor,
     path::{Path, PathBuf},
 };
-use sway_utils::constants;
+use sway_utils::{constants, find_manifest_dir};
 use tar::Archive;
 
 // A collection of remote dependency related functions
 
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(untagged)]
-pub enum Dependency {
-    /// In the simple format, only a version is specified, eg.
-    /// `package = "<version>"`
-    Simple(String),
-    /// The simple format is equivalent to a detailed dependency
-    /// specifying only a version, eg.
-    /// `package = { version = "<version>" }`
-    Detailed(DependencyDetails),
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "kebab-case")]
-pub struct DependencyDetails {
-    pub(crate) version: Option<String>,
-    pub(crate) path: Option<String>,
-    pub(crate) git: Option<String>,
-    pub(crate) branch: Option<String>,
-}
+#[derive(Clone, Copy)]
 pub enum OfflineMode {
     Yes,
     No,
@@ -84,96 +66,280 @@ pub struct TaggedRelease {
     pub published_at: String,
 }
 
-/// Downloads a non-local dependency that's hosted on GitHub.
-/// By default, it stores the dependency in `~/.forc/`.
-/// A given dependency `dep` is stored under `~/.forc/dep/default/$owner-$repo-$hash`.
-/// If no hash (nor any other type of reference) is provided, Forc
-/// will download the default branch at the latest commit.
-/// If a branch is specified, it will go in `~/.forc/dep/$branch/$owner-$repo-$hash.
-/// If a version is specified, it will go in `~/.forc/dep/$version/$owner-$repo-$hash.
-/// Version takes precedence over branch reference.
+/// Downloads a non-local dependency that's hosted on GitHub, at the given commit.
+/// By default, it stores the dependency in `~/.forc/`, see [github_dep_install_dir]. Each commit
+/// of the dependency is downloaded to a directory of its own, so a commit that has been
+/// downloaded already is used as it is.
 pub fn download_github_dep(
     dep_name: &str,
     repo_base_url: &str,
     branch: &Option<String>,
     version: &Option<String>,
+    commit: &str,
     offline_mode: OfflineMode,
-) -> Result<String> {
-    let home_dir = match home_dir() {
-        None => return Err(anyhow!("Couldn't find home directory (`~/`)")),
-        Some(p) => p.to_str().unwrap().to_owned(),
-    };
-
-    // hash the dep name into a number to avoid bad characters
-    let mut s = DefaultHasher::new();
-    dep_name.hash(&mut s);
-    let hashed_dep_name = s.finish().to_string();
-
-    // Version tag takes precedence over branch reference.
-    let out_dir = match &version {
-        Some(v) => PathBuf::from(format!(
-            "{}/{}/{}/{}",
-            home_dir,
-            constants::FORC_DEPENDENCIES_DIRECTORY,
-            hashed_dep_name,
-            v
-        )),
-        // If no version specified, check if a branch was specified
-        None => match &branch {
-            Some(b) => PathBuf::from(format!(
-                "{}/{}/{}/{}",
-                home_dir,
-                constants::FORC_DEPENDENCIES_DIRECTORY,
-                hashed_dep_name,
-                b
-            )),
-            // If no version and no branch, use default
-            None => PathBuf::from(format!(
-                "{}/{}/{}/default",
-                home_dir,
-                constants::FORC_DEPENDENCIES_DIRECTORY,
-                hashed_dep_name
-            )),
-        },
-    };
+) -> Result<PathBuf> {
+    let out_dir = github_dep_install_dir(dep_name, branch, version).map_err(|e| anyhow!(e))?;
 
-    // Check if dependency is already installed, if so, return its path.
-    if out_dir.exists() {
-        for entry in fs::read_dir(&out_dir)? {
-            let path = entry?.path();
-            // If the path to that dependency at that branch/version already
-            // exists and there's a directory inside of it,
-            // this directory should be the installation path.
-
-            if path.is_dir() {
-                return Ok(path.to_str().unwrap().to_string());
-            }
-        }
+    // Check if the commit is already installed, if so, return its path.
+    if let Some(path) = find_installed_commit(&out_dir, commit) {
+        return Ok(path);
     }
 
     // If offline mode is enabled, don't proceed as it will
     // make use of the network to download the dependency from
     // GitHub.
-    // If it's offline mode and the dependency already exists
-    // locally, then it would've been returned in the block above.
     if let OfflineMode::Yes = offline_mode {
         return Err(anyhow!(
-            "Can't build dependency: dependency {} doesn't exist locally and offline mode is enabled",
-            dep_name
+            "Can't build dependency: dependency {} at commit {} doesn't exist locally and offline mode is enabled",
+            dep_name,
+            commit
         ));
     }
 
-    let github_api_url = build_github_repo_api_url(repo_base_url, branch, version);
+    let github_api_url = format!(
+        "{}/{}",
+        build_github_repo_api_url(repo_base_url, &None, &None),
+        commit
+    );
 
     let _ = crate::utils::helpers::println_green(&format!(
         "  Downloading {:?} ({:?})",
         dep_name, out_dir
     ));
 
-    match download_tarball(&github_api_url, &out_dir) {
-        Ok(downloaded_dir) => Ok(downloaded_dir),
-        Err(e) => Err(anyhow!("couldn't download from {}: {}", &github_api_url, e)),
+    download_tarball(&github_api_url, &out_dir)
+        .map_err(|e| anyhow!("couldn't download from {}: {}", &github_api_url, e))?;
+    find_installed_commit(&out_dir, commit).ok_or_else(|| {
+        anyhow!(
+            "couldn't find downloaded dependency in directory: {}",
+            out_dir.display(),
+        )
+    })
+}
+
+/// Returns the full hash of the commit a GitHub repository is at, at the given version tag or
+/// branch. Version takes precedence over branch reference, and if neither is given the default
+/// branch is used.
+pub fn resolve_github_commit(
+    dependency_url: &str,
+    branch: &Option<String>,
+    version: &Option<String>,
+) -> Result<String> {
+    // Quick protection against `git` dependency URL ending with `/`.
+    let dependency_url = dependency_url.trim_end_matches('/');
+
+    let mut pieces = dependency_url.rsplit('/');
+
+    let project_name: &str = match pieces.next() {
+        Some(p) => p,
+        None => dependency_url,
+    };
+
+    let owner_name: &str = match pieces.next() {
+        Some(p) => p,
+        None => dependency_url,
+    };
+
+    let reference = version
+        .as_ref()
+        .or(branch.as_ref())
+        .map_or("HEAD", String::as_str);
+    let api_endpoint = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}",
+        owner_name, project_name, reference
+    );
+
+    let handle = ureq::builder().user_agent("forc-builder").build();
+    let resp = handle
+        .get(&api_endpoint)
+        .call()
+        .with_context(|| format!("couldn't find commit {} of {}", reference, dependency_url))?;
+    let commit: GithubCommit = serde_json::from_str(&resp.into_string()?)?;
+
+    if commit.sha.is_empty() {
+        bail!(
+            "failed to extract hash from GitHub commit API, response: {:?}",
+            commit
+        )
     }
+
+    Ok(commit.sha)
+}
+
+/// Makes sure every dependency of the package or workspace in `manifest_dir`, and every
+/// dependency of those, has been downloaded, and returns the lock recording what was used.
+/// Dependencies found in `Forc.lock` are fetched at the commit locked there; the others are fetched
+/// at the commit their branch or tag points to now. The lock file is written if it has changed.
+///
+/// The members of a workspace share the lock file at its root, so all of their dependencies are
+/// fetched, whichever of them is being built.
+pub fn fetch_and_lock(manifest_dir: &Path, offline_mode: bool) -> Result<Lock> {
+    let (lock_dir, package_dirs) = find_lock_dir(manifest_dir).map_err(|e| anyhow!(e))?;
+    let lock = Lock::read(&lock_dir).map_err(|e| anyhow!(e))?;
+    let new_lock = fetch_dependencies(&lock_dir, &package_dirs, &lock, offline_mode.into())?;
+    if new_lock != lock {
+        new_lock.write(&lock_dir).map_err(|e| anyhow!(e))?;
+    }
+    Ok(new_lock)
+}
+
+/// Downloads the dependencies of the packages in `package_dirs`, transitively, at the commits
+/// locked in `lock`, and returns the lock for all of them along with the packages themselves.
+/// The paths of packages found by path are locked relative to `lock_dir`.
+pub fn fetch_dependencies(
+    lock_dir: &Path,
+    package_dirs: &[PathBuf],
+    lock: &Lock,
+    offline_mode: OfflineMode,
+) -> Result<Lock> {
+    let mut new_lock = Lock::default();
+    for manifest_dir in package_dirs {
+        let manifest = read_manifest(manifest_dir).map_err(|e| anyhow!(e))?;
+        let package = LockedPackage::local(&manifest.project.name, manifest_dir, lock_dir);
+        // a member of a workspace may have been reached from another already
+        if new_lock.contains(&package) {
+            continue;
+        }
+        fetch_package(
+            manifest_dir,
+            &manifest,
+            package,
+            lock_dir,
+            lock,
+            offline_mode,
+            &mut new_lock,
+        )?;
+    }
+    new_lock.sort();
+    Ok(new_lock)
+}
+
+fn fetch_package(
+    manifest_dir: &Path,
+    manifest: &Manifest,
+    mut package: LockedPackage,
+    lock_dir: &Path,
+    lock: &Lock,
+    offline_mode: OfflineMode,
+    new_lock: &mut Lock,
+) -> Result<()> {
+    let mut dependencies = vec![];
+    for (dep_name, dependency) in manifest.dependencies.iter().flatten() {
+        let details = match dependency {
+            Dependency::Simple(..) => {
+                bail!("Not yet implemented: Simple version-spec dependencies require a registry.")
+            }
+            Dependency::Detailed(details) => details,
+        };
+        let (dep_package, dep_path) = match (&details.git, &details.path) {
+            (Some(git), _) => fetch_git_dep(dep_name, git, details, lock, offline_mode)?,
+            // dependency paths are relative to the manifest of the package depending on them
+            (None, Some(path)) => {
+                let dep_path = manifest_dir.join(path);
+                (
+                    LockedPackage::local(dep_name, &dep_path, lock_dir),
+                    dep_path,
+                )
+            }
+            (None, None) => bail!(
+                "Dependency {:?} has neither a path nor a git repository.",
+                dep_name
+            ),
+        };
+        package.dependencies.push(dep_name.clone());
+        dependencies.push((dep_package, dep_path));
+    }
+
+    // the package is locked before its dependencies are fetched, so that a package depending on
+    // itself doesn't send this round in circles
+    new_lock.packages.push(package);
+    for (dep_package, dep_path) in dependencies {
+        if new_lock.contains(&dep_package) {
+            continue;
+        }
+        let dep_manifest_dir = find_manifest_dir(&dep_path)
+            .ok_or_else(|| anyhow!("Manifest not found for dependency {:?}.", dep_path))?;
+        let dep_manifest = read_manifest(&dep_manifest_dir).map_err(|e| anyhow!(e))?;
+        fetch_package(
+            &dep_manifest_dir,
+            &dep_manifest,
+            dep_package,
+            lock_dir,
+            lock,
+            offline_mode,
+            new_lock,
+        )?;
+    }
+    Ok(())
+}
+
+/// Downloads the git dependency `dep_name` at the commit locked for it, or at the latest commit
+/// if it isn't locked yet, and checks its contents haven't changed since it was locked.
+fn fetch_git_dep(
+    dep_name: &str,
+    git: &str,
+    details: &DependencyDetails,
+    lock: &Lock,
+    offline_mode: OfflineMode,
+) -> Result<(LockedPackage, PathBuf)> {
+    // the qualified name of the dependency includes its source and some metadata to prevent
+    // conflating dependencies from different sources
+    let fully_qualified_dep_name = format!("{}-{}", dep_name, git);
+    let locked = lock.find_git(dep_name, details);
+    let commit = match (
+        locked.and_then(|locked| locked.commit.clone()),
+        offline_mode,
+    ) {
+        (Some(commit), _) => commit,
+        (None, OfflineMode::No) => resolve_github_commit(git, &details.branch, &details.version)
+            .with_context(|| format!("Couldn't fetch dependency ({:?})", dep_name))?,
+        // without the network, lock whichever commit was downloaded last
+        (None, OfflineMode::Yes) => {
+            let out_dir = github_dep_install_dir(
+                &fully_qualified_dep_name,
+                &details.branch,
+                &details.version,
+            )
+            .map_err(|e| anyhow!(e))?;
+            get_current_dependency_version(&out_dir)
+                .with_context(|| {
+                    format!(
+                        "Can't build dependency: dependency {} isn't locked nor downloaded and offline mode is enabled",
+                        dep_name
+                    )
+                })?
+                .hash
+        }
+    };
+
+    let path = download_github_dep(
+        &fully_qualified_dep_name,
+        git,
+        &details.branch,
+        &details.version,
+        &commit,
+        offline_mode,
+    )
+    .with_context(|| format!("Couldn't download dependency ({:?})", dep_name))?;
+
+    let checksum = checksum_dir(&path).map_err(|e| anyhow!(e))?;
+    if let Some(locked_checksum) = locked.and_then(|locked| locked.checksum.as_ref()) {
+        if *locked_checksum != checksum {
+            bail!(
+                "The contents of dependency {:?} at commit {} don't match the checksum in `{}`. \
+                 Delete {} to download it again.",
+                dep_name,
+                commit,
+                constants::LOCK_FILE_NAME,
+                path.display()
+            );
+        }
+    }
+
+    Ok((
+        LockedPackage::git(dep_name, details, commit, checksum),
+        path,
+    ))
 }
 
 /// Builds a proper URL that's used to call GitHub's API.
@@ -226,7 +392,8 @@ pub fn build_github_repo_api_url(
     }
 }
 
-pub fn download_tarball(url: &str, out_dir: &Path) -> Result<String> {
+/// Downloads the tarball at `url` and unpacks it in `out_dir`.
+pub fn download_tarball(url: &str, out_dir: &Path) -> Result<()> {
     let mut data = Vec::new();
 
     // Download the tarball.
@@ -242,54 +409,24 @@ pub fn download_tarball(url: &str, out_dir: &Path) -> Result<String> {
                 "failed to unpack tarball in directory: {}",
                 out_dir.display()
             )
-        })?;
-
-    for entry in fs::read_dir(out_dir)? {
-        let path = entry?.path();
-        match path.is_dir() {
-            true => return Ok(path.to_str().unwrap().to_string()),
-            false => (),
-        }
-    }
-
-    Err(anyhow!(
-        "couldn't find downloaded dependency in directory: {}",
-        out_dir.display(),
-    ))
-}
-
-pub fn replace_dep_version(
-    target_directory: &Path,
-    git: &str,
-    dep: &DependencyDetails,
-) -> Result<()> {
-    let current = get_current_dependency_version(target_directory)?;
-
-    let api_url = build_github_repo_api_url(git, &dep.branch, &dep.version);
-    download_tarball(&api_url, target_directory)?;
-
-    // Delete old one
-    match fs::remove_dir_all(current.path) {
-        Ok(_) => Ok(()),
-        Err(e) => {
-            return Err(anyhow!(
-                "failed to remove old version of the dependency ({}): {}",
-                git,
-                e
-            ))
-        }
-    }
+        })
 }
 
+/// The version of a dependency downloaded to `dep_dir` most recently, if several are.
 pub fn get_current_dependency_version(dep_dir: &Path) -> Result<VersionedDependencyDirectory> {
-    let mut entries =
+    let entries =
         fs::read_dir(dep_dir).context(format!("couldn't read directory {}", dep_dir.display()))?;
-    let entry = match entries.next() {
-        Some(entry) => entry,
+    let mut versions = vec![];
+    for entry in entries {
+        let entry = entry?;
+        let modified = entry.metadata()?.modified()?;
+        versions.push((modified, entry.path()));
+    }
+    // the order of the entries of a directory is arbitrary, so ties are broken by name
+    let path = match versions.into_iter().max() {
+        Some((_, path)) => path,
         None => bail!("Dependency directory is empty. Run `forc build` to install dependencies."),
     };
-
-    let path = entry?.path();
     if !path.is_dir() {
         bail!("{} isn't a directory.", dep_dir.display())
     }
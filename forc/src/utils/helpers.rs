@@ -1,13 +1,11 @@
-use super::manifest::Manifest;
 use annotate_snippets::{
     display_list::{DisplayList, FormatOptions},
     snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation},
 };
 use std::ffi::OsStr;
 use std::io::{self, Write};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::str;
-use std::sync::Arc;
 use sway_core::{error::LineCol, CompileError, CompileWarning, TreeType};
 use sway_utils::constants;
 use termcolor::{self, Color as TermColor, ColorChoice, ColorSpec, StandardStream, WriteColor};
@@ -17,59 +15,6 @@ pub fn is_sway_file(file: &Path) -> bool {
     Some(OsStr::new(constants::SWAY_EXTENSION)) == res
 }
 
-pub fn find_main_path(manifest_dir: &Path, manifest: &Manifest) -> PathBuf {
-    let mut code_dir = manifest_dir.to_path_buf();
-    code_dir.push(constants::SRC_DIR);
-    code_dir.push(&manifest.project.entry);
-    code_dir
-}
-
-pub fn find_file_name<'sc>(manifest_dir: &Path, main_path: &'sc Path) -> Result<&'sc Path, String> {
-    let mut file_path = manifest_dir.to_path_buf();
-    file_path.pop();
-    let file_name = match main_path.strip_prefix(file_path.clone()) {
-        Ok(o) => o,
-        Err(err) => return Err(err.to_string()),
-    };
-    Ok(file_name)
-}
-
-pub fn read_manifest(manifest_dir: &Path) -> Result<Manifest, String> {
-    let manifest_path = {
-        let mut man = PathBuf::from(manifest_dir);
-        man.push(constants::MANIFEST_FILE_NAME);
-        man
-    };
-    let manifest_path_str = format!("{:?}", manifest_path);
-    let manifest = match std::fs::read_to_string(manifest_path) {
-        Ok(o) => o,
-        Err(e) => {
-            return Err(format!(
-                "failed to read manifest at {:?}: {}",
-                manifest_path_str, e
-            ))
-        }
-    };
-    match toml::from_str(&manifest) {
-        Ok(o) => Ok(o),
-        Err(e) => Err(format!("Error parsing manifest: {}.", e)),
-    }
-}
-
-pub fn get_main_file(manifest_of_dep: &Manifest, manifest_dir: &Path) -> Result<Arc<str>, String> {
-    let main_path = {
-        let mut code_dir = PathBuf::from(manifest_dir);
-        code_dir.push(constants::SRC_DIR);
-        code_dir.push(&manifest_of_dep.project.entry);
-        code_dir
-    };
-
-    // some hackery to get around lifetimes for now, until the AST returns a non-lifetime-bound AST
-    let main_file = std::fs::read_to_string(&main_path).map_err(|e| e.to_string())?;
-    let main_file = Arc::from(main_file);
-    Ok(main_file)
-}
-
 pub fn print_on_success(
     silent_mode: bool,
     proj_name: &str,
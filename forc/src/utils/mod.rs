@@ -3,4 +3,3 @@ pub mod client;
 pub mod defaults;
 pub mod dependency;
 pub mod helpers;
-pub mod manifest;
@@ -14,3 +14,15 @@ const NUM_COMPILER_RESERVED_REGISTERS: u8 = 1;
 pub(crate) const DATA_SECTION_REGISTER: u8 = NUM_FREE_REGISTERS - 2;
 pub(crate) const NUM_ALLOCATABLE_REGISTERS: u8 =
     NUM_FREE_REGISTERS - NUM_COMPILER_RESERVED_REGISTERS;
+
+/// The word offsets of the fields in the header of a `Vec<T>`: a pointer to its heap buffer, the
+/// capacity of that buffer in elements and the number of elements in use.
+pub(crate) const VEC_PTR_OFFSET: u64 = 0;
+pub(crate) const VEC_CAP_OFFSET: u64 = 1;
+pub(crate) const VEC_LEN_OFFSET: u64 = 2;
+/// The number of elements allocated for a `Vec<T>` the first time it is pushed to.
+pub(crate) const VEC_INITIAL_CAPACITY: u64 = 4;
+
+/// The bit of the `$flag` register that makes overflowing arithmetic wrap around instead of
+/// reverting.
+pub(crate) const F_WRAPPING: u64 = 0b10;
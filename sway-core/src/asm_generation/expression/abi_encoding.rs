@@ -0,0 +1,667 @@
+//! The code which moves values holding a `String` or a `Vec<T>` across a contract call.
+//!
+//! The header of a heap type points to a buffer in the heap of the context that created it. A
+//! called contract may read the memory of its caller, but may not write to it, and a caller's
+//! `$hp` is restored when the call returns, so that the buffers of the returned value are freed.
+//! Hence:
+//!
+//! - An ABI function copies its argument, and the buffers it refers to, into its own heap before
+//!   running.
+//! - A returned value is encoded as a single block of data holding the value followed by its
+//!   buffers, in which every header points to its buffer by its offset from the start of the
+//!   block. The caller copies the block into its own heap and adds the block's address to those
+//!   offsets.
+use super::array::set_large_register_value;
+use super::compiler_constants::{VEC_CAP_OFFSET, VEC_LEN_OFFSET, VEC_PTR_OFFSET};
+use super::vec::{load_header_field, store_header_field};
+use super::*;
+use crate::type_engine::{resolve_type, TypeId};
+
+/// The header of a `String` or a `Vec<T>` within a value.
+struct HeapHeader {
+    /// The offset of the header from the start of the value, in words.
+    offset_in_words: u64,
+    /// The size of the slot of each element of the buffer in bytes, or `None` for the bytes of a
+    /// string.
+    elem_size_in_bytes: Option<u64>,
+    /// The headers within each element of the buffer.
+    elem_headers: Vec<HeapHeader>,
+}
+
+impl HeapHeader {
+    /// The capacity a copied buffer of `size_reg` bytes holding `len_reg` elements has.
+    fn copied_capacity<'a>(
+        &self,
+        len_reg: &'a VirtualRegister,
+        size_reg: &'a VirtualRegister,
+    ) -> &'a VirtualRegister {
+        match self.elem_size_in_bytes {
+            Some(_) => len_reg,
+            None => size_reg,
+        }
+    }
+}
+
+/// Finds the headers of the heap types within a value of type `ty` which starts
+/// `offset_in_words` into the value being searched. Enums are skipped, as their contents are
+/// referred to rather than held by them.
+#[allow(clippy::result_large_err)]
+fn heap_headers(
+    ty: &TypeInfo,
+    offset_in_words: u64,
+    span: &Span,
+) -> Result<Vec<HeapHeader>, CompileError> {
+    Ok(match ty {
+        TypeInfo::DynamicString => vec![HeapHeader {
+            offset_in_words,
+            elem_size_in_bytes: None,
+            elem_headers: vec![],
+        }],
+        TypeInfo::Vector(elem_type) => {
+            let elem_type = resolve_type(*elem_type, span)?;
+            vec![HeapHeader {
+                offset_in_words,
+                // The same slots as those of `Vec<T>` operations.
+                elem_size_in_bytes: Some(std::cmp::max(elem_type.size_in_words(span)?, 1) * 8),
+                elem_headers: heap_headers(&elem_type, 0, span)?,
+            }]
+        }
+        TypeInfo::Ref(ty) | TypeInfo::Alias { ty, .. } => {
+            heap_headers(&resolve_type(*ty, span)?, offset_in_words, span)?
+        }
+        TypeInfo::Tuple(fields) => {
+            fields_heap_headers(fields.iter().copied(), offset_in_words, span)?
+        }
+        TypeInfo::Struct { fields, .. } => fields_heap_headers(
+            fields.iter().map(|field| field.r#type),
+            offset_in_words,
+            span,
+        )?,
+        TypeInfo::Array(elem_type, count) => {
+            fields_heap_headers((0..*count).map(|_| *elem_type), offset_in_words, span)?
+        }
+        _ => vec![],
+    })
+}
+
+/// Finds the headers of the heap types within a sequence of contiguous fields.
+#[allow(clippy::result_large_err)]
+fn fields_heap_headers(
+    fields: impl Iterator<Item = TypeId>,
+    mut offset_in_words: u64,
+    span: &Span,
+) -> Result<Vec<HeapHeader>, CompileError> {
+    let mut headers = vec![];
+    for field in fields {
+        let field = resolve_type(field, span)?;
+        headers.append(&mut heap_headers(&field, offset_in_words, span)?);
+        offset_in_words += field.size_in_words(span)?;
+    }
+    Ok(headers)
+}
+
+/// The headers of the heap types within a value of type `ty`, and the size of the value in
+/// bytes.
+fn value_heap_headers(ty: TypeId, span: &Span) -> CompileResult<(Vec<HeapHeader>, u64)> {
+    let warnings = vec![];
+    let mut errors = vec![];
+    let ty = check_std_result!(resolve_type(ty, span), warnings, errors);
+    let headers = check_std_result!(heap_headers(&ty, 0, span), warnings, errors);
+    let size_in_words = check_std_result!(ty.size_in_words(span), warnings, errors);
+    ok((headers, size_in_words * 8), warnings, errors)
+}
+
+/// Replaces the value `value_reg` points to, which may be in the memory of the caller of an ABI
+/// function, with a copy of it in the heap of the ABI function.
+pub(crate) fn convert_abi_argument_to_owned(
+    ty: TypeId,
+    value_reg: &VirtualRegister,
+    span: &Span,
+    register_sequencer: &mut RegisterSequencer,
+) -> CompileResult<Vec<Op>> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+    let mut bytecode = vec![];
+    let (headers, size_in_bytes) = check!(
+        value_heap_headers(ty, span),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+    if headers.is_empty() {
+        return ok(bytecode, warnings, errors);
+    }
+
+    let size_reg = register_sequencer.next();
+    let size_reg = set_large_register_value(size_in_bytes, &size_reg, &mut bytecode, span).clone();
+    let copy_reg = compile_allocate(&size_reg, span, &mut bytecode, register_sequencer);
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MCP(copy_reg.clone(), value_reg.clone(), size_reg),
+        span.clone(),
+        "copy abi argument",
+    ));
+    compile_walk(
+        &headers,
+        &copy_reg,
+        &|header, header_reg, bytecode, register_sequencer| {
+            let (len_reg, buf_size_reg) =
+                compile_buffer_size(header, header_reg, span, bytecode, register_sequencer);
+            let buf_reg = compile_allocate(&buf_size_reg, span, bytecode, register_sequencer);
+            compile_copy_buffer(
+                &buf_reg,
+                header_reg,
+                &buf_size_reg,
+                span,
+                bytecode,
+                register_sequencer,
+            );
+            bytecode.push(store_header_field(
+                header_reg,
+                &buf_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+            bytecode.push(store_header_field(
+                header_reg,
+                header.copied_capacity(&len_reg, &buf_size_reg),
+                VEC_CAP_OFFSET,
+                span,
+            ));
+        },
+        &|_, _, _, _| (),
+        span,
+        &mut bytecode,
+        register_sequencer,
+    );
+    bytecode.push(Op::register_move_comment(
+        value_reg.clone(),
+        copy_reg,
+        span.clone(),
+        "use copy of abi argument",
+    ));
+    ok(bytecode, warnings, errors)
+}
+
+/// Encodes the value `value_reg` points to for returning with `RETD`, if it holds any heap types,
+/// leaving the address of the encoded data in `value_reg` and its size in bytes in `size_reg`.
+/// Returns `None` if the value has no heap types to encode.
+///
+/// The encoded data is copied by the caller to the top of its heap, which overlaps the top of
+/// the heap of this context. Twice the size of the data is allocated for it, and the data
+/// written to the lower half, so that it can't overlap its copy.
+pub(crate) fn convert_return_value_to_encoded(
+    ty: TypeId,
+    value_reg: &VirtualRegister,
+    size_reg: &VirtualRegister,
+    span: &Span,
+    register_sequencer: &mut RegisterSequencer,
+) -> CompileResult<Option<Vec<Op>>> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+    let mut bytecode = vec![];
+    let (headers, value_size_in_bytes) = check!(
+        value_heap_headers(ty, span),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+    if headers.is_empty() {
+        return ok(None, warnings, errors);
+    }
+
+    // The size of the value and all of its buffers.
+    let value_size_reg = register_sequencer.next();
+    let value_size_reg =
+        set_large_register_value(value_size_in_bytes, &value_size_reg, &mut bytecode, span).clone();
+    bytecode.push(Op::register_move_comment(
+        size_reg.clone(),
+        value_size_reg.clone(),
+        span.clone(),
+        "size of encoded value",
+    ));
+    compile_walk(
+        &headers,
+        value_reg,
+        &|header, header_reg, bytecode, register_sequencer| {
+            let (_, buf_size_reg) =
+                compile_buffer_size(header, header_reg, span, bytecode, register_sequencer);
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADD(size_reg.clone(), size_reg.clone(), buf_size_reg),
+                span.clone(),
+                "add buffer to size of encoded value",
+            ));
+        },
+        &|_, _, _, _| (),
+        span,
+        &mut bytecode,
+        register_sequencer,
+    );
+
+    let alloc_size_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADD(alloc_size_reg.clone(), size_reg.clone(), size_reg.clone()),
+        span.clone(),
+        "twice the size of encoded value",
+    ));
+    let data_reg = compile_allocate(&alloc_size_reg, span, &mut bytecode, register_sequencer);
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MCP(data_reg.clone(), value_reg.clone(), value_size_reg.clone()),
+        span.clone(),
+        "copy value to encoded value",
+    ));
+    // Where the next buffer is copied to.
+    let cursor_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADD(cursor_reg.clone(), data_reg.clone(), value_size_reg),
+        span.clone(),
+        "address of first buffer of encoded value",
+    ));
+    compile_walk(
+        &headers,
+        &data_reg,
+        &|header, header_reg, bytecode, register_sequencer| {
+            let (len_reg, buf_size_reg) =
+                compile_buffer_size(header, header_reg, span, bytecode, register_sequencer);
+            compile_copy_buffer(
+                &cursor_reg,
+                header_reg,
+                &buf_size_reg,
+                span,
+                bytecode,
+                register_sequencer,
+            );
+            bytecode.push(store_header_field(
+                header_reg,
+                &cursor_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+            bytecode.push(store_header_field(
+                header_reg,
+                header.copied_capacity(&len_reg, &buf_size_reg),
+                VEC_CAP_OFFSET,
+                span,
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADD(cursor_reg.clone(), cursor_reg.clone(), buf_size_reg.clone()),
+                span.clone(),
+                "address of next buffer of encoded value",
+            ));
+        },
+        // The buffer's address is only replaced by its offset once its own elements are encoded.
+        &|_, header_reg, bytecode, register_sequencer| {
+            let ptr_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &ptr_reg,
+                header_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::SUB(ptr_reg.clone(), ptr_reg.clone(), data_reg.clone()),
+                span.clone(),
+                "offset of buffer in encoded value",
+            ));
+            bytecode.push(store_header_field(
+                header_reg,
+                &ptr_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+        },
+        span,
+        &mut bytecode,
+        register_sequencer,
+    );
+    bytecode.push(Op::register_move_comment(
+        value_reg.clone(),
+        data_reg,
+        span.clone(),
+        "return encoded value",
+    ));
+    ok(Some(bytecode), warnings, errors)
+}
+
+/// Decodes a value of type `ty` returned by a contract call, if it holds any heap types, copying
+/// the returned data into the heap of this context and leaving the address of the value in
+/// `value_reg`.
+pub(crate) fn convert_returned_value_to_decoded(
+    ty: TypeId,
+    value_reg: &VirtualRegister,
+    span: &Span,
+    register_sequencer: &mut RegisterSequencer,
+) -> CompileResult<Vec<Op>> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+    let mut bytecode = vec![];
+    let (headers, _) = check!(
+        value_heap_headers(ty, span),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+    if headers.is_empty() {
+        return ok(bytecode, warnings, errors);
+    }
+
+    let data_reg = compile_allocate(
+        &VirtualRegister::Constant(ConstantRegister::ReturnLength),
+        span,
+        &mut bytecode,
+        register_sequencer,
+    );
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MCP(
+            data_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::ReturnValue),
+            VirtualRegister::Constant(ConstantRegister::ReturnLength),
+        ),
+        span.clone(),
+        "copy returned value",
+    ));
+    compile_walk(
+        &headers,
+        &data_reg,
+        &|_, header_reg, bytecode, register_sequencer| {
+            let ptr_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &ptr_reg,
+                header_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADD(ptr_reg.clone(), ptr_reg.clone(), data_reg.clone()),
+                span.clone(),
+                "address of returned buffer",
+            ));
+            bytecode.push(store_header_field(
+                header_reg,
+                &ptr_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+        },
+        &|_, _, _, _| (),
+        span,
+        &mut bytecode,
+        register_sequencer,
+    );
+    bytecode.push(Op::register_move_comment(
+        value_reg.clone(),
+        data_reg,
+        span.clone(),
+        "use copy of returned value",
+    ));
+    ok(bytecode, warnings, errors)
+}
+
+/// Generates the code for one of the passes above over a header, given a register holding the
+/// header's address.
+type HeaderVisitor<'a> =
+    &'a dyn Fn(&HeapHeader, &VirtualRegister, &mut Vec<Op>, &mut RegisterSequencer);
+
+/// Visits every header within the value `value_reg` points to, including those within the
+/// elements of the buffers it refers to. `enter` is applied to a header before the headers within
+/// the elements of its buffer are visited, and `exit` after.
+fn compile_walk(
+    headers: &[HeapHeader],
+    value_reg: &VirtualRegister,
+    enter: HeaderVisitor,
+    exit: HeaderVisitor,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    for header in headers {
+        let header_reg = register_sequencer.next();
+        let offset_reg = register_sequencer.next();
+        let offset_reg =
+            set_large_register_value(header.offset_in_words * 8, &offset_reg, bytecode, span)
+                .clone();
+        bytecode.push(Op::new_with_comment(
+            VirtualOp::ADD(header_reg.clone(), value_reg.clone(), offset_reg),
+            span.clone(),
+            "address of header",
+        ));
+        enter(header, &header_reg, bytecode, register_sequencer);
+        if let (Some(elem_size_in_bytes), false) =
+            (header.elem_size_in_bytes, header.elem_headers.is_empty())
+        {
+            compile_walk_elems(
+                header,
+                elem_size_in_bytes,
+                &header_reg,
+                enter,
+                exit,
+                span,
+                bytecode,
+                register_sequencer,
+            );
+        }
+        exit(header, &header_reg, bytecode, register_sequencer);
+    }
+}
+
+/// Visits the headers within each element of the buffer of the header `header_reg` points to.
+#[allow(clippy::too_many_arguments)]
+fn compile_walk_elems(
+    header: &HeapHeader,
+    elem_size_in_bytes: u64,
+    header_reg: &VirtualRegister,
+    enter: HeaderVisitor,
+    exit: HeaderVisitor,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let len_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &len_reg,
+        header_reg,
+        VEC_LEN_OFFSET,
+        span,
+    ));
+    let index_reg = register_sequencer.next();
+    bytecode.push(Op::register_move_comment(
+        index_reg.clone(),
+        VirtualRegister::Constant(ConstantRegister::Zero),
+        span.clone(),
+        "first element",
+    ));
+    let loop_label = register_sequencer.get_label();
+    let done_label = register_sequencer.get_label();
+    bytecode.push(Op::jump_label_comment(
+        loop_label.clone(),
+        span.clone(),
+        "visit element",
+    ));
+    let is_done_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::EQ(is_done_reg.clone(), index_reg.clone(), len_reg),
+        span.clone(),
+        "are all elements visited",
+    ));
+    bytecode.push(Op::jump_if_not_equal(
+        is_done_reg,
+        VirtualRegister::Constant(ConstantRegister::Zero),
+        done_label.clone(),
+    ));
+    let size_reg = register_sequencer.next();
+    let size_reg = set_large_register_value(elem_size_in_bytes, &size_reg, bytecode, span).clone();
+    let elem_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MUL(elem_reg.clone(), index_reg.clone(), size_reg),
+        span.clone(),
+        "element offset",
+    ));
+    let ptr_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &ptr_reg,
+        header_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADD(elem_reg.clone(), ptr_reg, elem_reg.clone()),
+        span.clone(),
+        "element address",
+    ));
+    compile_walk(
+        &header.elem_headers,
+        &elem_reg,
+        enter,
+        exit,
+        span,
+        bytecode,
+        register_sequencer,
+    );
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADDI(
+            index_reg.clone(),
+            index_reg,
+            VirtualImmediate12 { value: 1 },
+        ),
+        span.clone(),
+        "next element",
+    ));
+    bytecode.push(Op::jump_to_label(loop_label));
+    bytecode.push(Op::jump_label_comment(
+        done_label,
+        span.clone(),
+        "after visiting elements",
+    ));
+}
+
+/// Loads the length of the buffer of the header `header_reg` points to, and computes the size of
+/// the part of the buffer in use in bytes, rounded up to a whole word. Returns the registers
+/// holding the two.
+fn compile_buffer_size(
+    header: &HeapHeader,
+    header_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) -> (VirtualRegister, VirtualRegister) {
+    let len_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &len_reg,
+        header_reg,
+        VEC_LEN_OFFSET,
+        span,
+    ));
+    let size_reg = register_sequencer.next();
+    match header.elem_size_in_bytes {
+        Some(elem_size_in_bytes) => {
+            let elem_size_reg = register_sequencer.next();
+            let elem_size_reg =
+                set_large_register_value(elem_size_in_bytes, &elem_size_reg, bytecode, span)
+                    .clone();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::MUL(size_reg.clone(), len_reg.clone(), elem_size_reg),
+                span.clone(),
+                "size of buffer",
+            ));
+        }
+        None => {
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADDI(
+                    size_reg.clone(),
+                    len_reg.clone(),
+                    VirtualImmediate12 { value: 7 },
+                ),
+                span.clone(),
+                "round string size up",
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::SRLI(
+                    size_reg.clone(),
+                    size_reg.clone(),
+                    VirtualImmediate12 { value: 3 },
+                ),
+                span.clone(),
+                "round string size up",
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::SLLI(
+                    size_reg.clone(),
+                    size_reg.clone(),
+                    VirtualImmediate12 { value: 3 },
+                ),
+                span.clone(),
+                "round string size up",
+            ));
+        }
+    }
+    (len_reg, size_reg)
+}
+
+/// Copies `size_reg` bytes of the buffer of the header `header_reg` points to, to `dst_reg`.
+/// `MCP` can't copy zero bytes, so empty buffers are skipped.
+fn compile_copy_buffer(
+    dst_reg: &VirtualRegister,
+    header_reg: &VirtualRegister,
+    size_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let is_empty_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::EQ(
+            is_empty_reg.clone(),
+            size_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::Zero),
+        ),
+        span.clone(),
+        "is buffer empty",
+    ));
+    let skip_label = register_sequencer.get_label();
+    bytecode.push(Op::jump_if_not_equal(
+        is_empty_reg,
+        VirtualRegister::Constant(ConstantRegister::Zero),
+        skip_label.clone(),
+    ));
+    let src_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &src_reg,
+        header_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MCP(dst_reg.clone(), src_reg, size_reg.clone()),
+        span.clone(),
+        "copy buffer",
+    ));
+    bytecode.push(Op::jump_label_comment(
+        skip_label,
+        span.clone(),
+        "after copying buffer",
+    ));
+}
+
+/// Allocates `size_reg` bytes on the heap and returns a register holding their address.
+fn compile_allocate(
+    size_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) -> VirtualRegister {
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ALOC(size_reg.clone()),
+        span.clone(),
+        "allocate heap memory",
+    ));
+    let ptr_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADDI(
+            ptr_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::HeapPointer),
+            VirtualImmediate12 { value: 1 },
+        ),
+        span.clone(),
+        "heap memory address",
+    ));
+    ptr_reg
+}
@@ -344,7 +344,7 @@ pub(super) fn convert_array_index_to_asm(
 // We want the first (and usually probably the only) operation to OR with Zero, so we recurse for
 // each set of 12 bits until we hit a zero value, and then return the Zero register to be used
 // next.  Thereafter we OR the destination register.
-fn set_large_register_value<'a>(
+pub(super) fn set_large_register_value<'a>(
     value: u64,
     dst_reg: &'a VirtualRegister,
     bytecode: &mut Vec<Op>,
@@ -1,5 +1,6 @@
 use super::*;
 use crate::semantic_analysis::ast_node::*;
+use crate::type_engine::TypeId;
 use either::Either;
 /// Converts a function application of a contract ABI function into assembly
 #[allow(clippy::too_many_arguments)]
@@ -9,6 +10,7 @@ pub(crate) fn convert_contract_call_to_asm(
     bal: &TypedExpression,
     coin_color: &TypedExpression,
     user_argument: &TypedExpression,
+    return_type: TypeId,
     register_sequencer: &mut RegisterSequencer,
     return_register: &VirtualRegister,
     namespace: &mut AsmNamespace,
@@ -167,7 +169,14 @@ pub(crate) fn convert_contract_call_to_asm(
     asm_buf.push(Op::register_move(
         return_register.into(),
         VirtualRegister::Constant(ConstantRegister::ReturnValue),
-        span,
+        span.clone(),
+    ));
+    // values holding heap types are returned encoded, and copied into this context's heap
+    asm_buf.append(&mut check!(
+        convert_returned_value_to_decoded(return_type, return_register, &span, register_sequencer),
+        vec![],
+        warnings,
+        errors
     ));
 
     ok(asm_buf, warnings, errors)
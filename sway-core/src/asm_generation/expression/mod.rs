@@ -10,13 +10,20 @@ use crate::{
 };
 use sway_types::span::Span;
 
+mod abi_encoding;
 mod array;
 mod contract_call;
 mod enums;
 mod if_exp;
 mod lazy_op;
+mod string;
 mod structs;
 mod subfield;
+mod vec;
+pub(crate) use abi_encoding::{
+    convert_abi_argument_to_owned, convert_return_value_to_encoded,
+    convert_returned_value_to_decoded,
+};
 use contract_call::convert_contract_call_to_asm;
 use enums::convert_enum_instantiation_to_asm;
 use if_exp::convert_if_exp_to_asm;
@@ -52,6 +59,7 @@ pub(crate) fn convert_expression_to_asm(
             arguments,
             function_body,
             selector,
+            ..
         } => {
             if let Some(metadata) = selector {
                 assert_eq!(
@@ -69,6 +77,7 @@ pub(crate) fn convert_expression_to_asm(
                     &arguments[2].1,
                     // user parameter
                     &arguments[3].1,
+                    exp.return_type,
                     register_sequencer,
                     return_register,
                     namespace,
@@ -212,11 +221,17 @@ pub(crate) fn convert_expression_to_asm(
                     warnings,
                     errors
                 );
+                let mut overflow_check = if namespace.checked_arithmetic() {
+                    build_overflow_check(&opcode, register_sequencer)
+                } else {
+                    vec![]
+                };
                 asm_buf.push(Op {
                     opcode: either::Either::Left(opcode),
                     comment: String::new(),
                     owning_span: Some(op.span.clone()),
                 });
+                asm_buf.append(&mut overflow_check);
             }
             // Now, load the designated asm return register into the desired return register
             match (returns, return_register) {
@@ -361,6 +376,29 @@ pub(crate) fn convert_expression_to_asm(
         }
         // ABI casts are purely compile-time constructs and generate no corresponding bytecode
         TypedExpressionVariant::AbiCast { .. } => ok(vec![], warnings, errors),
+        TypedExpressionVariant::VecOperation {
+            op,
+            arguments,
+            elem_type,
+        } => vec::convert_vec_operation_to_asm(
+            *op,
+            arguments,
+            *elem_type,
+            &exp.span,
+            namespace,
+            return_register,
+            register_sequencer,
+        ),
+        TypedExpressionVariant::StringOperation { op, arguments } => {
+            string::convert_string_operation_to_asm(
+                *op,
+                arguments,
+                &exp.span,
+                namespace,
+                return_register,
+                register_sequencer,
+            )
+        }
         a => {
             println!("unimplemented: {:?}", a);
             errors.push(CompileError::Unimplemented(
@@ -0,0 +1,486 @@
+use super::array::set_large_register_value;
+use super::compiler_constants::{VEC_CAP_OFFSET, VEC_LEN_OFFSET, VEC_PTR_OFFSET};
+use super::vec::{
+    compile_empty_header, compile_revert_if_zero, load_header_field, store_header_field,
+};
+use super::*;
+use crate::semantic_analysis::ast_node::StringOp;
+
+/// Generates the code for the built-in methods on `String`.
+///
+/// A string value is a pointer to a header laid out like that of a `Vec<T>`, with the capacity
+/// and length counted in bytes. Strings are never modified in place, so the buffer is allocated
+/// with exactly the room needed, rounded up to a whole word to keep the heap word aligned.
+pub(super) fn convert_string_operation_to_asm(
+    op: StringOp,
+    arguments: &[TypedExpression],
+    span: &Span,
+    namespace: &mut AsmNamespace,
+    return_register: &VirtualRegister,
+    register_sequencer: &mut RegisterSequencer,
+) -> CompileResult<Vec<Op>> {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+    let mut bytecode = Vec::new();
+
+    let mut arg_regs = Vec::new();
+    for arg in arguments {
+        let arg_reg = register_sequencer.next();
+        bytecode.append(&mut check!(
+            convert_expression_to_asm(arg, namespace, &arg_reg, register_sequencer),
+            return err(warnings, errors),
+            warnings,
+            errors
+        ));
+        arg_regs.push(arg_reg);
+    }
+
+    match op {
+        StringOp::New => compile_empty_header(return_register, span, &mut bytecode),
+        StringOp::From => {
+            let len = match check_std_result!(
+                resolve_type(arguments[0].return_type, &arguments[0].span),
+                warnings,
+                errors
+            ) {
+                TypeInfo::Str(len) => len,
+                _ => unreachable!("the argument to String::from is type checked to be a str"),
+            };
+            compile_empty_header(return_register, span, &mut bytecode);
+            if len > 0 {
+                let len_reg = register_sequencer.next();
+                let len_reg = set_large_register_value(len, &len_reg, &mut bytecode, span).clone();
+                let cap_reg = register_sequencer.next();
+                let cap_reg =
+                    set_large_register_value((len + 7) & !7, &cap_reg, &mut bytecode, span).clone();
+                let ptr_reg = compile_allocate(&cap_reg, span, &mut bytecode, register_sequencer);
+                if len <= 8 {
+                    // Strings of up to a word are held directly in a register.
+                    bytecode.push(Op::write_register_to_memory_comment(
+                        ptr_reg.clone(),
+                        arg_regs[0].clone(),
+                        VirtualImmediate12 { value: 0 },
+                        span.clone(),
+                        "copy str to string buffer",
+                    ));
+                } else {
+                    bytecode.push(Op::new_with_comment(
+                        VirtualOp::MCP(ptr_reg.clone(), arg_regs[0].clone(), len_reg.clone()),
+                        span.clone(),
+                        "copy str to string buffer",
+                    ));
+                }
+                compile_store_header(
+                    return_register,
+                    &ptr_reg,
+                    &cap_reg,
+                    &len_reg,
+                    span,
+                    &mut bytecode,
+                );
+            }
+        }
+        StringOp::Concat => {
+            let (lhs_reg, rhs_reg) = (&arg_regs[0], &arg_regs[1]);
+            let lhs_len_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &lhs_len_reg,
+                lhs_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+            let rhs_len_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &rhs_len_reg,
+                rhs_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+            let len_reg = register_sequencer.next();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADD(len_reg.clone(), lhs_len_reg.clone(), rhs_len_reg.clone()),
+                span.clone(),
+                "concatenated string length",
+            ));
+            // Round the capacity up to a whole number of words.
+            let cap_reg = register_sequencer.next();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADDI(
+                    cap_reg.clone(),
+                    len_reg.clone(),
+                    VirtualImmediate12 { value: 7 },
+                ),
+                span.clone(),
+                "round string capacity up",
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::SRLI(
+                    cap_reg.clone(),
+                    cap_reg.clone(),
+                    VirtualImmediate12 { value: 3 },
+                ),
+                span.clone(),
+                "round string capacity up",
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::SLLI(
+                    cap_reg.clone(),
+                    cap_reg.clone(),
+                    VirtualImmediate12 { value: 3 },
+                ),
+                span.clone(),
+                "round string capacity up",
+            ));
+            let ptr_reg = compile_allocate(&cap_reg, span, &mut bytecode, register_sequencer);
+            compile_copy_bytes(
+                &ptr_reg,
+                lhs_reg,
+                &lhs_len_reg,
+                span,
+                &mut bytecode,
+                register_sequencer,
+            );
+            let rhs_dst_reg = register_sequencer.next();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADD(rhs_dst_reg.clone(), ptr_reg.clone(), lhs_len_reg),
+                span.clone(),
+                "address after first string",
+            ));
+            compile_copy_bytes(
+                &rhs_dst_reg,
+                rhs_reg,
+                &rhs_len_reg,
+                span,
+                &mut bytecode,
+                register_sequencer,
+            );
+            compile_empty_header(return_register, span, &mut bytecode);
+            compile_store_header(
+                return_register,
+                &ptr_reg,
+                &cap_reg,
+                &len_reg,
+                span,
+                &mut bytecode,
+            );
+        }
+        StringOp::ByteAt => {
+            let (string_reg, index_reg) = (&arg_regs[0], &arg_regs[1]);
+            let len_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &len_reg,
+                string_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+            let in_bounds_reg = register_sequencer.next();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::LT(in_bounds_reg.clone(), index_reg.clone(), len_reg),
+                span.clone(),
+                "compare string index against length",
+            ));
+            compile_revert_if_zero(&in_bounds_reg, span, &mut bytecode, register_sequencer);
+            compile_load_byte(
+                return_register,
+                string_reg,
+                index_reg,
+                span,
+                &mut bytecode,
+                register_sequencer,
+            );
+        }
+        StringOp::Len => {
+            bytecode.push(load_header_field(
+                return_register,
+                &arg_regs[0],
+                VEC_LEN_OFFSET,
+                span,
+            ));
+        }
+        StringOp::Eq | StringOp::Neq => {
+            let (lhs_reg, rhs_reg) = (&arg_regs[0], &arg_regs[1]);
+            let lhs_len_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &lhs_len_reg,
+                lhs_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+            let rhs_len_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &rhs_len_reg,
+                rhs_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::EQ(return_register.clone(), lhs_len_reg.clone(), rhs_len_reg),
+                span.clone(),
+                "compare string lengths",
+            ));
+            // Only compare the bytes if the lengths match and there are any bytes to compare.
+            let done_label = register_sequencer.get_label();
+            bytecode.push(Op::jump_if_not_equal(
+                return_register.clone(),
+                VirtualRegister::Constant(ConstantRegister::One),
+                done_label.clone(),
+            ));
+            let is_empty_reg = register_sequencer.next();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::EQ(
+                    is_empty_reg.clone(),
+                    lhs_len_reg.clone(),
+                    VirtualRegister::Constant(ConstantRegister::Zero),
+                ),
+                span.clone(),
+                "are both strings empty",
+            ));
+            bytecode.push(Op::jump_if_not_equal(
+                is_empty_reg,
+                VirtualRegister::Constant(ConstantRegister::Zero),
+                done_label.clone(),
+            ));
+            let lhs_ptr_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &lhs_ptr_reg,
+                lhs_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+            let rhs_ptr_reg = register_sequencer.next();
+            bytecode.push(load_header_field(
+                &rhs_ptr_reg,
+                rhs_reg,
+                VEC_PTR_OFFSET,
+                span,
+            ));
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::MEQ(
+                    return_register.clone(),
+                    lhs_ptr_reg,
+                    rhs_ptr_reg,
+                    lhs_len_reg,
+                ),
+                span.clone(),
+                "compare string bytes",
+            ));
+            bytecode.push(Op::jump_label_comment(
+                done_label,
+                span.clone(),
+                "after string comparison",
+            ));
+            if op == StringOp::Neq {
+                bytecode.push(Op::new_with_comment(
+                    VirtualOp::EQ(
+                        return_register.clone(),
+                        return_register.clone(),
+                        VirtualRegister::Constant(ConstantRegister::Zero),
+                    ),
+                    span.clone(),
+                    "negate string comparison",
+                ));
+            }
+        }
+    }
+
+    ok(bytecode, warnings, errors)
+}
+
+/// Loads the byte at `index_reg` of the string with header `header_reg` into `return_register`.
+///
+/// The VM's `LB` reads from the index of its address register rather than from the address it
+/// holds, so instead the word containing the byte is loaded and the byte shifted out of it. String
+/// buffers are always word aligned.
+fn compile_load_byte(
+    return_register: &VirtualRegister,
+    header_reg: &VirtualRegister,
+    index_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let word_offset_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::SRLI(
+            word_offset_reg.clone(),
+            index_reg.clone(),
+            VirtualImmediate12 { value: 3 },
+        ),
+        span.clone(),
+        "index of word holding string byte",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::SLLI(
+            word_offset_reg.clone(),
+            word_offset_reg.clone(),
+            VirtualImmediate12 { value: 3 },
+        ),
+        span.clone(),
+        "offset of word holding string byte",
+    ));
+    let word_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &word_reg,
+        header_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADD(word_reg.clone(), word_reg.clone(), word_offset_reg.clone()),
+        span.clone(),
+        "address of word holding string byte",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::LW(
+            word_reg.clone(),
+            word_reg.clone(),
+            VirtualImmediate12 { value: 0 },
+        ),
+        span.clone(),
+        "load word holding string byte",
+    ));
+    // Words are big endian, so the first byte is the most significant:
+    // shift = 56 - 8 * (index - word_offset).
+    let shift_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::SUB(shift_reg.clone(), index_reg.clone(), word_offset_reg),
+        span.clone(),
+        "string byte position in word",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MULI(
+            shift_reg.clone(),
+            shift_reg.clone(),
+            VirtualImmediate12 { value: 8 },
+        ),
+        span.clone(),
+        "string byte position in bits",
+    ));
+    let top_byte_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADDI(
+            top_byte_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::Zero),
+            VirtualImmediate12 { value: 56 },
+        ),
+        span.clone(),
+        "position of first byte in word",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::SUB(shift_reg.clone(), top_byte_reg, shift_reg.clone()),
+        span.clone(),
+        "string byte shift",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::SRL(word_reg.clone(), word_reg.clone(), shift_reg),
+        span.clone(),
+        "shift string byte down",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ANDI(
+            return_register.clone(),
+            word_reg,
+            VirtualImmediate12 { value: 0xff },
+        ),
+        span.clone(),
+        "mask string byte",
+    ));
+}
+
+/// Allocates `size_reg` bytes on the heap and returns a register holding their address.
+fn compile_allocate(
+    size_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) -> VirtualRegister {
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ALOC(size_reg.clone()),
+        span.clone(),
+        "allocate string buffer",
+    ));
+    let ptr_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADDI(
+            ptr_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::HeapPointer),
+            VirtualImmediate12 { value: 1 },
+        ),
+        span.clone(),
+        "string buffer address",
+    ));
+    ptr_reg
+}
+
+/// Copies the `len_reg` bytes of the string with header `src_header_reg` to `dst_reg`. `MCP`
+/// can't copy zero bytes, so empty strings are skipped.
+fn compile_copy_bytes(
+    dst_reg: &VirtualRegister,
+    src_header_reg: &VirtualRegister,
+    len_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let is_empty_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::EQ(
+            is_empty_reg.clone(),
+            len_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::Zero),
+        ),
+        span.clone(),
+        "is string empty",
+    ));
+    let skip_label = register_sequencer.get_label();
+    bytecode.push(Op::jump_if_not_equal(
+        is_empty_reg,
+        VirtualRegister::Constant(ConstantRegister::Zero),
+        skip_label.clone(),
+    ));
+    let src_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &src_reg,
+        src_header_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MCP(dst_reg.clone(), src_reg, len_reg.clone()),
+        span.clone(),
+        "copy string bytes",
+    ));
+    bytecode.push(Op::jump_label_comment(
+        skip_label,
+        span.clone(),
+        "after copying string bytes",
+    ));
+}
+
+fn compile_store_header(
+    header_reg: &VirtualRegister,
+    ptr_reg: &VirtualRegister,
+    cap_reg: &VirtualRegister,
+    len_reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+) {
+    bytecode.push(store_header_field(
+        header_reg,
+        ptr_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(store_header_field(
+        header_reg,
+        cap_reg,
+        VEC_CAP_OFFSET,
+        span,
+    ));
+    bytecode.push(store_header_field(
+        header_reg,
+        len_reg,
+        VEC_LEN_OFFSET,
+        span,
+    ));
+}
@@ -0,0 +1,521 @@
+use super::array::set_large_register_value;
+use super::compiler_constants::{
+    VEC_CAP_OFFSET, VEC_INITIAL_CAPACITY, VEC_LEN_OFFSET, VEC_PTR_OFFSET,
+};
+use super::*;
+use crate::semantic_analysis::ast_node::VecOp;
+use crate::type_engine::TypeId;
+
+/// How the elements of a vector are laid out in its heap buffer.
+struct ElemLayout {
+    /// The size of each element slot in bytes. Every slot is at least a word, so that pointers
+    /// into the buffer stay word aligned.
+    size_in_bytes: u64,
+    /// Whether elements are held directly in registers, rather than referred to by pointers.
+    is_register_value: bool,
+    is_zero_sized: bool,
+}
+
+fn elem_layout(elem_type: TypeId, span: &Span) -> CompileResult<ElemLayout> {
+    let warnings = vec![];
+    let mut errors = vec![];
+    let elem_type = check_std_result!(resolve_type(elem_type, span), warnings, errors);
+    let size_in_words = check_std_result!(elem_type.size_in_words(span), warnings, errors);
+    ok(
+        ElemLayout {
+            size_in_bytes: std::cmp::max(size_in_words, 1) * 8,
+            is_register_value: matches!(
+                elem_type,
+                TypeInfo::UnsignedInteger(_) | TypeInfo::Boolean | TypeInfo::Byte
+            ),
+            is_zero_sized: size_in_words == 0,
+        },
+        warnings,
+        errors,
+    )
+}
+
+/// Generates the code for the built-in methods on `Vec<T>`.
+///
+/// A vector value is a pointer to a three word header holding a pointer to its buffer on the
+/// heap, the capacity of that buffer and its length. The buffer is allocated with `ALOC` and is
+/// reallocated, with double the capacity, whenever a push finds it full.
+pub(super) fn convert_vec_operation_to_asm(
+    op: VecOp,
+    arguments: &[TypedExpression],
+    elem_type: TypeId,
+    span: &Span,
+    namespace: &mut AsmNamespace,
+    return_register: &VirtualRegister,
+    register_sequencer: &mut RegisterSequencer,
+) -> CompileResult<Vec<Op>> {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+    let mut bytecode = Vec::new();
+
+    if op == VecOp::New {
+        compile_empty_header(return_register, span, &mut bytecode);
+        return ok(bytecode, warnings, errors);
+    }
+
+    let layout = check!(
+        elem_layout(elem_type, span),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+    let header_reg = register_sequencer.next();
+    bytecode.append(&mut check!(
+        convert_expression_to_asm(&arguments[0], namespace, &header_reg, register_sequencer),
+        return err(warnings, errors),
+        warnings,
+        errors
+    ));
+    // Either the element being pushed or the index being read.
+    let arg_reg = register_sequencer.next();
+    if let Some(arg) = arguments.get(1) {
+        bytecode.append(&mut check!(
+            convert_expression_to_asm(arg, namespace, &arg_reg, register_sequencer),
+            return err(warnings, errors),
+            warnings,
+            errors
+        ));
+    }
+    let len_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &len_reg,
+        &header_reg,
+        VEC_LEN_OFFSET,
+        span,
+    ));
+
+    match op {
+        VecOp::New => unreachable!("handled above"),
+        VecOp::Len => {
+            bytecode.push(Op::unowned_register_move(return_register.clone(), len_reg));
+        }
+        VecOp::Push => {
+            compile_grow_if_full(
+                &header_reg,
+                &len_reg,
+                &layout,
+                span,
+                &mut bytecode,
+                register_sequencer,
+            );
+            let slot_reg = register_sequencer.next();
+            compile_slot_address(
+                &slot_reg,
+                &header_reg,
+                &len_reg,
+                &layout,
+                span,
+                &mut bytecode,
+                register_sequencer,
+            );
+            if layout.is_zero_sized {
+                // Nothing to store.
+            } else if layout.is_register_value {
+                bytecode.push(Op::write_register_to_memory_comment(
+                    slot_reg,
+                    arg_reg,
+                    VirtualImmediate12 { value: 0 },
+                    span.clone(),
+                    "store pushed element",
+                ));
+            } else {
+                let size_reg = register_sequencer.next();
+                let size_reg =
+                    set_large_register_value(layout.size_in_bytes, &size_reg, &mut bytecode, span);
+                bytecode.push(Op::new_with_comment(
+                    VirtualOp::MCP(slot_reg, arg_reg, size_reg.clone()),
+                    span.clone(),
+                    "copy pushed element",
+                ));
+            }
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::ADDI(
+                    len_reg.clone(),
+                    len_reg.clone(),
+                    VirtualImmediate12 { value: 1 },
+                ),
+                span.clone(),
+                "increment vec length",
+            ));
+            bytecode.push(store_header_field(
+                &header_reg,
+                &len_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+        }
+        VecOp::Pop => {
+            compile_revert_if_zero(&len_reg, span, &mut bytecode, register_sequencer);
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::SUBI(
+                    len_reg.clone(),
+                    len_reg.clone(),
+                    VirtualImmediate12 { value: 1 },
+                ),
+                span.clone(),
+                "decrement vec length",
+            ));
+            bytecode.push(store_header_field(
+                &header_reg,
+                &len_reg,
+                VEC_LEN_OFFSET,
+                span,
+            ));
+            check!(
+                compile_load_elem(
+                    return_register,
+                    &header_reg,
+                    &len_reg,
+                    &layout,
+                    span,
+                    &mut bytecode,
+                    register_sequencer,
+                ),
+                return err(warnings, errors),
+                warnings,
+                errors
+            );
+        }
+        VecOp::Get => {
+            let in_bounds_reg = register_sequencer.next();
+            bytecode.push(Op::new_with_comment(
+                VirtualOp::LT(in_bounds_reg.clone(), arg_reg.clone(), len_reg),
+                span.clone(),
+                "compare vec index against length",
+            ));
+            compile_revert_if_zero(&in_bounds_reg, span, &mut bytecode, register_sequencer);
+            check!(
+                compile_load_elem(
+                    return_register,
+                    &header_reg,
+                    &arg_reg,
+                    &layout,
+                    span,
+                    &mut bytecode,
+                    register_sequencer,
+                ),
+                return err(warnings, errors),
+                warnings,
+                errors
+            );
+        }
+    }
+
+    ok(bytecode, warnings, errors)
+}
+
+/// Allocates a zeroed header on the stack and puts its address in `return_register`. This is the
+/// empty value of both `Vec<T>` and `String`.
+pub(super) fn compile_empty_header(
+    return_register: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+) {
+    bytecode.push(Op::unowned_register_move(
+        return_register.clone(),
+        VirtualRegister::Constant(ConstantRegister::StackPointer),
+    ));
+    bytecode.push(Op::unowned_stack_allocate_memory(
+        VirtualImmediate24::new_unchecked(24, "the header is three words"),
+    ));
+    for offset in [VEC_PTR_OFFSET, VEC_CAP_OFFSET, VEC_LEN_OFFSET] {
+        bytecode.push(Op::write_register_to_memory_comment(
+            return_register.clone(),
+            VirtualRegister::Constant(ConstantRegister::Zero),
+            VirtualImmediate12::new_unchecked(offset, "header offsets are small"),
+            span.clone(),
+            "initialize empty header",
+        ));
+    }
+}
+
+pub(super) fn load_header_field(
+    dst: &VirtualRegister,
+    header: &VirtualRegister,
+    offset: u64,
+    span: &Span,
+) -> Op {
+    Op::new_with_comment(
+        VirtualOp::LW(
+            dst.clone(),
+            header.clone(),
+            VirtualImmediate12::new_unchecked(offset, "header offsets are small"),
+        ),
+        span.clone(),
+        "load header field",
+    )
+}
+
+pub(super) fn store_header_field(
+    header: &VirtualRegister,
+    src: &VirtualRegister,
+    offset: u64,
+    span: &Span,
+) -> Op {
+    Op::write_register_to_memory_comment(
+        header.clone(),
+        src.clone(),
+        VirtualImmediate12::new_unchecked(offset, "header offsets are small"),
+        span.clone(),
+        "store header field",
+    )
+}
+
+/// Reverts with code 1, the same as for an out of bounds array access, if `reg` is zero.
+pub(super) fn compile_revert_if_zero(
+    reg: &VirtualRegister,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let skip_label = register_sequencer.get_label();
+    bytecode.push(Op::jump_if_not_equal(
+        reg.clone(),
+        VirtualRegister::Constant(ConstantRegister::Zero),
+        skip_label.clone(),
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::RVRT(VirtualRegister::Constant(ConstantRegister::One)),
+        span.clone(),
+        "aborting due to out of bounds access",
+    ));
+    bytecode.push(Op::jump_label_comment(
+        skip_label,
+        span.clone(),
+        "after bounds check",
+    ));
+}
+
+/// If the vector's buffer is full, allocates one with double the capacity on the heap, copies the
+/// existing elements over and updates the header to point at it.
+fn compile_grow_if_full(
+    header_reg: &VirtualRegister,
+    len_reg: &VirtualRegister,
+    layout: &ElemLayout,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let cap_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &cap_reg,
+        header_reg,
+        VEC_CAP_OFFSET,
+        span,
+    ));
+    let not_full_label = register_sequencer.get_label();
+    bytecode.push(Op::jump_if_not_equal(
+        len_reg.clone(),
+        cap_reg.clone(),
+        not_full_label.clone(),
+    ));
+
+    // new_cap = cap * 2, or the initial capacity if nothing has been allocated yet.
+    let is_empty_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::EQ(
+            is_empty_reg.clone(),
+            cap_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::Zero),
+        ),
+        span.clone(),
+        "is vec buffer unallocated",
+    ));
+    let new_cap_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MULI(
+            new_cap_reg.clone(),
+            cap_reg,
+            VirtualImmediate12 { value: 2 },
+        ),
+        span.clone(),
+        "double vec capacity",
+    ));
+    let initial_cap_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MULI(
+            initial_cap_reg.clone(),
+            is_empty_reg.clone(),
+            VirtualImmediate12::new_unchecked(VEC_INITIAL_CAPACITY, "initial capacity is small"),
+        ),
+        span.clone(),
+        "initial vec capacity if unallocated",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADD(new_cap_reg.clone(), new_cap_reg.clone(), initial_cap_reg),
+        span.clone(),
+        "new vec capacity",
+    ));
+
+    // Allocate the new buffer. The heap grows downwards and `$hp` points to the byte just below
+    // the newly allocated memory.
+    let size_reg = register_sequencer.next();
+    let size_reg =
+        set_large_register_value(layout.size_in_bytes, &size_reg, bytecode, span).clone();
+    let bytes_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MUL(bytes_reg.clone(), new_cap_reg.clone(), size_reg.clone()),
+        span.clone(),
+        "new vec buffer size in bytes",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ALOC(bytes_reg),
+        span.clone(),
+        "allocate new vec buffer",
+    ));
+    let new_ptr_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADDI(
+            new_ptr_reg.clone(),
+            VirtualRegister::Constant(ConstantRegister::HeapPointer),
+            VirtualImmediate12 { value: 1 },
+        ),
+        span.clone(),
+        "new vec buffer address",
+    ));
+
+    // Copy the existing elements over, unless there aren't any.
+    let copied_label = register_sequencer.get_label();
+    bytecode.push(Op::jump_if_not_equal(
+        is_empty_reg,
+        VirtualRegister::Constant(ConstantRegister::Zero),
+        copied_label.clone(),
+    ));
+    let old_ptr_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &old_ptr_reg,
+        header_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    let old_bytes_reg = register_sequencer.next();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MUL(old_bytes_reg.clone(), len_reg.clone(), size_reg),
+        span.clone(),
+        "old vec buffer size in bytes",
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MCP(new_ptr_reg.clone(), old_ptr_reg, old_bytes_reg),
+        span.clone(),
+        "copy elements to new vec buffer",
+    ));
+    bytecode.push(Op::jump_label_comment(
+        copied_label,
+        span.clone(),
+        "after copying vec elements",
+    ));
+
+    bytecode.push(store_header_field(
+        header_reg,
+        &new_ptr_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(store_header_field(
+        header_reg,
+        &new_cap_reg,
+        VEC_CAP_OFFSET,
+        span,
+    ));
+    bytecode.push(Op::jump_label_comment(
+        not_full_label,
+        span.clone(),
+        "vec has room for another element",
+    ));
+}
+
+/// Puts the address of the element at `index_reg` into `slot_reg`.
+fn compile_slot_address(
+    slot_reg: &VirtualRegister,
+    header_reg: &VirtualRegister,
+    index_reg: &VirtualRegister,
+    layout: &ElemLayout,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) {
+    let size_reg = register_sequencer.next();
+    let size_reg =
+        set_large_register_value(layout.size_in_bytes, &size_reg, bytecode, span).clone();
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::MUL(slot_reg.clone(), index_reg.clone(), size_reg),
+        span.clone(),
+        "vec element offset",
+    ));
+    let ptr_reg = register_sequencer.next();
+    bytecode.push(load_header_field(
+        &ptr_reg,
+        header_reg,
+        VEC_PTR_OFFSET,
+        span,
+    ));
+    bytecode.push(Op::new_with_comment(
+        VirtualOp::ADD(slot_reg.clone(), ptr_reg, slot_reg.clone()),
+        span.clone(),
+        "vec element address",
+    ));
+}
+
+/// Loads the element at `index_reg` into `return_register`. Elements which don't fit in a
+/// register are copied onto the stack first, so that the value isn't clobbered if the vector's
+/// buffer is later written to.
+fn compile_load_elem(
+    return_register: &VirtualRegister,
+    header_reg: &VirtualRegister,
+    index_reg: &VirtualRegister,
+    layout: &ElemLayout,
+    span: &Span,
+    bytecode: &mut Vec<Op>,
+    register_sequencer: &mut RegisterSequencer,
+) -> CompileResult<()> {
+    let warnings = Vec::new();
+    let mut errors = Vec::new();
+    if layout.is_zero_sized {
+        return ok((), warnings, errors);
+    }
+    let slot_reg = register_sequencer.next();
+    compile_slot_address(
+        &slot_reg,
+        header_reg,
+        index_reg,
+        layout,
+        span,
+        bytecode,
+        register_sequencer,
+    );
+    if layout.is_register_value {
+        bytecode.push(Op::new_with_comment(
+            VirtualOp::LW(
+                return_register.clone(),
+                slot_reg,
+                VirtualImmediate12 { value: 0 },
+            ),
+            span.clone(),
+            "load vec element",
+        ));
+    } else {
+        let stack_size = check_std_result!(
+            VirtualImmediate24::new(layout.size_in_bytes, span.clone()),
+            warnings,
+            errors
+        );
+        bytecode.push(Op::unowned_register_move(
+            return_register.clone(),
+            VirtualRegister::Constant(ConstantRegister::StackPointer),
+        ));
+        bytecode.push(Op::unowned_stack_allocate_memory(stack_size));
+        let size_reg = register_sequencer.next();
+        let size_reg = set_large_register_value(layout.size_in_bytes, &size_reg, bytecode, span);
+        bytecode.push(Op::new_with_comment(
+            VirtualOp::MCP(return_register.clone(), slot_reg, size_reg.clone()),
+            span.clone(),
+            "copy vec element",
+        ));
+    }
+    ok((), warnings, errors)
+}
@@ -11,9 +11,9 @@ use std::collections::HashMap;
 
 use crate::{
     asm_generation::{
-        build_contract_abi_switch, build_preamble, finalized_asm::FinalizedAsm,
-        register_sequencer::RegisterSequencer, AbstractInstructionSet, DataId, DataSection,
-        SwayAsmSet,
+        build_arithmetic_flags, build_contract_abi_switch, build_overflow_check, build_preamble,
+        finalized_asm::FinalizedAsm, register_sequencer::RegisterSequencer, AbstractInstructionSet,
+        DataId, DataSection, SwayAsmSet,
     },
     asm_lang::{virtual_register::*, Label, Op, VirtualImmediate12, VirtualImmediate24, VirtualOp},
     error::*,
@@ -31,6 +31,7 @@ pub fn compile_ir_to_asm(ir: &Context, build_config: &BuildConfig) -> CompileRes
 
     let mut reg_seqr = RegisterSequencer::new();
     let mut bytecode: Vec<Op> = build_preamble(&mut reg_seqr).to_vec();
+    bytecode.append(&mut build_arithmetic_flags(build_config, &mut reg_seqr));
 
     // Eventually when we get this 'correct' with no hacks we'll want to compile all the modules
     // separately and then use a linker to connect them.  This way we could also keep binary caches
@@ -38,7 +39,7 @@ pub fn compile_ir_to_asm(ir: &Context, build_config: &BuildConfig) -> CompileRes
     assert!(ir.module_iter().count() == 1);
     let module = ir.module_iter().next().unwrap();
     let (data_section, mut ops, _) = check!(
-        compile_module_to_asm(reg_seqr, ir, module),
+        compile_module_to_asm(reg_seqr, ir, module, build_config),
         return err(warnings, errors),
         warnings,
         errors
@@ -84,8 +85,14 @@ fn compile_module_to_asm(
     reg_seqr: RegisterSequencer,
     context: &Context,
     module: Module,
+    build_config: &BuildConfig,
 ) -> CompileResult<(DataSection, Vec<Op>, RegisterSequencer)> {
-    let mut builder = AsmBuilder::new(DataSection::default(), reg_seqr, context);
+    let mut builder = AsmBuilder::new(
+        DataSection::default(),
+        reg_seqr,
+        context,
+        build_config.checked_arithmetic,
+    );
     match module.get_kind(context) {
         Kind::Script => {
             // We can't do function calls yet, so we expect everything to be inlined into `main`.
@@ -176,6 +183,9 @@ struct AsmBuilder<'ir> {
     // IR context we're compiling.
     context: &'ir Context,
 
+    // Whether arithmetic ops in asm blocks revert when they overflow.
+    checked_arithmetic: bool,
+
     // Final resulting VM bytecode ops.
     bytecode: Vec<Op>,
 }
@@ -204,7 +214,12 @@ impl<'ir> AsmBuilder<'ir> {
         }
     }
 
-    fn new(data_section: DataSection, reg_seqr: RegisterSequencer, context: &'ir Context) -> Self {
+    fn new(
+        data_section: DataSection,
+        reg_seqr: RegisterSequencer,
+        context: &'ir Context,
+        checked_arithmetic: bool,
+    ) -> Self {
         AsmBuilder {
             data_section,
             reg_seqr,
@@ -214,6 +229,7 @@ impl<'ir> AsmBuilder<'ir> {
             stack_base_reg: None,
             aggregate_layouts: HashMap::new(),
             context,
+            checked_arithmetic,
             bytecode: Vec::new(),
         }
     }
@@ -224,6 +240,8 @@ impl<'ir> AsmBuilder<'ir> {
         //
         // Stack offsets are in words to both enforce alignment and simplify use with LW/SW.
         let mut stack_base = 0_u64;
+        // Zero sized aggregates, such as unit structs, may still have pointers taken to them.
+        let mut has_stack_locals = false;
         for (_name, ptr) in function.locals_iter(self.context) {
             let ptr_content = &self.context.pointers[ptr.0];
             if !ptr_content.is_mutable && ptr_content.initializer.is_some() {
@@ -233,7 +251,7 @@ impl<'ir> AsmBuilder<'ir> {
                 self.ptr_map.insert(*ptr, Storage::Data(data_id));
             } else {
                 match ptr_content.ty {
-                    Type::Unit | Type::Bool | Type::Uint(_) => {
+                    Type::Unit | Type::Bool | Type::Uint(_) | Type::Pointer => {
                         let reg = self.reg_seqr.next();
                         self.ptr_map.insert(*ptr, Storage::Register(reg));
                     }
@@ -258,6 +276,7 @@ impl<'ir> AsmBuilder<'ir> {
                     Type::Struct(aggregate) => {
                         // Store this aggregate at the current stack base.
                         self.ptr_map.insert(*ptr, Storage::Stack(stack_base));
+                        has_stack_locals = true;
 
                         // Reserve space by incrementing the base.
                         stack_base += size_bytes_in_words!(self.aggregate_size(&aggregate));
@@ -284,7 +303,7 @@ impl<'ir> AsmBuilder<'ir> {
         }
 
         // Reserve space on the stack for ALL our locals which require it.
-        if stack_base > 0 {
+        if stack_base > 0 || has_stack_locals {
             let base_reg = self.reg_seqr.next();
             self.bytecode.push(Op::unowned_register_move_comment(
                 base_reg.clone(),
@@ -347,6 +366,7 @@ impl<'ir> AsmBuilder<'ir> {
         let mut errors = Vec::new();
         if let ValueContent::Instruction(instruction) = &self.context.values[instr_val.0] {
             match instruction {
+                Instruction::Alloc { ty, count } => self.compile_alloc(instr_val, ty, count),
                 Instruction::AsmBlock(asm, args) => {
                     check!(
                         self.compile_asm_block(instr_val, asm, args),
@@ -378,6 +398,11 @@ impl<'ir> AsmBuilder<'ir> {
                     ty,
                     indices,
                 } => self.compile_extract_value(instr_val, aggregate, ty, indices),
+                Instruction::GetElementPointer {
+                    ptr_val,
+                    ty,
+                    index_val,
+                } => self.compile_get_element_pointer(instr_val, ptr_val, ty, index_val),
                 Instruction::GetPointer(ptr) => self.compile_get_pointer(instr_val, ptr),
                 Instruction::InsertElement {
                     array,
@@ -392,9 +417,23 @@ impl<'ir> AsmBuilder<'ir> {
                     indices,
                 } => self.compile_insert_value(instr_val, aggregate, ty, value, indices),
                 Instruction::Load(ptr) => self.compile_load(instr_val, ptr),
+                Instruction::LoadFrom { ptr_val, ty } => {
+                    self.compile_load_from(instr_val, ptr_val, ty)
+                }
+                Instruction::MemCopy {
+                    dst_val,
+                    src_val,
+                    ty,
+                    count,
+                } => self.compile_mem_copy(dst_val, src_val, ty, count),
                 Instruction::Phi(_) => (), // Managing the phi value is done in br and cbr compilation.
                 Instruction::Ret(ret_val, ty) => self.compile_ret(ret_val, ty),
                 Instruction::Store { ptr, stored_val } => self.compile_store(ptr, stored_val),
+                Instruction::StoreTo {
+                    ptr_val,
+                    ty,
+                    stored_val,
+                } => self.compile_store_to(ptr_val, ty, stored_val),
             }
         } else {
             errors.push(CompileError::Internal(
@@ -482,11 +521,17 @@ impl<'ir> AsmBuilder<'ir> {
                 errors
             );
 
+            let mut overflow_check = if self.checked_arithmetic {
+                build_overflow_check(&opcode, &mut self.reg_seqr)
+            } else {
+                Vec::new()
+            };
             inline_ops.push(Op {
                 opcode: either::Either::Left(opcode),
                 comment: "asm block".into(),
                 owning_span: None,
             });
+            inline_ops.append(&mut overflow_check);
         }
 
         // Now, load the designated asm return register into the desired return register, but only
@@ -560,6 +605,28 @@ impl<'ir> AsmBuilder<'ir> {
         }
     }
 
+    fn compile_alloc(&mut self, instr_val: &Value, ty: &Type, count: &Value) {
+        let size_reg = self.compile_elements_size(ty, count);
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::ALOC(size_reg)),
+            comment: "alloc".into(),
+            owning_span: None,
+        });
+
+        // The heap grows downwards, leaving the heap pointer just below the new allocation.
+        let instr_reg = self.reg_seqr.next();
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::ADDI(
+                instr_reg.clone(),
+                VirtualRegister::Constant(ConstantRegister::HeapPointer),
+                VirtualImmediate12 { value: 1 },
+            )),
+            comment: "alloc address".into(),
+            owning_span: None,
+        });
+        self.reg_map.insert(*instr_val, instr_reg);
+    }
+
     fn compile_extract_element(
         &mut self,
         instr_val: &Value,
@@ -673,6 +740,24 @@ impl<'ir> AsmBuilder<'ir> {
         self.reg_map.insert(*instr_val, instr_reg);
     }
 
+    fn compile_get_element_pointer(
+        &mut self,
+        instr_val: &Value,
+        ptr_val: &Value,
+        ty: &Type,
+        index_val: &Value,
+    ) {
+        let base_reg = self.value_to_register(ptr_val);
+        let offs_reg = self.compile_elements_size(ty, index_val);
+        let instr_reg = self.reg_seqr.next();
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::ADD(instr_reg.clone(), base_reg, offs_reg)),
+            comment: "get_elem_ptr".into(),
+            owning_span: None,
+        });
+        self.reg_map.insert(*instr_val, instr_reg);
+    }
+
     fn compile_get_pointer(&mut self, instr_val: &Value, ptr: &Pointer) {
         // `get_ptr` is like a `load` except the value isn't dereferenced.
         match self.ptr_map.get(ptr) {
@@ -893,6 +978,37 @@ impl<'ir> AsmBuilder<'ir> {
         self.reg_map.insert(*instr_val, instr_reg);
     }
 
+    fn compile_load_from(&mut self, instr_val: &Value, ptr_val: &Value, ty: &Type) {
+        let ptr_reg = self.value_to_register(ptr_val);
+        if Self::is_copy_type(ty) {
+            let instr_reg = self.reg_seqr.next();
+            self.bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::LW(
+                    instr_reg.clone(),
+                    ptr_reg,
+                    VirtualImmediate12 { value: 0 },
+                )),
+                comment: "load_from".into(),
+                owning_span: None,
+            });
+            self.reg_map.insert(*instr_val, instr_reg);
+        } else {
+            // Like `load` of a local, an aggregate is represented by its address.
+            self.reg_map.insert(*instr_val, ptr_reg);
+        }
+    }
+
+    fn compile_mem_copy(&mut self, dst_val: &Value, src_val: &Value, ty: &Type, count: &Value) {
+        let dst_reg = self.value_to_register(dst_val);
+        let src_reg = self.value_to_register(src_val);
+        let size_reg = self.compile_elements_size(ty, count);
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::MCP(dst_reg, src_reg, size_reg)),
+            comment: "mem_copy".into(),
+            owning_span: None,
+        });
+    }
+
     // XXX This is copied from ret_or_retd_value() above, streamlined for IR types.
     fn compile_ret(&mut self, ret_val: &Value, ret_type: &Type) {
         if ret_type == &Type::Unit {
@@ -1024,6 +1140,77 @@ impl<'ir> AsmBuilder<'ir> {
         };
     }
 
+    fn compile_store_to(&mut self, ptr_val: &Value, ty: &Type, stored_val: &Value) {
+        let ptr_reg = self.value_to_register(ptr_val);
+        let stored_reg = self.value_to_register(stored_val);
+        if Self::is_copy_type(ty) {
+            self.bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::SW(
+                    ptr_reg,
+                    stored_reg,
+                    VirtualImmediate12 { value: 0 },
+                )),
+                comment: "store_to".into(),
+                owning_span: None,
+            });
+        } else {
+            let size_in_bytes = self.ir_type_size_in_bytes(ty);
+            if size_in_bytes > 0 {
+                let size_reg = self.compile_constant_size(size_in_bytes);
+                self.bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::MCP(ptr_reg, stored_reg, size_reg)),
+                    comment: "store_to".into(),
+                    owning_span: None,
+                });
+            }
+        }
+    }
+
+    // Types whose values are held in a register rather than referred to by their address.
+    fn is_copy_type(ty: &Type) -> bool {
+        matches!(ty, Type::Unit | Type::Bool | Type::Uint(_) | Type::Pointer)
+    }
+
+    // The size in bytes of `count` values of `ty` laid out as an array, in a new register.
+    fn compile_elements_size(&mut self, ty: &Type, count: &Value) -> VirtualRegister {
+        let elem_size = size_bytes_round_up_to_word_alignment!(self.ir_type_size_in_bytes(ty));
+        let count_reg = self.value_to_register(count);
+        let size_reg = self.reg_seqr.next();
+        match VirtualImmediate12::new(elem_size, Self::empty_span()) {
+            Ok(imm) => self.bytecode.push(Op {
+                opcode: Either::Left(VirtualOp::MULI(size_reg.clone(), count_reg, imm)),
+                comment: "size of elements".into(),
+                owning_span: None,
+            }),
+            Err(_) => {
+                let elem_size_reg = self.compile_constant_size(elem_size);
+                self.bytecode.push(Op {
+                    opcode: Either::Left(VirtualOp::MUL(
+                        size_reg.clone(),
+                        count_reg,
+                        elem_size_reg,
+                    )),
+                    comment: "size of elements".into(),
+                    owning_span: None,
+                });
+            }
+        }
+        size_reg
+    }
+
+    fn compile_constant_size(&mut self, size_in_bytes: u64) -> VirtualRegister {
+        let size_reg = self.reg_seqr.next();
+        let size_data_id = self
+            .data_section
+            .insert_data_value(&Literal::U64(size_in_bytes));
+        self.bytecode.push(Op {
+            opcode: Either::Left(VirtualOp::LWDataId(size_reg.clone(), size_data_id)),
+            comment: "load size".into(),
+            owning_span: None,
+        });
+        size_reg
+    }
+
     fn value_to_register(&mut self, value: &Value) -> VirtualRegister {
         match self.reg_map.get(value) {
             Some(reg) => reg.clone(),
@@ -1327,7 +1514,7 @@ impl<'ir> AsmBuilder<'ir> {
 
     fn ir_type_size_in_bytes(&mut self, ty: &Type) -> u64 {
         match ty {
-            Type::Unit | Type::Bool | Type::Uint(_) => 8,
+            Type::Unit | Type::Bool | Type::Uint(_) | Type::Pointer => 8,
             Type::B256 => 32,
             Type::String(n) => *n,
             Type::Array(aggregate) | Type::Struct(aggregate) => {
@@ -1356,13 +1543,8 @@ fn ir_constant_to_ast_literal(constant: &Constant) -> Literal {
         ConstantValue::Bool(b) => Literal::Boolean(*b),
         ConstantValue::Uint(n) => Literal::U64(*n),
         ConstantValue::B256(bs) => Literal::B256(*bs),
-        ConstantValue::String(_) => Literal::String(crate::span::Span {
-            span: pest::Span::new(
-                "STRINGS ARE UNIMPLEMENTED UNTIL WE REDO DATASECTION".into(),
-                0,
-                51,
-            )
-            .unwrap(),
+        ConstantValue::String(s) => Literal::String(crate::span::Span {
+            span: pest::Span::new(s.as_str().into(), 0, s.len()).unwrap(),
             path: None,
         }),
         ConstantValue::Array(_) => unimplemented!(),
@@ -1423,7 +1605,11 @@ mod tests {
                 print_intermediate_asm: false,
                 print_finalized_asm: false,
                 print_ir: false,
+                optimize: true,
+                checked_arithmetic: true,
                 generated_names: std::sync::Arc::new(std::sync::Mutex::new(vec![])),
+                module_cache: None,
+                type_engine: crate::type_engine::TypeEngine::new(),
             },
         );
 
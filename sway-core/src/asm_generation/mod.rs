@@ -3,7 +3,7 @@ use std::{collections::HashMap, fmt};
 use crate::semantic_analysis::ast_node::{TypedVariableDeclaration, VariableMutability};
 use crate::type_engine::resolve_type;
 use crate::{
-    asm_generation::expression::convert_abi_fn_to_asm,
+    asm_generation::{compiler_constants::F_WRAPPING, expression::convert_abi_fn_to_asm},
     asm_lang::{
         allocated_ops::{AllocatedOp, AllocatedRegister},
         virtual_register::*,
@@ -97,6 +97,8 @@ pub struct AbstractInstructionSet {
 /// ops or labels. In this struct, they are all "realized" to offsets.
 pub struct RealizedAbstractInstructionSet {
     ops: Vec<RealizedOp>,
+    /// The indices of the ops which may run after each op.
+    successors: Vec<Vec<usize>>,
 }
 
 impl RealizedAbstractInstructionSet {
@@ -115,16 +117,31 @@ impl RealizedAbstractInstructionSet {
                     op.opcode.registers().into_iter().cloned().collect(),
                 )
             })
-            .collect::<Vec<_>>();
+            .collect::<Vec<(RealizedOp, std::collections::BTreeSet<VirtualRegister>)>>();
+        let live_ranges = register_live_ranges(&op_register_mapping, &self.successors);
+        let last_uses = live_ranges
+            .iter()
+            .map(|(reg, (_, last_use))| (reg.clone(), *last_use))
+            .collect::<HashMap<_, _>>();
+        let mut live_range_starts = vec![vec![]; op_register_mapping.len()];
+        for (reg, (first_use, _)) in &live_ranges {
+            live_range_starts[*first_use].push(reg);
+        }
 
         // get registers from the pool.
         let mut pool = RegisterPool::init();
         let mut buf = vec![];
         for (ix, (op, _)) in op_register_mapping.iter().enumerate() {
+            // A register may be live before the first op which names it, if that op runs on a
+            // later pass through a loop, so it is reserved from the start of its range.
+            live_range_starts[ix].sort();
+            for reg in &live_range_starts[ix] {
+                if pool.get_register(reg, &last_uses, ix).is_none() {
+                    unimplemented!("The allocator cannot resolve a register mapping for this program. This is a temporary artifact of the extremely early stage version of this language. Try to lower the number of variables you use.")
+                }
+            }
             buf.push(AllocatedOp {
-                opcode: op
-                    .opcode
-                    .allocate_registers(&mut pool, &op_register_mapping, ix),
+                opcode: op.opcode.allocate_registers(&mut pool, &last_uses, ix),
                 comment: op.comment.clone(),
                 owning_span: op.owning_span.clone(),
             })
@@ -133,6 +150,67 @@ impl RealizedAbstractInstructionSet {
     }
 }
 
+/// Finds the first and last op index at which each register may hold a value which is needed.
+/// Blocks aren't always laid out in the order they run, so this follows the jumps between ops back
+/// from each op which reads a register, to the ops which may have written it.
+fn register_live_ranges(
+    op_register_mapping: &[(RealizedOp, std::collections::BTreeSet<VirtualRegister>)],
+    successors: &[Vec<usize>],
+) -> HashMap<VirtualRegister, (usize, usize)> {
+    let mut predecessors = vec![vec![]; successors.len()];
+    for (ix, succs) in successors.iter().enumerate() {
+        for succ in succs {
+            predecessors[*succ].push(ix);
+        }
+    }
+    let defs = op_register_mapping
+        .iter()
+        .map(|(op, _)| op.opcode.def_registers())
+        .collect::<Vec<_>>();
+    let mut occurrences: HashMap<&VirtualRegister, Vec<usize>> = HashMap::new();
+    for (ix, (_, regs)) in op_register_mapping.iter().enumerate() {
+        for reg in regs
+            .iter()
+            .filter(|reg| matches!(reg, VirtualRegister::Virtual(_)))
+        {
+            occurrences.entry(reg).or_default().push(ix);
+        }
+    }
+
+    occurrences
+        .into_iter()
+        .map(|(reg, ixs)| {
+            let mut live = vec![false; successors.len()];
+            for ix in &ixs {
+                live[*ix] = true;
+            }
+            // The register is live before each op which reads it, back to the ops which write it.
+            let mut live_before = vec![false; successors.len()];
+            let mut stack = ixs
+                .iter()
+                .copied()
+                .filter(|ix| !defs[*ix].contains(reg))
+                .collect::<Vec<_>>();
+            while let Some(ix) = stack.pop() {
+                if live_before[ix] {
+                    continue;
+                }
+                live_before[ix] = true;
+                for pred in &predecessors[ix] {
+                    live[*pred] = true;
+                    if !defs[*pred].contains(reg) {
+                        stack.push(*pred);
+                    }
+                }
+            }
+            let mut live = live.iter().enumerate().filter(|(_, live)| **live);
+            let first_use = live.next().map(|(ix, _)| ix).unwrap();
+            let last_use = live.next_back().map_or(first_use, |(ix, _)| ix);
+            (reg.clone(), (first_use, last_use))
+        })
+        .collect()
+}
+
 /// An [InstructionSet] is produced by allocating registers on an [AbstractInstructionSet].
 #[derive(Clone)]
 pub struct InstructionSet {
@@ -192,11 +270,15 @@ impl AbstractInstructionSet {
     /// and one to replace the labels in the organizational ops
     fn realize_labels(self, data_section: &DataSection) -> RealizedAbstractInstructionSet {
         let mut label_namespace: HashMap<&Label, u64> = Default::default();
+        // The index of the op following each label, once the labels and comments are removed.
+        let mut label_indices: HashMap<&Label, usize> = Default::default();
         let mut counter = 0;
+        let mut realized_count = 0;
         for op in &self.ops {
             match op.opcode {
                 Either::Right(OrganizationalOp::Label(ref lab)) => {
                     label_namespace.insert(lab, counter);
+                    label_indices.insert(lab, realized_count);
                 }
                 // A special case for LWDataId which may be 1 or 2 ops, depending on the source size.
                 Either::Left(VirtualOp::LWDataId(_, ref data_id)) => {
@@ -222,9 +304,17 @@ impl AbstractInstructionSet {
                     counter += 2
                 }
             }
+            if !matches!(
+                op.opcode,
+                Either::Right(OrganizationalOp::Label(_) | OrganizationalOp::Comment)
+            ) {
+                realized_count += 1;
+            }
         }
 
         let mut realized_ops = vec![];
+        // Each jump's op index, target op index and whether it may fall through instead.
+        let mut jumps = vec![];
         for Op {
             opcode,
             owning_span,
@@ -239,6 +329,7 @@ impl AbstractInstructionSet {
                 }),
                 Either::Right(org_op) => match org_op {
                     OrganizationalOp::Jump(ref lab) => {
+                        jumps.push((realized_ops.len(), label_indices[lab], false));
                         let offset = label_namespace.get(lab).unwrap();
                         let imm = VirtualImmediate24::new_unchecked(
                             *offset,
@@ -251,6 +342,7 @@ impl AbstractInstructionSet {
                         });
                     }
                     OrganizationalOp::JumpIfNotEq(r1, r2, ref lab) => {
+                        jumps.push((realized_ops.len(), label_indices[lab], true));
                         let offset = label_namespace.get(lab).unwrap();
                         let imm = VirtualImmediate12::new_unchecked(
                             *offset,
@@ -274,8 +366,35 @@ impl AbstractInstructionSet {
                 },
             };
         }
-        RealizedAbstractInstructionSet { ops: realized_ops }
+        let successors = op_successors(&realized_ops, &jumps);
+        RealizedAbstractInstructionSet {
+            ops: realized_ops,
+            successors,
+        }
+    }
+}
+
+/// Finds the indices of the ops which may run after each op, from the jumps between them.
+fn op_successors(ops: &[RealizedOp], jumps: &[(usize, usize, bool)]) -> Vec<Vec<usize>> {
+    let op_count = ops.len();
+    let mut successors: Vec<Vec<usize>> = ops
+        .iter()
+        .enumerate()
+        .map(|(ix, op)| match op.opcode {
+            VirtualOp::RET(_) | VirtualOp::RETD(..) | VirtualOp::RVRT(_) => vec![],
+            _ if ix + 1 < op_count => vec![ix + 1],
+            _ => vec![],
+        })
+        .collect();
+    for (jump_ix, target_ix, falls_through) in jumps {
+        if !falls_through {
+            successors[*jump_ix].clear();
+        }
+        if *target_ix < op_count {
+            successors[*jump_ix].push(*target_ix);
+        }
     }
+    successors
 }
 
 #[derive(Debug)]
@@ -308,7 +427,8 @@ impl RegisterPool {
     pub(crate) fn get_register(
         &mut self,
         virtual_register: &VirtualRegister,
-        op_register_mapping: &[(RealizedOp, std::collections::BTreeSet<VirtualRegister>)],
+        last_uses: &HashMap<VirtualRegister, usize>,
+        ix: usize,
     ) -> Option<AllocatedRegister> {
         // check if this register has already been allocated for
         if let a @ Some(_) = self.registers.iter().find_map(
@@ -324,10 +444,7 @@ impl RegisterPool {
         for RegisterAllocationStatus { in_use, .. } in
             self.registers.iter_mut().filter(|r| r.in_use.is_some())
         {
-            if virtual_register_is_never_accessed_again(
-                in_use.as_ref().unwrap(),
-                op_register_mapping,
-            ) {
+            if virtual_register_is_never_accessed_again(in_use.as_ref().unwrap(), last_uses, ix) {
                 *in_use = None;
             }
         }
@@ -349,9 +466,10 @@ impl RegisterPool {
 
 fn virtual_register_is_never_accessed_again(
     reg: &VirtualRegister,
-    ops: &[(RealizedOp, std::collections::BTreeSet<VirtualRegister>)],
+    last_uses: &HashMap<VirtualRegister, usize>,
+    ix: usize,
 ) -> bool {
-    !ops.iter().any(|(_, regs)| regs.contains(reg))
+    !matches!(last_uses.get(reg), Some(last_use) if *last_use >= ix)
 }
 
 /// helper function to check if a label is used in a given buffer of ops
@@ -572,6 +690,7 @@ impl fmt::Display for InstructionSet {
 pub(crate) struct AsmNamespace {
     data_section: DataSection,
     variables: HashMap<Ident, VirtualRegister>,
+    checked_arithmetic: bool,
 }
 
 /// An address which refers to a value in the data section of the asm.
@@ -585,6 +704,16 @@ impl fmt::Display for DataId {
 }
 
 impl AsmNamespace {
+    fn new(build_config: &BuildConfig) -> Self {
+        AsmNamespace {
+            checked_arithmetic: build_config.checked_arithmetic,
+            ..Default::default()
+        }
+    }
+    /// Whether arithmetic ops in asm blocks have to revert when they overflow.
+    pub(crate) fn checked_arithmetic(&self) -> bool {
+        self.checked_arithmetic
+    }
     pub(crate) fn insert_variable(&mut self, var_name: Ident, register_location: VirtualRegister) {
         self.variables.insert(var_name, register_location);
     }
@@ -623,8 +752,12 @@ pub(crate) fn compile_ast_to_asm(
             declarations: _,
             ..
         } => {
-            let mut namespace: AsmNamespace = Default::default();
+            let mut namespace = AsmNamespace::new(build_config);
             let mut asm_buf = build_preamble(&mut register_sequencer).to_vec();
+            asm_buf.append(&mut build_arithmetic_flags(
+                build_config,
+                &mut register_sequencer,
+            ));
             // generate any const decls
             read_module(
                 |ns| -> CompileResult<()> {
@@ -704,8 +837,12 @@ pub(crate) fn compile_ast_to_asm(
             declarations: _,
             ..
         } => {
-            let mut namespace: AsmNamespace = Default::default();
+            let mut namespace = AsmNamespace::new(build_config);
             let mut asm_buf = build_preamble(&mut register_sequencer).to_vec();
+            asm_buf.append(&mut build_arithmetic_flags(
+                build_config,
+                &mut register_sequencer,
+            ));
             read_module(
                 |ns| -> CompileResult<()> {
                     let mut warnings = vec![];
@@ -771,8 +908,12 @@ pub(crate) fn compile_ast_to_asm(
             declarations: _,
             ..
         } => {
-            let mut namespace: AsmNamespace = Default::default();
+            let mut namespace = AsmNamespace::new(build_config);
             let mut asm_buf = build_preamble(&mut register_sequencer).to_vec();
+            asm_buf.append(&mut build_arithmetic_flags(
+                build_config,
+                &mut register_sequencer,
+            ));
             read_module(
                 |ns| -> CompileResult<()> {
                     let mut warnings = vec![];
@@ -1183,6 +1324,69 @@ fn build_preamble(register_sequencer: &mut RegisterSequencer) -> [Op; 6] {
     ]
 }
 
+/// Sets the `F_WRAPPING` flag, so that overflowing arithmetic wraps around instead of reverting,
+/// unless the build asks for checked arithmetic. See [build_overflow_check] for how checked
+/// arithmetic reverts.
+fn build_arithmetic_flags(
+    build_config: &BuildConfig,
+    register_sequencer: &mut RegisterSequencer,
+) -> Vec<Op> {
+    if build_config.checked_arithmetic {
+        return vec![];
+    }
+    let flags_register = register_sequencer.next();
+    vec![
+        Op {
+            opcode: Either::Left(VirtualOp::ADDI(
+                flags_register.clone(),
+                VirtualRegister::Constant(ConstantRegister::Zero),
+                VirtualImmediate12::new_unchecked(F_WRAPPING, "F_WRAPPING fits in 12 bits"),
+            )),
+            comment: "unchecked arithmetic".into(),
+            owning_span: None,
+        },
+        Op {
+            opcode: Either::Left(VirtualOp::FLAG(flags_register)),
+            comment: "".into(),
+            owning_span: None,
+        },
+    ]
+}
+
+/// Reverts if `op` overflowed. The VM wraps overflowing arithmetic around whether or not the
+/// `F_WRAPPING` flag is set, so checked arithmetic tests `$of` after each arithmetic op.
+pub(crate) fn build_overflow_check(
+    op: &VirtualOp,
+    register_sequencer: &mut RegisterSequencer,
+) -> Vec<Op> {
+    use VirtualOp::*;
+    match op {
+        // loading an immediate can't overflow
+        ADDI(_, VirtualRegister::Constant(ConstantRegister::Zero), _) => return vec![],
+        ADD(..) | ADDI(..) | EXP(..) | EXPI(..) | MUL(..) | MULI(..) | SUB(..) | SUBI(..) => (),
+        _ => return vec![],
+    }
+    let overflow_label = register_sequencer.get_label();
+    let no_overflow_label = register_sequencer.get_label();
+    vec![
+        Op::jump_if_not_equal(
+            VirtualRegister::Constant(ConstantRegister::Zero),
+            VirtualRegister::Constant(ConstantRegister::Overflow),
+            overflow_label.clone(),
+        ),
+        Op::jump_to_label_comment(no_overflow_label.clone(), "checked arithmetic"),
+        Op::unowned_jump_label(overflow_label),
+        Op {
+            opcode: Either::Left(VirtualOp::RVRT(VirtualRegister::Constant(
+                ConstantRegister::Overflow,
+            ))),
+            comment: "arithmetic overflow".into(),
+            owning_span: None,
+        },
+        Op::unowned_jump_label(no_overflow_label),
+    ]
+}
+
 /// Builds the contract switch statement, or function selector, which takes the selector
 /// stored in the call frame (see https://github.com/FuelLabs/sway/issues/97#issuecomment-870150684
 /// for an explanation of its location)
@@ -1316,6 +1520,18 @@ fn compile_contract_to_selectors(
         asm_buf.push(load_cgas(cgas_register.clone()));
         asm_buf.push(load_bal(bal_register.clone()));
         asm_buf.push(load_coin_color(coin_color_register.clone()));
+        // the argument may point into the caller's memory, which can't be written to
+        asm_buf.append(&mut check!(
+            convert_abi_argument_to_owned(
+                decl.parameters[3].r#type,
+                &user_argument_register,
+                &decl.parameters[3].type_span,
+                register_sequencer
+            ),
+            vec![],
+            warnings,
+            errors
+        ));
 
         asm_buf.append(&mut check!(
             convert_abi_fn_to_asm(
@@ -1395,7 +1611,7 @@ fn ret_or_retd_value(
     namespace: &mut AsmNamespace,
 ) -> CompileResult<Vec<Op>> {
     let mut errors = vec![];
-    let warnings = vec![];
+    let mut warnings = vec![];
     let mut asm_buf = vec![];
     let main_func_ret_ty: TypeInfo = match resolve_type(func.return_type, &func.return_type_span) {
         Ok(o) => o,
@@ -1429,7 +1645,28 @@ fn ret_or_retd_value(
     let size_of_main_func_return_bytes = main_func_ret_ty.size_in_words(&span).expect(
         "TODO(static span): Internal error: Static spans will allow for a proper error here.",
     ) * 8;
-    if size_of_main_func_return_bytes <= 8 {
+    // values holding heap types are returned encoded along with their buffers
+    let rb_register = register_sequencer.next();
+    let encoded_value = check!(
+        convert_return_value_to_encoded(
+            func.return_type,
+            &return_register,
+            &rb_register,
+            &func.return_type_span,
+            register_sequencer
+        ),
+        return err(warnings, errors),
+        warnings,
+        errors
+    );
+    if let Some(mut encoded_value) = encoded_value {
+        asm_buf.append(&mut encoded_value);
+        asm_buf.push(Op {
+            owning_span: None,
+            opcode: Either::Left(VirtualOp::RETD(return_register, rb_register)),
+            comment: format!("{} fn return encoded value", func.name.as_str()),
+        });
+    } else if size_of_main_func_return_bytes <= 8 {
         asm_buf.push(Op {
             owning_span: None,
             opcode: Either::Left(VirtualOp::RET(return_register)),
@@ -1438,7 +1675,6 @@ fn ret_or_retd_value(
     } else {
         // if the type is larger than one word, then we use RETD to return data
         // RB is the size_in_bytes
-        let rb_register = register_sequencer.next();
         let size_bytes = namespace.insert_data_value(&Literal::U64(size_of_main_func_return_bytes));
         // `return_register` is $rA
         asm_buf.push(Op {
@@ -8,7 +8,7 @@ use super::{
     allocated_ops::{AllocatedOpcode, AllocatedRegister},
     virtual_immediate::*,
     virtual_register::*,
-    DataId, RealizedOp,
+    DataId,
 };
 use crate::asm_generation::RegisterPool;
 
@@ -223,10 +223,59 @@ impl VirtualOp {
         .collect()
     }
 
+    /// The registers which this op overwrites without reading.
+    pub(crate) fn def_registers(&self) -> BTreeSet<&VirtualRegister> {
+        use VirtualOp::*;
+        let (dst, srcs) = match self {
+            ADD(r1, r2, r3)
+            | AND(r1, r2, r3)
+            | DIV(r1, r2, r3)
+            | EQ(r1, r2, r3)
+            | EXP(r1, r2, r3)
+            | GT(r1, r2, r3)
+            | LT(r1, r2, r3)
+            | MLOG(r1, r2, r3)
+            | MROO(r1, r2, r3)
+            | MOD(r1, r2, r3)
+            | MUL(r1, r2, r3)
+            | OR(r1, r2, r3)
+            | SLL(r1, r2, r3)
+            | SRL(r1, r2, r3)
+            | SUB(r1, r2, r3)
+            | XOR(r1, r2, r3)
+            | CIMV(r1, r2, r3)
+            | BAL(r1, r2, r3) => (r1, vec![r2, r3]),
+            ADDI(r1, r2, _)
+            | ANDI(r1, r2, _)
+            | DIVI(r1, r2, _)
+            | EXPI(r1, r2, _)
+            | MODI(r1, r2, _)
+            | MULI(r1, r2, _)
+            | ORI(r1, r2, _)
+            | SLLI(r1, r2, _)
+            | SRLI(r1, r2, _)
+            | SUBI(r1, r2, _)
+            | XORI(r1, r2, _)
+            | LB(r1, r2, _)
+            | LW(r1, r2, _) => (r1, vec![r2]),
+            MOVE(r1, r2) | NOT(r1, r2) | CTMV(r1, r2) | CSIZ(r1, r2) | SRW(r1, r2) => {
+                (r1, vec![r2])
+            }
+            MEQ(r1, r2, r3, r4) => (r1, vec![r2, r3, r4]),
+            LWDataId(r1, _) | BHEI(r1) | GM(r1, _) => (r1, vec![]),
+            _ => return BTreeSet::new(),
+        };
+        if srcs.contains(&dst) {
+            BTreeSet::new()
+        } else {
+            std::iter::once(dst).collect()
+        }
+    }
+
     pub(crate) fn allocate_registers(
         &self,
         pool: &mut RegisterPool,
-        op_register_mapping: &[(RealizedOp, BTreeSet<VirtualRegister>)],
+        last_uses: &HashMap<VirtualRegister, usize>,
         ix: usize,
     ) -> AllocatedOpcode {
         let virtual_registers = self.registers();
@@ -235,9 +284,7 @@ impl VirtualOp {
             .into_iter()
             .map(|x| match x {
                 VirtualRegister::Constant(c) => (x, Some(AllocatedRegister::Constant(c.clone()))),
-                VirtualRegister::Virtual(_) => {
-                    (x, pool.get_register(x, &op_register_mapping[ix..]))
-                }
+                VirtualRegister::Virtual(_) => (x, pool.get_register(x, last_uses, ix)),
             })
             .map(|(x, register_opt)| register_opt.map(|register| (x, register)))
             .collect::<Option<Vec<_>>>();
@@ -1,3 +1,4 @@
+use crate::{module_cache::ModuleCache, type_engine::TypeEngine};
 use std::{
     path::PathBuf,
     sync::{Arc, Mutex},
@@ -13,7 +14,11 @@ pub struct BuildConfig {
     pub(crate) print_intermediate_asm: bool,
     pub(crate) print_finalized_asm: bool,
     pub(crate) print_ir: bool,
+    pub(crate) optimize: bool,
+    pub(crate) checked_arithmetic: bool,
     pub(crate) generated_names: Arc<Mutex<Vec<&'static str>>>,
+    pub(crate) module_cache: Option<ModuleCache>,
+    pub(crate) type_engine: TypeEngine,
 }
 
 impl BuildConfig {
@@ -33,7 +38,11 @@ impl BuildConfig {
             print_intermediate_asm: false,
             print_finalized_asm: false,
             print_ir: false,
+            optimize: true,
+            checked_arithmetic: true,
             generated_names: Arc::new(Mutex::new(vec![])),
+            module_cache: None,
+            type_engine: TypeEngine::new(),
         }
     }
 
@@ -62,6 +71,51 @@ impl BuildConfig {
         }
     }
 
+    /// Runs the optimisation passes of the IR pipeline that aren't needed to generate code.
+    pub fn optimize(self, a: bool) -> Self {
+        Self {
+            optimize: a,
+            ..self
+        }
+    }
+
+    /// Makes arithmetic overflow revert, by checking `$of` after each arithmetic op in asm blocks.
+    /// Otherwise, programs set the `F_WRAPPING` flag when they start, and overflowing arithmetic
+    /// wraps around.
+    pub fn checked_arithmetic(self, a: bool) -> Self {
+        Self {
+            checked_arithmetic: a,
+            ..self
+        }
+    }
+
+    /// Sets the directory that `dep` statements are resolved relative to, for compiling files
+    /// other than the project's entry point.
+    pub fn dir_of_code(self, dir: PathBuf) -> Self {
+        Self {
+            dir_of_code: Arc::new(dir),
+            ..self
+        }
+    }
+
+    /// Reuses the modules included by earlier compilations that haven't changed since, instead
+    /// of type checking them again.
+    pub fn module_cache(self, cache: ModuleCache) -> Self {
+        Self {
+            module_cache: Some(cache),
+            ..self
+        }
+    }
+
+    /// Creates the types of the compilation in `type_engine`. Compilations sharing a namespace,
+    /// like a package and its dependencies, must share an engine too.
+    pub fn type_engine(self, type_engine: TypeEngine) -> Self {
+        Self {
+            type_engine,
+            ..self
+        }
+    }
+
     pub fn path(&self) -> Arc<PathBuf> {
         self.file_name.clone()
     }
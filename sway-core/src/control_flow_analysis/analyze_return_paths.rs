@@ -180,6 +180,7 @@ fn connect_declaration(
         | AbiDeclaration(_)
         | StructDeclaration(_)
         | EnumDeclaration(_)
+        | TypeAliasDeclaration(_)
         | GenericTypeForFunctionScope { .. } => leaves.to_vec(),
         VariableDeclaration(_) | ConstantDeclaration(_) => {
             let entry_node = graph.add_node(node.into());
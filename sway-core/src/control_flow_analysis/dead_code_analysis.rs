@@ -82,7 +82,7 @@ impl ControlFlowGraph {
             .filter(|CompileWarning { span, .. }| {
                 // if any other warnings contain a span which completely covers this one, filter
                 // out this one.
-                all_warnings.iter().any(
+                !all_warnings.iter().any(
                     |CompileWarning {
                          span: other_span, ..
                      }| {
@@ -177,6 +177,23 @@ impl ControlFlowGraph {
                 })
                 .collect(),
         };
+        // tests are run on their own, whatever the kind of program they are in
+        let tests = graph.graph.node_indices().filter(|i| {
+            matches!(
+                graph.graph[*i],
+                ControlFlowGraphNode::ProgramNode(TypedAstNode {
+                    content: TypedAstNodeContent::Declaration(
+                        TypedDeclaration::FunctionDeclaration(TypedFunctionDeclaration {
+                            is_test: true,
+                            ..
+                        })
+                    ),
+                    ..
+                })
+            )
+        });
+        let tests = tests.collect::<Vec<_>>();
+        graph.entry_points.extend(tests);
         Ok(())
     }
 }
@@ -375,7 +392,9 @@ fn connect_declaration(
             connect_impl_trait(trait_name, graph, methods, entry_node, tree_type)?;
             Ok(leaves.to_vec())
         }
-        ErrorRecovery | GenericTypeForFunctionScope { .. } => Ok(leaves.to_vec()),
+        TypeAliasDeclaration(_) | ErrorRecovery | GenericTypeForFunctionScope { .. } => {
+            Ok(leaves.to_vec())
+        }
     }
 }
 
@@ -953,6 +972,25 @@ fn connect_expression(
             )?;
             Ok(prefix_idx)
         }
+        VecOperation { arguments, .. } | StringOperation { arguments, .. } => {
+            let mut nodes = vec![];
+            for arg in arguments {
+                nodes.append(&mut connect_expression(
+                    &arg.expression,
+                    graph,
+                    leaves,
+                    exit_node,
+                    "vec operation argument",
+                    tree_type,
+                    arg.span.clone(),
+                )?);
+            }
+            if nodes.is_empty() {
+                Ok(leaves.to_vec())
+            } else {
+                Ok(nodes)
+            }
+        }
         a => {
             println!("Unimplemented: {:?}", a);
             Err(CompileError::Unimplemented(
@@ -1045,6 +1083,11 @@ fn construct_dead_code_warning_from_node(node: &TypedAstNode) -> Option<CompileW
             content: TypedAstNodeContent::Declaration(TypedDeclaration::AbiDeclaration { .. }),
             ..
         } => return None,
+        // uses of type aliases are resolved away during type checking, so they are not tracked
+        TypedAstNode {
+            content: TypedAstNodeContent::Declaration(TypedDeclaration::TypeAliasDeclaration(..)),
+            ..
+        } => return None,
         TypedAstNode {
             content: TypedAstNodeContent::Declaration(..),
             span,
@@ -199,6 +199,9 @@ pub enum Warning {
     NonClassCaseEnumVariantName {
         variant_name: Ident,
     },
+    NonClassCaseTypeAliasName {
+        name: Ident,
+    },
     NonSnakeCaseStructFieldName {
         field_name: Ident,
     },
@@ -213,7 +216,7 @@ pub enum Warning {
         cast_to: IntegerBits,
     },
     UnusedReturnValue {
-        r#type: TypeInfo,
+        r#type: String,
     },
     SimilarMethodFound {
         lib: Ident,
@@ -239,6 +242,29 @@ pub enum Warning {
     },
 }
 
+impl Warning {
+    /// The name a warning about the style of a name suggests instead, along with the name it is
+    /// about.
+    pub fn suggested_name(&self) -> Option<(&Ident, String)> {
+        use Warning::*;
+        match self {
+            NonClassCaseStructName { struct_name: name }
+            | NonClassCaseTraitName { name }
+            | NonClassCaseEnumName { enum_name: name }
+            | NonClassCaseEnumVariantName { variant_name: name }
+            | NonClassCaseTypeAliasName { name } => {
+                Some((name, to_upper_camel_case(name.as_str())))
+            }
+            NonSnakeCaseStructFieldName { field_name: name }
+            | NonSnakeCaseFunctionName { name } => Some((name, to_snake_case(name.as_str()))),
+            NonScreamingSnakeCaseConstName { name } => {
+                Some((name, to_screaming_snake_case(name.as_str())))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for Warning {
     // This trait requires `fmt` with this exact signature.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -267,6 +293,13 @@ impl fmt::Display for Warning {
                 enum_name,
                 to_upper_camel_case(enum_name.as_str())
             ),
+            NonClassCaseTypeAliasName { name } => write!(
+                f,
+                "Type alias \"{}\"'s capitalization is not idiomatic. Type aliases should have a \
+                 ClassCase name, like \"{}\".",
+                name,
+                to_upper_camel_case(name.as_str())
+            ),
             NonSnakeCaseStructFieldName { field_name } => write!(
                 f,
                 "Struct field name \"{}\" is not idiomatic. Struct field names should have a \
@@ -310,7 +343,7 @@ impl fmt::Display for Warning {
                 f,
                 "This returns a value of type {}, which is not assigned to anything and is \
                  ignored.",
-                r#type.friendly_type_str()
+                r#type
             ),
             SimilarMethodFound { lib, module, name } => write!(
                 f,
@@ -511,6 +544,9 @@ pub enum CompileError {
     #[error("Functions are missing from this trait implementation: {missing_functions}")]
     MissingInterfaceSurfaceMethods {
         missing_functions: String,
+        /// The signatures of the missing functions, as they would be written in the
+        /// implementation.
+        signatures: Vec<String>,
         span: Span,
     },
     #[error("Expected {expected} type arguments, but instead found {given}.")]
@@ -773,6 +809,14 @@ pub enum CompileError {
         call_chain: String, // Pretty list of symbols, e.g., "a, b and c".
         span: Span,
     },
+    #[error("Type alias {name} refers to itself, so it has no underlying type.")]
+    RecursiveTypeAlias { name: Ident, span: Span },
+    #[error("Type alias {name} refers to itself via {alias_chain}, so it has no underlying type.")]
+    RecursiveTypeAliasChain {
+        name: Ident,
+        alias_chain: String,
+        span: Span,
+    },
     #[error(
         "The size of this type is not known. Try putting it on the heap or changing the type."
     )]
@@ -795,6 +839,22 @@ pub enum CompileError {
         count: usize,
         span: Span,
     },
+    #[error(
+        "Wrong number of fields in the pattern for the tuple struct \"{struct_name}\"; the struct \
+         has {expected} but the pattern has {found}."
+    )]
+    TupleStructPatternArity {
+        struct_name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    #[error(
+        "Refutable pattern in `let` binding. A `let` binding must match every value of its type, \
+         so it cannot contain enum variants or literals. Consider using a `match` expression \
+         instead."
+    )]
+    RefutablePatternInLet { span: Span },
     #[error("The name \"{name}\" shadows another symbol with the same name.")]
     ShadowsOtherSymbol { name: String, span: Span },
     #[error("The name \"{name}\" imported through `*` shadows another symbol with the same name.")]
@@ -825,6 +885,10 @@ pub enum CompileError {
         trait_name: String,
         span: Span,
     },
+    #[error("Unknown attribute \"{name}\". The only attribute is \"test\".")]
+    UnknownAttribute { name: String, span: Span },
+    #[error("Test function \"{name}\" cannot take parameters or type parameters. Tests are run on their own, with nothing to pass them.")]
+    TestFunctionWithParameters { name: String, span: Span },
 }
 
 impl std::convert::From<TypeError> for CompileError {
@@ -839,11 +903,11 @@ pub enum TypeError {
         "Mismatched types.\n\
          expected: {expected}\n\
          found:    {received}.\n\
-         {help}", expected=look_up_type_id(*expected).friendly_type_str(), received=look_up_type_id(*received).friendly_type_str(), help=if !help_text.is_empty() { format!("help: {}", help_text) } else { String::new() }
+         {help}", help=if !help_text.is_empty() { format!("help: {}", help_text) } else { String::new() }
     )]
     MismatchedType {
-        expected: TypeId,
-        received: TypeId,
+        expected: String,
+        received: String,
         help_text: String,
         span: Span,
     },
@@ -862,6 +926,18 @@ impl TypeError {
 }
 
 impl CompileError {
+    /// The name an error about something that couldn't be found in scope is about.
+    pub fn unresolved_name(&self) -> Option<&str> {
+        use CompileError::*;
+        match self {
+            SymbolNotFound { name, .. } | UnknownVariable { var_name: name, .. } => Some(name),
+            UnknownFunction { name, .. }
+            | StructNotFound { name, .. }
+            | TraitNotFound { name, .. } => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
     pub fn to_friendly_error_string(&self) -> String {
         match self {
             CompileError::ParseFailure { err, .. } => format!(
@@ -1007,6 +1083,8 @@ impl CompileError {
             ArgumentParameterTypeMismatch { span, .. } => span,
             RecursiveCall { span, .. } => span,
             RecursiveCallChain { span, .. } => span,
+            RecursiveTypeAlias { span, .. } => span,
+            RecursiveTypeAliasChain { span, .. } => span,
             TypeWithUnknownSize { span, .. } => span,
             InfiniteDependencies { span, .. } => span,
             GMFromExternalContract { span, .. } => span,
@@ -1015,6 +1093,8 @@ impl CompileError {
             ContractStorageFromExternalContext { span, .. } => span,
             ArrayOutOfBounds { span, .. } => span,
             TupleOutOfBounds { span, .. } => span,
+            TupleStructPatternArity { span, .. } => span,
+            RefutablePatternInLet { span } => span,
             ShadowsOtherSymbol { span, .. } => span,
             StarImportShadowsOtherSymbol { span, .. } => span,
             MatchWrongType { span, .. } => span,
@@ -1028,6 +1108,8 @@ impl CompileError {
             AsteriskWithAlias { span, .. } => span,
             AbiAsSupertrait { span, .. } => span,
             NameDefinedMultipleTimesForTrait { span, .. } => span,
+            UnknownAttribute { span, .. } => span,
+            TestFunctionWithParameters { span, .. } => span,
         }
     }
 
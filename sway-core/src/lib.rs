@@ -6,9 +6,9 @@ pub mod error;
 mod asm_generation;
 mod asm_lang;
 mod build_config;
-mod concurrent_slab;
 pub mod constants;
 mod control_flow_analysis;
+pub mod module_cache;
 mod optimize;
 pub mod parse_tree;
 mod parser;
@@ -21,6 +21,7 @@ pub use crate::parser::{Rule, SwayParser};
 use crate::{
     asm_generation::{checks, compile_ast_to_asm},
     error::*,
+    semantic_analysis::{read_module, TypedAstNodeContent},
     source_map::SourceMap,
 };
 pub use asm_generation::{AbstractInstructionSet, FinalizedAsm, SwayAsmSet};
@@ -32,15 +33,17 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 pub use semantic_analysis::{
-    create_module, retrieve_module, Namespace, NamespaceRef, NamespaceWrapper, TreeType,
-    TypedDeclaration, TypedFunctionDeclaration, TypedParseTree,
+    create_module, import_paths, items_at_path, members_of_type, module_items, retrieve_module,
+    submodules, AbiSelector, InlayHint, InlayHintKind, ModuleItem, Namespace, NamespaceRef,
+    NamespaceWrapper, Symbol, SymbolKind, TreeType, TypedDeclaration, TypedFunctionDeclaration,
+    TypedParseTree,
 };
 pub mod types;
 pub use crate::parse_tree::{Declaration, Expression, UseStatement, WhileLoop, *};
 
 pub use error::{CompileError, CompileResult, CompileWarning};
-use sway_types::{ident::Ident, span};
-pub use type_engine::TypeInfo;
+use sway_types::{ident::Ident, span, JsonABI};
+pub use type_engine::{TypeEngine, TypeInfo};
 
 /// Represents a parsed, but not yet type-checked, Sway program.
 /// A Sway program can be either a contract, script, predicate, or
@@ -125,9 +128,16 @@ impl ParseTree {
 /// # }
 /// ```
 ///
+/// The types written in the program are created in the type engine of `config`, or in one of
+/// their own if there is no `config`.
+///
 /// # Panics
 /// Panics if the generated parser from Pest panics.
 pub fn parse(input: Arc<str>, config: Option<&BuildConfig>) -> CompileResult<SwayParseTree> {
+    let type_engine = config
+        .map(|config| config.type_engine.clone())
+        .unwrap_or_default();
+    let _type_engine = type_engine.enter();
     let mut warnings: Vec<CompileWarning> = Vec::new();
     let mut errors: Vec<CompileError> = Vec::new();
     let mut parsed = match SwayParser::parse(Rule::program, input.clone()) {
@@ -159,6 +169,8 @@ pub fn parse(input: Arc<str>, config: Option<&BuildConfig>) -> CompileResult<Swa
 pub enum CompilationResult {
     Success {
         asm: FinalizedAsm,
+        tree_type: TreeType,
+        json_abi: JsonABI,
         warnings: Vec<CompileWarning>,
     },
     Library {
@@ -189,6 +201,8 @@ pub enum CompileAstResult {
 pub enum BytecodeCompilationResult {
     Success {
         bytes: Vec<u8>,
+        tree_type: TreeType,
+        json_abi: JsonABI,
         warnings: Vec<CompileWarning>,
     },
     Library {
@@ -228,13 +242,15 @@ fn get_end(err: &pest::error::Error<Rule>) -> usize {
 
 /// This struct represents the compilation of an internal dependency
 /// defined through an include statement (the `dep` keyword).
+#[derive(Clone)]
 pub(crate) struct InnerDependencyCompileResult {
     name: Ident,
     namespace: Namespace,
+    parse_tree: TypedParseTree,
 }
 /// For internal compiler use.
-/// Compiles an included file and returns its control flow and dead code graphs.
-/// These graphs are merged into the parent program's graphs for accurate analysis.
+/// Compiles an included file and returns its namespace and typed parse tree.
+/// The tree is added to the parent program's dead code graph for accurate analysis.
 ///
 /// TODO -- there is _so_ much duplicated code and messiness in this file around the
 /// different types of compilation and stuff. After we get to a good state with the MVP,
@@ -246,6 +262,7 @@ pub(crate) fn compile_inner_dependency(
     dead_code_graph: &mut ControlFlowGraph,
     dependency_graph: &mut HashMap<String, HashSet<String>>,
 ) -> CompileResult<InnerDependencyCompileResult> {
+    let _type_engine = build_config.type_engine.enter();
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
     let parse_tree = check!(
@@ -285,20 +302,11 @@ pub(crate) fn compile_inner_dependency(
     let graph = ControlFlowGraph::construct_return_path_graph(&typed_parse_tree);
     errors.append(&mut graph.analyze_return_paths());
 
-    // The dead code will be analyzed later wholistically with the rest of the program
-    // since we can't tell what is dead and what isn't just from looking at this file
-    if let Err(e) = ControlFlowGraph::append_to_dead_code_graph(
-        &typed_parse_tree,
-        &parse_tree.tree_type,
-        dead_code_graph,
-    ) {
-        errors.push(e)
-    };
-
     ok(
         InnerDependencyCompileResult {
             name: library_name.clone(),
-            namespace: typed_parse_tree.into_namespace(),
+            namespace: typed_parse_tree.clone().into_namespace(),
+            parse_tree: typed_parse_tree,
         },
         warnings,
         errors,
@@ -311,6 +319,7 @@ pub fn compile_to_ast(
     build_config: &BuildConfig,
     dependency_graph: &mut HashMap<String, HashSet<String>>,
 ) -> CompileAstResult {
+    let _type_engine = build_config.type_engine.enter();
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
     let parse_tree = check!(
@@ -335,7 +344,10 @@ pub fn compile_to_ast(
             &mut dead_code_graph,
             dependency_graph,
         ),
-        return CompileAstResult::Failure { errors, warnings },
+        return CompileAstResult::Failure {
+            errors: dedup_unsorted(errors),
+            warnings: dedup_unsorted(warnings),
+        },
         warnings,
         errors
     );
@@ -369,6 +381,7 @@ pub fn compile_to_asm(
     build_config: BuildConfig,
     dependency_graph: &mut HashMap<String, HashSet<String>>,
 ) -> CompilationResult {
+    let _type_engine = build_config.type_engine.enter();
     match compile_to_ast(input, initial_namespace, &build_config, dependency_graph) {
         CompileAstResult::Failure { warnings, errors } => {
             CompilationResult::Failure { warnings, errors }
@@ -381,9 +394,10 @@ pub fn compile_to_asm(
             let mut errors = vec![];
             match tree_type {
                 TreeType::Contract | TreeType::Script | TreeType::Predicate => {
+                    let json_abi = parse_tree.generate_json_abi();
                     let asm = check!(
                         if build_config.use_ir {
-                            compile_ast_to_ir_to_asm(*parse_tree, tree_type, &build_config)
+                            compile_ast_to_ir_to_asm(*parse_tree, tree_type.clone(), &build_config)
                         } else {
                             compile_ast_to_asm(*parse_tree, &build_config)
                         },
@@ -394,7 +408,12 @@ pub fn compile_to_asm(
                     if !errors.is_empty() {
                         return CompilationResult::Failure { errors, warnings };
                     }
-                    CompilationResult::Success { asm, warnings }
+                    CompilationResult::Success {
+                        asm,
+                        tree_type,
+                        json_abi,
+                        warnings,
+                    }
                 }
                 TreeType::Library { name } => CompilationResult::Library {
                     warnings,
@@ -451,12 +470,14 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     // The only other optimisation we have at the moment is constant combining.  In lieu of a
     // forthcoming pass manager we can just call it here now.  We can re-use the inline functions
     // list.
-    check!(
-        combine_constants(&mut ir, &functions_to_inline_to),
-        return err(warnings, errors),
-        warnings,
-        errors
-    );
+    if build_config.optimize {
+        check!(
+            combine_constants(&mut ir, &functions_to_inline_to),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+    }
 
     if build_config.print_ir {
         println!("{}", ir);
@@ -513,6 +534,8 @@ pub fn compile_to_bytecode(
     match compile_to_asm(input, initial_namespace, build_config, dependency_graph) {
         CompilationResult::Success {
             mut asm,
+            tree_type,
+            json_abi,
             mut warnings,
         } => {
             let mut asm_res = asm.to_bytecode_mut(source_map);
@@ -526,6 +549,8 @@ pub fn compile_to_bytecode(
                 // asm_res is confirmed to be Some(bytes).
                 BytecodeCompilationResult::Success {
                     bytes: asm_res.value.unwrap(),
+                    tree_type,
+                    json_abi,
                     warnings,
                 }
             }
@@ -539,6 +564,160 @@ pub fn compile_to_bytecode(
     }
 }
 
+/// A unit test, a function marked with `#[test]`, compiled on its own as the entry point of a
+/// script.
+pub struct CompiledTest {
+    /// The path of the test from the root of the program, such as `my_test` or `utils::my_test`.
+    pub name: String,
+    pub bytecode: Vec<u8>,
+    /// Maps the instructions of the test's bytecode back to the source they were compiled from.
+    pub source_map: SourceMap,
+}
+
+/// Represents the result of compiling the unit tests of a Sway program via
+/// [compile_tests_to_bytecode].
+pub enum TestCompilationResult {
+    Success {
+        tests: Vec<CompiledTest>,
+        warnings: Vec<CompileWarning>,
+    },
+    Failure {
+        warnings: Vec<CompileWarning>,
+        errors: Vec<CompileError>,
+    },
+}
+
+/// Given input Sway source code, compile each of its unit tests, including those of the libraries
+/// it declares with `dep`, to the bytecode of a script which runs it. The tests are sorted by name.
+pub fn compile_tests_to_bytecode(
+    input: Arc<str>,
+    initial_namespace: crate::semantic_analysis::NamespaceRef,
+    build_config: BuildConfig,
+    dependency_graph: &mut HashMap<String, HashSet<String>>,
+) -> TestCompilationResult {
+    let _type_engine = build_config.type_engine.enter();
+    let (parse_tree, mut warnings) =
+        match compile_to_ast(input, initial_namespace, &build_config, dependency_graph) {
+            CompileAstResult::Success {
+                parse_tree,
+                warnings,
+                ..
+            } => (parse_tree, warnings),
+            CompileAstResult::Failure { warnings, errors } => {
+                return TestCompilationResult::Failure { warnings, errors }
+            }
+        };
+    let mut errors = vec![];
+
+    // the declarations of the program, less its entry points, are compiled along with each test
+    let declarations = parse_tree
+        .all_nodes()
+        .iter()
+        .filter_map(|node| match &node.content {
+            TypedAstNodeContent::Declaration(TypedDeclaration::FunctionDeclaration(func))
+                if func.is_test || func.name.as_str() == "main" =>
+            {
+                None
+            }
+            TypedAstNodeContent::Declaration(TypedDeclaration::ImplTrait {
+                type_implementing_for: TypeInfo::Contract,
+                ..
+            }) => None,
+            TypedAstNodeContent::Declaration(decl) => Some(decl.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let namespace = parse_tree.get_namespace_ref();
+
+    let mut tests = vec![];
+    for (name, test) in test_functions(namespace, &build_config) {
+        let main_function = TypedFunctionDeclaration {
+            name: Ident::new_with_override("main", test.name.span().clone()),
+            is_test: false,
+            ..test
+        };
+        let script = TypedParseTree::Script {
+            main_function,
+            namespace,
+            declarations: declarations.clone(),
+            all_nodes: vec![],
+        };
+        let mut asm = check!(
+            if build_config.use_ir {
+                compile_ast_to_ir_to_asm(script, TreeType::Script, &build_config)
+            } else {
+                compile_ast_to_asm(script, &build_config)
+            },
+            continue,
+            warnings,
+            errors
+        );
+        let mut source_map = SourceMap::new();
+        let bytecode = check!(
+            asm.to_bytecode_mut(&mut source_map),
+            continue,
+            warnings,
+            errors
+        );
+        tests.push(CompiledTest {
+            name,
+            bytecode,
+            source_map,
+        });
+    }
+
+    if !errors.is_empty() {
+        return TestCompilationResult::Failure { warnings, errors };
+    }
+    TestCompilationResult::Success { tests, warnings }
+}
+
+/// The unit tests declared in the files of the program being built, found in `namespace` and the
+/// modules reachable from it, each with its path from `namespace`. A module reachable by several
+/// paths is named by the shortest.
+fn test_functions(
+    namespace: NamespaceRef,
+    build_config: &BuildConfig,
+) -> Vec<(String, TypedFunctionDeclaration)> {
+    // tests of the packages the program depends on are theirs to run
+    let source_dir = build_config.file_name.parent().map(|dir| dir.to_path_buf());
+    let is_in_program = |func: &TypedFunctionDeclaration| match (&func.span.path, &source_dir) {
+        (Some(path), Some(source_dir)) => path.starts_with(source_dir),
+        _ => false,
+    };
+
+    let mut tests = vec![];
+    let mut visited = vec![];
+    let mut modules = std::collections::VecDeque::from(vec![(vec![], namespace)]);
+    while let Some((path, module)) = modules.pop_front() {
+        if visited.contains(&module) {
+            continue;
+        }
+        visited.push(module);
+        read_module(
+            |m| {
+                for decl in m.get_all_declared_symbols() {
+                    if let TypedDeclaration::FunctionDeclaration(func) = decl {
+                        if func.is_test && is_in_program(func) {
+                            let mut test_path = path.clone();
+                            test_path.push(func.name.as_str().to_string());
+                            tests.push((test_path.join("::"), func.clone()));
+                        }
+                    }
+                }
+                for (name, submodule) in m.get_all_named_modules() {
+                    let mut submodule_path = path.clone();
+                    submodule_path.push(name.clone());
+                    modules.push_back((submodule_path, *submodule));
+                }
+            },
+            module,
+        );
+    }
+    tests.sort_by(|(a, _), (b, _)| a.cmp(b));
+    tests
+}
+
 /// Given a [TypedParseTree], which is type-checked Sway source, construct a graph to analyze
 /// control flow and determine if it is valid.
 fn perform_control_flow_analysis(
@@ -564,7 +743,7 @@ fn parse_root_from_pairs(
     input: impl Iterator<Item = Pair<Rule>>,
     config: Option<&BuildConfig>,
 ) -> CompileResult<SwayParseTree> {
-    let path = config.map(|config| config.dir_of_code.clone());
+    let path = config.map(|config| config.path());
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
     let mut fuel_ast_opt = None;
@@ -834,6 +1013,142 @@ fn test_unary_ordering() {
     };
 }
 
+#[test]
+fn test_boolean_prefixed_variable_names() {
+    use crate::parse_tree::declaration::FunctionDeclaration;
+    let prog = parse(
+        r#"
+    script;
+    fn main() -> u64 {
+        let true_count = 0;
+        let falsey = true_count;
+        falsey
+    }"#
+        .into(),
+        None,
+    );
+    let mut warnings: Vec<CompileWarning> = Vec::new();
+    let mut errors: Vec<CompileError> = Vec::new();
+    let prog = prog.unwrap(&mut warnings, &mut errors);
+    assert!(errors.is_empty(), "{:?}", errors);
+    if let AstNode {
+        content:
+            AstNodeContent::Declaration(Declaration::FunctionDeclaration(FunctionDeclaration {
+                body,
+                ..
+            })),
+        ..
+    } = &prog.tree.root_nodes[0]
+    {
+        let names = body
+            .contents
+            .iter()
+            .filter_map(|node| match &node.content {
+                AstNodeContent::Declaration(Declaration::VariableDeclaration(decl)) => {
+                    Some(decl.name.as_str())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["true_count", "falsey"]);
+    } else {
+        panic!("Was not ast node")
+    };
+}
+
+#[test]
+fn test_test_attribute() {
+    use crate::parse_tree::declaration::FunctionDeclaration;
+    let prog = parse(
+        r#"
+    script;
+    fn main() {}
+
+    #[test]
+    fn adds() {}
+    "#
+        .into(),
+        None,
+    );
+    let mut warnings: Vec<CompileWarning> = Vec::new();
+    let mut errors: Vec<CompileError> = Vec::new();
+    let prog = prog.unwrap(&mut warnings, &mut errors);
+    let is_test = prog
+        .tree
+        .root_nodes
+        .iter()
+        .filter_map(|node| match &node.content {
+            AstNodeContent::Declaration(Declaration::FunctionDeclaration(
+                FunctionDeclaration { name, is_test, .. },
+            )) => Some((name.as_str(), *is_test)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(is_test, [("main", false), ("adds", true)]);
+
+    let prog = parse(
+        r#"
+    script;
+    #[inline]
+    fn main() {}
+    "#
+        .into(),
+        None,
+    );
+    assert!(matches!(
+        prog.errors.as_slice(),
+        [CompileError::UnknownAttribute { name, .. }] if name == "inline"
+    ));
+}
+
+#[test]
+fn test_unchecked_arithmetic() {
+    let compile = |checked_arithmetic| {
+        let build_config =
+            BuildConfig::root_from_file_name_and_manifest_path("main.sw".into(), ".".into())
+                .checked_arithmetic(checked_arithmetic);
+        let program = "script; fn main() -> u64 { asm(a: 1, b) { add b a a; b: u64 } }";
+        match compile_to_asm(
+            program.into(),
+            create_module(),
+            build_config,
+            &mut HashMap::new(),
+        ) {
+            CompilationResult::Success { asm, .. } => asm.to_string(),
+            _ => panic!("the program compiles"),
+        }
+    };
+    let checked = compile(true);
+    assert!(!checked.contains("flag"));
+    assert!(checked.contains("rvrt $of"));
+    let unchecked = compile(false);
+    assert!(unchecked.contains("flag"));
+    assert!(!unchecked.contains("rvrt"));
+}
+
+#[test]
+fn test_dead_code_warnings_are_not_nested() {
+    let build_config =
+        BuildConfig::root_from_file_name_and_manifest_path("main.sw".into(), ".".into());
+    let program = "script; fn unused() -> u64 { let x = 1; x } fn main() -> u64 { 42 }";
+    let warnings = match compile_to_asm(
+        program.into(),
+        create_module(),
+        build_config,
+        &mut HashMap::new(),
+    ) {
+        CompilationResult::Success { warnings, .. } => warnings,
+        _ => panic!("the program compiles"),
+    };
+    // The dead function is reported, but not the dead declaration inside it.
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(
+        warnings[0].warning_content,
+        Warning::DeadFunctionDeclaration
+    ));
+    assert!(warnings[0].span.as_str().starts_with("fn unused"));
+}
+
 /// We want compile errors and warnings to retain their ordering, since typically
 /// they are grouped by relevance. However, we want to deduplicate them.
 /// Stdlib dedup in Rust assumes sorted data for efficiency, but we don't want that.
@@ -0,0 +1,262 @@
+use crate::{error::*, type_engine::TypeEngine, InnerDependencyCompileResult, Namespace};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The modules included with `dep` statements by earlier compilations, so that the files which
+/// haven't changed since aren't type checked again. A module can see the modules included before
+/// it and the ones it includes itself, so it is only reused if none of their files have changed
+/// either.
+///
+/// Every compilation needs a handle of its own, from [ModuleCache::for_compilation], to keep
+/// track of what it has included so far. Each module is type checked in a type engine of its own,
+/// which is kept with it and layered under the engines of the compilations that include it.
+#[derive(Clone, Default)]
+pub struct ModuleCache {
+    modules: Arc<Mutex<HashMap<ModuleKey, CachedModule>>>,
+    /// The files included so far by the compilation this handle belongs to, with the hashes of
+    /// their contents, in the order they were included.
+    included: Arc<Mutex<Vec<(PathBuf, u64)>>>,
+}
+
+impl fmt::Debug for ModuleCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModuleCache").finish_non_exhaustive()
+    }
+}
+
+/// The path of a module's file, and the hash of the files included before it.
+type ModuleKey = (PathBuf, u64);
+
+#[derive(Clone)]
+struct CachedModule {
+    result: InnerDependencyCompileResult,
+    warnings: Vec<CompileWarning>,
+    type_engine: TypeEngine,
+    /// The hash of the contents of the module's file.
+    hash: u64,
+    /// The files the module includes, directly or through other modules, with the hashes of
+    /// their contents.
+    includes: Vec<(PathBuf, u64)>,
+}
+
+impl ModuleCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A handle to the same modules, for another compilation.
+    pub fn for_compilation(&self) -> Self {
+        ModuleCache {
+            modules: self.modules.clone(),
+            included: Arc::default(),
+        }
+    }
+
+    /// Includes the module in the file at `path`, whose contents are `text`, in a compilation
+    /// whose types are in `type_engine`. The module from an earlier compilation is returned if
+    /// it can be reused, otherwise it is compiled with `compile`, in the engine it is given, and
+    /// kept for later compilations if that succeeds.
+    pub(crate) fn include(
+        &self,
+        path: &Path,
+        text: &str,
+        type_engine: &TypeEngine,
+        compile: impl FnOnce(TypeEngine) -> CompileResult<InnerDependencyCompileResult>,
+    ) -> CompileResult<InnerDependencyCompileResult> {
+        let hash = hash_of(text);
+        let (key, scope_len) = {
+            let included = self.included.lock().unwrap();
+            ((path.to_path_buf(), hash_of(&*included)), included.len())
+        };
+
+        let cached = self.modules.lock().unwrap().get(&key).cloned();
+        if let Some(module) = cached.filter(|module| module.hash == hash && module.is_current()) {
+            let mut included = self.included.lock().unwrap();
+            included.extend(module.includes);
+            included.push((path.to_path_buf(), hash));
+            type_engine.attach(module.type_engine);
+            return ok(module.result, module.warnings, vec![]);
+        }
+
+        // Modules are type checked before anything else in the file including them, so they can
+        // only see the types of the dependencies and of the modules included before them.
+        let module_engine = type_engine.sibling();
+        let compiled = compile(module_engine.clone());
+        type_engine.attach(module_engine.clone());
+        let mut included = self.included.lock().unwrap();
+        let includes = included[scope_len.min(included.len())..].to_vec();
+        included.push((path.to_path_buf(), hash));
+        if let (Some(result), true) = (&compiled.value, compiled.errors.is_empty()) {
+            let mut modules = self.modules.lock().unwrap();
+            // the modules compiled from older versions of the file won't be used again
+            modules.retain(|(module_path, _), module| module_path != path || module.hash == hash);
+            modules.insert(
+                key,
+                CachedModule {
+                    result: result.clone(),
+                    warnings: compiled.warnings.clone(),
+                    type_engine: module_engine,
+                    hash,
+                    includes,
+                },
+            );
+        }
+
+        compiled
+    }
+
+    /// The modules in the cache which no other module includes, by the names their libraries
+    /// declare, apart from the module in the file at `path` and those which include it. These are
+    /// the modules a file of the project could import items from, other than its dependencies.
+    pub fn importable_modules(&self, path: &Path) -> Vec<(String, Namespace)> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let modules = self.modules.lock().unwrap();
+        let included = |module_path: &Path| {
+            modules.values().any(|module| {
+                module
+                    .includes
+                    .iter()
+                    .any(|(included, _)| included == module_path)
+            })
+        };
+        let importable = modules
+            .iter()
+            .filter(|((module_path, _), module)| {
+                *module_path != path
+                    && !included(module_path)
+                    && !module
+                        .includes
+                        .iter()
+                        .any(|(included, _)| *included == path)
+            })
+            .map(|((module_path, _), module)| {
+                let InnerDependencyCompileResult {
+                    name, namespace, ..
+                } = &module.result;
+                (module_path, (name.as_str().to_string(), namespace.clone()))
+            })
+            .collect::<BTreeMap<_, _>>();
+        importable.into_values().collect()
+    }
+}
+
+impl CachedModule {
+    /// Whether the files this module includes are the same as when it was compiled.
+    fn is_current(&self) -> bool {
+        self.includes.iter().all(|(path, hash)| {
+            matches!(std::fs::read_to_string(path), Ok(text) if hash_of(&text) == *hash)
+        })
+    }
+}
+
+fn hash_of(value: &(impl Hash + ?Sized)) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile_to_ast, create_module, BuildConfig, CompileAstResult, TreeType};
+    use std::collections::HashMap;
+
+    fn compile_library(source: &str, type_engine: TypeEngine) -> InnerDependencyCompileResult {
+        let build_config =
+            BuildConfig::root_from_file_name_and_manifest_path("lib.sw".into(), "".into())
+                .type_engine(type_engine);
+        match compile_to_ast(
+            Arc::from(source),
+            create_module(),
+            &build_config,
+            &mut HashMap::new(),
+        ) {
+            CompileAstResult::Success {
+                parse_tree,
+                tree_type: TreeType::Library { name },
+                ..
+            } => InnerDependencyCompileResult {
+                name,
+                namespace: parse_tree.clone().into_namespace(),
+                parse_tree: *parse_tree,
+            },
+            _ => panic!("expected a library"),
+        }
+    }
+
+    #[test]
+    fn unchanged_modules_are_reused() {
+        let a = Path::new("/project/src/a.sw");
+        let b = Path::new("/project/src/b.sw");
+        let a_text = "library a;\n\npub fn one() -> u64 {\n    1\n}\n";
+        let b_text = "library b;\n\npub fn two() -> u64 {\n    2\n}\n";
+        let compiled = |text: &'static str| {
+            move |type_engine| ok(compile_library(text, type_engine), vec![], vec![])
+        };
+        let reused = |_| -> CompileResult<InnerDependencyCompileResult> {
+            panic!("the module should have been reused")
+        };
+        let engine = TypeEngine::new();
+
+        let cache = ModuleCache::new();
+        let first = cache.for_compilation();
+        first.include(a, a_text, &engine, compiled(a_text));
+        first.include(b, b_text, &engine, compiled(b_text));
+
+        // nothing changed
+        let second = cache.for_compilation();
+        let module = second.include(a, a_text, &engine, reused).value.unwrap();
+        assert_eq!(module.name.as_str(), "a");
+        second.include(b, b_text, &engine, reused);
+
+        // `b` can see `a`, so it is compiled again once `a` changes
+        let a_changed = "library a;\n\npub fn one() -> u64 {\n    11\n}\n";
+        let third = cache.for_compilation();
+        let mut compiles = 0;
+        third.include(a, a_changed, &engine, |type_engine| {
+            compiles += 1;
+            compiled(a_changed)(type_engine)
+        });
+        third.include(b, b_text, &engine, |type_engine| {
+            compiles += 1;
+            compiled(b_text)(type_engine)
+        });
+        assert_eq!(compiles, 2);
+
+        // and `a` on its own is reused from the last compilation
+        cache
+            .for_compilation()
+            .include(a, a_changed, &engine, reused);
+    }
+
+    #[test]
+    fn modules_can_be_imported_from_other_files() {
+        let a = Path::new("/project/src/a.sw");
+        let b = Path::new("/project/src/b.sw");
+        let a_text = "library a;\n\npub fn one() -> u64 {\n    1\n}\n";
+        let b_text = "library b;\n\npub fn two() -> u64 {\n    2\n}\n";
+        let engine = TypeEngine::new();
+        let cache = ModuleCache::new();
+        let compilation = cache.for_compilation();
+        for (path, text) in [(a, a_text), (b, b_text)] {
+            compilation.include(path, text, &engine, |type_engine| {
+                ok(compile_library(text, type_engine), vec![], vec![])
+            });
+        }
+
+        let names = |path| {
+            cache
+                .importable_modules(path)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(Path::new("/project/src/main.sw")), vec!["a", "b"]);
+        // a file can't import from itself
+        assert_eq!(names(a), vec!["b"]);
+    }
+}
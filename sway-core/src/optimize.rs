@@ -2,6 +2,9 @@ use std::collections::HashMap;
 use std::iter::FromIterator;
 
 use crate::{
+    asm_generation::compiler_constants::{
+        VEC_CAP_OFFSET, VEC_INITIAL_CAPACITY, VEC_LEN_OFFSET, VEC_PTR_OFFSET,
+    },
     parse_tree::{AsmOp, AsmRegister, LazyOp, Literal, Visibility},
     semantic_analysis::{ast_node::TypedCodeBlock, ast_node::*, *},
     type_engine::*,
@@ -178,6 +181,7 @@ fn compile_declarations(
             | TypedDeclaration::VariableDeclaration(_)
             | TypedDeclaration::Reassignment(_)
             | TypedDeclaration::AbiDeclaration(_)
+            | TypedDeclaration::TypeAliasDeclaration(_)
             | TypedDeclaration::GenericTypeForFunctionScope { .. }
             | TypedDeclaration::ErrorRecovery => (),
         }
@@ -468,6 +472,10 @@ impl FnCompiler {
                             Ok(Constant::get_unit(context))
                         }
                         TypedDeclaration::AbiDeclaration(_) => Err("abi decl".into()),
+                        TypedDeclaration::TypeAliasDeclaration(_) => {
+                            // Aliases are fully resolved during type checking.
+                            Ok(Constant::get_unit(context))
+                        }
                         TypedDeclaration::GenericTypeForFunctionScope { .. } => {
                             Err("gen ty for fn scope".into())
                         }
@@ -571,6 +579,14 @@ impl FnCompiler {
                 elem_to_access_span: span,
                 resolved_type_of_parent: tuple_type,
             } => self.compile_tuple_elem_expr( context, *prefix, tuple_type, idx, span),
+            TypedExpressionVariant::VecOperation {
+                op,
+                arguments,
+                elem_type,
+            } => self.compile_vec_operation(context, op, arguments, elem_type, ast_expr.span),
+            TypedExpressionVariant::StringOperation { op, arguments } => {
+                self.compile_string_operation(context, op, arguments, ast_expr.span)
+            }
             // XXX IGNORE FOR NOW?
             TypedExpressionVariant::AbiCast { .. } => Ok(Constant::get_unit(context)),
         }
@@ -726,6 +742,7 @@ impl FnCompiler {
                     },
                     visibility: Visibility::Private,
                     is_contract_call: false,
+                    is_test: false,
                     purity: Default::default(),
                 };
 
@@ -1358,6 +1375,621 @@ impl FnCompiler {
             .ins(context)
             .asm_block(registers, body, returns))
     }
+
+    // ---------------------------------------------------------------------------------------------
+
+    fn compile_vec_operation(
+        &mut self,
+        context: &mut Context,
+        op: VecOp,
+        arguments: Vec<TypedExpression>,
+        elem_type: TypeId,
+        span: Span,
+    ) -> Result<Value, String> {
+        // The vector header is three words: a pointer to the heap buffer, its capacity and the
+        // number of elements in use.  Everything but `new` works on the header in place, reading
+        // and writing its fields and accessing the buffer through its pointer.
+        if op == VecOp::New {
+            return Ok(self.compile_empty_header(context));
+        }
+
+        let elem_type = resolve_type(elem_type, &span).map_err(|ty_err| format!("{:?}", ty_err))?;
+        let is_zero_sized = elem_type
+            .size_in_words(&span)
+            .map_err(|ce| format!("{:?}", ce))?
+            == 0;
+        let elem_ty = convert_resolved_type(context, &elem_type)?;
+
+        let mut arguments = arguments.into_iter();
+        let header = match arguments.next() {
+            Some(vec_expr) => self.compile_expression(context, vec_expr)?,
+            None => return Err("Missing vector argument to Vec method.".into()),
+        };
+        let arg = arguments
+            .next()
+            .map(|arg_expr| self.compile_expression(context, arg_expr))
+            .transpose()?;
+
+        let header_ty = vec_header_aggregate(context);
+        let len =
+            self.current_block
+                .ins(context)
+                .extract_value(header, header_ty, vec![VEC_LEN_OFFSET]);
+        match op {
+            VecOp::New => unreachable!(),
+            VecOp::Len => Ok(len),
+            VecOp::Push => {
+                let elem = arg.ok_or("Missing element argument to Vec::push.")?;
+                self.compile_vec_grow_if_full(context, header, len, elem_ty);
+                if !is_zero_sized {
+                    let slot = self.compile_vec_slot(context, header, len, elem_ty);
+                    self.current_block
+                        .ins(context)
+                        .store_to(slot, elem_ty, elem);
+                }
+                let new_len = self.builtin_asm(
+                    context,
+                    &[("len", Some(len)), ("new_len", None)],
+                    &[("addi", &["new_len", "len"], Some(1))],
+                    Some("new_len"),
+                );
+                self.current_block.ins(context).insert_value(
+                    header,
+                    header_ty,
+                    new_len,
+                    vec![VEC_LEN_OFFSET],
+                );
+                Ok(Constant::get_unit(context))
+            }
+            VecOp::Pop => {
+                let is_not_empty = self.builtin_asm(
+                    context,
+                    &[("len", Some(len)), ("result", None)],
+                    &[("gt", &["result", "len", "zero"], None)],
+                    Some("result"),
+                );
+                self.compile_vec_revert_unless(context, is_not_empty);
+                let index = self.builtin_asm(
+                    context,
+                    &[("len", Some(len)), ("index", None)],
+                    &[("subi", &["index", "len"], Some(1))],
+                    Some("index"),
+                );
+                self.current_block.ins(context).insert_value(
+                    header,
+                    header_ty,
+                    index,
+                    vec![VEC_LEN_OFFSET],
+                );
+                self.compile_vec_load_elem(context, header, index, elem_ty, is_zero_sized)
+            }
+            VecOp::Get => {
+                let index = arg.ok_or("Missing index argument to Vec::get.")?;
+                let in_bounds = self.builtin_asm(
+                    context,
+                    &[("index", Some(index)), ("len", Some(len)), ("result", None)],
+                    &[("lt", &["result", "index", "len"], None)],
+                    Some("result"),
+                );
+                self.compile_vec_revert_unless(context, in_bounds);
+                self.compile_vec_load_elem(context, header, index, elem_ty, is_zero_sized)
+            }
+        }
+    }
+
+    fn compile_vec_grow_if_full(
+        &mut self,
+        context: &mut Context,
+        header: Value,
+        len: Value,
+        elem_ty: Type,
+    ) {
+        let header_ty = vec_header_aggregate(context);
+        let cap =
+            self.current_block
+                .ins(context)
+                .extract_value(header, header_ty, vec![VEC_CAP_OFFSET]);
+        let is_full = self.builtin_asm(
+            context,
+            &[("len", Some(len)), ("cap", Some(cap)), ("result", None)],
+            &[("eq", &["result", "len", "cap"], None)],
+            Some("result"),
+        );
+        let grow_block = self.function.create_block(context, None);
+        let copy_block = self.function.create_block(context, None);
+        let commit_block = self.function.create_block(context, None);
+        let done_block = self.function.create_block(context, None);
+        self.current_block
+            .ins(context)
+            .conditional_branch(is_full, grow_block, done_block, None);
+
+        // Allocate a buffer of double the capacity, or the initial capacity if there was none.
+        self.current_block = grow_block;
+        let initial_cap = Constant::get_uint(context, 64, VEC_INITIAL_CAPACITY);
+        let new_cap = self.builtin_asm(
+            context,
+            &[
+                ("len", Some(len)),
+                ("initial_cap", Some(initial_cap)),
+                ("is_empty", None),
+                ("new_cap", None),
+            ],
+            &[
+                ("eq", &["is_empty", "len", "zero"], None),
+                ("mul", &["is_empty", "is_empty", "initial_cap"], None),
+                ("muli", &["new_cap", "len"], Some(2)),
+                ("add", &["new_cap", "new_cap", "is_empty"], None),
+            ],
+            Some("new_cap"),
+        );
+        let new_buf = self.current_block.ins(context).alloc(elem_ty, new_cap);
+        self.current_block.ins(context).insert_value(
+            header,
+            header_ty,
+            new_cap,
+            vec![VEC_CAP_OFFSET],
+        );
+        let has_elems = self.builtin_asm(
+            context,
+            &[("len", Some(len)), ("result", None)],
+            &[("gt", &["result", "len", "zero"], None)],
+            Some("result"),
+        );
+        self.current_block.ins(context).conditional_branch(
+            has_elems,
+            copy_block,
+            commit_block,
+            None,
+        );
+
+        // Copy the existing elements over to the new buffer.
+        self.current_block = copy_block;
+        let old_buf =
+            self.current_block
+                .ins(context)
+                .extract_value(header, header_ty, vec![VEC_PTR_OFFSET]);
+        self.current_block
+            .ins(context)
+            .mem_copy(new_buf, old_buf, elem_ty, len);
+        self.current_block.ins(context).branch(commit_block, None);
+
+        self.current_block = commit_block;
+        self.current_block.ins(context).insert_value(
+            header,
+            header_ty,
+            new_buf,
+            vec![VEC_PTR_OFFSET],
+        );
+        self.current_block.ins(context).branch(done_block, None);
+
+        self.current_block = done_block;
+    }
+
+    fn compile_vec_revert_unless(&mut self, context: &mut Context, cond: Value) {
+        let fail_block = self.function.create_block(context, None);
+        let ok_block = self.function.create_block(context, None);
+        self.current_block
+            .ins(context)
+            .conditional_branch(cond, ok_block, fail_block, None);
+
+        self.current_block = fail_block;
+        self.builtin_asm(context, &[], &[("rvrt", &["one"], None)], None);
+        self.current_block.ins(context).branch(ok_block, None);
+
+        self.current_block = ok_block;
+    }
+
+    /// A pointer to the element at `index` in the vector's buffer.
+    fn compile_vec_slot(
+        &mut self,
+        context: &mut Context,
+        header: Value,
+        index: Value,
+        elem_ty: Type,
+    ) -> Value {
+        let header_ty = vec_header_aggregate(context);
+        let buf =
+            self.current_block
+                .ins(context)
+                .extract_value(header, header_ty, vec![VEC_PTR_OFFSET]);
+        self.current_block
+            .ins(context)
+            .get_elem_ptr(buf, elem_ty, index)
+    }
+
+    fn compile_vec_load_elem(
+        &mut self,
+        context: &mut Context,
+        header: Value,
+        index: Value,
+        elem_ty: Type,
+        is_zero_sized: bool,
+    ) -> Result<Value, String> {
+        if is_zero_sized {
+            return Ok(Constant::get_unit(context));
+        }
+        let slot = self.compile_vec_slot(context, header, index, elem_ty);
+        if matches!(elem_ty, Type::Unit | Type::Bool | Type::Uint(_)) {
+            return Ok(self.current_block.ins(context).load_from(slot, elem_ty));
+        }
+
+        // Larger elements are copied out of the buffer into a fresh local, so that later pushes
+        // and pops can't change them.
+        let local_ptr =
+            self.function
+                .new_unique_local_ptr(context, "__vec_elem".into(), elem_ty, true, None);
+        let elem = self.current_block.ins(context).get_ptr(local_ptr);
+        let one = Constant::get_uint(context, 64, 1);
+        self.current_block
+            .ins(context)
+            .mem_copy(elem, slot, elem_ty, one);
+        Ok(elem)
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    fn compile_string_operation(
+        &mut self,
+        context: &mut Context,
+        op: StringOp,
+        arguments: Vec<TypedExpression>,
+        span: Span,
+    ) -> Result<Value, String> {
+        // A string has the same header as a vector, with the capacity and length in bytes.  The
+        // buffers are never modified so they're allocated with exactly the room needed, rounded up
+        // to a whole word to keep the heap word aligned.
+        let str_len = match (op, arguments.first()) {
+            (StringOp::From, Some(arg)) => match resolve_type(arg.return_type, &span) {
+                Ok(TypeInfo::Str(len)) => len,
+                _ => return Err("Argument to String::from is not a str.".into()),
+            },
+            _ => 0,
+        };
+        let args = arguments
+            .into_iter()
+            .map(|arg| self.compile_expression(context, arg))
+            .collect::<Result<Vec<Value>, String>>()?;
+
+        match op {
+            StringOp::New => Ok(self.compile_empty_header(context)),
+            StringOp::From => {
+                if str_len == 0 {
+                    return Ok(self.compile_empty_header(context));
+                }
+                let len = Constant::get_uint(context, 64, str_len);
+                let cap = Constant::get_uint(context, 64, (str_len + 7) & !7);
+                let header = self.compile_new_header_ptr(context);
+                // Strings of up to a word are held directly in a register.
+                let copy = if str_len <= 8 {
+                    ("sw", &["buf", "src"][..], Some(0))
+                } else {
+                    ("mcp", &["buf", "src", "len"][..], None)
+                };
+                self.builtin_asm(
+                    context,
+                    &[
+                        ("header", Some(header)),
+                        ("src", Some(args[0])),
+                        ("len", Some(len)),
+                        ("cap", Some(cap)),
+                        ("buf", None),
+                    ],
+                    &[
+                        ("aloc", &["cap"], None),
+                        ("addi", &["buf", "hp"], Some(1)),
+                        copy,
+                        ("sw", &["header", "buf"], Some(VEC_PTR_OFFSET)),
+                        ("sw", &["header", "cap"], Some(VEC_CAP_OFFSET)),
+                        ("sw", &["header", "len"], Some(VEC_LEN_OFFSET)),
+                    ],
+                    None,
+                );
+                Ok(header)
+            }
+            StringOp::Concat => {
+                let (lhs, rhs) = (args[0], args[1]);
+                let lhs_len = self.compile_header_field(context, lhs, VEC_LEN_OFFSET);
+                let rhs_len = self.compile_header_field(context, rhs, VEC_LEN_OFFSET);
+                let len = self.builtin_asm(
+                    context,
+                    &[
+                        ("lhs_len", Some(lhs_len)),
+                        ("rhs_len", Some(rhs_len)),
+                        ("len", None),
+                    ],
+                    &[("add", &["len", "lhs_len", "rhs_len"], None)],
+                    Some("len"),
+                );
+                let cap = self.builtin_asm(
+                    context,
+                    &[("len", Some(len)), ("cap", None)],
+                    &[
+                        ("addi", &["cap", "len"], Some(7)),
+                        ("srli", &["cap", "cap"], Some(3)),
+                        ("slli", &["cap", "cap"], Some(3)),
+                    ],
+                    Some("cap"),
+                );
+                let buf = self.builtin_asm(
+                    context,
+                    &[("cap", Some(cap)), ("buf", None)],
+                    &[("aloc", &["cap"], None), ("addi", &["buf", "hp"], Some(1))],
+                    Some("buf"),
+                );
+                self.compile_string_copy_bytes(context, buf, lhs, lhs_len);
+                let rhs_buf = self.builtin_asm(
+                    context,
+                    &[
+                        ("buf", Some(buf)),
+                        ("lhs_len", Some(lhs_len)),
+                        ("rhs_buf", None),
+                    ],
+                    &[("add", &["rhs_buf", "buf", "lhs_len"], None)],
+                    Some("rhs_buf"),
+                );
+                self.compile_string_copy_bytes(context, rhs_buf, rhs, rhs_len);
+                let header = self.compile_new_header_ptr(context);
+                self.builtin_asm(
+                    context,
+                    &[
+                        ("header", Some(header)),
+                        ("buf", Some(buf)),
+                        ("cap", Some(cap)),
+                        ("len", Some(len)),
+                    ],
+                    &[
+                        ("sw", &["header", "buf"], Some(VEC_PTR_OFFSET)),
+                        ("sw", &["header", "cap"], Some(VEC_CAP_OFFSET)),
+                        ("sw", &["header", "len"], Some(VEC_LEN_OFFSET)),
+                    ],
+                    None,
+                );
+                Ok(header)
+            }
+            StringOp::ByteAt => {
+                let (string, index) = (args[0], args[1]);
+                let len = self.compile_header_field(context, string, VEC_LEN_OFFSET);
+                let in_bounds = self.builtin_asm(
+                    context,
+                    &[("index", Some(index)), ("len", Some(len)), ("result", None)],
+                    &[("lt", &["result", "index", "len"], None)],
+                    Some("result"),
+                );
+                self.compile_vec_revert_unless(context, in_bounds);
+                Ok(self.builtin_asm(
+                    context,
+                    &[
+                        ("string", Some(string)),
+                        ("index", Some(index)),
+                        ("word_offset", None),
+                        ("word", None),
+                        ("shift", None),
+                        ("top_byte", None),
+                        ("byte", None),
+                    ],
+                    &[
+                        // The VM's `LB` reads from the index of its address register rather
+                        // than from the address it holds, so the byte is shifted out of the
+                        // big endian word holding it instead.
+                        ("srli", &["word_offset", "index"], Some(3)),
+                        ("slli", &["word_offset", "word_offset"], Some(3)),
+                        ("lw", &["word", "string"], Some(VEC_PTR_OFFSET)),
+                        ("add", &["word", "word", "word_offset"], None),
+                        ("lw", &["word", "word"], Some(0)),
+                        ("sub", &["shift", "index", "word_offset"], None),
+                        ("muli", &["shift", "shift"], Some(8)),
+                        ("addi", &["top_byte", "zero"], Some(56)),
+                        ("sub", &["shift", "top_byte", "shift"], None),
+                        ("srl", &["word", "word", "shift"], None),
+                        ("andi", &["byte", "word"], Some(0xff)),
+                    ],
+                    Some("byte"),
+                ))
+            }
+            StringOp::Len => Ok(self.compile_header_field(context, args[0], VEC_LEN_OFFSET)),
+            StringOp::Eq | StringOp::Neq => {
+                let (lhs, rhs) = (args[0], args[1]);
+                let lhs_len = self.compile_header_field(context, lhs, VEC_LEN_OFFSET);
+                let rhs_len = self.compile_header_field(context, rhs, VEC_LEN_OFFSET);
+                let same_len = self.builtin_asm(
+                    context,
+                    &[
+                        ("lhs_len", Some(lhs_len)),
+                        ("rhs_len", Some(rhs_len)),
+                        ("result", None),
+                    ],
+                    &[("eq", &["result", "lhs_len", "rhs_len"], None)],
+                    Some("result"),
+                );
+                // Only compare the bytes if the lengths match and there are any bytes to compare.
+                let compare_bytes = self.builtin_asm(
+                    context,
+                    &[
+                        ("same_len", Some(same_len)),
+                        ("lhs_len", Some(lhs_len)),
+                        ("result", None),
+                    ],
+                    &[
+                        ("gt", &["result", "lhs_len", "zero"], None),
+                        ("and", &["result", "result", "same_len"], None),
+                    ],
+                    Some("result"),
+                );
+                let compare_block = self.function.create_block(context, None);
+                let skip_block = self.function.create_block(context, None);
+                let done_block = self.function.create_block(context, None);
+                self.current_block.ins(context).conditional_branch(
+                    compare_bytes,
+                    compare_block,
+                    skip_block,
+                    None,
+                );
+
+                self.current_block = compare_block;
+                let same_bytes = self.builtin_asm(
+                    context,
+                    &[
+                        ("lhs", Some(lhs)),
+                        ("rhs", Some(rhs)),
+                        ("len", Some(lhs_len)),
+                        ("lhs_buf", None),
+                        ("rhs_buf", None),
+                        ("result", None),
+                    ],
+                    &[
+                        ("lw", &["lhs_buf", "lhs"], Some(VEC_PTR_OFFSET)),
+                        ("lw", &["rhs_buf", "rhs"], Some(VEC_PTR_OFFSET)),
+                        ("meq", &["result", "lhs_buf", "rhs_buf", "len"], None),
+                    ],
+                    Some("result"),
+                );
+                self.current_block
+                    .ins(context)
+                    .branch(done_block, Some(same_bytes));
+
+                skip_block.ins(context).branch(done_block, Some(same_len));
+
+                self.current_block = done_block;
+                let is_eq = done_block.get_phi(context);
+                Ok(if op == StringOp::Neq {
+                    self.builtin_asm(
+                        context,
+                        &[("is_eq", Some(is_eq)), ("result", None)],
+                        &[("eq", &["result", "is_eq", "zero"], None)],
+                        Some("result"),
+                    )
+                } else {
+                    is_eq
+                })
+            }
+        }
+    }
+
+    /// Copies `len` bytes from the buffer of the string `src_header` to `dst`.  `MCP` can't copy
+    /// zero bytes, so empty strings are skipped.
+    fn compile_string_copy_bytes(
+        &mut self,
+        context: &mut Context,
+        dst: Value,
+        src_header: Value,
+        len: Value,
+    ) {
+        let is_not_empty = self.builtin_asm(
+            context,
+            &[("len", Some(len)), ("result", None)],
+            &[("gt", &["result", "len", "zero"], None)],
+            Some("result"),
+        );
+        let copy_block = self.function.create_block(context, None);
+        let done_block = self.function.create_block(context, None);
+        self.current_block.ins(context).conditional_branch(
+            is_not_empty,
+            copy_block,
+            done_block,
+            None,
+        );
+
+        self.current_block = copy_block;
+        self.builtin_asm(
+            context,
+            &[
+                ("dst", Some(dst)),
+                ("src_header", Some(src_header)),
+                ("len", Some(len)),
+                ("src", None),
+            ],
+            &[
+                ("lw", &["src", "src_header"], Some(VEC_PTR_OFFSET)),
+                ("mcp", &["dst", "src", "len"], None),
+            ],
+            None,
+        );
+        self.current_block.ins(context).branch(done_block, None);
+
+        self.current_block = done_block;
+    }
+
+    /// The value of an empty vector or string, a header with a null buffer.
+    fn compile_empty_header(&mut self, context: &mut Context) -> Value {
+        let header_ty = vec_header_aggregate(context);
+        let header = Constant::get_undef(context, Type::Struct(header_ty));
+        [VEC_PTR_OFFSET, VEC_CAP_OFFSET, VEC_LEN_OFFSET]
+            .iter()
+            .fold(header, |header, idx| {
+                let zero = Constant::get_uint(context, 64, 0);
+                self.current_block
+                    .ins(context)
+                    .insert_value(header, header_ty, zero, vec![*idx])
+            })
+    }
+
+    /// The address of a new, uninitialised header in the function's local storage.
+    fn compile_new_header_ptr(&mut self, context: &mut Context) -> Value {
+        let header_ty = Type::Struct(vec_header_aggregate(context));
+        let header_ptr =
+            self.function
+                .new_unique_local_ptr(context, "__header".into(), header_ty, true, None);
+        self.current_block.ins(context).get_ptr(header_ptr)
+    }
+
+    fn compile_header_field(&mut self, context: &mut Context, header: Value, offset: u64) -> Value {
+        self.builtin_asm(
+            context,
+            &[("header", Some(header)), ("field", None)],
+            &[("lw", &["field", "header"], Some(offset))],
+            Some("field"),
+        )
+    }
+
+    // ---------------------------------------------------------------------------------------------
+
+    /// Adds an asm block built from static register names and opcodes.  Registers with an
+    /// initialiser must only be read, as they share a register with their initialising value.
+    fn builtin_asm(
+        &mut self,
+        context: &mut Context,
+        args: &[(&str, Option<Value>)],
+        body: &[(&str, &[&str], Option<u64>)],
+        returns: Option<&str>,
+    ) -> Value {
+        let args = args
+            .iter()
+            .map(|(name, initializer)| AsmArg {
+                name: ident_from_str(name),
+                initializer: *initializer,
+            })
+            .collect();
+        let body = body
+            .iter()
+            .map(|(op_name, op_args, immediate)| AsmInstruction {
+                name: ident_from_str(op_name),
+                args: op_args.iter().map(|arg| ident_from_str(arg)).collect(),
+                immediate: immediate.map(|imm| ident_from_str(&format!("i{}", imm))),
+            })
+            .collect();
+        self.current_block
+            .ins(context)
+            .asm_block(args, body, returns.map(ident_from_str))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+fn ident_from_str(s: &str) -> Ident {
+    Ident::new(Span {
+        span: pest::Span::new(s.into(), 0, s.len()).unwrap(),
+        path: None,
+    })
+}
+
+/// The header of a `Vec<T>` or `String` is an anonymous struct of its buffer pointer, capacity and
+/// length.
+fn vec_header_aggregate(context: &mut Context) -> Aggregate {
+    Aggregate::new_struct(
+        context,
+        None,
+        vec![Type::Pointer, Type::Uint(64), Type::Uint(64)],
+    )
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -1453,6 +2085,12 @@ fn convert_resolved_type(context: &mut Context, ast_type: &TypeInfo) -> Result<T
             let elem_type = convert_resolved_typeid_no_span(context, elem_type_id)?;
             Type::Array(Aggregate::new_array(context, elem_type, *count as u64))
         }
+        TypeInfo::Vector(elem_type_id) => {
+            // The elements live on the heap, but their type must still be known to the context.
+            convert_resolved_typeid_no_span(context, elem_type_id)?;
+            Type::Struct(vec_header_aggregate(context))
+        }
+        TypeInfo::DynamicString => Type::Struct(vec_header_aggregate(context)),
         TypeInfo::Tuple(fields) => {
             if fields.is_empty() {
                 // XXX We've removed Unit from the core compiler, replaced with an empty Tuple.
@@ -1475,6 +2113,7 @@ fn convert_resolved_type(context: &mut Context, ast_type: &TypeInfo) -> Result<T
         TypeInfo::Unknown => return Err("unknown type found in AST..?".into()),
         TypeInfo::UnknownGeneric { .. } => return Err("unknowngeneric type found in AST..?".into()),
         TypeInfo::Ref(_) => return Err("ref type found in AST..?".into()),
+        TypeInfo::Alias { ty, .. } => convert_resolved_typeid_no_span(context, ty)?,
         TypeInfo::ErrorRecovery => return Err("error recovery type found in AST..?".into()),
     })
 }
@@ -1528,7 +2167,9 @@ mod tests {
         let expected_bytes = std::fs::read(&path).unwrap();
         let expected = String::from_utf8_lossy(&expected_bytes);
 
-        let typed_ast = parse_to_typed_ast(&input);
+        let type_engine = crate::type_engine::TypeEngine::new();
+        let _type_engine = type_engine.enter();
+        let typed_ast = parse_to_typed_ast(&input, &type_engine);
         let ir = super::compile_ast(typed_ast).unwrap();
         let output = sway_ir::printer::to_string(&ir);
 
@@ -1584,7 +2225,10 @@ mod tests {
 
     // -------------------------------------------------------------------------------------------------
 
-    fn parse_to_typed_ast(input: &str) -> TypedParseTree {
+    fn parse_to_typed_ast(
+        input: &str,
+        type_engine: &crate::type_engine::TypeEngine,
+    ) -> TypedParseTree {
         let mut parsed =
             SwayParser::parse(Rule::program, std::sync::Arc::from(input)).expect("parse_tree");
 
@@ -1606,7 +2250,11 @@ mod tests {
             print_intermediate_asm: false,
             print_finalized_asm: false,
             print_ir: false,
+            optimize: true,
+            checked_arithmetic: true,
             generated_names: std::sync::Arc::new(std::sync::Mutex::new(vec![])),
+            module_cache: None,
+            type_engine: type_engine.clone(),
         };
         TypedParseTree::type_check(
             parse_tree.tree,
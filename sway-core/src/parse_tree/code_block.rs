@@ -1,4 +1,4 @@
-use super::WhileLoop;
+use super::{ForLoop, WhileLoop};
 use crate::{
     build_config::BuildConfig,
     error::*,
@@ -108,6 +108,21 @@ impl CodeBlock {
                         },
                     }]
                 }
+                Rule::for_loop => {
+                    let res = check!(
+                        ForLoop::parse_from_pair(pair.clone(), config),
+                        continue,
+                        warnings,
+                        errors
+                    );
+                    vec![AstNode {
+                        content: AstNodeContent::Expression(res.desugar(config)),
+                        span: span::Span {
+                            span: pair.as_span(),
+                            path: path.clone(),
+                        },
+                    }]
+                }
                 a => {
                     println!("In code block parsing: {:?} {:?}", a, pair.as_str());
                     errors.push(CompileError::UnimplementedRule(
@@ -7,6 +7,7 @@ mod reassignment;
 mod storage;
 mod r#struct;
 mod r#trait;
+mod type_alias;
 mod type_parameter;
 mod variable;
 
@@ -19,6 +20,7 @@ pub use r#struct::*;
 pub use r#trait::*;
 pub(crate) use reassignment::*;
 pub use storage::*;
+pub use type_alias::*;
 pub(crate) use type_parameter::*;
 pub use variable::*;
 
@@ -39,6 +41,7 @@ pub enum Declaration {
     AbiDeclaration(AbiDeclaration),
     ConstantDeclaration(ConstantDeclaration),
     StorageDeclaration(StorageDeclaration),
+    TypeAliasDeclaration(TypeAliasDeclaration),
 }
 impl Declaration {
     pub(crate) fn parse_non_var_from_pair(
@@ -122,6 +125,12 @@ impl Declaration {
                 warnings,
                 errors
             )),
+            Rule::type_alias_decl => Declaration::TypeAliasDeclaration(check!(
+                TypeAliasDeclaration::parse_from_pair(decl_inner, config),
+                return err(warnings, errors),
+                warnings,
+                errors
+            )),
             a => unreachable!("declarations don't have any other sub-types: {:?}", a),
         };
         ok(parsed_declaration, warnings, errors)
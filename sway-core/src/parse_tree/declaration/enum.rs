@@ -47,7 +47,7 @@ impl EnumDeclaration {
         let type_mapping = insert_type_parameters(&self.type_parameters);
         for variant in &self.variants {
             variants_buf.push(check!(
-                variant.to_typed_decl(namespace, self_type, variant.span.clone(), &type_mapping),
+                variant.to_typed_decl(namespace, self_type, &type_mapping),
                 continue,
                 warnings,
                 errors
@@ -154,7 +154,6 @@ impl EnumVariant {
         &self,
         namespace: NamespaceRef,
         self_type: TypeId,
-        span: Span,
         type_mapping: &[(TypeParameter, TypeId)],
     ) -> CompileResult<TypedEnumVariant> {
         let mut errors = vec![];
@@ -164,8 +163,8 @@ impl EnumVariant {
             } else {
                 namespace
                     .resolve_type_with_self(self.r#type.clone(), self_type)
-                    .unwrap_or_else(|_| {
-                        errors.push(CompileError::UnknownType { span });
+                    .unwrap_or_else(|err| {
+                        errors.push(err);
                         insert_type(TypeInfo::ErrorRecovery)
                     })
             };
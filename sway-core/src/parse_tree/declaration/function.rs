@@ -25,14 +25,30 @@ pub struct FunctionDeclaration {
     pub(crate) return_type: TypeInfo,
     pub(crate) type_parameters: Vec<TypeParameter>,
     pub(crate) return_type_span: Span,
+    /// Whether this function is a unit test, marked with `#[test]`.
+    pub is_test: bool,
 }
 
 impl FunctionDeclaration {
     pub fn parse_from_pair(pair: Pair<Rule>, config: Option<&BuildConfig>) -> CompileResult<Self> {
         let path = config.map(|c| c.path());
-        let mut parts = pair.clone().into_inner();
+        let mut parts = pair.clone().into_inner().peekable();
         let mut warnings = Vec::new();
         let mut errors = Vec::new();
+        let mut is_test = false;
+        while let Some(attribute) = parts.next_if(|pair| pair.as_rule() == Rule::attribute) {
+            let attribute_name = attribute.into_inner().next().unwrap();
+            match attribute_name.as_str() {
+                "test" => is_test = true,
+                name => errors.push(CompileError::UnknownAttribute {
+                    name: name.to_string(),
+                    span: Span {
+                        span: attribute_name.as_span(),
+                        path: path.clone(),
+                    },
+                }),
+            }
+        }
         let signature_or_visibility = parts.next().unwrap();
         let (visibility, signature) = if signature_or_visibility.as_rule() == Rule::visibility {
             (
@@ -165,6 +181,7 @@ impl FunctionDeclaration {
                 },
                 return_type,
                 type_parameters,
+                is_test,
             },
             warnings,
             errors,
@@ -9,7 +9,9 @@ use crate::{
 
 use sway_types::{ident::Ident, span::Span};
 
+use lazy_static::lazy_static;
 use pest::iterators::Pair;
+use std::sync::RwLock;
 
 #[derive(Debug, Clone)]
 pub struct StructDeclaration {
@@ -17,6 +19,20 @@ pub struct StructDeclaration {
     pub(crate) fields: Vec<StructField>,
     pub(crate) type_parameters: Vec<TypeParameter>,
     pub visibility: Visibility,
+    pub(crate) kind: StructKind,
+}
+
+/// The form a struct was declared in. Tuple structs and unit structs are otherwise represented
+/// exactly like structs with named fields, where the fields of a tuple struct are named by
+/// their position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructKind {
+    /// `struct Point { x: u64, y: u64 }`
+    Named,
+    /// `struct Meters(u64);`
+    Tuple,
+    /// `struct Marker;`
+    Unit,
 }
 
 #[derive(Debug, Clone)]
@@ -24,7 +40,6 @@ pub(crate) struct StructField {
     pub(crate) name: Ident,
     pub(crate) r#type: TypeInfo,
     pub(crate) span: Span,
-    pub(crate) type_span: Span,
 }
 
 impl StructDeclaration {
@@ -41,6 +56,7 @@ impl StructDeclaration {
         let mut type_params_pair = None;
         let mut where_clause_pair = None;
         let mut fields_pair = None;
+        let mut kind = StructKind::Unit;
         for pair in decl {
             match pair.as_rule() {
                 Rule::type_params => {
@@ -50,6 +66,11 @@ impl StructDeclaration {
                     where_clause_pair = Some(pair);
                 }
                 Rule::struct_fields => {
+                    kind = StructKind::Named;
+                    fields_pair = Some(pair);
+                }
+                Rule::tuple_struct_fields => {
+                    kind = StructKind::Tuple;
                     fields_pair = Some(pair);
                 }
                 Rule::struct_keyword => (),
@@ -71,15 +92,20 @@ impl StructDeclaration {
         )
         .unwrap_or_else(&mut warnings, &mut errors, Vec::new);
 
-        let fields = if let Some(fields) = fields_pair {
-            check!(
+        let fields = match fields_pair {
+            Some(fields) if kind == StructKind::Tuple => check!(
+                StructField::parse_from_tuple_pairs(fields, config),
+                Vec::new(),
+                warnings,
+                errors
+            ),
+            Some(fields) => check!(
                 StructField::parse_from_pairs(fields, config),
                 Vec::new(),
                 warnings,
                 errors
-            )
-        } else {
-            Vec::new()
+            ),
+            None => Vec::new(),
         };
 
         let span = Span {
@@ -106,6 +132,7 @@ impl StructDeclaration {
                 fields,
                 type_parameters,
                 visibility,
+                kind,
             },
             warnings,
             errors,
@@ -142,24 +169,66 @@ impl StructField {
                     field_name: name.clone(),
                 }
             );
-            let type_pair = fields[i + 1].clone();
+            let r#type = check!(
+                TypeInfo::parse_from_pair(fields[i + 1].clone(), config),
+                TypeInfo::Tuple(Vec::new()),
+                warnings,
+                errors
+            );
+            fields_buf.push(StructField { name, r#type, span });
+        }
+        ok(fields_buf, warnings, errors)
+    }
+    pub(crate) fn parse_from_tuple_pairs(
+        pair: Pair<Rule>,
+        config: Option<&BuildConfig>,
+    ) -> CompileResult<Vec<Self>> {
+        let path = config.map(|c| c.path());
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        let mut fields_buf = Vec::new();
+        for (i, type_pair) in pair.into_inner().enumerate() {
             let type_span = Span {
                 span: type_pair.as_span(),
                 path: path.clone(),
             };
             let r#type = check!(
-                TypeInfo::parse_from_pair(fields[i + 1].clone(), config),
+                TypeInfo::parse_from_pair(type_pair, config),
                 TypeInfo::Tuple(Vec::new()),
                 warnings,
                 errors
             );
             fields_buf.push(StructField {
-                name,
+                name: tuple_struct_field_name(i, type_span.clone()),
                 r#type,
-                span,
-                type_span,
+                span: type_span,
             });
         }
         ok(fields_buf, warnings, errors)
     }
 }
+
+lazy_static! {
+    /// The names of the fields of tuple structs, by their position. Each is allocated the first
+    /// time it is needed and shared by every tuple struct after that, since an [Ident] can only
+    /// hold a `'static` name which doesn't come from its span.
+    static ref TUPLE_STRUCT_FIELD_NAMES: RwLock<Vec<&'static str>> = Default::default();
+}
+
+/// The name of the field at position `index` of a tuple struct, i.e. the `0` in `meters.0`.
+pub(crate) fn tuple_struct_field_name(index: usize, span: Span) -> Ident {
+    let name = TUPLE_STRUCT_FIELD_NAMES
+        .read()
+        .expect("poisoned lock")
+        .get(index)
+        .copied();
+    let name = name.unwrap_or_else(|| {
+        let mut names = TUPLE_STRUCT_FIELD_NAMES.write().expect("poisoned lock");
+        while names.len() <= index {
+            let name = names.len().to_string();
+            names.push(Box::leak(name.into_boxed_str()));
+        }
+        names[index]
+    });
+    Ident::new_with_override(name, span)
+}
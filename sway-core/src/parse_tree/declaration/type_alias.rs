@@ -0,0 +1,100 @@
+use crate::{
+    build_config::BuildConfig,
+    error::*,
+    parse_tree::{declaration::TypeParameter, ident, Visibility},
+    parser::Rule,
+    style::is_upper_camel_case,
+    type_engine::TypeInfo,
+};
+
+use sway_types::{ident::Ident, span::Span};
+
+use pest::iterators::Pair;
+
+/// A type alias, e.g. `type Balances = [b256; 8];` or `type Pair<T> = (T, T);`.
+#[derive(Debug, Clone)]
+pub struct TypeAliasDeclaration {
+    pub name: Ident,
+    pub(crate) type_parameters: Vec<TypeParameter>,
+    pub(crate) r#type: TypeInfo,
+    pub visibility: Visibility,
+    pub(crate) span: Span,
+}
+
+impl TypeAliasDeclaration {
+    pub(crate) fn parse_from_pair(
+        decl: Pair<Rule>,
+        config: Option<&BuildConfig>,
+    ) -> CompileResult<Self> {
+        let path = config.map(|c| c.path());
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        let span = Span {
+            span: decl.as_span(),
+            path: path.clone(),
+        };
+        let mut visibility = Visibility::Private;
+        let mut name = None;
+        let mut type_params_pair = None;
+        let mut type_pair = None;
+        for pair in decl.into_inner() {
+            match pair.as_rule() {
+                Rule::visibility => {
+                    visibility = Visibility::parse_from_pair(pair);
+                }
+                Rule::type_keyword => (),
+                Rule::type_alias_name => {
+                    name = Some(pair);
+                }
+                Rule::type_params => {
+                    type_params_pair = Some(pair);
+                }
+                Rule::type_name => {
+                    type_pair = Some(pair);
+                }
+                a => unreachable!("{:?}", a),
+            }
+        }
+        let name = name.expect("guaranteed to exist by grammar");
+        let type_pair = type_pair.expect("guaranteed to exist by grammar");
+
+        let type_parameters =
+            TypeParameter::parse_from_type_params_and_where_clause(type_params_pair, None, config)
+                .unwrap_or_else(&mut warnings, &mut errors, Vec::new);
+
+        let r#type = check!(
+            TypeInfo::parse_from_pair(type_pair, config),
+            TypeInfo::ErrorRecovery,
+            warnings,
+            errors
+        );
+
+        let name_span = Span {
+            span: name.as_span(),
+            path,
+        };
+        let name = check!(
+            ident::parse_from_pair(name, config),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+        assert_or_warn!(
+            is_upper_camel_case(name.as_str()),
+            warnings,
+            name_span,
+            Warning::NonClassCaseTypeAliasName { name: name.clone() }
+        );
+        ok(
+            TypeAliasDeclaration {
+                name,
+                type_parameters,
+                r#type,
+                visibility,
+                span,
+            },
+            warnings,
+            errors,
+        )
+    }
+}
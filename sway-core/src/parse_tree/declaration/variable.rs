@@ -2,9 +2,12 @@ use crate::parser::Rule;
 use crate::{
     error::{err, ok},
     ident,
-    parse_tree::Expression,
+    parse_tree::{
+        matcher, tuple_struct_field_name, DelayedResolutionVariant, DelayedStructFieldResolution,
+        Expression, Scrutinee,
+    },
     type_engine::TypeInfo,
-    BuildConfig, CompileResult, Ident,
+    BuildConfig, CompileError, CompileResult, Ident,
 };
 
 use pest::iterators::Pair;
@@ -33,6 +36,10 @@ pub struct VariableDeclaration {
 enum VariableDeclarationLHS {
     Name(LHSName),
     Tuple(LHSTuple),
+    Struct(LHSStruct),
+    /// A pattern which can fail to match, such as `Shape::Square(side)`. It is an error, but its
+    /// names are still bound so that the rest of the scope can be checked.
+    Refutable(Scrutinee),
 }
 
 struct LHSName {
@@ -46,6 +53,16 @@ struct LHSTuple {
     span: Span,
 }
 
+/// A struct pattern such as `Point { x, y: py }`. Tuple struct patterns such as `Meters(m)` are
+/// represented the same way, with the fields named by their position.
+struct LHSStruct {
+    struct_name: Ident,
+    fields: Vec<(Ident, VariableDeclarationLHS)>,
+    /// The number of fields of a tuple struct pattern, which must be the number the struct has.
+    arity: Option<usize>,
+    span: Span,
+}
+
 impl VariableDeclaration {
     pub(crate) fn parse_from_pair(
         pair: Pair<Rule>,
@@ -94,13 +111,19 @@ impl VariableDeclaration {
             warnings,
             errors
         );
-        VariableDeclaration::desugar_to_decls(
-            lhs,
-            type_ascription,
-            type_ascription_span,
-            body,
-            config,
-        )
+        let decls = check!(
+            VariableDeclaration::desugar_to_decls(
+                lhs,
+                type_ascription,
+                type_ascription_span,
+                body,
+                config,
+            ),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+        ok(decls, warnings, errors)
     }
 
     fn desugar_to_decls(
@@ -124,7 +147,21 @@ impl VariableDeclaration {
                     type_ascription_span,
                 }]
             }
-            VariableDeclarationLHS::Tuple(lhs_tuple) => {
+            // Without a type ascription there is no need to save the body first, as a struct
+            // pattern saves the value it destructures itself.
+            VariableDeclarationLHS::Struct(lhs_struct) if type_ascription == TypeInfo::Unknown => {
+                check!(
+                    VariableDeclaration::desugar_to_decls_inner(
+                        VariableDeclarationLHS::Struct(lhs_struct),
+                        body,
+                        config
+                    ),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                )
+            }
+            lhs => {
                 let name = ident::random_name(body.span(), config);
                 let save_body_first = VariableDeclaration {
                     name: name.clone(),
@@ -139,10 +176,7 @@ impl VariableDeclaration {
                 };
                 let mut decls = vec![save_body_first];
                 decls.append(&mut check!(
-                    VariableDeclaration::desugar_to_decls_inner(
-                        VariableDeclarationLHS::Tuple(lhs_tuple),
-                        new_body
-                    ),
+                    VariableDeclaration::desugar_to_decls_inner(lhs, new_body, config),
                     return err(warnings, errors),
                     warnings,
                     errors
@@ -156,6 +190,7 @@ impl VariableDeclaration {
     fn desugar_to_decls_inner(
         lhs: VariableDeclarationLHS,
         body: Expression,
+        config: Option<&BuildConfig>,
     ) -> CompileResult<Vec<Self>> {
         let mut warnings = vec![];
         let mut errors = vec![];
@@ -181,7 +216,7 @@ impl VariableDeclaration {
                         span: span.clone(),
                     };
                     decls.append(&mut check!(
-                        VariableDeclaration::desugar_to_decls_inner(elem, new_body),
+                        VariableDeclaration::desugar_to_decls_inner(elem, new_body, config),
                         return err(warnings, errors),
                         warnings,
                         errors
@@ -189,6 +224,77 @@ impl VariableDeclaration {
                 }
                 decls
             }
+            VariableDeclarationLHS::Struct(LHSStruct {
+                struct_name,
+                fields,
+                arity,
+                span,
+            }) => {
+                // Save the value being destructured first, ascribing it the type named by the
+                // pattern so that destructuring a value of some other type is a type error.
+                let name = ident::random_name(body.span(), config);
+                let mut decls = vec![VariableDeclaration {
+                    name: name.clone(),
+                    type_ascription: TypeInfo::Custom {
+                        name: struct_name.clone(),
+                        type_arguments: vec![],
+                    },
+                    type_ascription_span: Some(struct_name.span().clone()),
+                    body: body.clone(),
+                    is_mutable: false,
+                }];
+                for (field, elem) in fields {
+                    let prefix = Box::new(Expression::VariableExpression {
+                        name: name.clone(),
+                        span: body.span(),
+                    });
+                    // the fields of a tuple struct are resolved once its number of fields is known
+                    let new_body = match arity {
+                        Some(_) => Expression::DelayedMatchTypeResolution {
+                            variant: DelayedResolutionVariant::StructField(
+                                DelayedStructFieldResolution {
+                                    exp: prefix,
+                                    struct_name: struct_name.clone(),
+                                    field,
+                                    arity,
+                                },
+                            ),
+                            span: span.clone(),
+                        },
+                        None => Expression::SubfieldExpression {
+                            prefix,
+                            field_to_access: field,
+                            span: span.clone(),
+                        },
+                    };
+                    decls.append(&mut check!(
+                        VariableDeclaration::desugar_to_decls_inner(elem, new_body, config),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    ));
+                }
+                decls
+            }
+            VariableDeclarationLHS::Refutable(scrutinee) => {
+                let bindings = check!(
+                    matcher(&body, &scrutinee),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
+                let (_, bindings) = bindings.unwrap_or_default();
+                bindings
+                    .into_iter()
+                    .map(|(name, body)| VariableDeclaration {
+                        name,
+                        body,
+                        is_mutable: false,
+                        type_ascription: TypeInfo::Unknown,
+                        type_ascription_span: None,
+                    })
+                    .collect()
+            }
         };
         ok(decls, warnings, errors)
     }
@@ -208,27 +314,12 @@ impl VariableDeclarationLHS {
         };
         let inner = pair.into_inner().next().expect("gaurenteed by grammar.");
         let lhs = match inner.as_rule() {
-            Rule::var_name => {
-                let mut parts = inner.into_inner();
-                let maybe_mut_keyword = parts.next().unwrap();
-                let is_mutable = maybe_mut_keyword.as_rule() == Rule::mut_keyword;
-                let name_pair = if is_mutable {
-                    parts.next().unwrap()
-                } else {
-                    maybe_mut_keyword
-                };
-                let name = check!(
-                    ident::parse_from_pair(name_pair, config),
-                    return err(warnings, errors),
-                    warnings,
-                    errors
-                );
-                VariableDeclarationLHS::Name(LHSName {
-                    name,
-                    is_mutable,
-                    span,
-                })
-            }
+            Rule::var_name => check!(
+                VariableDeclarationLHS::parse_var_name(inner, config),
+                return err(warnings, errors),
+                warnings,
+                errors
+            ),
             Rule::var_tuple => {
                 let fields = inner.into_inner().collect::<Vec<_>>();
                 let mut fields_buf = Vec::with_capacity(fields.len());
@@ -245,15 +336,141 @@ impl VariableDeclarationLHS {
                     span,
                 })
             }
+            Rule::var_struct => {
+                let mut parts = inner.into_inner();
+                let struct_name = check!(
+                    ident::parse_from_pair(parts.next().expect("gaurenteed by grammar."), config),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
+                let mut fields_buf = vec![];
+                for field in parts {
+                    let mut field_parts = field.into_inner();
+                    let first = field_parts.next().expect("gaurenteed by grammar.");
+                    let field = match field_parts.next() {
+                        // `field: pattern`
+                        Some(field_lhs) => (
+                            check!(
+                                ident::parse_from_pair(first, config),
+                                return err(warnings, errors),
+                                warnings,
+                                errors
+                            ),
+                            check!(
+                                VariableDeclarationLHS::parse_from_pair(field_lhs, config),
+                                return err(warnings, errors),
+                                warnings,
+                                errors
+                            ),
+                        ),
+                        // the shorthand `field`, which binds a variable of the same name
+                        None => {
+                            let field_lhs = check!(
+                                VariableDeclarationLHS::parse_var_name(first, config),
+                                return err(warnings, errors),
+                                warnings,
+                                errors
+                            );
+                            let name = match &field_lhs {
+                                VariableDeclarationLHS::Name(LHSName { name, .. }) => name.clone(),
+                                _ => unreachable!(),
+                            };
+                            (name, field_lhs)
+                        }
+                    };
+                    fields_buf.push(field);
+                }
+                VariableDeclarationLHS::Struct(LHSStruct {
+                    struct_name,
+                    fields: fields_buf,
+                    arity: None,
+                    span,
+                })
+            }
+            Rule::var_tuple_struct => {
+                let mut parts = inner.into_inner();
+                let struct_name = check!(
+                    ident::parse_from_pair(parts.next().expect("gaurenteed by grammar."), config),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
+                let elems = parts.next().expect("gaurenteed by grammar.").into_inner();
+                let mut fields_buf = vec![];
+                for (pos, elem) in elems.enumerate() {
+                    let elem = check!(
+                        VariableDeclarationLHS::parse_from_pair(elem, config),
+                        return err(warnings, errors),
+                        warnings,
+                        errors
+                    );
+                    fields_buf.push((tuple_struct_field_name(pos, elem.span()), elem));
+                }
+                VariableDeclarationLHS::Struct(LHSStruct {
+                    struct_name,
+                    arity: Some(fields_buf.len()),
+                    fields: fields_buf,
+                    span,
+                })
+            }
+            Rule::var_refutable => {
+                errors.push(CompileError::RefutablePatternInLet { span });
+                let scrutinee = check!(
+                    Scrutinee::parse_from_pair_inner(
+                        inner.into_inner().next().expect("gaurenteed by grammar."),
+                        config
+                    ),
+                    return err(warnings, errors),
+                    warnings,
+                    errors
+                );
+                VariableDeclarationLHS::Refutable(scrutinee)
+            }
             a => unreachable!("Grammar should prevent this case from being {:?}", a),
         };
         ok(lhs, warnings, errors)
     }
 
+    fn parse_var_name(pair: Pair<Rule>, config: Option<&BuildConfig>) -> CompileResult<Self> {
+        assert_eq!(pair.as_rule(), Rule::var_name);
+        let mut warnings = vec![];
+        let mut errors = vec![];
+        let span = Span {
+            span: pair.as_span(),
+            path: config.map(|x| x.path()),
+        };
+        let mut parts = pair.into_inner();
+        let maybe_mut_keyword = parts.next().unwrap();
+        let is_mutable = maybe_mut_keyword.as_rule() == Rule::mut_keyword;
+        let name_pair = if is_mutable {
+            parts.next().unwrap()
+        } else {
+            maybe_mut_keyword
+        };
+        let name = check!(
+            ident::parse_from_pair(name_pair, config),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+        ok(
+            VariableDeclarationLHS::Name(LHSName {
+                name,
+                is_mutable,
+                span,
+            }),
+            warnings,
+            errors,
+        )
+    }
+
     pub(crate) fn span(&self) -> Span {
         match self {
             VariableDeclarationLHS::Name(LHSName { span, .. }) => span.clone(),
             VariableDeclarationLHS::Tuple(LHSTuple { span, .. }) => span.clone(),
+            VariableDeclarationLHS::Struct(LHSStruct { span, .. }) => span.clone(),
+            VariableDeclarationLHS::Refutable(scrutinee) => scrutinee.span(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::{
     error::{err, ok},
+    parse_tree::tuple_struct_field_name,
     CallPath, CompileError, CompileResult, DelayedEnumVariantResolution, DelayedResolutionVariant,
     DelayedStructFieldResolution, DelayedTupleVariantResolution, Expression, Ident, Literal,
     Scrutinee, StructScrutineeField,
@@ -65,6 +66,11 @@ pub fn matcher(exp: &Expression, scrutinee: &Scrutinee) -> CompileResult<Matcher
             fields,
             span,
         } => match_struct(exp, struct_name, fields, span),
+        Scrutinee::TupleStructScrutinee {
+            struct_name,
+            elems,
+            span,
+        } => match_tuple_struct(exp, struct_name, elems, span),
         Scrutinee::EnumScrutinee {
             call_path,
             args,
@@ -126,6 +132,7 @@ fn match_struct(
                 exp: Box::new(exp.clone()),
                 struct_name: struct_name.to_owned(),
                 field: field_name.clone(),
+                arity: None,
             }),
             span: span.clone(),
         };
@@ -156,6 +163,44 @@ fn match_struct(
     ok(Some((match_req_map, match_impl_map)), warnings, errors)
 }
 
+fn match_tuple_struct(
+    exp: &Expression,
+    struct_name: &Ident,
+    elems: &[Scrutinee],
+    span: &Span,
+) -> CompileResult<MatcherResult> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+    let mut match_req_map = vec![];
+    let mut match_impl_map = vec![];
+    for (pos, elem) in elems.iter().enumerate() {
+        let delayed_resolution_exp = Expression::DelayedMatchTypeResolution {
+            variant: DelayedResolutionVariant::StructField(DelayedStructFieldResolution {
+                exp: Box::new(exp.clone()),
+                struct_name: struct_name.to_owned(),
+                field: tuple_struct_field_name(pos, elem.span()),
+                arity: Some(elems.len()),
+            }),
+            span: span.clone(),
+        };
+        let new_matches = check!(
+            matcher(&delayed_resolution_exp, elem),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+        match new_matches {
+            Some((mut new_match_req_map, mut new_match_impl_map)) => {
+                match_req_map.append(&mut new_match_req_map);
+                match_impl_map.append(&mut new_match_impl_map);
+            }
+            None => return ok(None, warnings, errors),
+        }
+    }
+
+    ok(Some((match_req_map, match_impl_map)), warnings, errors)
+}
+
 fn match_enum(
     exp: &Expression,
     call_path: &CallPath,
@@ -25,7 +25,7 @@ pub(crate) use asm::*;
 pub(crate) use match_branch::MatchBranch;
 pub(crate) use match_condition::CatchAll;
 pub(crate) use match_condition::MatchCondition;
-use matcher::matcher;
+pub(crate) use matcher::matcher;
 pub(crate) use method_name::MethodName;
 pub(crate) use scrutinee::{Scrutinee, StructScrutineeField};
 pub(crate) use unary_op::UnaryOp;
@@ -170,6 +170,9 @@ pub struct DelayedStructFieldResolution {
     pub exp: Box<Expression>,
     pub struct_name: Ident,
     pub field: Ident,
+    /// The number of fields of the tuple struct pattern the field is matched by, which must be
+    /// the number of fields the struct has.
+    pub arity: Option<usize>,
 }
 
 /// During type checking, this gets replaced with enum arg access.
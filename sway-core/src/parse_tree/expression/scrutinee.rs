@@ -32,6 +32,11 @@ pub enum Scrutinee {
         fields: Vec<StructScrutineeField>,
         span: Span,
     },
+    TupleStructScrutinee {
+        struct_name: Ident,
+        elems: Vec<Scrutinee>,
+        span: Span,
+    },
     EnumScrutinee {
         call_path: CallPath,
         args: Vec<Scrutinee>,
@@ -57,6 +62,7 @@ impl Scrutinee {
             Scrutinee::Unit { span } => span.clone(),
             Scrutinee::Variable { span, .. } => span.clone(),
             Scrutinee::StructScrutinee { span, .. } => span.clone(),
+            Scrutinee::TupleStructScrutinee { span, .. } => span.clone(),
             Scrutinee::EnumScrutinee { span, .. } => span.clone(),
             Scrutinee::Tuple { span, .. } => span.clone(),
         }
@@ -106,6 +112,12 @@ impl Scrutinee {
                 warnings,
                 errors
             ),
+            Rule::tuple_struct_scrutinee => check!(
+                Self::parse_from_pair_tuple_struct(scrutinee, config, span),
+                return err(warnings, errors),
+                warnings,
+                errors
+            ),
             Rule::enum_scrutinee => check!(
                 Self::parse_from_pair_enum(scrutinee, config, span),
                 return err(warnings, errors),
@@ -240,6 +252,38 @@ impl Scrutinee {
         ok(scrutinee, warnings, errors)
     }
 
+    fn parse_from_pair_tuple_struct(
+        scrutinee: Pair<Rule>,
+        config: Option<&BuildConfig>,
+        span: Span,
+    ) -> CompileResult<Self> {
+        let mut warnings = vec![];
+        let mut errors = vec![];
+        let mut parts = scrutinee.into_inner();
+        let struct_name = check!(
+            ident::parse_from_pair(parts.next().unwrap(), config),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+        let mut elems = vec![];
+        for part in parts.next().unwrap().into_inner() {
+            elems.push(check!(
+                Scrutinee::parse_from_pair(part, config),
+                return err(warnings, errors),
+                warnings,
+                errors
+            ));
+        }
+
+        let scrutinee = Scrutinee::TupleStructScrutinee {
+            struct_name,
+            elems,
+            span,
+        };
+        ok(scrutinee, warnings, errors)
+    }
+
     fn parse_from_pair_enum(
         scrutinee: Pair<Rule>,
         config: Option<&BuildConfig>,
@@ -0,0 +1,210 @@
+use crate::{
+    build_config::BuildConfig,
+    error::{err, ok, CompileResult},
+    parse_tree::{ident, Expression, Literal, MethodName, Op, OpVariant, Reassignment},
+    parser::Rule,
+    AstNode, AstNodeContent, CodeBlock, Declaration, TypeInfo, VariableDeclaration, WhileLoop,
+};
+
+use sway_types::{ident::Ident, span::Span};
+
+use pest::iterators::Pair;
+
+/// A parsed for loop over the elements of a vector. Contains the name of the loop variable, the
+/// vector from an [Expression], and the `body` from a [CodeBlock].
+#[derive(Debug, Clone)]
+pub struct ForLoop {
+    pub(crate) var_name: Ident,
+    pub(crate) iterable: Expression,
+    pub(crate) body: CodeBlock,
+    pub(crate) span: Span,
+}
+
+impl ForLoop {
+    pub(crate) fn parse_from_pair(
+        pair: Pair<Rule>,
+        config: Option<&BuildConfig>,
+    ) -> CompileResult<Self> {
+        let path = config.map(|c| c.path());
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        let span = Span {
+            span: pair.as_span(),
+            path: path.clone(),
+        };
+        let mut iter = pair.into_inner();
+        let _for_keyword = iter.next().unwrap();
+        let var_name = iter.next().unwrap();
+        let _in_keyword = iter.next().unwrap();
+        let iterable = iter.next().unwrap();
+        let body = iter.next().unwrap();
+        let whole_block_span = Span {
+            span: body.as_span(),
+            path: path.clone(),
+        };
+
+        let var_name = check!(
+            ident::parse_from_pair(var_name, config),
+            return err(warnings, errors),
+            warnings,
+            errors
+        );
+
+        let iterable = check!(
+            Expression::parse_from_pair(iterable.clone(), config),
+            Expression::Tuple {
+                fields: vec![],
+                span: Span {
+                    span: iterable.as_span(),
+                    path,
+                }
+            },
+            warnings,
+            errors
+        );
+
+        let body = check!(
+            CodeBlock::parse_from_pair(body, config),
+            CodeBlock {
+                contents: Default::default(),
+                whole_block_span,
+            },
+            warnings,
+            errors
+        );
+
+        ok(
+            ForLoop {
+                var_name,
+                iterable,
+                body,
+                span,
+            },
+            warnings,
+            errors,
+        )
+    }
+
+    /// Desugars the loop into a [WhileLoop] which indexes into the vector, bound to a fresh name
+    /// in a code block of its own so that it is only evaluated once:
+    ///
+    /// ```ignore
+    /// for x in v {
+    ///     ...
+    /// }
+    /// ```
+    ///
+    /// becomes
+    ///
+    /// ```ignore
+    /// {
+    ///     let __vec = v;
+    ///     let mut __index = 0;
+    ///     while __index < __vec.len() {
+    ///         let x = __vec.get(__index);
+    ///         __index = __index + 1;
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    pub(crate) fn desugar(self, config: Option<&BuildConfig>) -> Expression {
+        let ForLoop {
+            var_name,
+            iterable,
+            body,
+            span,
+        } = self;
+        let iterable_span = iterable.span();
+        let vec_name = ident::random_name(iterable_span.clone(), config);
+        let index_name = ident::random_name(span.clone(), config);
+        let variable = |name: &Ident| Expression::VariableExpression {
+            name: name.clone(),
+            span: name.span().clone(),
+        };
+        let vec_method = |method_name: &'static str, mut arguments: Vec<Expression>| {
+            arguments.insert(0, variable(&vec_name));
+            Expression::MethodApplication {
+                method_name: MethodName::FromModule {
+                    method_name: Ident::new_with_override(method_name, iterable_span.clone()),
+                },
+                arguments,
+                span: iterable_span.clone(),
+            }
+        };
+        let core_op = |op_variant: OpVariant, arguments: Vec<Expression>| {
+            Expression::core_ops(
+                Op {
+                    op_variant,
+                    span: span.clone(),
+                },
+                arguments,
+                span.clone(),
+            )
+        };
+        let declaration = |declaration: Declaration, span: &Span| AstNode {
+            content: AstNodeContent::Declaration(declaration),
+            span: span.clone(),
+        };
+        let let_binding = |name: &Ident, body: Expression, is_mutable: bool| {
+            Declaration::VariableDeclaration(VariableDeclaration {
+                name: name.clone(),
+                type_ascription: TypeInfo::Unknown,
+                type_ascription_span: None,
+                body,
+                is_mutable,
+            })
+        };
+        let index_literal = |value: u64| Expression::Literal {
+            value: Literal::U64(value),
+            span: span.clone(),
+        };
+
+        let mut loop_contents = vec![
+            declaration(
+                let_binding(
+                    &var_name,
+                    vec_method("get", vec![variable(&index_name)]),
+                    false,
+                ),
+                var_name.span(),
+            ),
+            declaration(
+                Declaration::Reassignment(Reassignment {
+                    lhs: Box::new(variable(&index_name)),
+                    rhs: core_op(
+                        OpVariant::Add,
+                        vec![variable(&index_name), index_literal(1)],
+                    ),
+                    span: span.clone(),
+                }),
+                &span,
+            ),
+        ];
+        loop_contents.extend(body.contents);
+        let while_loop = WhileLoop {
+            condition: core_op(
+                OpVariant::LessThan,
+                vec![variable(&index_name), vec_method("len", vec![])],
+            ),
+            body: CodeBlock {
+                contents: loop_contents,
+                whole_block_span: body.whole_block_span,
+            },
+        };
+
+        Expression::CodeBlock {
+            contents: CodeBlock {
+                contents: vec![
+                    declaration(let_binding(&vec_name, iterable, false), &iterable_span),
+                    declaration(let_binding(&index_name, index_literal(0), true), &span),
+                    AstNode {
+                        content: AstNodeContent::WhileLoop(while_loop),
+                        span: span.clone(),
+                    },
+                ],
+                whole_block_span: span.clone(),
+            },
+            span,
+        }
+    }
+}
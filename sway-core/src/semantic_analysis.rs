@@ -1,15 +1,21 @@
 //! Type checking for Sway.
 pub mod ast_node;
+mod items;
 mod namespace;
 mod node_dependencies;
+mod symbols;
 mod syntax_tree;
 pub(crate) mod type_check_arguments;
 pub(crate) use ast_node::{TypedAstNode, TypedAstNodeContent, TypedExpression};
 pub use ast_node::{TypedConstantDeclaration, TypedDeclaration, TypedFunctionDeclaration};
+pub use items::{module_items, submodules, ModuleItem};
 pub use namespace::Namespace;
 pub use namespace::*;
+pub use symbols::{
+    import_paths, items_at_path, members_of_type, InlayHint, InlayHintKind, Symbol, SymbolKind,
+};
 pub use syntax_tree::TreeType;
-pub use syntax_tree::TypedParseTree;
+pub use syntax_tree::{AbiSelector, TypedParseTree};
 pub use type_check_arguments::*;
 
 const ERROR_RECOVERY_DECLARATION: TypedDeclaration = TypedDeclaration::ErrorRecovery;
@@ -24,6 +24,7 @@ pub enum TypedDeclaration {
         type_implementing_for: TypeInfo,
     },
     AbiDeclaration(TypedAbiDeclaration),
+    TypeAliasDeclaration(TypedTypeAliasDeclaration),
     // If type parameters are defined for a function, they are put in the namespace just for
     // the body of that function.
     GenericTypeForFunctionScope {
@@ -50,6 +51,7 @@ impl TypedDeclaration {
             } => {
                 methods.iter_mut().for_each(|x| x.copy_types(type_mapping));
             }
+            TypeAliasDeclaration(ref mut alias_decl) => alias_decl.copy_types(type_mapping),
             // generics in an ABI is unsupported by design
             AbiDeclaration(..) => (),
             GenericTypeForFunctionScope { .. } | ErrorRecovery => (),
@@ -71,6 +73,7 @@ impl TypedDeclaration {
             Reassignment(_) => "reassignment",
             ImplTrait { .. } => "impl trait",
             AbiDeclaration(..) => "abi",
+            TypeAliasDeclaration(..) => "type alias",
             GenericTypeForFunctionScope { .. } => "generic type parameter",
             ErrorRecovery => "error",
         }
@@ -102,6 +105,10 @@ impl TypedDeclaration {
                         .collect(),
                 }),
                 TypedDeclaration::Reassignment(TypedReassignment { rhs, .. }) => rhs.return_type,
+                TypedDeclaration::TypeAliasDeclaration(TypedTypeAliasDeclaration {
+                    r#type,
+                    ..
+                }) => *r#type,
                 TypedDeclaration::GenericTypeForFunctionScope { name } => {
                     insert_type(TypeInfo::UnknownGeneric { name: name.clone() })
                 }
@@ -134,6 +141,7 @@ impl TypedDeclaration {
                 .iter()
                 .fold(lhs[0].span(), |acc, this| join_spans(acc, this.span())),
             AbiDeclaration(TypedAbiDeclaration { span, .. }) => span.clone(),
+            TypeAliasDeclaration(TypedTypeAliasDeclaration { span, .. }) => span.clone(),
             ImplTrait { span, .. } => span.clone(),
             ErrorRecovery | GenericTypeForFunctionScope { .. } => {
                 unreachable!("No span exists for these ast node types")
@@ -170,6 +178,9 @@ impl TypedDeclaration {
                     name.as_str().into(),
                 TypedDeclaration::EnumDeclaration(TypedEnumDeclaration { name, .. }) =>
                     name.as_str().into(),
+                TypedDeclaration::TypeAliasDeclaration(TypedTypeAliasDeclaration {
+                    name, ..
+                }) => name.as_str().into(),
                 TypedDeclaration::Reassignment(TypedReassignment { lhs, .. }) => lhs
                     .iter()
                     .map(|x| x.name.as_str())
@@ -195,7 +206,8 @@ impl TypedDeclaration {
             | ConstantDeclaration(TypedConstantDeclaration { visibility, .. })
             | FunctionDeclaration(TypedFunctionDeclaration { visibility, .. })
             | TraitDeclaration(TypedTraitDeclaration { visibility, .. })
-            | StructDeclaration(TypedStructDeclaration { visibility, .. }) => *visibility,
+            | StructDeclaration(TypedStructDeclaration { visibility, .. })
+            | TypeAliasDeclaration(TypedTypeAliasDeclaration { visibility, .. }) => *visibility,
         }
     }
 }
@@ -218,6 +230,7 @@ pub struct TypedStructDeclaration {
     pub(crate) fields: Vec<TypedStructField>,
     pub(crate) type_parameters: Vec<TypeParameter>,
     pub(crate) visibility: Visibility,
+    pub(crate) kind: StructKind,
 }
 
 impl TypedStructDeclaration {
@@ -377,6 +390,48 @@ impl TypedConstantDeclaration {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct TypedTypeAliasDeclaration {
+    pub(crate) name: Ident,
+    pub(crate) type_parameters: Vec<TypeParameter>,
+    /// The aliased type, resolved where the alias was declared. For a generic alias the type
+    /// parameters appear in here as [TypeInfo::UnknownGeneric]s.
+    pub(crate) r#type: TypeId,
+    pub(crate) visibility: Visibility,
+    pub(crate) span: Span,
+}
+
+impl TypedTypeAliasDeclaration {
+    /// Creates a fresh copy of the aliased type for a single use of the alias, substituting
+    /// `type_arguments` for the alias's type parameters.
+    pub(crate) fn instantiate(&self, type_arguments: &[TypeId]) -> TypeId {
+        let type_mapping = self
+            .type_parameters
+            .iter()
+            .cloned()
+            .zip(type_arguments.iter().cloned())
+            .collect::<Vec<_>>();
+        let ty = match look_up_type_id(self.r#type).matches_type_parameter(&type_mapping) {
+            Some(matching_id) => matching_id,
+            None => insert_type(look_up_type_id(self.r#type)),
+        };
+        insert_type(TypeInfo::Alias {
+            name: self.name.clone(),
+            ty,
+        })
+    }
+
+    pub(crate) fn copy_types(&mut self, type_mapping: &[(TypeParameter, TypeId)]) {
+        self.r#type = if let Some(matching_id) =
+            look_up_type_id(self.r#type).matches_type_parameter(type_mapping)
+        {
+            insert_type(TypeInfo::Ref(matching_id))
+        } else {
+            insert_type(look_up_type_id_raw(self.r#type))
+        };
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TypedTraitDeclaration {
     pub(crate) name: Ident,
@@ -471,6 +526,7 @@ impl TypedTraitFn {
             visibility: Visibility::Public,
             type_parameters: vec![],
             is_contract_call: mode == Mode::ImplAbiFn,
+            is_test: false,
         }
     }
 }
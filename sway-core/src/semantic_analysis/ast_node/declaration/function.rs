@@ -35,6 +35,8 @@ pub struct TypedFunctionDeclaration {
     /// whether this function exists in another contract and requires a call to it or not
     pub(crate) is_contract_call: bool,
     pub(crate) purity: Purity,
+    /// Whether this function is a unit test, marked with `#[test]`.
+    pub(crate) is_test: bool,
 }
 
 impl TypedFunctionDeclaration {
@@ -65,9 +67,17 @@ impl TypedFunctionDeclaration {
             return_type_span,
             visibility,
             purity,
+            is_test,
             ..
         } = fn_decl.clone();
         opts.purity = purity;
+        // tests are run on their own, so there is nothing to pass them
+        if is_test && (!parameters.is_empty() || !type_parameters.is_empty()) {
+            errors.push(CompileError::TestFunctionWithParameters {
+                name: name.as_str().to_string(),
+                span: span.clone(),
+            });
+        }
         // insert type parameters as Unknown types
         let type_mapping = insert_type_parameters(&type_parameters);
         let return_type =
@@ -76,10 +86,8 @@ impl TypedFunctionDeclaration {
             } else {
                 namespace
                     .resolve_type_with_self(return_type, self_type)
-                    .unwrap_or_else(|_| {
-                        errors.push(CompileError::UnknownType {
-                            span: return_type_span.clone(),
-                        });
+                    .unwrap_or_else(|err| {
+                        errors.push(err);
                         insert_type(TypeInfo::ErrorRecovery)
                     })
             };
@@ -89,21 +97,14 @@ impl TypedFunctionDeclaration {
         type_parameters.iter().for_each(|param| {
             namespace.insert(param.name_ident.clone(), param.into());
         });
-        for FunctionParameter {
-            name,
-            r#type,
-            type_span,
-        } in parameters.clone()
-        {
+        for FunctionParameter { name, r#type, .. } in parameters.clone() {
             let r#type = if let Some(matching_id) = r#type.matches_type_parameter(&type_mapping) {
                 insert_type(TypeInfo::Ref(matching_id))
             } else {
                 namespace
                     .resolve_type_with_self(r#type, self_type)
-                    .unwrap_or_else(|_| {
-                        errors.push(CompileError::UnknownType {
-                            span: type_span.clone(),
-                        });
+                    .unwrap_or_else(|err| {
+                        errors.push(err);
                         insert_type(TypeInfo::ErrorRecovery)
                     })
             };
@@ -167,10 +168,8 @@ impl TypedFunctionDeclaration {
                     } else {
                         namespace
                             .resolve_type_with_self(r#type, self_type)
-                            .unwrap_or_else(|_| {
-                                errors.push(CompileError::UnknownType {
-                                    span: type_span.clone(),
-                                });
+                            .unwrap_or_else(|err| {
+                                errors.push(err);
                                 insert_type(TypeInfo::ErrorRecovery)
                             })
                     },
@@ -266,6 +265,7 @@ impl TypedFunctionDeclaration {
                 visibility,
                 // if this is for a contract, then it is a contract call
                 is_contract_call: mode == Mode::ImplAbiFn,
+                is_test,
                 purity,
             },
             warnings,
@@ -471,6 +471,7 @@ impl TypedFunctionDeclaration {
 #[test]
 fn test_function_selector_behavior() {
     use crate::type_engine::IntegerBits;
+    let _type_engine = crate::type_engine::TypeEngine::new().enter();
     let decl = TypedFunctionDeclaration {
         purity: Default::default(),
         name: Ident::new_with_override(
@@ -500,6 +501,7 @@ fn test_function_selector_behavior() {
         },
         visibility: Visibility::Public,
         is_contract_call: false,
+        is_test: false,
     };
 
     let selector_text = match decl.to_selector_name().value {
@@ -567,6 +569,7 @@ fn test_function_selector_behavior() {
         },
         visibility: Visibility::Public,
         is_contract_call: false,
+        is_test: false,
     };
 
     let selector_text = match decl.to_selector_name().value {
@@ -13,6 +13,8 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
 mod method_application;
+mod string_operation;
+mod vec_operation;
 use crate::type_engine::TypeId;
 use method_application::type_check_method_application;
 
@@ -332,10 +334,8 @@ impl TypedExpression {
 
         typed_expression.return_type = namespace
             .resolve_type_with_self(look_up_type_id(typed_expression.return_type), self_type)
-            .unwrap_or_else(|_| {
-                errors.push(CompileError::UnknownType {
-                    span: expr_span.clone(),
-                });
+            .unwrap_or_else(|err| {
+                errors.push(err);
                 insert_type(TypeInfo::ErrorRecovery)
             });
 
@@ -425,6 +425,21 @@ impl TypedExpression {
                 expression: TypedExpressionVariant::VariableExpression { name: name.clone() },
                 span,
             },
+            // a unit struct is constructed by just naming it
+            Some(TypedDeclaration::StructDeclaration(decl)) if decl.kind == StructKind::Unit => {
+                TypedExpression {
+                    return_type: insert_type(TypeInfo::Struct {
+                        name: decl.name.as_str().to_string(),
+                        fields: vec![],
+                    }),
+                    is_constant: IsConstant::No,
+                    expression: TypedExpressionVariant::StructExpression {
+                        struct_name: decl.name,
+                        fields: vec![],
+                    },
+                    span,
+                }
+            }
             Some(a) => {
                 errors.push(CompileError::NotAVariable {
                     name: name.span().as_str().to_string(),
@@ -447,7 +462,7 @@ impl TypedExpression {
     #[allow(clippy::type_complexity)]
     fn type_check_function_application(
         arguments: TypeCheckArguments<'_, (CallPath, Vec<Expression>, Vec<(TypeInfo, Span)>)>,
-        _span: Span,
+        app_span: Span,
     ) -> CompileResult<TypedExpression> {
         let TypeCheckArguments {
             checkee: (name, arguments, type_arguments),
@@ -468,7 +483,35 @@ impl TypedExpression {
             warnings,
             errors
         );
+        // a tuple struct is constructed like a function call, with the arguments being its
+        // positional fields
+        if let TypedDeclaration::StructDeclaration(decl) = &function_declaration {
+            if decl.kind == StructKind::Tuple {
+                let fields = arguments
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, value)| StructExpressionField {
+                        name: tuple_struct_field_name(i, value.span()),
+                        span: value.span(),
+                        value,
+                    })
+                    .collect();
+                return Self::type_check_struct_expression_with_definition(
+                    app_span,
+                    decl.clone(),
+                    fields,
+                    namespace,
+                    crate_namespace,
+                    self_type,
+                    build_config,
+                    dead_code_graph,
+                    dependency_graph,
+                    opts,
+                );
+            }
+        }
         let TypedFunctionDeclaration {
+            name: decl_name,
             parameters,
             return_type,
             body,
@@ -580,6 +623,7 @@ impl TypedExpression {
                     arguments: typed_call_arguments,
                     name,
                     function_body: body,
+                    decl_name,
                     selector: None, // regular functions cannot be in a contract call; only methods
                 },
                 span,
@@ -852,14 +896,8 @@ impl TypedExpression {
         let mut errors = vec![];
         let return_type = namespace
             .resolve_type_with_self(asm.return_type.clone(), self_type)
-            .unwrap_or_else(|_| {
-                errors.push(CompileError::UnknownType {
-                    span: asm
-                        .returns
-                        .clone()
-                        .map(|x| x.1)
-                        .unwrap_or_else(|| asm.whole_block_span.clone()),
-                });
+            .unwrap_or_else(|err| {
+                errors.push(err);
                 insert_type(TypeInfo::ErrorRecovery)
             });
         // type check the initializers
@@ -919,28 +957,59 @@ impl TypedExpression {
         dependency_graph: &mut HashMap<String, HashSet<String>>,
         opts: TCOpts,
     ) -> CompileResult<TypedExpression> {
-        let mut warnings = vec![];
-        let mut errors = vec![];
-        let mut typed_fields_buf = vec![];
-
         let definition: TypedStructDeclaration =
             match namespace.clone().get_symbol(&struct_name).value {
                 Some(TypedDeclaration::StructDeclaration(st)) => st,
                 Some(_) => {
-                    errors.push(CompileError::DeclaredNonStructAsStruct {
-                        name: struct_name.clone(),
-                        span,
-                    });
-                    return err(warnings, errors);
+                    return err(
+                        vec![],
+                        vec![CompileError::DeclaredNonStructAsStruct {
+                            name: struct_name.clone(),
+                            span,
+                        }],
+                    );
                 }
                 None => {
-                    errors.push(CompileError::StructNotFound {
-                        name: struct_name.clone(),
-                        span,
-                    });
-                    return err(warnings, errors);
+                    return err(
+                        vec![],
+                        vec![CompileError::StructNotFound {
+                            name: struct_name.clone(),
+                            span,
+                        }],
+                    );
                 }
             };
+        Self::type_check_struct_expression_with_definition(
+            span,
+            definition,
+            fields,
+            namespace,
+            crate_namespace,
+            self_type,
+            build_config,
+            dead_code_graph,
+            dependency_graph,
+            opts,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn type_check_struct_expression_with_definition(
+        span: Span,
+        definition: TypedStructDeclaration,
+        fields: Vec<StructExpressionField>,
+        namespace: crate::semantic_analysis::NamespaceRef,
+        crate_namespace: NamespaceRef,
+        self_type: TypeId,
+        build_config: &BuildConfig,
+        dead_code_graph: &mut ControlFlowGraph,
+        dependency_graph: &mut HashMap<String, HashSet<String>>,
+        opts: TCOpts,
+    ) -> CompileResult<TypedExpression> {
+        let mut warnings = vec![];
+        let mut errors = vec![];
+        let mut typed_fields_buf = vec![];
+
         // if this is a generic struct, i.e. it has some type
         // parameters, monomorphize it before unifying the
         // types
@@ -1097,7 +1166,7 @@ impl TypedExpression {
                 resolved_type_of_parent: parent.return_type,
                 prefix: Box::new(parent),
                 field_to_access: field.clone(),
-                field_to_access_span: span.clone(),
+                field_to_access_span: field_to_access.span().clone(),
             },
             return_type: field.r#type,
             is_constant: IsConstant::No,
@@ -1133,7 +1202,7 @@ impl TypedExpression {
             let field_type_id = field_type_ids_opt
                 .as_ref()
                 .map(|field_type_ids| field_type_ids[i])
-                .unwrap_or_default();
+                .unwrap_or_else(|| insert_type(TypeInfo::Unknown));
             let field_span = field.span();
             let typed_field = check!(
                 TypedExpression::type_check(TypeCheckArguments {
@@ -1203,6 +1272,40 @@ impl TypedExpression {
             warnings,
             errors
         );
+        // the fields of a tuple struct are accessed by position, just like the elements of a tuple
+        if let TypeInfo::Struct { name, fields } = look_up_type_id(parent.return_type) {
+            let field = match fields
+                .iter()
+                .find(|OwnedTypedStructField { name, .. }| *name == index.to_string())
+            {
+                Some(field) => field.clone(),
+                None => {
+                    errors.push(CompileError::FieldNotFound {
+                        field_name: tuple_struct_field_name(index, index_span.clone()),
+                        available_fields: fields
+                            .iter()
+                            .map(|OwnedTypedStructField { name, .. }| name.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        struct_name: name,
+                        span: index_span,
+                    });
+                    return err(warnings, errors);
+                }
+            };
+            let exp = TypedExpression {
+                expression: TypedExpressionVariant::StructFieldAccess {
+                    resolved_type_of_parent: parent.return_type,
+                    prefix: Box::new(parent),
+                    field_to_access: field.clone(),
+                    field_to_access_span: index_span,
+                },
+                return_type: field.r#type,
+                is_constant: IsConstant::No,
+                span,
+            };
+            return ok(exp, warnings, errors);
+        }
         let mut tuple_elem_to_access = None;
         let tuple_elems = check!(
             namespace.get_tuple_elems(parent.return_type, parent.span.as_str(), &parent.span),
@@ -1822,6 +1925,7 @@ impl TypedExpression {
                 exp,
                 struct_name,
                 field,
+                arity,
             }) => {
                 let args = TypeCheckArguments {
                     checkee: *exp,
@@ -1860,6 +1964,19 @@ impl TypedExpression {
                     let exp = error_recovery_expr(span);
                     return ok(exp, warnings, errors);
                 }
+                match arity {
+                    Some(arity) if arity != struct_fields.len() => {
+                        errors.push(CompileError::TupleStructPatternArity {
+                            struct_name: struct_name.as_str().to_string(),
+                            expected: struct_fields.len(),
+                            found: arity,
+                            span: span.clone(),
+                        });
+                        let exp = error_recovery_expr(span);
+                        return ok(exp, warnings, errors);
+                    }
+                    _ => (),
+                }
                 let mut field_to_access = None;
                 for struct_field in struct_fields.iter() {
                     if struct_field.name == *field.as_str() {
@@ -2000,9 +2117,10 @@ mod tests {
 
     use super::*;
 
-    fn do_type_check(expr: Expression, type_annotation: TypeId) -> CompileResult<TypedExpression> {
-        let namespace = create_module();
-        let self_type = insert_type(TypeInfo::Unknown);
+    fn do_type_check(
+        expr: Expression,
+        type_annotation: impl FnOnce() -> TypeId,
+    ) -> CompileResult<TypedExpression> {
         let build_config = BuildConfig {
             file_name: Arc::new("test.sw".into()),
             dir_of_code: Arc::new("".into()),
@@ -2011,8 +2129,15 @@ mod tests {
             print_intermediate_asm: false,
             print_finalized_asm: false,
             print_ir: false,
+            optimize: true,
+            checked_arithmetic: true,
             generated_names: Arc::new(Mutex::new(vec![])),
+            module_cache: None,
+            type_engine: TypeEngine::new(),
         };
+        let _type_engine = build_config.type_engine.enter();
+        let namespace = create_module();
+        let self_type = insert_type(TypeInfo::Unknown);
         let mut dead_code_graph: ControlFlowGraph = Default::default();
         let mut dependency_graph = HashMap::new();
 
@@ -2020,7 +2145,7 @@ mod tests {
             checkee: expr,
             namespace,
             crate_namespace: namespace,
-            return_type_annotation: type_annotation,
+            return_type_annotation: type_annotation(),
             help_text: Default::default(),
             self_type,
             build_config: &build_config,
@@ -2032,10 +2157,9 @@ mod tests {
     }
 
     fn do_type_check_for_boolx2(expr: Expression) -> CompileResult<TypedExpression> {
-        do_type_check(
-            expr,
-            insert_type(TypeInfo::Array(insert_type(TypeInfo::Boolean), 2)),
-        )
+        do_type_check(expr, || {
+            insert_type(TypeInfo::Array(insert_type(TypeInfo::Boolean), 2))
+        })
     }
 
     #[test]
@@ -2067,8 +2191,8 @@ mod tests {
                              expected,
                              received,
                              ..
-                         }) if expected.friendly_type_str() == "bool"
-                                && received.friendly_type_str() == "u64"));
+                         }) if expected == "bool"
+                                && received == "u64"));
     }
 
     #[test]
@@ -2100,15 +2224,15 @@ mod tests {
                              expected,
                              received,
                              ..
-                         }) if expected.friendly_type_str() == "u64"
-                                && received.friendly_type_str() == "bool"));
+                         }) if expected == "u64"
+                                && received == "bool"));
         assert!(matches!(&comp_res.errors[1],
                          CompileError::TypeError(TypeError::MismatchedType {
                              expected,
                              received,
                              ..
-                         }) if expected.friendly_type_str() == "[bool; 2]"
-                                && received.friendly_type_str() == "[u64; 2]"));
+                         }) if expected == "[bool; 2]"
+                                && received == "[u64; 2]"));
     }
 
     #[test]
@@ -2144,8 +2268,8 @@ mod tests {
                              expected,
                              received,
                              ..
-                         }) if expected.friendly_type_str() == "[bool; 2]"
-                                && received.friendly_type_str() == "[bool; 3]"));
+                         }) if expected == "[bool; 2]"
+                                && received == "[bool; 3]"));
     }
 
     #[test]
@@ -2160,10 +2284,9 @@ mod tests {
             span: empty_span,
         };
 
-        let comp_res = do_type_check(
-            expr,
-            insert_type(TypeInfo::Array(insert_type(TypeInfo::Boolean), 0)),
-        );
+        let comp_res = do_type_check(expr, || {
+            insert_type(TypeInfo::Array(insert_type(TypeInfo::Boolean), 0))
+        });
         assert!(comp_res.warnings.is_empty() && comp_res.errors.is_empty());
     }
 }
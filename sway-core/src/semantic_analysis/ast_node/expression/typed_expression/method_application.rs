@@ -1,3 +1,5 @@
+use super::string_operation::{find_string_operation, type_check_string_operation};
+use super::vec_operation::{find_vec_operation, type_check_vec_operation};
 use super::*;
 use crate::build_config::BuildConfig;
 use crate::control_flow_analysis::ControlFlowGraph;
@@ -44,6 +46,58 @@ pub(crate) fn type_check_method_application(
         ));
     }
 
+    // `Vec` and `String` only name the built in types if no declaration in scope has their name.
+    let method_name = match method_name {
+        MethodName::FromType {
+            call_path,
+            type_name: Some(type_name),
+            is_absolute,
+        } => {
+            let type_name = match namespace.resolve_builtin_type(&type_name, self_type) {
+                Some(Ok(type_id)) => look_up_type_id(type_id),
+                Some(Err(e)) => {
+                    errors.push(e);
+                    return err(warnings, errors);
+                }
+                None => type_name,
+            };
+            MethodName::FromType {
+                call_path,
+                type_name: Some(type_name),
+                is_absolute,
+            }
+        }
+        method_name => method_name,
+    };
+
+    // The methods on `Vec<T>` are built into the compiler rather than declared anywhere.
+    if let Some((op, elem_type)) = find_vec_operation(&method_name, &args_buf) {
+        let method_ident = match method_name {
+            MethodName::FromType { call_path, .. } => call_path.suffix,
+            MethodName::FromModule { method_name } => method_name,
+        };
+        return type_check_vec_operation(
+            op,
+            elem_type,
+            method_ident,
+            args_buf.into(),
+            span,
+            namespace,
+            self_type,
+        )
+        .flat_map(|exp| ok(exp, warnings, errors));
+    }
+
+    // As are the methods on `String`, along with its comparison operators.
+    if let Some(op) = find_string_operation(&method_name, &args_buf) {
+        let method_ident = match method_name {
+            MethodName::FromType { call_path, .. } => call_path.suffix,
+            MethodName::FromModule { method_name } => method_name,
+        };
+        return type_check_string_operation(op, method_ident, args_buf.into(), span, self_type)
+            .flat_map(|exp| ok(exp, warnings, errors));
+    }
+
     let method = match method_name {
         MethodName::FromType {
             ref type_name,
@@ -159,6 +213,7 @@ pub(crate) fn type_check_method_application(
                     },
                     arguments: args_and_names,
                     function_body: method.body.clone(),
+                    decl_name: method.name.clone(),
                     selector: if method.is_contract_call {
                         let contract_address = match contract_caller
                             .map(|x| crate::type_engine::look_up_type_id(x.return_type))
@@ -236,6 +291,7 @@ pub(crate) fn type_check_method_application(
                     name: call_path.clone(),
                     arguments: args_and_names,
                     function_body: method.body.clone(),
+                    decl_name: method.name.clone(),
                     selector: if method.is_contract_call {
                         let contract_address = match contract_caller
                             .map(|x| crate::type_engine::look_up_type_id(x.return_type))
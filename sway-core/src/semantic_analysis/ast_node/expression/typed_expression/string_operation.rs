@@ -0,0 +1,135 @@
+use super::*;
+use crate::parse_tree::MethodName;
+use std::collections::VecDeque;
+
+/// If `method_name` refers to one of the built-in methods on `String`, or to `==` or `!=` between
+/// two strings, returns the operation.
+pub(crate) fn find_string_operation(
+    method_name: &MethodName,
+    args_buf: &VecDeque<TypedExpression>,
+) -> Option<StringOp> {
+    let first_arg_is_string = matches!(
+        args_buf.front().map(|arg| look_up_type_id(arg.return_type)),
+        Some(TypeInfo::DynamicString)
+    );
+    match method_name {
+        MethodName::FromType {
+            type_name: Some(TypeInfo::DynamicString),
+            call_path,
+            ..
+        } if call_path.prefixes.is_empty() => StringOp::from_method_name(call_path.suffix.as_str()),
+        // Operators are desugared to calls to `core::ops`.
+        MethodName::FromType {
+            type_name: None,
+            call_path,
+            ..
+        } if first_arg_is_string
+            && call_path.prefixes.len() == 2
+            && call_path.prefixes[0].as_str() == "core"
+            && call_path.prefixes[1].as_str() == "ops" =>
+        {
+            match StringOp::from_method_name(call_path.suffix.as_str()) {
+                Some(op @ (StringOp::Eq | StringOp::Neq)) => Some(op),
+                _ => None,
+            }
+        }
+        MethodName::FromModule { method_name } if first_arg_is_string => {
+            match StringOp::from_method_name(method_name.as_str()) {
+                Some(StringOp::New | StringOp::From) | None => None,
+                Some(op) => Some(op),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Type checks a call to one of the built-in methods on `String`. Like those on `Vec<T>` they have
+/// no declaration and are lowered directly to heap operations by the code generators.
+pub(crate) fn type_check_string_operation(
+    op: StringOp,
+    method_name: Ident,
+    arguments: Vec<TypedExpression>,
+    span: Span,
+    self_type: TypeId,
+) -> CompileResult<TypedExpression> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    let string_type = insert_type(TypeInfo::DynamicString);
+    let u64_type = insert_type(TypeInfo::UnsignedInteger(IntegerBits::SixtyFour));
+    let (param_types, return_type) = match op {
+        StringOp::New => (vec![], string_type),
+        // The length of the `str[N]` is checked below, any length will do.
+        StringOp::From => (vec![None], string_type),
+        StringOp::Concat => (vec![Some(string_type), Some(string_type)], string_type),
+        StringOp::ByteAt => (
+            vec![Some(string_type), Some(u64_type)],
+            insert_type(TypeInfo::UnsignedInteger(IntegerBits::Eight)),
+        ),
+        StringOp::Len => (vec![Some(string_type)], u64_type),
+        StringOp::Eq | StringOp::Neq => (
+            vec![Some(string_type), Some(string_type)],
+            insert_type(TypeInfo::Boolean),
+        ),
+    };
+    if arguments.len() > param_types.len() {
+        errors.push(CompileError::TooManyArgumentsForFunction {
+            span: span.clone(),
+            method_name: method_name.clone(),
+            expected: param_types.len(),
+            received: arguments.len(),
+        });
+    }
+    if arguments.len() < param_types.len() {
+        errors.push(CompileError::TooFewArgumentsForFunction {
+            span: span.clone(),
+            method_name,
+            expected: param_types.len(),
+            received: arguments.len(),
+        });
+        return err(warnings, errors);
+    }
+
+    for (arg, param_type) in arguments.iter().zip(param_types) {
+        let param_type = match param_type {
+            Some(param_type) => param_type,
+            None => {
+                if !matches!(
+                    resolve_type(arg.return_type, &arg.span),
+                    Ok(TypeInfo::Str(_))
+                ) {
+                    errors.push(CompileError::ArgumentParameterTypeMismatch {
+                        span: arg.span.clone(),
+                        provided: arg.return_type.friendly_type_str(),
+                        should_be: "str[N]".into(),
+                    });
+                }
+                continue;
+            }
+        };
+        match crate::type_engine::unify_with_self(arg.return_type, param_type, self_type, &arg.span)
+        {
+            Ok(mut ws) => {
+                warnings.append(&mut ws);
+            }
+            Err(_e) => {
+                errors.push(CompileError::ArgumentParameterTypeMismatch {
+                    span: arg.span.clone(),
+                    provided: arg.return_type.friendly_type_str(),
+                    should_be: param_type.friendly_type_str(),
+                });
+            }
+        }
+    }
+
+    ok(
+        TypedExpression {
+            expression: TypedExpressionVariant::StringOperation { op, arguments },
+            return_type,
+            is_constant: IsConstant::No,
+            span,
+        },
+        warnings,
+        errors,
+    )
+}
@@ -0,0 +1,157 @@
+use super::*;
+use crate::parse_tree::MethodName;
+use std::collections::VecDeque;
+
+/// If `method_name` refers to one of the built-in methods on `Vec<T>`, returns the operation and
+/// the element type of the vector it operates on.
+pub(crate) fn find_vec_operation(
+    method_name: &MethodName,
+    args_buf: &VecDeque<TypedExpression>,
+) -> Option<(VecOp, TypeId)> {
+    match method_name {
+        MethodName::FromType {
+            type_name: Some(TypeInfo::Vector(elem_type)),
+            call_path,
+            ..
+        } if call_path.prefixes.is_empty() => {
+            VecOp::from_method_name(call_path.suffix.as_str()).map(|op| (op, *elem_type))
+        }
+        MethodName::FromModule { method_name } => {
+            let elem_type = match args_buf.front().map(|arg| look_up_type_id(arg.return_type)) {
+                Some(TypeInfo::Vector(elem_type)) => elem_type,
+                _ => return None,
+            };
+            match VecOp::from_method_name(method_name.as_str()) {
+                Some(VecOp::New) | None => None,
+                Some(op) => Some((op, elem_type)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Type checks a call to one of the built-in methods on `Vec<T>`. Unlike regular methods these
+/// have no declaration; they are lowered directly to heap operations by the code generators.
+pub(crate) fn type_check_vec_operation(
+    op: VecOp,
+    elem_type: TypeId,
+    method_name: Ident,
+    arguments: Vec<TypedExpression>,
+    span: Span,
+    namespace: NamespaceRef,
+    self_type: TypeId,
+) -> CompileResult<TypedExpression> {
+    let mut warnings = vec![];
+    let mut errors = vec![];
+
+    // `~Vec::new()` can't know its element type up front, so each new vector gets its own.
+    let elem_type = match (op, look_up_type_id(elem_type)) {
+        (VecOp::New, TypeInfo::Unknown) => insert_type(TypeInfo::Unknown),
+        _ => elem_type,
+    };
+    let vec_type = insert_type(TypeInfo::Vector(elem_type));
+
+    let (expected_args, return_type) = match op {
+        VecOp::New => (0, vec_type),
+        VecOp::Push => (2, insert_type(TypeInfo::Tuple(Vec::new()))),
+        VecOp::Pop => (1, elem_type),
+        VecOp::Get => (2, elem_type),
+        VecOp::Len => (
+            1,
+            insert_type(TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)),
+        ),
+    };
+    if arguments.len() > expected_args {
+        errors.push(CompileError::TooManyArgumentsForFunction {
+            span: span.clone(),
+            method_name: method_name.clone(),
+            expected: expected_args,
+            received: arguments.len(),
+        });
+    }
+    if arguments.len() < expected_args {
+        errors.push(CompileError::TooFewArgumentsForFunction {
+            span: span.clone(),
+            method_name,
+            expected: expected_args,
+            received: arguments.len(),
+        });
+        return err(warnings, errors);
+    }
+
+    // The first argument is the vector itself, the second one (if any) is either the element
+    // being pushed or the index being read.
+    let param_types = match op {
+        VecOp::New => vec![],
+        VecOp::Push => vec![vec_type, elem_type],
+        VecOp::Pop | VecOp::Len => vec![vec_type],
+        VecOp::Get => vec![
+            vec_type,
+            insert_type(TypeInfo::UnsignedInteger(IntegerBits::SixtyFour)),
+        ],
+    };
+    for (arg, param_type) in arguments.iter().zip(param_types) {
+        match crate::type_engine::unify_with_self(arg.return_type, param_type, self_type, &arg.span)
+        {
+            Ok(mut ws) => {
+                warnings.append(&mut ws);
+            }
+            Err(_e) => {
+                errors.push(CompileError::ArgumentParameterTypeMismatch {
+                    span: arg.span.clone(),
+                    provided: arg.return_type.friendly_type_str(),
+                    should_be: param_type.friendly_type_str(),
+                });
+            }
+        }
+    }
+
+    if let (VecOp::Push | VecOp::Pop, Some(vec)) = (op, arguments.first()) {
+        check_vec_is_mutable(vec, namespace, &mut errors);
+    }
+
+    ok(
+        TypedExpression {
+            expression: TypedExpressionVariant::VecOperation {
+                op,
+                arguments,
+                elem_type,
+            },
+            return_type,
+            is_constant: IsConstant::No,
+            span,
+        },
+        warnings,
+        errors,
+    )
+}
+
+/// Pushing to or popping from a vector mutates it, so if it is (a field of) a variable then that
+/// variable must be declared as mutable.
+fn check_vec_is_mutable(
+    vec: &TypedExpression,
+    namespace: NamespaceRef,
+    errors: &mut Vec<CompileError>,
+) {
+    match &vec.expression {
+        TypedExpressionVariant::VariableExpression { name } => {
+            if let Some(TypedDeclaration::VariableDeclaration(TypedVariableDeclaration {
+                is_mutable,
+                ..
+            })) = namespace.get_symbol(name).value
+            {
+                if !is_mutable.is_mutable() {
+                    errors.push(CompileError::AssignmentToNonMutable(
+                        name.as_str().to_string(),
+                        vec.span.clone(),
+                    ));
+                }
+            }
+        }
+        TypedExpressionVariant::StructFieldAccess { prefix, .. }
+        | TypedExpressionVariant::TupleElemAccess { prefix, .. } => {
+            check_vec_is_mutable(prefix, namespace, errors)
+        }
+        _ => (),
+    }
+}
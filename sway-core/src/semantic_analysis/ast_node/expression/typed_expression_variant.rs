@@ -15,6 +15,8 @@ pub(crate) enum TypedExpressionVariant {
         name: CallPath,
         arguments: Vec<(Ident, TypedExpression)>,
         function_body: TypedCodeBlock,
+        /// The name of the function where it was declared, which may be in another module.
+        decl_name: Ident,
         /// If this is `Some(val)` then `val` is the metadata. If this is `None`, then
         /// there is no selector.
         selector: Option<ContractCallMetadata>,
@@ -90,6 +92,103 @@ pub(crate) enum TypedExpressionVariant {
         // this span may be used for errors in the future, although it is not right now.
         span: Span,
     },
+    /// A built-in operation on a `Vec<T>`. Apart from `VecOp::New`, the first argument is always
+    /// the vector being operated on.
+    VecOperation {
+        op: VecOp,
+        arguments: Vec<TypedExpression>,
+        elem_type: TypeId,
+    },
+    /// A built-in operation on a `String`. Apart from `StringOp::New` and `StringOp::From`, the
+    /// first argument is always the string being operated on.
+    StringOperation {
+        op: StringOp,
+        arguments: Vec<TypedExpression>,
+    },
+}
+
+/// The operations available on the built-in `Vec<T>` type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum VecOp {
+    /// `~Vec::new()`, an empty vector which does not allocate until it is first pushed to.
+    New,
+    /// `v.push(elem)`, appends an element, growing the heap buffer if it is full.
+    Push,
+    /// `v.pop()`, removes and returns the last element. Reverts if the vector is empty.
+    Pop,
+    /// `v.get(index)`, returns the element at `index`. Reverts if it is out of bounds.
+    Get,
+    /// `v.len()`, the number of elements in the vector.
+    Len,
+}
+
+impl VecOp {
+    pub(crate) fn from_method_name(name: &str) -> Option<Self> {
+        match name {
+            "new" => Some(VecOp::New),
+            "push" => Some(VecOp::Push),
+            "pop" => Some(VecOp::Pop),
+            "get" => Some(VecOp::Get),
+            "len" => Some(VecOp::Len),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn method_name(&self) -> &'static str {
+        match self {
+            VecOp::New => "new",
+            VecOp::Push => "push",
+            VecOp::Pop => "pop",
+            VecOp::Get => "get",
+            VecOp::Len => "len",
+        }
+    }
+}
+
+/// The operations available on the built-in `String` type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StringOp {
+    /// `~String::new()`, an empty string which does not allocate.
+    New,
+    /// `~String::from(s)`, a copy of the `str[N]` `s` on the heap.
+    From,
+    /// `a.concat(b)`, a new string holding the bytes of `a` followed by those of `b`.
+    Concat,
+    /// `s.byte_at(index)`, the byte at `index` as a `u8`. Reverts if it is out of bounds.
+    ByteAt,
+    /// `s.len()`, the length of the string in bytes.
+    Len,
+    /// `a == b`, whether both strings hold the same bytes.
+    Eq,
+    /// `a != b`.
+    Neq,
+}
+
+impl StringOp {
+    pub(crate) fn from_method_name(name: &str) -> Option<Self> {
+        match name {
+            "new" => Some(StringOp::New),
+            "from" => Some(StringOp::From),
+            "concat" => Some(StringOp::Concat),
+            "byte_at" => Some(StringOp::ByteAt),
+            "len" => Some(StringOp::Len),
+            "eq" => Some(StringOp::Eq),
+            "neq" => Some(StringOp::Neq),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn method_name(&self) -> &'static str {
+        match self {
+            StringOp::New => "new",
+            StringOp::From => "from",
+            StringOp::Concat => "concat",
+            StringOp::ByteAt => "byte_at",
+            StringOp::Len => "len",
+            StringOp::Eq => "eq",
+            StringOp::Neq => "neq",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -154,6 +253,12 @@ impl TypedExpressionVariant {
             TypedExpressionVariant::AbiCast { abi_name, .. } => {
                 format!("abi cast {}", abi_name.suffix.as_str())
             }
+            TypedExpressionVariant::VecOperation { op, .. } => {
+                format!("Vec::{} built-in", op.method_name())
+            }
+            TypedExpressionVariant::StringOperation { op, .. } => {
+                format!("String::{} built-in", op.method_name())
+            }
             TypedExpressionVariant::StructFieldAccess {
                 resolved_type_of_parent,
                 field_to_access,
@@ -317,6 +422,26 @@ impl TypedExpressionVariant {
                 };
             }
             AbiCast { address, .. } => address.copy_types(type_mapping),
+            VecOperation {
+                arguments,
+                ref mut elem_type,
+                ..
+            } => {
+                *elem_type = if let Some(matching_id) =
+                    look_up_type_id(*elem_type).matches_type_parameter(type_mapping)
+                {
+                    insert_type(TypeInfo::Ref(matching_id))
+                } else {
+                    insert_type(look_up_type_id_raw(*elem_type))
+                };
+
+                arguments
+                    .iter_mut()
+                    .for_each(|x| x.copy_types(type_mapping));
+            }
+            StringOperation { arguments, .. } => arguments
+                .iter_mut()
+                .for_each(|x| x.copy_types(type_mapping)),
         }
     }
 }
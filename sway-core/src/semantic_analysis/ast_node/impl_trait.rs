@@ -398,6 +398,11 @@ fn type_check_trait_implementation(
 
     // check that the implementation checklist is complete
     if !function_checklist.is_empty() {
+        let signatures = interface_surface
+            .iter()
+            .filter(|trait_fn| function_checklist.contains(&&trait_fn.name))
+            .map(trait_fn_signature)
+            .collect();
         errors.push(CompileError::MissingInterfaceSurfaceMethods {
             span: block_span.clone(),
             missing_functions: function_checklist
@@ -405,7 +410,36 @@ fn type_check_trait_implementation(
                 .map(|ident| ident.as_str().to_string())
                 .collect::<Vec<_>>()
                 .join("\n"),
+            signatures,
         });
     }
     ok(functions_buf, warnings, errors)
 }
+
+/// The signature of `trait_fn` with its types written the way they are in the trait, such as
+/// `fn add(self, other: Self) -> Self`.
+fn trait_fn_signature(trait_fn: &TypedTraitFn) -> String {
+    let parameters = trait_fn
+        .parameters
+        .iter()
+        .map(|param| {
+            if param.name.as_str() == "self" {
+                "self".to_string()
+            } else {
+                format!("{}: {}", param.name.as_str(), param.type_span.as_str())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    // without a return type, the span is that of the parameters
+    if look_up_type_id(trait_fn.return_type).is_unit() {
+        format!("fn {}({})", trait_fn.name.as_str(), parameters)
+    } else {
+        format!(
+            "fn {}({}) -> {}",
+            trait_fn.name.as_str(),
+            parameters,
+            trait_fn.return_type_span.as_str()
+        )
+    }
+}
@@ -28,7 +28,7 @@ pub(crate) use declaration::{
 pub use declaration::{
     TypedAbiDeclaration, TypedConstantDeclaration, TypedDeclaration, TypedEnumDeclaration,
     TypedEnumVariant, TypedFunctionDeclaration, TypedFunctionParameter, TypedStructDeclaration,
-    TypedStructField,
+    TypedStructField, TypedTypeAliasDeclaration,
 };
 
 pub mod impl_trait;
@@ -149,10 +149,8 @@ impl TypedAstNode {
                                             value| {
             let type_id = namespace
                 .resolve_type_with_self(type_ascription, self_type)
-                .unwrap_or_else(|_| {
-                    errors.push(CompileError::UnknownType {
-                        span: node.span.clone(),
-                    });
+                .unwrap_or_else(|err| {
+                    errors.push(err);
                     insert_type(TypeInfo::ErrorRecovery)
                 });
             TypedExpression::type_check(TypeCheckArguments {
@@ -214,16 +212,14 @@ impl TypedAstNode {
                         Declaration::VariableDeclaration(VariableDeclaration {
                             name,
                             type_ascription,
-                            type_ascription_span,
                             body,
                             is_mutable,
+                            ..
                         }) => {
                             let type_ascription = namespace
                                 .resolve_type_with_self(type_ascription, self_type)
-                                .unwrap_or_else(|_| {
-                                    errors.push(CompileError::UnknownType {
-                                        span: type_ascription_span.expect("Invariant violated: type checked an annotation that did not exist in the source"),
-                                    });
+                                .unwrap_or_else(|err| {
+                                    errors.push(err);
                                     insert_type(TypeInfo::ErrorRecovery)
                                 });
 
@@ -580,37 +576,29 @@ impl TypedAstNode {
                             let fields = decl
                                 .fields
                                 .into_iter()
-                                .map(
-                                    |StructField {
-                                         name,
-                                         r#type,
-                                         span,
-                                         type_span,
-                                     }| TypedStructField {
-                                        name,
-                                        r#type: if let Some(matching_id) =
-                                            r#type.matches_type_parameter(&type_mapping)
-                                        {
-                                            insert_type(TypeInfo::Ref(matching_id))
-                                        } else {
-                                            namespace
-                                                .resolve_type_with_self(r#type, self_type)
-                                                .unwrap_or_else(|_| {
-                                                    errors.push(CompileError::UnknownType {
-                                                        span: type_span.clone(),
-                                                    });
-                                                    insert_type(TypeInfo::ErrorRecovery)
-                                                })
-                                        },
-                                        span,
+                                .map(|StructField { name, r#type, span }| TypedStructField {
+                                    name,
+                                    r#type: if let Some(matching_id) =
+                                        r#type.matches_type_parameter(&type_mapping)
+                                    {
+                                        insert_type(TypeInfo::Ref(matching_id))
+                                    } else {
+                                        namespace
+                                            .resolve_type_with_self(r#type, self_type)
+                                            .unwrap_or_else(|err| {
+                                                errors.push(err);
+                                                insert_type(TypeInfo::ErrorRecovery)
+                                            })
                                     },
-                                )
+                                    span,
+                                })
                                 .collect::<Vec<_>>();
                             let decl = TypedStructDeclaration {
                                 name: decl.name.clone(),
                                 type_parameters: decl.type_parameters.clone(),
                                 fields,
                                 visibility: decl.visibility,
+                                kind: decl.kind,
                             };
 
                             // insert struct into namespace
@@ -626,6 +614,44 @@ impl TypedAstNode {
 
                             TypedDeclaration::StructDeclaration(decl)
                         }
+                        Declaration::TypeAliasDeclaration(TypeAliasDeclaration {
+                            name,
+                            type_parameters,
+                            r#type,
+                            visibility,
+                            span,
+                        }) => {
+                            // the aliased type is resolved in a scope where the alias's type
+                            // parameters are known, so that they are left generic until the
+                            // alias is instantiated at a use site
+                            let alias_namespace = create_new_scope(namespace);
+                            for param in &type_parameters {
+                                alias_namespace.insert(param.name_ident.clone(), param.into());
+                            }
+                            let r#type = alias_namespace
+                                .resolve_type_with_self(r#type, self_type)
+                                .unwrap_or_else(|err| {
+                                    errors.push(err);
+                                    insert_type(TypeInfo::ErrorRecovery)
+                                });
+                            let decl = TypedTypeAliasDeclaration {
+                                name: name.clone(),
+                                type_parameters,
+                                r#type,
+                                visibility,
+                                span,
+                            };
+                            check!(
+                                namespace.insert(
+                                    name,
+                                    TypedDeclaration::TypeAliasDeclaration(decl.clone()),
+                                ),
+                                return err(warnings, errors),
+                                warnings,
+                                errors
+                            );
+                            TypedDeclaration::TypeAliasDeclaration(decl)
+                        }
                         Declaration::AbiDeclaration(AbiDeclaration {
                             name,
                             interface_surface,
@@ -806,7 +832,7 @@ impl TypedAstNode {
         } = node
         {
             let warning = Warning::UnusedReturnValue {
-                r#type: node.type_info(),
+                r#type: node.type_info().friendly_type_str(),
             };
             assert_or_warn!(
                 node.type_info().is_unit() || node.type_info() == TypeInfo::ErrorRecovery,
@@ -852,7 +878,7 @@ fn import_new_file(
         return ok((), warnings, errors);
     };
 
-    let file_as_string = match res {
+    let file_as_string: Arc<str> = match res {
         Ok(s) => Arc::from(s),
         Err(e) => {
             errors.push(CompileError::FileCouldNotBeRead {
@@ -865,6 +891,7 @@ fn import_new_file(
     };
 
     let mut dep_config = build_config.clone();
+    let file_path = canonical_path.clone();
     let dep_path = {
         canonical_path.pop();
         canonical_path
@@ -872,22 +899,39 @@ fn import_new_file(
     dep_config.file_name = file_name;
     dep_config.dir_of_code = Arc::new(dep_path);
     let dep_namespace = create_new_scope(namespace);
-    let crate::InnerDependencyCompileResult {
-        name,
-        namespace: module,
-        ..
-    } = check!(
+    let compile = |type_engine| {
         crate::compile_inner_dependency(
-            file_as_string,
+            file_as_string.clone(),
             dep_namespace,
-            dep_config,
+            dep_config.type_engine(type_engine),
             dead_code_graph,
-            dependency_graph
+            dependency_graph,
+        )
+    };
+    let compiled = match &build_config.module_cache {
+        Some(cache) => cache.include(
+            &file_path,
+            &file_as_string,
+            &build_config.type_engine,
+            compile,
         ),
-        return err(warnings, errors),
-        warnings,
-        errors
-    );
+        None => compile(build_config.type_engine.clone()),
+    };
+    let crate::InnerDependencyCompileResult {
+        name,
+        namespace: module,
+        parse_tree,
+    } = check!(compiled, return err(warnings, errors), warnings, errors);
+
+    // The dead code will be analyzed later wholistically with the rest of the program
+    // since we can't tell what is dead and what isn't just from looking at this file
+    if let Err(e) = ControlFlowGraph::append_to_dead_code_graph(
+        &parse_tree,
+        &TreeType::Library { name: name.clone() },
+        dead_code_graph,
+    ) {
+        errors.push(e)
+    };
 
     let name = match statement.alias {
         Some(ref alias) => alias,
@@ -1123,10 +1167,8 @@ fn type_check_interface_surface(
                                         r#type,
                                         crate::type_engine::insert_type(TypeInfo::SelfType),
                                     )
-                                    .unwrap_or_else(|_| {
-                                        errors.push(CompileError::UnknownType {
-                                            span: type_span.clone(),
-                                        });
+                                    .unwrap_or_else(|err| {
+                                        errors.push(err);
                                         insert_type(TypeInfo::ErrorRecovery)
                                     }),
                                 type_span,
@@ -1138,10 +1180,8 @@ fn type_check_interface_surface(
                             return_type,
                             crate::type_engine::insert_type(TypeInfo::SelfType),
                         )
-                        .unwrap_or_else(|_| {
-                            errors.push(CompileError::UnknownType {
-                                span: return_type_span,
-                            });
+                        .unwrap_or_else(|err| {
+                            errors.push(err);
                             insert_type(TypeInfo::ErrorRecovery)
                         }),
                 },
@@ -1186,10 +1226,8 @@ fn type_check_trait_methods(
                         r#type.clone(),
                         crate::type_engine::insert_type(TypeInfo::SelfType),
                     )
-                    .unwrap_or_else(|_| {
-                        errors.push(CompileError::UnknownType {
-                            span: name.span().clone(),
-                        });
+                    .unwrap_or_else(|err| {
+                        errors.push(err);
                         insert_type(TypeInfo::ErrorRecovery)
                     });
                 function_namespace.insert(
@@ -1214,7 +1252,7 @@ fn type_check_trait_methods(
         // the type scope
         let mut generic_params_buf_for_error_message = Vec::new();
         for param in parameters.iter() {
-            if let TypeInfo::Custom { ref name } = param.r#type {
+            if let TypeInfo::Custom { ref name, .. } = param.r#type {
                 generic_params_buf_for_error_message.push(name.to_string());
             }
         }
@@ -1233,7 +1271,10 @@ fn type_check_trait_methods(
                     |TypeParameter {
                          name: this_name, ..
                      }| {
-                        if let TypeInfo::Custom { name: this_name } = this_name {
+                        if let TypeInfo::Custom {
+                            name: this_name, ..
+                        } = this_name
+                        {
                             this_name == name
                         } else {
                             false
@@ -1265,10 +1306,8 @@ fn type_check_trait_methods(
                                 r#type,
                                 crate::type_engine::insert_type(TypeInfo::SelfType),
                             )
-                            .unwrap_or_else(|_| {
-                                errors.push(CompileError::UnknownType {
-                                    span: type_span.clone(),
-                                });
+                            .unwrap_or_else(|err| {
+                                errors.push(err);
                                 insert_type(TypeInfo::ErrorRecovery)
                             }),
                         type_span,
@@ -1280,10 +1319,8 @@ fn type_check_trait_methods(
         // TODO check code block implicit return
         let return_type = function_namespace
             .resolve_type_with_self(return_type, self_type)
-            .unwrap_or_else(|_| {
-                errors.push(CompileError::UnknownType {
-                    span: return_type_span.clone(),
-                });
+            .unwrap_or_else(|err| {
+                errors.push(err);
                 insert_type(TypeInfo::ErrorRecovery)
             });
         let (body, _code_block_implicit_return) = check!(
@@ -1318,6 +1355,7 @@ fn type_check_trait_methods(
             visibility: Visibility::Public,
             return_type_span,
             is_contract_call: false,
+            is_test: false,
             purity,
         });
     }
@@ -1344,6 +1382,7 @@ fn error_recovery_function_declaration(decl: FunctionDeclaration) -> TypedFuncti
         },
         span,
         is_contract_call: false,
+        is_test: false,
         return_type_span,
         parameters: Default::default(),
         visibility,
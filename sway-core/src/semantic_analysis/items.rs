@@ -0,0 +1,418 @@
+//! The items declared in the modules of a type checked program, with their signatures, for
+//! generating documentation.
+use super::{
+    ast_node::{declaration::TypedTraitFn, *},
+    symbols::{named_modules, source_type_str},
+    *,
+};
+use crate::parse_tree::{FunctionDeclaration, TypeParameter};
+use crate::type_engine::{look_up_type_id, FriendlyTypeString, TypeId};
+use crate::Ident;
+
+/// An item declared in a module, such as a function or a struct, or one declared within another
+/// item, such as a field or a method.
+#[derive(Clone, Debug)]
+pub struct ModuleItem {
+    /// The name of the item where it is declared. Its span locates the declaration, along with
+    /// the doc comments written above it.
+    pub name: Ident,
+    pub kind: SymbolKind,
+    pub is_public: bool,
+    /// The declaration on a single line with its types resolved, such as
+    /// `pub fn add(a: u64, b: u64) -> u64` or `struct Pair<T>`.
+    pub signature: String,
+    /// The fields of a struct, the variants of an enum, the methods of a trait or an ABI, or the
+    /// methods implemented for a struct or an enum.
+    pub members: Vec<ModuleItem>,
+}
+
+/// The items declared in the module `namespace`, sorted by name. Those brought in with `use` are
+/// left out.
+pub fn module_items(namespace: NamespaceRef) -> Vec<ModuleItem> {
+    let declarations = read_module(
+        |m| m.get_all_declared_symbols().cloned().collect::<Vec<_>>(),
+        namespace,
+    );
+    // importing a constant copies its declaration into the importing module, while every other
+    // item is only declared in the file of the module it belongs to
+    let module_file = declarations
+        .iter()
+        .filter(|decl| !matches!(decl, TypedDeclaration::VariableDeclaration(..)))
+        .find_map(|decl| declaration_name(decl).map(|name| name.span().path.clone()));
+    let mut items = declarations
+        .iter()
+        .filter(|decl| match (&module_file, declaration_name(decl)) {
+            (Some(file), Some(name)) => name.span().path == *file,
+            _ => true,
+        })
+        .filter_map(|decl| module_item(namespace, decl))
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+    items
+}
+
+/// The modules within `namespace` along with their names: the libraries it declares with `dep`
+/// and, for the root module of a program, the packages it depends on.
+pub fn submodules(namespace: NamespaceRef) -> Vec<(String, NamespaceRef)> {
+    read_module(|m| named_modules(m, &[]), namespace)
+        .into_iter()
+        .map(|(mut path, module)| (path.remove(0), module))
+        .collect()
+}
+
+fn declaration_name(decl: &TypedDeclaration) -> Option<&Ident> {
+    match decl {
+        TypedDeclaration::VariableDeclaration(TypedVariableDeclaration { name, .. })
+        | TypedDeclaration::ConstantDeclaration(TypedConstantDeclaration { name, .. })
+        | TypedDeclaration::FunctionDeclaration(TypedFunctionDeclaration { name, .. })
+        | TypedDeclaration::TraitDeclaration(TypedTraitDeclaration { name, .. })
+        | TypedDeclaration::StructDeclaration(TypedStructDeclaration { name, .. })
+        | TypedDeclaration::EnumDeclaration(TypedEnumDeclaration { name, .. })
+        | TypedDeclaration::AbiDeclaration(TypedAbiDeclaration { name, .. })
+        | TypedDeclaration::TypeAliasDeclaration(TypedTypeAliasDeclaration { name, .. }) => {
+            Some(name)
+        }
+        TypedDeclaration::Reassignment(..)
+        | TypedDeclaration::ImplTrait { .. }
+        | TypedDeclaration::GenericTypeForFunctionScope { .. }
+        | TypedDeclaration::ErrorRecovery => None,
+    }
+}
+
+fn module_item(namespace: NamespaceRef, decl: &TypedDeclaration) -> Option<ModuleItem> {
+    let is_public = decl.visibility().is_public();
+    let item = |name: &Ident, kind, signature: String, members| {
+        Some(ModuleItem {
+            name: name.clone(),
+            kind,
+            is_public,
+            signature: format!("{}{}", if is_public { "pub " } else { "" }, signature),
+            members,
+        })
+    };
+    match decl {
+        // the constants declared at the top level of a module are the only variables in it
+        TypedDeclaration::VariableDeclaration(TypedVariableDeclaration { name, body, .. }) => {
+            let signature = format!("const {}: {}", name.as_str(), type_str(body.return_type));
+            item(name, SymbolKind::Constant, signature, vec![])
+        }
+        TypedDeclaration::ConstantDeclaration(TypedConstantDeclaration { name, value, .. }) => {
+            let signature = format!("const {}: {}", name.as_str(), type_str(value.return_type));
+            item(name, SymbolKind::Constant, signature, vec![])
+        }
+        TypedDeclaration::FunctionDeclaration(func) => item(
+            &func.name,
+            SymbolKind::Function,
+            function_signature(func),
+            vec![],
+        ),
+        TypedDeclaration::TraitDeclaration(TypedTraitDeclaration {
+            name,
+            interface_surface,
+            methods,
+            type_parameters,
+            ..
+        }) => {
+            let signature = format!(
+                "trait {}{}",
+                name.as_str(),
+                type_parameters_str(type_parameters)
+            );
+            let members = interface_members(interface_surface, methods);
+            item(name, SymbolKind::Trait, signature, members)
+        }
+        TypedDeclaration::StructDeclaration(TypedStructDeclaration {
+            name,
+            fields,
+            type_parameters,
+            ..
+        }) => {
+            let signature = format!(
+                "struct {}{}",
+                name.as_str(),
+                type_parameters_str(type_parameters)
+            );
+            let mut members = fields
+                .iter()
+                .map(|field| {
+                    member(
+                        &field.name,
+                        SymbolKind::Field,
+                        format!("{}: {}", field.name.as_str(), type_str(field.r#type)),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if let Some(type_id) = decl.return_type().value {
+                members.extend(methods_of_type(namespace, type_id));
+            }
+            item(name, SymbolKind::Struct, signature, members)
+        }
+        TypedDeclaration::EnumDeclaration(enum_decl) => {
+            let signature = format!(
+                "enum {}{}",
+                enum_decl.name.as_str(),
+                type_parameters_str(&enum_decl.type_parameters)
+            );
+            let mut members = enum_decl
+                .variants
+                .iter()
+                .map(|variant| {
+                    let signature = if is_unit(variant.r#type) {
+                        variant.name.as_str().to_string()
+                    } else {
+                        format!("{}: {}", variant.name.as_str(), type_str(variant.r#type))
+                    };
+                    member(&variant.name, SymbolKind::Variant, signature)
+                })
+                .collect::<Vec<_>>();
+            members.extend(methods_of_type(namespace, enum_decl.as_type()));
+            item(&enum_decl.name, SymbolKind::Enum, signature, members)
+        }
+        // an ABI has no visibility, any contract can implement it and any script can call it
+        TypedDeclaration::AbiDeclaration(TypedAbiDeclaration {
+            name,
+            interface_surface,
+            methods,
+            ..
+        }) => Some(ModuleItem {
+            name: name.clone(),
+            kind: SymbolKind::Abi,
+            is_public: true,
+            signature: format!("abi {}", name.as_str()),
+            members: interface_members(interface_surface, methods),
+        }),
+        TypedDeclaration::TypeAliasDeclaration(TypedTypeAliasDeclaration {
+            name,
+            type_parameters,
+            r#type,
+            ..
+        }) => {
+            let signature = format!(
+                "type {}{} = {}",
+                name.as_str(),
+                type_parameters_str(type_parameters),
+                type_str(*r#type)
+            );
+            item(name, SymbolKind::TypeAlias, signature, vec![])
+        }
+        TypedDeclaration::Reassignment(..)
+        | TypedDeclaration::ImplTrait { .. }
+        | TypedDeclaration::GenericTypeForFunctionScope { .. }
+        | TypedDeclaration::ErrorRecovery => None,
+    }
+}
+
+fn member(name: &Ident, kind: SymbolKind, signature: String) -> ModuleItem {
+    ModuleItem {
+        name: name.clone(),
+        kind,
+        is_public: true,
+        signature,
+        members: vec![],
+    }
+}
+
+/// The methods a trait or an ABI requires, followed by those it provides.
+fn interface_members(
+    interface_surface: &[TypedTraitFn],
+    methods: &[FunctionDeclaration],
+) -> Vec<ModuleItem> {
+    let required = interface_surface.iter().map(|trait_fn| {
+        let parameters = trait_fn
+            .parameters
+            .iter()
+            .map(|param| parameter_str(&param.name, type_str(param.r#type)));
+        let signature = signature_of_fn(
+            &trait_fn.name,
+            "",
+            parameters,
+            Some(type_str(trait_fn.return_type)).filter(|_| !is_unit(trait_fn.return_type)),
+        );
+        member(&trait_fn.name, SymbolKind::Function, signature)
+    });
+    // the provided methods are only type checked where the trait is implemented, so their types
+    // are shown as they are written
+    let provided = methods.iter().map(|method| {
+        let parameters = method
+            .parameters
+            .iter()
+            .map(|param| parameter_str(&param.name, param.type_span.as_str().trim().to_string()));
+        let signature = signature_of_fn(
+            &method.name,
+            &type_parameters_str(&method.type_parameters),
+            parameters,
+            Some(method.return_type_span.as_str().trim().to_string())
+                .filter(|_| !method.return_type.is_unit()),
+        );
+        member(&method.name, SymbolKind::Function, signature)
+    });
+    required.chain(provided).collect()
+}
+
+/// The methods implemented for `type_id` in `namespace`, once each.
+fn methods_of_type(namespace: NamespaceRef, type_id: TypeId) -> Vec<ModuleItem> {
+    let mut methods: Vec<ModuleItem> = vec![];
+    for method in namespace.get_methods_for_type(type_id) {
+        if methods
+            .iter()
+            .any(|other| other.name.span() == method.name.span())
+        {
+            continue;
+        }
+        methods.push(member(
+            &method.name,
+            SymbolKind::Function,
+            function_signature(&method),
+        ));
+    }
+    methods
+}
+
+fn function_signature(func: &TypedFunctionDeclaration) -> String {
+    let parameters = func
+        .parameters
+        .iter()
+        .map(|param| parameter_str(&param.name, type_str(param.r#type)));
+    signature_of_fn(
+        &func.name,
+        &type_parameters_str(&func.type_parameters),
+        parameters,
+        Some(type_str(func.return_type)).filter(|_| !is_unit(func.return_type)),
+    )
+}
+
+fn signature_of_fn(
+    name: &Ident,
+    type_parameters: &str,
+    parameters: impl Iterator<Item = String>,
+    return_type: Option<String>,
+) -> String {
+    let parameters = parameters.collect::<Vec<_>>().join(", ");
+    match return_type {
+        Some(return_type) => format!(
+            "fn {}{}({}) -> {}",
+            name.as_str(),
+            type_parameters,
+            parameters,
+            return_type
+        ),
+        None => format!("fn {}{}({})", name.as_str(), type_parameters, parameters),
+    }
+}
+
+/// The parameter `name: type`, or just `self` for the receiver of a method.
+fn parameter_str(name: &Ident, type_str: String) -> String {
+    if name.as_str() == "self" {
+        "self".into()
+    } else {
+        format!("{}: {}", name.as_str(), type_str)
+    }
+}
+
+fn type_parameters_str(type_parameters: &[TypeParameter]) -> String {
+    if type_parameters.is_empty() {
+        return String::new();
+    }
+    let names = type_parameters
+        .iter()
+        .map(|param| param.name_ident.as_str())
+        .collect::<Vec<_>>();
+    format!("<{}>", names.join(", "))
+}
+
+/// `type_id` the way it would be written in the source, where it can be.
+fn type_str(type_id: TypeId) -> String {
+    source_type_str(type_id).unwrap_or_else(|| type_id.friendly_type_str())
+}
+
+fn is_unit(type_id: TypeId) -> bool {
+    look_up_type_id(type_id).is_unit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile_to_ast, type_engine::TypeEngine, BuildConfig, CompileAstResult};
+    use std::{collections::HashMap, sync::Arc};
+
+    fn items_of(source: &str) -> Vec<ModuleItem> {
+        let build_config =
+            BuildConfig::root_from_file_name_and_manifest_path("main.sw".into(), "".into())
+                .type_engine(TypeEngine::current());
+        match compile_to_ast(
+            Arc::from(source),
+            create_module(),
+            &build_config,
+            &mut HashMap::new(),
+        ) {
+            CompileAstResult::Success { parse_tree, .. } => {
+                module_items(parse_tree.namespace_ref())
+            }
+            CompileAstResult::Failure { errors, .. } => panic!("{:?}", errors),
+        }
+    }
+
+    fn signatures(items: &[ModuleItem]) -> Vec<&str> {
+        items.iter().map(|item| item.signature.as_str()).collect()
+    }
+
+    #[test]
+    fn items_are_listed_with_their_signatures_and_members() {
+        let _type_engine = TypeEngine::new().enter();
+        let items = items_of(
+            r#"library shapes;
+
+pub const ORIGIN: u64 = 0;
+
+pub struct Point {
+    x: u64,
+    y: u64,
+}
+
+impl Point {
+    fn x(self) -> u64 {
+        self.x
+    }
+}
+
+pub enum Shape {
+    Dot: Point,
+    Empty: (),
+}
+
+pub trait Area {
+    fn area(self) -> u64;
+} {
+    fn is_empty(self) -> bool {
+        false
+    }
+}
+
+fn scale(point: Point, factor: u64) -> Point {
+    Point { x: factor, y: point.y }
+}
+"#,
+        );
+
+        assert_eq!(
+            signatures(&items),
+            vec![
+                "pub trait Area",
+                "pub const ORIGIN: u64",
+                "pub struct Point",
+                "pub enum Shape",
+                "fn scale(point: Point, factor: u64) -> Point",
+            ]
+        );
+        assert!(!items[4].is_public);
+        assert_eq!(
+            signatures(&items[0].members),
+            vec!["fn area(self) -> u64", "fn is_empty(self) -> bool"]
+        );
+        assert_eq!(
+            signatures(&items[2].members),
+            vec!["x: u64", "y: u64", "fn x(self) -> u64"]
+        );
+        assert_eq!(signatures(&items[3].members), vec!["Dot: Point", "Empty"]);
+    }
+}
@@ -35,10 +35,37 @@ impl Namespace {
         self.symbols.values()
     }
 
+    /// The item declared in this namespace as `name`.
+    pub(crate) fn get_declared_symbol(&self, name: &Ident) -> Option<&TypedDeclaration> {
+        self.symbols.get(name)
+    }
+
     pub fn get_all_imported_modules(&self) -> impl Iterator<Item = &NamespaceRef> {
         self.modules.values()
     }
 
+    /// The modules within this scope along with their names.
+    pub(crate) fn get_all_named_modules(
+        &self,
+    ) -> impl Iterator<Item = (&ModuleName, &NamespaceRef)> {
+        self.modules.iter()
+    }
+
+    /// The names brought into this namespace by `use` statements, which resolve to declarations
+    /// in other modules.
+    pub(crate) fn get_all_imported_names(&self) -> impl Iterator<Item = &Ident> {
+        self.use_synonyms.keys()
+    }
+
+    /// The items brought into this namespace by `use` statements, each as it is named in the
+    /// statement along with the name it is brought in as, which differs if it was aliased.
+    pub(crate) fn get_all_imported_items(&self) -> impl Iterator<Item = (&Ident, &Ident)> {
+        self.use_synonyms.keys().map(move |name| {
+            let item = self.use_aliases.get(name.as_str()).unwrap_or(name);
+            (item, name)
+        })
+    }
+
     pub(crate) fn insert(&mut self, name: Ident, item: TypedDeclaration) -> CompileResult<()> {
         let mut warnings = vec![];
         let mut errors = vec![];
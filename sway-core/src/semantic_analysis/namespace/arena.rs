@@ -16,9 +16,29 @@ pub trait NamespaceWrapper {
     ///
     ///
     /// If a self type is given and anything on this ref chain refers to self, update the chain.
-    #[allow(clippy::result_unit_err)]
-    fn resolve_type_with_self(&self, ty: TypeInfo, self_type: TypeId) -> Result<TypeId, ()>;
+    #[allow(clippy::result_large_err)]
+    fn resolve_type_with_self(
+        &self,
+        ty: TypeInfo,
+        self_type: TypeId,
+    ) -> Result<TypeId, CompileError>;
+    /// Resolves the type of an element of a tuple, array or vector, leaving it untouched if it is
+    /// already resolved.
+    #[allow(clippy::result_large_err)]
+    fn resolve_inner_type_with_self(
+        &self,
+        ty: TypeId,
+        self_type: TypeId,
+    ) -> Result<TypeId, CompileError>;
     fn resolve_type_without_self(&self, ty: &TypeInfo) -> TypeId;
+    /// Resolves `Vec<T>` and `String` to the types built into the compiler, unless a declaration
+    /// in scope has their name. Returns `None` for any other type.
+    #[allow(clippy::result_large_err)]
+    fn resolve_builtin_type(
+        &self,
+        ty: &TypeInfo,
+        self_type: TypeId,
+    ) -> Option<Result<TypeId, CompileError>>;
     fn insert(&self, name: Ident, item: TypedDeclaration) -> CompileResult<()>;
     fn insert_module(&self, module_name: String, module_contents: Namespace);
     fn insert_module_ref(&self, module_name: String, ix: NamespaceRef);
@@ -361,10 +381,8 @@ impl NamespaceWrapper for NamespaceRef {
         // but if nothing turns up then we try the namespace where the type itself is declared.
         let r#type = namespace
             .resolve_type_with_self(look_up_type_id(r#type), self_type)
-            .unwrap_or_else(|_| {
-                errors.push(CompileError::UnknownType {
-                    span: method_name.span().clone(),
-                });
+            .unwrap_or_else(|err| {
+                errors.push(err);
                 insert_type(TypeInfo::ErrorRecovery)
             });
         let local_methods = self.get_methods_for_type(r#type);
@@ -590,107 +608,197 @@ impl NamespaceWrapper for NamespaceRef {
     fn insert(&self, name: Ident, item: TypedDeclaration) -> CompileResult<()> {
         write_module(|ns| ns.insert(name, item), *self)
     }
-    fn resolve_type_with_self(&self, ty: TypeInfo, self_type: TypeId) -> Result<TypeId, ()> {
+    #[allow(clippy::result_large_err)]
+    fn resolve_type_with_self(
+        &self,
+        ty: TypeInfo,
+        self_type: TypeId,
+    ) -> Result<TypeId, CompileError> {
         let mut warnings = vec![];
         let mut errors = vec![];
         Ok(match ty {
-            TypeInfo::Custom { ref name } => {
-                match self.get_symbol(name).ok(&mut warnings, &mut errors) {
-                    Some(TypedDeclaration::StructDeclaration(decl)) => {
-                        let old_struct = TypeInfo::Struct {
-                            name: decl.name.as_str().to_string(),
-                            fields: decl
+            TypeInfo::Custom {
+                ref name,
+                ref type_arguments,
+            } => match self.get_symbol(name).ok(&mut warnings, &mut errors) {
+                Some(TypedDeclaration::StructDeclaration(decl)) => {
+                    let old_struct = TypeInfo::Struct {
+                        name: decl.name.as_str().to_string(),
+                        fields: decl
+                            .fields
+                            .iter()
+                            .map(TypedStructField::as_owned_typed_struct_field)
+                            .collect::<Vec<_>>(),
+                    };
+                    let mut new_struct = old_struct.clone();
+                    if !decl.type_parameters.is_empty() {
+                        let new_decl = decl.monomorphize();
+                        new_struct = TypeInfo::Struct {
+                            name: new_decl.name.as_str().to_string(),
+                            fields: new_decl
                                 .fields
                                 .iter()
                                 .map(TypedStructField::as_owned_typed_struct_field)
                                 .collect::<Vec<_>>(),
                         };
-                        let mut new_struct = old_struct.clone();
-                        if !decl.type_parameters.is_empty() {
-                            let new_decl = decl.monomorphize();
-                            new_struct = TypeInfo::Struct {
-                                name: new_decl.name.as_str().to_string(),
-                                fields: new_decl
-                                    .fields
-                                    .iter()
-                                    .map(TypedStructField::as_owned_typed_struct_field)
-                                    .collect::<Vec<_>>(),
-                            };
-                            self.copy_methods_to_type(old_struct, new_struct.clone());
-                        }
-                        crate::type_engine::insert_type(new_struct)
+                        self.copy_methods_to_type(old_struct, new_struct.clone());
                     }
-                    Some(TypedDeclaration::EnumDeclaration(decl)) => {
-                        let old_enum = TypeInfo::Enum {
-                            name: decl.name.as_str().to_string(),
-                            variant_types: decl
+                    crate::type_engine::insert_type(new_struct)
+                }
+                Some(TypedDeclaration::EnumDeclaration(decl)) => {
+                    let old_enum = TypeInfo::Enum {
+                        name: decl.name.as_str().to_string(),
+                        variant_types: decl
+                            .variants
+                            .iter()
+                            .map(TypedEnumVariant::as_owned_typed_enum_variant)
+                            .collect(),
+                    };
+                    let mut new_enum = old_enum.clone();
+                    if !decl.type_parameters.is_empty() {
+                        let new_decl = decl.monomorphize();
+                        new_enum = TypeInfo::Enum {
+                            name: new_decl.name.as_str().to_string(),
+                            variant_types: new_decl
                                 .variants
                                 .iter()
                                 .map(TypedEnumVariant::as_owned_typed_enum_variant)
                                 .collect(),
                         };
-                        let mut new_enum = old_enum.clone();
-                        if !decl.type_parameters.is_empty() {
-                            let new_decl = decl.monomorphize();
-                            new_enum = TypeInfo::Enum {
-                                name: new_decl.name.as_str().to_string(),
-                                variant_types: new_decl
-                                    .variants
-                                    .iter()
-                                    .map(TypedEnumVariant::as_owned_typed_enum_variant)
-                                    .collect(),
-                            };
-                            self.copy_methods_to_type(old_enum, new_enum.clone());
-                        }
-                        crate::type_engine::insert_type(new_enum)
+                        self.copy_methods_to_type(old_enum, new_enum.clone());
                     }
-                    Some(TypedDeclaration::GenericTypeForFunctionScope { name, .. }) => {
-                        crate::type_engine::insert_type(TypeInfo::UnknownGeneric { name })
+                    crate::type_engine::insert_type(new_enum)
+                }
+                Some(TypedDeclaration::GenericTypeForFunctionScope { name, .. }) => {
+                    crate::type_engine::insert_type(TypeInfo::UnknownGeneric { name })
+                }
+                Some(TypedDeclaration::TypeAliasDeclaration(decl)) => {
+                    if type_arguments.len() != decl.type_parameters.len() {
+                        return Err(CompileError::IncorrectNumberOfTypeArguments {
+                            given: type_arguments.len(),
+                            expected: decl.type_parameters.len(),
+                            span: name.span().clone(),
+                        });
                     }
-                    _ => return Err(()),
+                    let type_arguments = type_arguments
+                        .iter()
+                        .map(|type_argument| {
+                            self.resolve_type_with_self(type_argument.clone(), self_type)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    decl.instantiate(&type_arguments)
                 }
-            }
+                None if is_builtin_type_name(name) => {
+                    let type_arguments = type_arguments
+                        .iter()
+                        .map(|type_argument| {
+                            self.resolve_type_with_self(type_argument.clone(), self_type)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    insert_type(builtin_type(name, &type_arguments)?)
+                }
+                _ => {
+                    return Err(CompileError::UnknownType {
+                        span: name.span().clone(),
+                    })
+                }
+            },
             TypeInfo::SelfType => self_type,
             TypeInfo::Ref(id) => id,
+            // The elements of tuples and arrays may themselves name custom types which need
+            // resolving, e.g. the alias `type Entry = (Key, u64);`.
+            TypeInfo::Tuple(fields) => insert_type(TypeInfo::Tuple(
+                fields
+                    .into_iter()
+                    .map(|field| self.resolve_inner_type_with_self(field, self_type))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            TypeInfo::Array(elem_ty, count) => insert_type(TypeInfo::Array(
+                self.resolve_inner_type_with_self(elem_ty, self_type)?,
+                count,
+            )),
+            TypeInfo::Vector(elem_ty) => insert_type(TypeInfo::Vector(
+                self.resolve_inner_type_with_self(elem_ty, self_type)?,
+            )),
             o => insert_type(o),
         })
     }
+    fn resolve_inner_type_with_self(
+        &self,
+        ty: TypeId,
+        self_type: TypeId,
+    ) -> Result<TypeId, CompileError> {
+        match look_up_type_id(ty) {
+            inner @ (TypeInfo::Custom { .. }
+            | TypeInfo::Tuple(_)
+            | TypeInfo::Array(..)
+            | TypeInfo::Vector(_)) => self.resolve_type_with_self(inner, self_type),
+            _ => Ok(ty),
+        }
+    }
     fn resolve_type_without_self(&self, ty: &TypeInfo) -> TypeId {
         let ty = ty.clone();
         let mut warnings = vec![];
         let mut errors = vec![];
         match ty {
-            TypeInfo::Custom { name } => {
-                match self.get_symbol(&name).ok(&mut warnings, &mut errors) {
-                    Some(TypedDeclaration::StructDeclaration(TypedStructDeclaration {
-                        name,
-                        fields,
-                        ..
-                    })) => crate::type_engine::insert_type(TypeInfo::Struct {
-                        name: name.as_str().to_string(),
-                        fields: fields
-                            .iter()
-                            .map(TypedStructField::as_owned_typed_struct_field)
-                            .collect::<Vec<_>>(),
-                    }),
-                    Some(TypedDeclaration::EnumDeclaration(TypedEnumDeclaration {
-                        name,
-                        variants,
-                        ..
-                    })) => crate::type_engine::insert_type(TypeInfo::Enum {
-                        name: name.as_str().to_string(),
-                        variant_types: variants
-                            .iter()
-                            .map(TypedEnumVariant::as_owned_typed_enum_variant)
-                            .collect(),
-                    }),
-                    _ => crate::type_engine::insert_type(TypeInfo::Unknown),
+            TypeInfo::Custom {
+                name,
+                type_arguments,
+            } => match self.get_symbol(&name).ok(&mut warnings, &mut errors) {
+                Some(TypedDeclaration::StructDeclaration(TypedStructDeclaration {
+                    name,
+                    fields,
+                    ..
+                })) => crate::type_engine::insert_type(TypeInfo::Struct {
+                    name: name.as_str().to_string(),
+                    fields: fields
+                        .iter()
+                        .map(TypedStructField::as_owned_typed_struct_field)
+                        .collect::<Vec<_>>(),
+                }),
+                Some(TypedDeclaration::EnumDeclaration(TypedEnumDeclaration {
+                    name,
+                    variants,
+                    ..
+                })) => crate::type_engine::insert_type(TypeInfo::Enum {
+                    name: name.as_str().to_string(),
+                    variant_types: variants
+                        .iter()
+                        .map(TypedEnumVariant::as_owned_typed_enum_variant)
+                        .collect(),
+                }),
+                Some(TypedDeclaration::TypeAliasDeclaration(decl))
+                    if decl.type_parameters.is_empty() =>
+                {
+                    decl.instantiate(&[])
                 }
-            }
+                None if is_builtin_type_name(&name) => {
+                    let type_arguments = type_arguments
+                        .iter()
+                        .map(|type_argument| self.resolve_type_without_self(type_argument))
+                        .collect::<Vec<_>>();
+                    insert_type(builtin_type(&name, &type_arguments).unwrap_or(TypeInfo::Unknown))
+                }
+                _ => crate::type_engine::insert_type(TypeInfo::Unknown),
+            },
             TypeInfo::Ref(id) => id,
             o => insert_type(o),
         }
     }
+    fn resolve_builtin_type(
+        &self,
+        ty: &TypeInfo,
+        self_type: TypeId,
+    ) -> Option<Result<TypeId, CompileError>> {
+        match ty {
+            TypeInfo::Custom { name, .. }
+                if is_builtin_type_name(name) && self.get_symbol(name).value.is_none() =>
+            {
+                Some(self.resolve_type_with_self(ty.clone(), self_type))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Create a new module ([Namespace]), insert it into the arena, and get its id back.
@@ -732,6 +840,28 @@ where
     res
 }
 
+/// `Vec` and `String` are built into the compiler, but aren't keywords: a declaration with one of
+/// their names takes their place.
+fn is_builtin_type_name(name: &Ident) -> bool {
+    matches!(name.as_str(), "Vec" | "String")
+}
+
+/// The built in type `name` refers to, given its resolved type arguments. A bare `Vec` is a vector
+/// of elements of a type which is yet to be inferred, as in `~Vec::new()`.
+#[allow(clippy::result_large_err)]
+fn builtin_type(name: &Ident, type_arguments: &[TypeId]) -> Result<TypeInfo, CompileError> {
+    match (name.as_str(), type_arguments) {
+        ("Vec", []) => Ok(TypeInfo::Vector(insert_type(TypeInfo::Unknown))),
+        ("Vec", [elem_type]) => Ok(TypeInfo::Vector(*elem_type)),
+        ("String", []) => Ok(TypeInfo::DynamicString),
+        _ => Err(CompileError::IncorrectNumberOfTypeArguments {
+            given: type_arguments.len(),
+            expected: if name.as_str() == "Vec" { 1 } else { 0 },
+            span: name.span().clone(),
+        }),
+    }
+}
+
 lazy_static! {
     /// The arena which contains all modules in all dependencies and the main compilation target.
     pub static ref MODULES: RwLock<Arena<Namespace>> = Default::default();
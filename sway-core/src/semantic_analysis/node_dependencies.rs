@@ -2,8 +2,12 @@ use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
 use crate::{
-    error::*, parse_tree::Scrutinee, parse_tree::*, type_engine::IntegerBits, AstNode,
-    AstNodeContent, CodeBlock, Declaration, Expression, ReturnStatement, TypeInfo, WhileLoop,
+    error::*,
+    parse_tree::Scrutinee,
+    parse_tree::*,
+    type_engine::{look_up_type_id, IntegerBits},
+    AstNode, AstNodeContent, CodeBlock, Declaration, Expression, ReturnStatement, TypeInfo,
+    WhileLoop,
 };
 
 use sway_types::{ident::Ident, span::Span};
@@ -18,6 +22,7 @@ pub(crate) fn order_ast_nodes_by_dependency(nodes: Vec<AstNode>) -> CompileResul
 
     // Check here for recursive calls now that we have a nice map of the dependencies to help us.
     let mut errors = find_recursive_calls(&decl_dependencies);
+    errors.extend(find_recursive_type_aliases(&nodes, &decl_dependencies));
     if !errors.is_empty() {
         // Because we're pulling these errors out of a HashMap they'll probably be in a funny
         // order.  Here we'll sort them by span start.
@@ -108,19 +113,105 @@ fn build_recursion_error(fn_sym: Ident, span: Span, chain: &[Ident]) -> CompileE
             call_chain: chain[0].as_str().to_string(),
             span,
         },
-        n => {
-            let mut msg = chain[0].as_str().to_string();
-            for ident in &chain[1..(n - 1)] {
-                msg.push_str(", ");
-                msg.push_str(ident.as_str());
-            }
-            msg.push_str(" and ");
-            msg.push_str(chain[n - 1].as_str());
-            CompileError::RecursiveCallChain {
-                fn_name: fn_sym,
-                call_chain: msg,
-                span,
+        _ => CompileError::RecursiveCallChain {
+            fn_name: fn_sym,
+            call_chain: describe_chain(chain),
+            span,
+        },
+    }
+}
+
+/// Lists the symbols in a chain for an error message, e.g., "a, b and c".
+fn describe_chain(chain: &[Ident]) -> String {
+    let n = chain.len();
+    let mut msg = chain[0].as_str().to_string();
+    if n > 1 {
+        for ident in &chain[1..(n - 1)] {
+            msg.push_str(", ");
+            msg.push_str(ident.as_str());
+        }
+        msg.push_str(" and ");
+        msg.push_str(chain[n - 1].as_str());
+    }
+    msg
+}
+
+// -------------------------------------------------------------------------------------------------
+// Type alias cycle detection.  An alias which refers back to itself, e.g., `type A = B; type B = A;`,
+// never reaches a real type.
+
+fn find_recursive_type_aliases(
+    nodes: &[AstNode],
+    decl_dependencies: &DependencyMap,
+) -> Vec<CompileError> {
+    let aliases = nodes
+        .iter()
+        .filter_map(|node| match &node.content {
+            AstNodeContent::Declaration(Declaration::TypeAliasDeclaration(decl)) => {
+                Some(&decl.name)
             }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    aliases
+        .iter()
+        .filter_map(|alias| {
+            let mut chain = Vec::new();
+            find_recursive_type_alias_chain(decl_dependencies, &aliases, alias, &mut chain)
+        })
+        .collect()
+}
+
+fn find_recursive_type_alias_chain(
+    decl_dependencies: &DependencyMap,
+    aliases: &[&Ident],
+    alias: &Ident,
+    chain: &mut Vec<Ident>,
+) -> Option<CompileError> {
+    if chain.iter().any(|seen| seen.as_str() == alias.as_str()) {
+        // As with recursive calls, only report the cycle from the alias at the start of the chain
+        // if it is actually in the cycle.
+        return if chain[0].as_str() != alias.as_str() {
+            None
+        } else {
+            Some(build_type_alias_recursion_error(
+                chain[0].clone(),
+                &chain[1..],
+            ))
+        };
+    }
+    decl_dependencies
+        .get(&DependentSymbol::Symbol(alias.as_str().to_string()))
+        .and_then(|deps_set| {
+            chain.push(alias.clone());
+            let result = deps_set.deps.iter().find_map(|dep_sym| match dep_sym {
+                DependentSymbol::Symbol(dep_name) => aliases
+                    .iter()
+                    .find(|dep_alias| dep_alias.as_str() == dep_name)
+                    .and_then(|dep_alias| {
+                        find_recursive_type_alias_chain(
+                            decl_dependencies,
+                            aliases,
+                            dep_alias,
+                            chain,
+                        )
+                    }),
+                _ => None,
+            });
+            chain.pop();
+            result
+        })
+}
+
+fn build_type_alias_recursion_error(name: Ident, chain: &[Ident]) -> CompileError {
+    let span = name.span().clone();
+    if chain.is_empty() {
+        CompileError::RecursiveTypeAlias { name, span }
+    } else {
+        CompileError::RecursiveTypeAliasChain {
+            name,
+            alias_chain: describe_chain(chain),
+            span,
         }
     }
 }
@@ -319,6 +410,9 @@ impl Dependencies {
                             .gather_from_expr(initializer)
                     },
                 ),
+            Declaration::TypeAliasDeclaration(TypeAliasDeclaration { r#type, .. }) => {
+                self.gather_from_typeinfo(r#type)
+            }
         }
     }
 
@@ -492,10 +586,24 @@ impl Dependencies {
     }
 
     fn gather_from_typeinfo(mut self, type_info: &TypeInfo) -> Self {
-        if let TypeInfo::Custom { name } = type_info {
-            self.deps.insert(DependentSymbol::Symbol(name.to_string()));
+        match type_info {
+            TypeInfo::Custom {
+                name,
+                type_arguments,
+            } => {
+                self.deps.insert(DependentSymbol::Symbol(name.to_string()));
+                self.gather_from_iter(type_arguments.iter(), |deps, type_argument| {
+                    deps.gather_from_typeinfo(type_argument)
+                })
+            }
+            TypeInfo::Tuple(fields) => self.gather_from_iter(fields.iter(), |deps, field| {
+                deps.gather_from_typeinfo(&look_up_type_id(*field))
+            }),
+            TypeInfo::Array(elem_type, _) | TypeInfo::Vector(elem_type) => {
+                self.gather_from_typeinfo(&look_up_type_id(*elem_type))
+            }
+            _ => self,
         }
-        self
     }
 
     fn gather_from_iter<I: Iterator, F: FnMut(Self, I::Item) -> Self>(self, iter: I, f: F) -> Self {
@@ -566,6 +674,7 @@ fn decl_name(decl: &Declaration) -> Option<DependentSymbol> {
         Declaration::EnumDeclaration(decl) => dep_sym(decl.name.as_str().to_string()),
         Declaration::TraitDeclaration(decl) => dep_sym(decl.name.as_str().to_string()),
         Declaration::AbiDeclaration(decl) => dep_sym(decl.name.as_str().to_string()),
+        Declaration::TypeAliasDeclaration(decl) => dep_sym(decl.name.as_str().to_string()),
 
         // These have the added complexity of converting CallPath and/or TypeInfo into a name.
         Declaration::ImplSelf(decl) => {
@@ -600,7 +709,7 @@ fn type_info_name(type_info: &TypeInfo) -> String {
             IntegerBits::SixtyFour => "uint64",
         },
         TypeInfo::Boolean => "bool",
-        TypeInfo::Custom { name } => name.as_str(),
+        TypeInfo::Custom { name, .. } | TypeInfo::Alias { name, .. } => name.as_str(),
         TypeInfo::Tuple(fields) if fields.is_empty() => "unit",
         TypeInfo::Tuple(..) => "tuple",
         TypeInfo::SelfType => "self",
@@ -616,6 +725,8 @@ fn type_info_name(type_info: &TypeInfo) -> String {
         TypeInfo::Struct { .. } => "struct",
         TypeInfo::Enum { .. } => "enum",
         TypeInfo::Array(..) => "array",
+        TypeInfo::Vector(..) => "vec",
+        TypeInfo::DynamicString => "string",
     }
     .to_string()
 }
@@ -0,0 +1,1340 @@
+//! An index of the names in a type checked program, for editor tooling such as the language
+//! server. Each [Symbol] ties a name as it is written in the source to its resolved type and to
+//! the place it was declared, which may be in another module or package. [InlayHint]s show what
+//! was inferred where the source leaves it out.
+use super::{
+    ast_node::{declaration::TypedTraitFn, *},
+    *,
+};
+use crate::type_engine::{look_up_type_id, FriendlyTypeString, TypeId, TypeInfo};
+use crate::{CallPath, Ident};
+
+use sway_types::span::Span;
+
+/// What kind of item a [Symbol] names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Variable,
+    Constant,
+    Parameter,
+    Function,
+    Struct,
+    Field,
+    Enum,
+    Variant,
+    Trait,
+    Abi,
+    TypeAlias,
+}
+
+/// A single occurrence of a name in a type checked program, either where it is declared or where
+/// it is used.
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    /// The name as it is written at this occurrence.
+    pub ident: Ident,
+    pub kind: SymbolKind,
+    /// The name as it is written where the item was declared, if the declaration could be found.
+    /// Its span records the file of the declaration.
+    pub declaration: Option<Ident>,
+    /// The type of a variable, parameter or field, the return type of a function, or the type a
+    /// struct, enum or type alias declares.
+    pub type_id: Option<TypeId>,
+    /// The declaration on a single line with its types resolved, such as `let mut x: u64` or
+    /// `fn add(a: u64, b: u64) -> u64`.
+    pub detail: String,
+    /// The parameters of a function, each as `name: type` the way they appear in `detail`.
+    pub parameters: Vec<String>,
+}
+
+impl Symbol {
+    /// Whether this occurrence is the declaration of the item rather than a use of it.
+    pub fn is_declaration(&self) -> bool {
+        match &self.declaration {
+            Some(declaration) => declaration.span() == self.ident.span(),
+            None => false,
+        }
+    }
+
+    /// The same item, named at another occurrence.
+    fn used_at(&self, ident: Ident) -> Symbol {
+        Symbol {
+            ident,
+            ..self.clone()
+        }
+    }
+}
+
+/// What an [InlayHint] shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InlayHintKind {
+    /// The inferred type of a variable, shown after its name.
+    Type,
+    /// The name of the parameter an argument is passed to, shown before the argument.
+    Parameter,
+}
+
+/// A label for an editor to show inline, for something that was inferred rather than written.
+#[derive(Clone, Debug)]
+pub struct InlayHint {
+    /// The source the hint belongs to: the name of a variable, or an argument.
+    pub span: Span,
+    pub kind: InlayHintKind,
+    /// The text to show, such as `: u64` or `amount:`.
+    pub label: String,
+}
+
+impl TypedParseTree {
+    /// Every name written in this program, in the order they appear in the tree. Names which were
+    /// generated by the compiler, such as those of the functions operators desugar to, are left
+    /// out.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.collect_symbols().symbols
+    }
+
+    /// The types of the variables declared without a type annotation, including those bound by
+    /// destructuring and by `match` arms, and the names of the parameters arguments are passed to.
+    pub fn inlay_hints(&self) -> Vec<InlayHint> {
+        self.collect_symbols().hints
+    }
+
+    fn collect_symbols(&self) -> SymbolCollector {
+        let mut collector = SymbolCollector {
+            namespace: self.namespace_ref(),
+            scopes: vec![vec![]],
+            symbols: vec![],
+            hints: vec![],
+        };
+        for node in self.all_nodes() {
+            collector.visit_node(node);
+        }
+        collector.visit_imports();
+        collector
+    }
+
+    /// The items that can be named at the top level of this program: those declared in it and
+    /// those brought in with `use`.
+    pub fn visible_declarations(&self) -> Vec<Symbol> {
+        let namespace = self.namespace_ref();
+        let mut declarations = read_module(
+            |m| {
+                m.get_all_declared_symbols()
+                    .filter_map(declaration_symbol)
+                    .collect::<Vec<_>>()
+            },
+            namespace,
+        );
+        let imported_names = read_module(
+            |m| m.get_all_imported_names().cloned().collect::<Vec<_>>(),
+            namespace,
+        );
+        for name in imported_names {
+            if let Some(symbol) = namespace
+                .get_symbol(&name)
+                .value
+                .as_ref()
+                .and_then(declaration_symbol)
+            {
+                declarations.push(symbol);
+            }
+        }
+        declarations
+    }
+
+    /// The root namespace of this program, for looking up members and paths with
+    /// [members_of_type] and [items_at_path].
+    pub fn namespace_ref(&self) -> NamespaceRef {
+        use TypedParseTree::*;
+        match self {
+            Library { namespace, .. }
+            | Script { namespace, .. }
+            | Contract { namespace, .. }
+            | Predicate { namespace, .. } => *namespace,
+        }
+    }
+}
+
+/// The fields and methods that can follow a `.` on a value of type `type_id`, with methods found
+/// through the trait implementations visible in `namespace`.
+pub fn members_of_type(namespace: NamespaceRef, type_id: TypeId) -> Vec<Symbol> {
+    let mut members = vec![];
+    if let TypeInfo::Struct { name, .. } = look_up_type_id(type_id) {
+        if let Some(decl) = find_struct(namespace, &name) {
+            members.extend(decl.fields.iter().map(field_symbol));
+        }
+    }
+    members.extend(methods_of_type(namespace, type_id));
+    members
+}
+
+/// The items that can follow `path::`: the items declared in the module at `path`, or the variants
+/// and methods of the enum or struct it names.
+pub fn items_at_path(namespace: NamespaceRef, path: &[&str]) -> Vec<Symbol> {
+    let path = path
+        .iter()
+        .map(|name| ident_from_str(name))
+        .collect::<Vec<_>>();
+    let (suffix, prefixes) = match path.split_last() {
+        Some(split) => split,
+        None => return vec![],
+    };
+
+    if let Some(module) = namespace.find_module_relative(&path).value {
+        return read_module(
+            |m| {
+                m.get_all_declared_symbols()
+                    .filter_map(declaration_symbol)
+                    .collect()
+            },
+            module,
+        );
+    }
+
+    let call_path = CallPath {
+        prefixes: prefixes.to_vec(),
+        suffix: suffix.clone(),
+    };
+    match namespace.get_call_path(&call_path).value {
+        Some(TypedDeclaration::EnumDeclaration(decl)) => {
+            let mut items = decl.variants.iter().map(variant_symbol).collect::<Vec<_>>();
+            items.extend(methods_of_type(namespace, decl.as_type()));
+            items
+        }
+        Some(decl @ TypedDeclaration::StructDeclaration(..)) => match decl.return_type().value {
+            Some(type_id) => methods_of_type(namespace, type_id),
+            None => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+/// The paths a `use` statement could import a public item named `name` by, such as
+/// `["std", "chain", "log_u64"]`, searching every module reachable from `namespace` and from the
+/// `modules` given with their names, such as those a project includes with `dep`.
+pub fn import_paths(
+    namespace: NamespaceRef,
+    modules: &[(String, Namespace)],
+    name: &str,
+) -> Vec<Vec<String>> {
+    let ident = ident_from_str(name);
+    let declares_name = |module: &Namespace| matches!(module.get_declared_symbol(&ident), Some(decl) if decl.visibility().is_public());
+    let import_path = |path: &[String]| {
+        let mut import_path = path.to_vec();
+        import_path.push(name.to_string());
+        import_path
+    };
+
+    let mut paths = vec![];
+    let mut visited = vec![namespace];
+    let mut pending = read_module(|m| named_modules(m, &[]), namespace);
+    for (module_name, module) in modules {
+        let path = vec![module_name.clone()];
+        if declares_name(module) {
+            paths.push(import_path(&path));
+        }
+        pending.extend(named_modules(module, &path));
+    }
+    while let Some((path, module)) = pending.pop() {
+        if visited.contains(&module) {
+            continue;
+        }
+        visited.push(module);
+        let (declares_name, submodules) =
+            read_module(|m| (declares_name(m), named_modules(m, &path)), module);
+        if declares_name {
+            paths.push(import_path(&path));
+        }
+        pending.extend(submodules);
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// The modules of `module`, each with its path from the root of the search.
+pub(super) fn named_modules(
+    module: &Namespace,
+    path: &[String],
+) -> Vec<(Vec<String>, NamespaceRef)> {
+    module
+        .get_all_named_modules()
+        .map(|(name, module)| {
+            let mut module_path = path.to_vec();
+            module_path.push(name.clone());
+            (module_path, *module)
+        })
+        .collect()
+}
+
+fn methods_of_type(namespace: NamespaceRef, type_id: TypeId) -> Vec<Symbol> {
+    namespace
+        .get_methods_for_type(type_id)
+        .iter()
+        .map(function_symbol)
+        .collect()
+}
+
+/// Makes an identifier for a name which isn't taken from any source file.
+fn ident_from_str(name: &str) -> Ident {
+    let span = pest::Span::new(name.into(), 0, name.len()).expect("the span covers the name");
+    Ident::new(Span { span, path: None })
+}
+
+struct SymbolCollector {
+    /// The root namespace of the program, for items declared outside of any function.
+    namespace: NamespaceRef,
+    /// The variables and parameters in scope, innermost scope last.
+    scopes: Vec<Vec<Symbol>>,
+    symbols: Vec<Symbol>,
+    hints: Vec<InlayHint>,
+}
+
+impl SymbolCollector {
+    fn add(&mut self, symbol: Symbol) {
+        if is_written(&symbol.ident) {
+            self.symbols.push(symbol);
+        }
+    }
+
+    /// Adds a variable or parameter declaration, making it visible to the rest of the scope.
+    fn declare(&mut self, symbol: Symbol) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(symbol.clone());
+        }
+        self.add(symbol);
+    }
+
+    /// Adds the type of a `let` binding, unless it was written out.
+    fn add_type_hint(&mut self, decl: &TypedVariableDeclaration) {
+        let is_let = !decl.const_decl_origin
+            && !matches!(decl.is_mutable, VariableMutability::ExportedConst);
+        if !is_let || !is_written(&decl.name) || is_annotated(&decl.name) {
+            return;
+        }
+        if let Some(label) = source_type_str(decl.body.return_type) {
+            self.hints.push(InlayHint {
+                span: decl.name.span().clone(),
+                kind: InlayHintKind::Type,
+                label: format!(": {}", label),
+            });
+        }
+    }
+
+    /// Adds the names of the parameters the arguments of a call are passed to. The receiver of a
+    /// method is left out, as are arguments which are variables of the same name.
+    fn add_parameter_hints(&mut self, arguments: &[(Ident, TypedExpression)]) {
+        for (name, arg) in arguments {
+            let is_same_variable = matches!(
+                &arg.expression,
+                TypedExpressionVariant::VariableExpression { name: variable } if variable == name
+            );
+            if name.as_str() == "self" || is_same_variable {
+                continue;
+            }
+            self.hints.push(InlayHint {
+                span: arg.span.clone(),
+                kind: InlayHintKind::Parameter,
+                label: format!("{}:", name.as_str()),
+            });
+        }
+    }
+
+    fn add_use(&mut self, ident: &Ident, declaration: Option<Symbol>) {
+        if let Some(declaration) = declaration {
+            self.add(declaration.used_at(ident.clone()));
+        }
+    }
+
+    /// Adds the names of the types, traits and ABIs written in the type annotation, `impl` header
+    /// or other source at `span`.
+    fn add_type_uses(&mut self, span: &Span) {
+        for name in names_in(span) {
+            let declaration = self
+                .namespace
+                .get_symbol(&name)
+                .value
+                .as_ref()
+                .and_then(declaration_symbol)
+                .filter(|symbol| {
+                    matches!(
+                        symbol.kind,
+                        SymbolKind::Struct
+                            | SymbolKind::Enum
+                            | SymbolKind::TypeAlias
+                            | SymbolKind::Trait
+                            | SymbolKind::Abi
+                    )
+                });
+            self.add_use(&name, declaration);
+        }
+    }
+
+    /// Adds the items named in the `use` statements of the program.
+    fn visit_imports(&mut self) {
+        let items = read_module(
+            |m| {
+                m.get_all_imported_items()
+                    .map(|(item, name)| (item.clone(), name.clone()))
+                    .collect::<Vec<_>>()
+            },
+            self.namespace,
+        );
+        for (item, name) in items {
+            let declaration = self
+                .namespace
+                .get_symbol(&name)
+                .value
+                .as_ref()
+                .and_then(declaration_symbol)
+                .map(|declaration| declaration.used_at(item.clone()));
+            // the items of a `*` import are named where they are declared, not in the statement
+            if let Some(symbol) = declaration.filter(|symbol| !symbol.is_declaration()) {
+                self.add(symbol);
+            }
+        }
+    }
+
+    /// Finds what `name` refers to where it is used, preferring the innermost local.
+    fn resolve(&self, name: &Ident) -> Option<Symbol> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|symbol| symbol.ident.as_str() == name.as_str())
+            .cloned()
+            .or_else(|| {
+                self.namespace
+                    .get_symbol(name)
+                    .value
+                    .as_ref()
+                    .and_then(declaration_symbol)
+            })
+    }
+
+    fn visit_node(&mut self, node: &TypedAstNode) {
+        match &node.content {
+            TypedAstNodeContent::ReturnStatement(TypedReturnStatement { expr })
+            | TypedAstNodeContent::Expression(expr)
+            | TypedAstNodeContent::ImplicitReturnExpression(expr) => self.visit_expression(expr),
+            TypedAstNodeContent::Declaration(decl) => self.visit_declaration(decl),
+            TypedAstNodeContent::WhileLoop(TypedWhileLoop { condition, body }) => {
+                self.visit_expression(condition);
+                self.visit_code_block(body);
+            }
+            TypedAstNodeContent::SideEffect => (),
+        }
+    }
+
+    fn visit_code_block(&mut self, block: &TypedCodeBlock) {
+        self.scopes.push(vec![]);
+        for node in &block.contents {
+            self.visit_node(node);
+        }
+        self.scopes.pop();
+    }
+
+    fn visit_function(&mut self, decl: &TypedFunctionDeclaration) {
+        self.add(function_symbol(decl));
+        self.scopes.push(vec![]);
+        for param in &decl.parameters {
+            self.declare(Symbol {
+                ident: param.name.clone(),
+                kind: SymbolKind::Parameter,
+                declaration: Some(param.name.clone()),
+                type_id: Some(param.r#type),
+                detail: typed_name(&param.name, param.r#type),
+                parameters: vec![],
+            });
+            self.add_type_uses(&param.type_span);
+        }
+        self.add_type_uses(&decl.return_type_span);
+        self.visit_code_block(&decl.body);
+        self.scopes.pop();
+    }
+
+    fn visit_declaration(&mut self, decl: &TypedDeclaration) {
+        match decl {
+            TypedDeclaration::VariableDeclaration(TypedVariableDeclaration {
+                name, body, ..
+            })
+            | TypedDeclaration::ConstantDeclaration(TypedConstantDeclaration {
+                name,
+                value: body,
+                ..
+            }) => {
+                if let Some(annotation) = annotation_of(name) {
+                    self.add_type_uses(&annotation);
+                }
+                // The initializer can't see the name it initializes.
+                self.visit_expression(body);
+                if let Some(symbol) = declaration_symbol(decl) {
+                    self.declare(symbol);
+                }
+                if let TypedDeclaration::VariableDeclaration(decl) = decl {
+                    self.add_type_hint(decl);
+                }
+            }
+            TypedDeclaration::FunctionDeclaration(decl) => self.visit_function(decl),
+            TypedDeclaration::TraitDeclaration(TypedTraitDeclaration {
+                interface_surface, ..
+            })
+            | TypedDeclaration::AbiDeclaration(TypedAbiDeclaration {
+                interface_surface, ..
+            }) => {
+                self.add_declaration(decl);
+                for trait_fn in interface_surface {
+                    self.add(trait_fn_symbol(trait_fn));
+                    for param in &trait_fn.parameters {
+                        self.add_type_uses(&param.type_span);
+                    }
+                    self.add_type_uses(&trait_fn.return_type_span);
+                }
+            }
+            TypedDeclaration::StructDeclaration(TypedStructDeclaration { fields, .. }) => {
+                self.add_declaration(decl);
+                for field in fields {
+                    self.add(field_symbol(field));
+                    self.add_annotation_uses(&field.name);
+                }
+            }
+            TypedDeclaration::EnumDeclaration(TypedEnumDeclaration { variants, .. }) => {
+                self.add_declaration(decl);
+                for variant in variants {
+                    self.add(variant_symbol(variant));
+                    self.add_annotation_uses(&variant.name);
+                }
+            }
+            TypedDeclaration::TypeAliasDeclaration(..) => self.add_declaration(decl),
+            TypedDeclaration::Reassignment(TypedReassignment { lhs, rhs }) => {
+                self.visit_expression(rhs);
+                // Only the variable itself is resolved, not the fields after it.
+                if let Some(ReassignmentLhs { name, .. }) = lhs.first() {
+                    self.add_use(name, self.resolve(name));
+                }
+            }
+            TypedDeclaration::ImplTrait {
+                trait_name,
+                span,
+                methods,
+                ..
+            } => {
+                let trait_decl = self
+                    .namespace
+                    .get_call_path(trait_name)
+                    .value
+                    .as_ref()
+                    .and_then(declaration_symbol);
+                self.add_use(&trait_name.suffix, trait_decl);
+                // the type the block implements for, named in its header
+                let header_len = span
+                    .as_str()
+                    .find('{')
+                    .unwrap_or_else(|| span.as_str().len());
+                if let Some(header) = sub_span(span, 0, header_len) {
+                    for name in names_in(&header) {
+                        if name.span() != trait_name.suffix.span() {
+                            self.add_type_uses(name.span());
+                        }
+                    }
+                }
+                for method in methods {
+                    self.visit_function(method);
+                }
+            }
+            TypedDeclaration::GenericTypeForFunctionScope { .. }
+            | TypedDeclaration::ErrorRecovery => (),
+        }
+    }
+
+    /// Adds the type uses in the annotation written after the field or variant named `name`, or
+    /// in the type of a tuple struct field, which is named by its position instead.
+    fn add_annotation_uses(&mut self, name: &Ident) {
+        if !is_written(name) {
+            self.add_type_uses(name.span());
+        } else if let Some(annotation) = annotation_of(name) {
+            self.add_type_uses(&annotation);
+        }
+    }
+
+    fn add_declaration(&mut self, decl: &TypedDeclaration) {
+        if let Some(symbol) = declaration_symbol(decl) {
+            self.add(symbol);
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &TypedExpression) {
+        use TypedExpressionVariant::*;
+        match &expr.expression {
+            Literal(_) | FunctionParameter => (),
+            FunctionApplication {
+                name,
+                arguments,
+                decl_name,
+                ..
+            } => {
+                self.add(function_symbol_at(
+                    &name.suffix,
+                    decl_name,
+                    arguments.iter().map(|(name, arg)| (name, arg.return_type)),
+                    expr.return_type,
+                ));
+                // Operators desugar to calls, but their arguments have no names to show.
+                if is_written(&name.suffix) {
+                    self.add_parameter_hints(arguments);
+                }
+                for (_, arg) in arguments {
+                    self.visit_expression(arg);
+                }
+            }
+            LazyOperator { lhs, rhs, .. } => {
+                self.visit_expression(lhs);
+                self.visit_expression(rhs);
+            }
+            VariableExpression { name } => self.add_use(name, self.resolve(name)),
+            Tuple { fields: exprs }
+            | Array { contents: exprs }
+            | VecOperation {
+                arguments: exprs, ..
+            }
+            | StringOperation {
+                arguments: exprs, ..
+            } => {
+                for expr in exprs {
+                    self.visit_expression(expr);
+                }
+            }
+            ArrayIndex { prefix, index } => {
+                self.visit_expression(prefix);
+                self.visit_expression(index);
+            }
+            StructExpression {
+                struct_name,
+                fields,
+            } => {
+                let decl = find_struct(self.namespace, struct_name.as_str());
+                if let (Some(decl), Some(written_name)) = (&decl, leading_name(&expr.span)) {
+                    self.add_use(
+                        &written_name,
+                        declaration_symbol(&TypedDeclaration::StructDeclaration(decl.clone())),
+                    );
+                }
+                for field in fields {
+                    let field_decl = decl.as_ref().and_then(|decl| {
+                        decl.fields
+                            .iter()
+                            .find(|decl_field| decl_field.name == field.name)
+                    });
+                    if let Some(field_decl) = field_decl {
+                        self.add_use(&field.name, Some(field_symbol(field_decl)));
+                    }
+                    self.visit_expression(&field.value);
+                }
+            }
+            CodeBlock(block) => self.visit_code_block(block),
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.visit_expression(condition);
+                self.visit_expression(then);
+                if let Some(r#else) = r#else {
+                    self.visit_expression(r#else);
+                }
+            }
+            AsmExpression { registers, .. } => {
+                for initializer in registers.iter().filter_map(|reg| reg.initializer.as_ref()) {
+                    self.visit_expression(initializer);
+                }
+            }
+            StructFieldAccess {
+                prefix,
+                field_to_access,
+                resolved_type_of_parent,
+                field_to_access_span,
+            } => {
+                self.visit_expression(prefix);
+                let field_decl = match look_up_type_id(*resolved_type_of_parent) {
+                    TypeInfo::Struct { name, .. } => {
+                        find_struct(self.namespace, &name).and_then(|decl| {
+                            decl.fields
+                                .into_iter()
+                                .find(|decl_field| decl_field.name.as_str() == field_to_access.name)
+                        })
+                    }
+                    _ => None,
+                };
+                if let Some(field_decl) = field_decl {
+                    self.add_use(
+                        &Ident::new(field_to_access_span.clone()),
+                        Some(field_symbol(&field_decl)),
+                    );
+                }
+            }
+            EnumArgAccess { prefix, .. } | TupleElemAccess { prefix, .. } => {
+                self.visit_expression(prefix)
+            }
+            EnumInstantiation {
+                enum_decl,
+                variant_name,
+                contents,
+                ..
+            } => {
+                let variant_decl = enum_decl
+                    .variants
+                    .iter()
+                    .find(|variant| variant.name == *variant_name);
+                self.add_use(variant_name, variant_decl.map(variant_symbol));
+                if let Some(contents) = contents {
+                    self.visit_expression(contents);
+                }
+            }
+            AbiCast {
+                abi_name, address, ..
+            } => {
+                let abi_decl = self
+                    .namespace
+                    .get_call_path(abi_name)
+                    .value
+                    .as_ref()
+                    .and_then(declaration_symbol);
+                self.add_use(&abi_name.suffix, abi_decl);
+                self.visit_expression(address);
+            }
+        }
+    }
+}
+
+/// Finds a struct named `name` that is declared in or imported into `namespace`.
+fn find_struct(namespace: NamespaceRef, name: &str) -> Option<TypedStructDeclaration> {
+    let is_struct_named = |decl: &TypedDeclaration| match decl {
+        TypedDeclaration::StructDeclaration(decl) if decl.name.as_str() == name => {
+            Some(decl.clone())
+        }
+        _ => None,
+    };
+    read_module(
+        |m| {
+            m.get_all_declared_symbols()
+                .find_map(is_struct_named)
+                .or_else(|| {
+                    m.get_all_imported_names()
+                        .filter(|imported| imported.as_str() == name)
+                        .find_map(|imported| {
+                            namespace
+                                .get_symbol(imported)
+                                .value
+                                .as_ref()
+                                .and_then(is_struct_named)
+                        })
+                })
+        },
+        namespace,
+    )
+}
+
+/// Whether `ident` appears in the source as it is, rather than being made up by the compiler.
+fn is_written(ident: &Ident) -> bool {
+    ident.as_str() == ident.span().as_str()
+}
+
+/// Whether the variable declared at `name` is followed by a type annotation in the source.
+fn is_annotated(name: &Ident) -> bool {
+    let span = name.span();
+    span.input()[span.end()..].trim_start().starts_with(':')
+}
+
+/// The type annotation written after the variable, field or variant declared at `name`, from its
+/// `:` up to the end of the type.
+fn annotation_of(name: &Ident) -> Option<Span> {
+    let span = name.span();
+    let rest = &span.input()[span.end()..];
+    let colon = rest.len() - rest.trim_start().len();
+    if !rest[colon..].starts_with(':') {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut len = rest.len();
+    for (i, c) in rest.char_indices().skip(colon + 1) {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' if depth > 0 => depth -= 1,
+            ',' | ';' | '=' | '{' | '}' | ')' if depth == 0 => {
+                len = i;
+                break;
+            }
+            _ => (),
+        }
+    }
+    sub_span(
+        span,
+        span.end() - span.start() + colon + 1,
+        span.end() - span.start() + len,
+    )
+}
+
+/// The names, such as those of types, written in the source at `span`.
+fn names_in(span: &Span) -> Vec<Ident> {
+    let text = span.as_str();
+    let mut names = vec![];
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !(c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, c)) = chars.peek() {
+            if !(c.is_alphanumeric() || c == '_') {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        // numbers, such as the lengths of arrays, aren't names
+        if !c.is_numeric() {
+            names.extend(sub_span(span, start, end).map(Ident::new));
+        }
+    }
+    names
+}
+
+/// The part of `span` from `start` to `end`, as offsets from its start.
+fn sub_span(span: &Span, start: usize, end: usize) -> Option<Span> {
+    let sub = pest::Span::new(
+        span.input().into(),
+        span.start() + start,
+        span.start() + end,
+    )?;
+    Some(Span {
+        span: sub,
+        path: span.path.clone(),
+    })
+}
+
+/// `type_id` the way it would be written in a type annotation, or nothing if it couldn't be
+/// inferred.
+pub(super) fn source_type_str(type_id: TypeId) -> Option<String> {
+    let type_str = match look_up_type_id(type_id) {
+        TypeInfo::Unknown | TypeInfo::ErrorRecovery | TypeInfo::Custom { .. } => return None,
+        // numbers which nothing constrains are compiled as `u64`
+        TypeInfo::Numeric => "u64".into(),
+        TypeInfo::Struct { name, .. } | TypeInfo::Enum { name, .. } => name,
+        TypeInfo::Alias { name, .. } => name.as_str().to_string(),
+        TypeInfo::Ref(id) => return source_type_str(id),
+        TypeInfo::Tuple(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| source_type_str(*field))
+                .collect::<Option<Vec<_>>>()?;
+            format!("({})", fields.join(", "))
+        }
+        TypeInfo::Array(elem_ty, count) => format!("[{}; {}]", source_type_str(elem_ty)?, count),
+        TypeInfo::Vector(elem_ty) => format!("Vec<{}>", source_type_str(elem_ty)?),
+        type_info => type_info.friendly_type_str(),
+    };
+    Some(type_str)
+}
+
+/// The name at the start of `span`. The typed form of a struct expression only keeps the name of
+/// the struct where it was declared, so this recovers the name as it was written.
+fn leading_name(span: &Span) -> Option<Ident> {
+    let len = span
+        .as_str()
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or_else(|| span.as_str().len());
+    if len == 0 {
+        return None;
+    }
+    let name = pest::Span::new(span.input().into(), span.start(), span.start() + len)?;
+    Some(Ident::new(Span {
+        span: name,
+        path: span.path.clone(),
+    }))
+}
+
+fn declaration_symbol(decl: &TypedDeclaration) -> Option<Symbol> {
+    let declared = |name: &Ident, kind, type_id, detail| {
+        Some(Symbol {
+            ident: name.clone(),
+            kind,
+            declaration: Some(name.clone()),
+            type_id,
+            detail,
+            parameters: vec![],
+        })
+    };
+    match decl {
+        TypedDeclaration::VariableDeclaration(TypedVariableDeclaration {
+            name,
+            body,
+            is_mutable,
+            ..
+        }) => {
+            let (kind, keyword) = match is_mutable {
+                VariableMutability::Mutable => (SymbolKind::Variable, "let mut"),
+                VariableMutability::Immutable => (SymbolKind::Variable, "let"),
+                VariableMutability::ExportedConst => (SymbolKind::Constant, "const"),
+            };
+            let detail = format!("{} {}", keyword, typed_name(name, body.return_type));
+            declared(name, kind, Some(body.return_type), detail)
+        }
+        TypedDeclaration::ConstantDeclaration(TypedConstantDeclaration { name, value, .. }) => {
+            let detail = format!("const {}", typed_name(name, value.return_type));
+            declared(name, SymbolKind::Constant, Some(value.return_type), detail)
+        }
+        TypedDeclaration::FunctionDeclaration(decl) => Some(function_symbol(decl)),
+        TypedDeclaration::TraitDeclaration(TypedTraitDeclaration { name, .. }) => {
+            let detail = format!("trait {}", name.as_str());
+            declared(name, SymbolKind::Trait, None, detail)
+        }
+        TypedDeclaration::StructDeclaration(TypedStructDeclaration { name, .. }) => {
+            let detail = format!("struct {}", name.as_str());
+            declared(name, SymbolKind::Struct, decl.return_type().value, detail)
+        }
+        TypedDeclaration::EnumDeclaration(decl) => {
+            let detail = format!("enum {}", decl.name.as_str());
+            declared(&decl.name, SymbolKind::Enum, Some(decl.as_type()), detail)
+        }
+        TypedDeclaration::AbiDeclaration(TypedAbiDeclaration { name, .. }) => {
+            let detail = format!("abi {}", name.as_str());
+            declared(name, SymbolKind::Abi, None, detail)
+        }
+        TypedDeclaration::TypeAliasDeclaration(TypedTypeAliasDeclaration {
+            name, r#type, ..
+        }) => {
+            let detail = format!("type {} = {}", name.as_str(), detail_type_str(*r#type));
+            declared(name, SymbolKind::TypeAlias, Some(*r#type), detail)
+        }
+        TypedDeclaration::Reassignment(..)
+        | TypedDeclaration::ImplTrait { .. }
+        | TypedDeclaration::GenericTypeForFunctionScope { .. }
+        | TypedDeclaration::ErrorRecovery => None,
+    }
+}
+
+fn function_symbol(decl: &TypedFunctionDeclaration) -> Symbol {
+    function_symbol_at(
+        &decl.name,
+        &decl.name,
+        decl.parameters
+            .iter()
+            .map(|param| (&param.name, param.r#type)),
+        decl.return_type,
+    )
+}
+
+fn trait_fn_symbol(trait_fn: &TypedTraitFn) -> Symbol {
+    function_symbol_at(
+        &trait_fn.name,
+        &trait_fn.name,
+        trait_fn
+            .parameters
+            .iter()
+            .map(|param| (&param.name, param.r#type)),
+        trait_fn.return_type,
+    )
+}
+
+/// A function named at `ident`, which was declared at `declaration`.
+fn function_symbol_at<'a>(
+    ident: &Ident,
+    declaration: &Ident,
+    parameters: impl Iterator<Item = (&'a Ident, TypeId)>,
+    return_type: TypeId,
+) -> Symbol {
+    let parameters = parameters
+        .map(|(name, type_id)| typed_name(name, type_id))
+        .collect::<Vec<_>>();
+    let detail = if look_up_type_id(return_type).is_unit() {
+        format!("fn {}({})", declaration.as_str(), parameters.join(", "))
+    } else {
+        format!(
+            "fn {}({}) -> {}",
+            declaration.as_str(),
+            parameters.join(", "),
+            detail_type_str(return_type)
+        )
+    };
+    Symbol {
+        ident: ident.clone(),
+        kind: SymbolKind::Function,
+        declaration: Some(declaration.clone()),
+        type_id: Some(return_type),
+        detail,
+        parameters,
+    }
+}
+
+fn field_symbol(field: &TypedStructField) -> Symbol {
+    Symbol {
+        ident: field.name.clone(),
+        kind: SymbolKind::Field,
+        declaration: Some(field.name.clone()),
+        type_id: Some(field.r#type),
+        detail: typed_name(&field.name, field.r#type),
+        parameters: vec![],
+    }
+}
+
+fn variant_symbol(variant: &TypedEnumVariant) -> Symbol {
+    let detail = if look_up_type_id(variant.r#type).is_unit() {
+        variant.name.as_str().to_string()
+    } else {
+        typed_name(&variant.name, variant.r#type)
+    };
+    Symbol {
+        ident: variant.name.clone(),
+        kind: SymbolKind::Variant,
+        declaration: Some(variant.name.clone()),
+        type_id: Some(variant.r#type),
+        detail,
+        parameters: vec![],
+    }
+}
+
+fn typed_name(name: &Ident, type_id: TypeId) -> String {
+    format!("{}: {}", name.as_str(), detail_type_str(type_id))
+}
+
+/// `type_id` as it would be written in the source, or as the compiler describes it if it
+/// couldn't be inferred.
+fn detail_type_str(type_id: TypeId) -> String {
+    source_type_str(type_id).unwrap_or_else(|| type_id.friendly_type_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile_to_ast, type_engine::TypeEngine, BuildConfig, CompileAstResult};
+    use std::{collections::HashMap, sync::Arc};
+
+    /// Compiles `source` in the type engine entered by the test, so that its types can be looked
+    /// at afterwards.
+    fn compile(source: &str) -> TypedParseTree {
+        compile_in(source, create_module())
+    }
+
+    /// Compiles `source` with the modules of `namespace` in scope.
+    fn compile_in(source: &str, namespace: NamespaceRef) -> TypedParseTree {
+        let build_config =
+            BuildConfig::root_from_file_name_and_manifest_path("main.sw".into(), "".into())
+                .type_engine(TypeEngine::current());
+        match compile_to_ast(
+            Arc::from(source),
+            namespace,
+            &build_config,
+            &mut HashMap::new(),
+        ) {
+            CompileAstResult::Success { parse_tree, .. } => *parse_tree,
+            CompileAstResult::Failure { errors, .. } => panic!("{:?}", errors),
+        }
+    }
+
+    fn symbols_of(source: &str) -> Vec<Symbol> {
+        compile(source).symbols()
+    }
+
+    /// The symbols named `name`, as (is declaration, declaration offset, detail).
+    fn occurrences(symbols: &[Symbol], name: &str) -> Vec<(bool, usize, String)> {
+        symbols
+            .iter()
+            .filter(|symbol| symbol.ident.as_str() == name)
+            .map(|symbol| {
+                (
+                    symbol.is_declaration(),
+                    symbol.declaration.as_ref().unwrap().span().start(),
+                    symbol.detail.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn uses_resolve_to_declarations() {
+        let _type_engine = TypeEngine::new().enter();
+        let source = r#"script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn make(x: u64) -> Point {
+    Point { x: x, y: 2 }
+}
+
+fn main() -> u64 {
+    let p = make(1);
+    p.y
+}
+"#;
+        let symbols = symbols_of(source);
+        let struct_decl = source.find("Point {").unwrap();
+        let y_decl = source.find("y: u64").unwrap();
+        let make_decl = source.find("make(").unwrap();
+        let param_decl = source.find("x: u64)").unwrap();
+        let p_decl = source.find("p = ").unwrap();
+
+        assert_eq!(
+            occurrences(&symbols, "Point"),
+            vec![
+                (true, struct_decl, "struct Point".into()),
+                (false, struct_decl, "struct Point".into()),
+                (false, struct_decl, "struct Point".into()),
+            ]
+        );
+        assert_eq!(
+            occurrences(&symbols, "make"),
+            vec![
+                (true, make_decl, "fn make(x: u64) -> Point".into()),
+                (false, make_decl, "fn make(x: u64) -> Point".into()),
+            ]
+        );
+        assert_eq!(
+            occurrences(&symbols, "p"),
+            vec![
+                (true, p_decl, "let p: Point".into()),
+                (false, p_decl, "let p: Point".into()),
+            ]
+        );
+        // The field in the struct expression and the variable it is initialized with resolve to
+        // different declarations.
+        let x_uses = occurrences(&symbols, "x");
+        assert!(x_uses.contains(&(false, param_decl, "x: u64".into())));
+        assert!(x_uses.contains(&(false, source.find("x: u64,").unwrap(), "x: u64".into())));
+        assert_eq!(
+            occurrences(&symbols, "y").last(),
+            Some(&(false, y_decl, "y: u64".into()))
+        );
+    }
+
+    #[test]
+    fn members_and_paths_are_found() {
+        let _type_engine = TypeEngine::new().enter();
+        let source = r#"script;
+
+enum Color {
+    Red: (),
+    Green: (),
+}
+
+struct Point {
+    x: u64,
+}
+
+impl Point {
+    fn get_x(self, scale: u64) -> u64 {
+        self.x
+    }
+}
+
+fn main() -> u64 {
+    let p = Point { x: 1 };
+    let c = Color::Red;
+    p.get_x(2)
+}
+"#;
+        let tree = compile(source);
+        let symbols = tree.symbols();
+        let p = symbols
+            .iter()
+            .find(|symbol| symbol.ident.as_str() == "p")
+            .unwrap();
+
+        let members = members_of_type(tree.namespace_ref(), p.type_id.unwrap());
+        let members = members
+            .iter()
+            .map(|member| (member.ident.as_str(), member.parameters.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            members,
+            vec![
+                ("x", vec![]),
+                (
+                    "get_x",
+                    vec!["self: Point".to_string(), "scale: u64".to_string()]
+                ),
+            ]
+        );
+
+        let variants = items_at_path(tree.namespace_ref(), &["Color"]);
+        let variants = variants
+            .iter()
+            .map(|variant| variant.ident.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(variants, vec!["Red", "Green"]);
+    }
+
+    #[test]
+    fn inferred_types_and_parameters_are_hinted() {
+        let _type_engine = TypeEngine::new().enter();
+        let source = r#"script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn scale(point: Point, factor: u64) -> Point {
+    point
+}
+
+fn main() -> u64 {
+    let factor = 2;
+    let annotated: u64 = 3;
+    let p = scale(Point { x: 1, y: annotated }, factor);
+    let Point { x, y: py } = p;
+    let (a, b) = (x, true);
+    match (py, b) {
+        (c, d) => { c },
+    }
+}
+"#;
+        let hints = compile(source)
+            .inlay_hints()
+            .into_iter()
+            .map(|hint| (hint.span.as_str().to_string(), hint.kind, hint.label))
+            .collect::<Vec<_>>();
+        let hint = |span: &str, kind, label: &str| (span.to_string(), kind, label.to_string());
+
+        for expected in [
+            hint("factor", InlayHintKind::Type, ": u64"),
+            hint("p", InlayHintKind::Type, ": Point"),
+            hint("x", InlayHintKind::Type, ": u64"),
+            hint("py", InlayHintKind::Type, ": u64"),
+            hint("a", InlayHintKind::Type, ": u64"),
+            hint("b", InlayHintKind::Type, ": bool"),
+            hint("c", InlayHintKind::Type, ": u64"),
+            hint("d", InlayHintKind::Type, ": bool"),
+            hint(
+                "Point { x: 1, y: annotated }",
+                InlayHintKind::Parameter,
+                "point:",
+            ),
+        ] {
+            assert!(hints.contains(&expected), "{:?} in {:?}", expected, hints);
+        }
+        // annotated variables, and arguments named like their parameter, need no hints
+        assert!(!hints.iter().any(|(span, _, _)| span == "annotated"));
+        assert!(!hints.iter().any(|(_, _, label)| label == "factor:"));
+    }
+
+    #[test]
+    fn public_items_of_modules_can_be_imported() {
+        let _type_engine = TypeEngine::new().enter();
+        let source = r#"library shapes;
+
+pub struct Point {
+    x: u64,
+}
+
+fn helper() {}
+"#;
+        let included = r#"library geometry;
+
+pub fn area() -> u64 {
+    1
+}
+"#;
+        let root = create_module();
+        root.insert_module_ref("shapes".into(), compile(source).namespace_ref());
+        let modules = [("geometry".to_string(), compile(included).into_namespace())];
+
+        assert_eq!(
+            import_paths(root, &modules, "Point"),
+            vec![vec!["shapes".to_string(), "Point".to_string()]]
+        );
+        assert_eq!(
+            import_paths(root, &modules, "area"),
+            vec![vec!["geometry".to_string(), "area".to_string()]]
+        );
+        assert!(import_paths(root, &modules, "helper").is_empty());
+    }
+
+    #[test]
+    fn types_and_imports_are_references() {
+        let _type_engine = TypeEngine::new().enter();
+        let library = r#"library shapes;
+
+pub struct Point {
+    x: u64,
+}
+
+pub fn area(p: Point) -> u64 {
+    p.x
+}
+"#;
+        let root = create_module();
+        root.insert_module_ref("shapes".into(), compile(library).namespace_ref());
+        let source = r#"script;
+
+use shapes::{Point, area};
+
+struct Segment {
+    from: Point,
+    to: (Point, u64),
+}
+
+trait Shift {
+    fn shift(self, by: u64) -> Point;
+}
+
+impl Shift for Point {
+    fn shift(self, by: u64) -> Point {
+        Point { x: by }
+    }
+}
+
+fn main() -> u64 {
+    let p: Point = Point { x: 1 };
+    area(p)
+}
+"#;
+        let symbols = compile_in(source, root).symbols();
+        // the occurrences of `name`, in the order they are written, with their declarations
+        let uses = |name: &str| {
+            let mut uses = symbols
+                .iter()
+                .filter(|symbol| symbol.ident.as_str() == name)
+                .map(|symbol| {
+                    (
+                        symbol.ident.span().start(),
+                        symbol.declaration.as_ref().unwrap().span().start(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            uses.sort();
+            uses.dedup();
+            uses
+        };
+        let offsets = |name: &str| {
+            source
+                .match_indices(name)
+                .map(|(offset, _)| offset)
+                .collect::<Vec<_>>()
+        };
+
+        // every `Point` is the struct of the library
+        let point_decl = library.find("Point {").unwrap();
+        assert_eq!(
+            uses("Point"),
+            offsets("Point")
+                .into_iter()
+                .map(|offset| (offset, point_decl))
+                .collect::<Vec<_>>()
+        );
+        let area_decl = library.find("area(").unwrap();
+        let areas = offsets("area")
+            .into_iter()
+            .map(|offset| (offset, area_decl));
+        assert_eq!(uses("area"), areas.collect::<Vec<_>>());
+        let shift_decl = source.find("Shift {").unwrap();
+        assert_eq!(
+            uses("Shift"),
+            vec![
+                (shift_decl, shift_decl),
+                (source.find("Shift for").unwrap(), shift_decl)
+            ]
+        );
+    }
+}
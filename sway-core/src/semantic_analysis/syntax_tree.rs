@@ -15,7 +15,7 @@ use crate::{
     AstNode, ParseTree,
 };
 
-use sway_types::{ident::Ident, span::Span};
+use sway_types::{ident::Ident, span::Span, JsonABI};
 
 use std::collections::{HashMap, HashSet};
 
@@ -54,6 +54,15 @@ pub enum TypedParseTree {
     },
 }
 
+/// The selector a contract's ABI method is called by, along with the declaration it was
+/// computed from.
+#[derive(Debug, Clone)]
+pub struct AbiSelector {
+    pub name: Ident,
+    pub span: Span,
+    pub selector: [u8; 4],
+}
+
 impl TypedParseTree {
     /// The `all_nodes` field in the AST variants is used to perform control flow and return flow
     /// analysis, while the direct copies of the declarations and main functions are used to create
@@ -68,6 +77,17 @@ impl TypedParseTree {
         }
     }
 
+    /// The JSON ABI of the program: the functions of the ABIs a contract implements, and nothing
+    /// for other programs.
+    pub fn generate_json_abi(&self) -> JsonABI {
+        match self {
+            TypedParseTree::Contract { abi_entries, .. } => {
+                abi_entries.iter().map(|x| x.generate_json_abi()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
     pub fn get_namespace_ref(self) -> NamespaceRef {
         use TypedParseTree::*;
         match self {
@@ -88,6 +108,29 @@ impl TypedParseTree {
         }
     }
 
+    /// The selectors of the ABI methods implemented by a contract. Other kinds of programs have
+    /// none.
+    pub fn abi_selectors(&self) -> CompileResult<Vec<AbiSelector>> {
+        let mut warnings = vec![];
+        let mut errors = vec![];
+        let abi_entries = match self {
+            TypedParseTree::Contract { abi_entries, .. } => abi_entries,
+            _ => return ok(vec![], warnings, errors),
+        };
+
+        let mut selectors = vec![];
+        for entry in abi_entries {
+            let selector = check!(entry.to_fn_selector_value(), continue, warnings, errors);
+            selectors.push(AbiSelector {
+                name: entry.name.clone(),
+                span: entry.span.clone(),
+                selector,
+            });
+        }
+
+        ok(selectors, warnings, errors)
+    }
+
     pub(crate) fn type_check(
         parsed: ParseTree,
         new_namespace: NamespaceRef,
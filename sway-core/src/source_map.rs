@@ -60,6 +60,26 @@ impl SourceMap {
         }
     }
 
+    /// The number of instructions generated from the source code within `span`.
+    pub fn instructions_within(&self, span: &Span) -> usize {
+        let path_index = match self
+            .paths
+            .iter()
+            .position(|p| Some(p) == span.path.as_deref())
+        {
+            Some(path_index) => PathIndex(path_index),
+            None => return 0,
+        };
+        self.map
+            .values()
+            .filter(|sms| {
+                sms.path == path_index
+                    && span.start() <= sms.range.start
+                    && sms.range.end <= span.end()
+            })
+            .count()
+    }
+
     /// Inverse source mapping
     pub fn addr_to_span(&self, pc: usize) -> Option<(PathBuf, LocationRange)> {
         self.map.get(&pc).map(|sms| {
@@ -25,13 +25,13 @@ impl JsonAbiString for TypeId {
     }
 }
 
-pub(crate) trait FriendlyTypeString {
+pub trait FriendlyTypeString {
     fn friendly_type_str(&self) -> String;
 }
 
 impl FriendlyTypeString for TypeId {
     fn friendly_type_str(&self) -> String {
-        look_up_type_id(*self).friendly_type_str()
+        look_up_type_id_for_display(*self).friendly_type_str()
     }
 }
 
@@ -51,6 +51,12 @@ impl ToJsonAbi for TypeId {
                     .map(|x| x.generate_json_abi())
                     .collect(),
             ),
+            // The buffer of a vector holds its elements, which are described like a field.
+            TypeInfo::Vector(elem_ty) => Some(vec![Property {
+                name: "elem".into(),
+                type_field: elem_ty.json_abi_str(),
+                components: elem_ty.generate_json_abi(),
+            }]),
             _ => None,
         }
     }
@@ -118,6 +124,33 @@ fn chain_of_refs_2() {
         TypeInfo::UnsignedInteger(IntegerBits::Eight)
     );
 }
+#[test]
+fn engines_do_not_share_changes() {
+    let sp = Span {
+        span: pest::Span::new(" ".into(), 0, 0).unwrap(),
+        path: None,
+    };
+    let parent = TypeEngine::new();
+    let id = {
+        let _type_engine = parent.enter();
+        insert_type(TypeInfo::Numeric)
+    };
+
+    // a child can see the types of its parent, and unify them in its own copy
+    let child = parent.child();
+    {
+        let _type_engine = child.enter();
+        let id2 = insert_type(TypeInfo::UnsignedInteger(IntegerBits::Eight));
+        unify_with_self(id, id2, id2, &sp).unwrap();
+        assert_eq!(
+            look_up_type_id(id),
+            TypeInfo::UnsignedInteger(IntegerBits::Eight)
+        );
+    }
+
+    let _type_engine = parent.child().enter();
+    assert_eq!(look_up_type_id(id), TypeInfo::Numeric);
+}
 
 fn parse_str_type(raw: &str, span: Span) -> CompileResult<TypeInfo> {
     if raw.starts_with("str[") {
@@ -1,29 +1,164 @@
 use super::*;
-use crate::concurrent_slab::ConcurrentSlab;
-use lazy_static::lazy_static;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use sway_types::span::Span;
 
-lazy_static! {
-    static ref TYPE_ENGINE: Engine = Engine::default();
+/// Type ids are unique across every engine in the process, so that the types of an engine and
+/// of the engines it is layered on never share an id.
+static NEXT_TYPE_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The engines entered on this thread, the last of which is the one the type checker uses.
+    static ENTERED: RefCell<Vec<TypeEngine>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The types created by a compilation. Each compilation owns an engine of its own, given to it
+/// in its [crate::BuildConfig], so compilations never see each other's types and the memory
+/// they use is freed along with the engine.
+///
+/// An engine can be layered on others with [TypeEngine::child], to see the types of the
+/// dependencies it is compiled against without being able to change them.
+#[derive(Clone, Default)]
+pub struct TypeEngine(Arc<Engine>);
+
+impl fmt::Debug for TypeEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeEngine").finish_non_exhaustive()
+    }
+}
+
+impl TypeEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A new engine which can see the types of this one. Changes it makes to them, through
+    /// unification, are only visible to itself.
+    pub fn child(&self) -> Self {
+        TypeEngine(Arc::new(Engine {
+            types: RwLock::default(),
+            parents: RwLock::new(vec![self.clone()]),
+        }))
+    }
+
+    /// A new engine which can see the same types as this one, apart from its own.
+    pub(crate) fn sibling(&self) -> Self {
+        TypeEngine(Arc::new(Engine {
+            types: RwLock::default(),
+            parents: RwLock::new(self.0.parents.read().unwrap().clone()),
+        }))
+    }
+
+    /// Makes the types of `other` visible to this engine as well.
+    pub(crate) fn attach(&self, other: TypeEngine) {
+        self.0.parents.write().unwrap().push(other);
+    }
+
+    /// Makes this the engine types are looked up in and created by on the current thread, until
+    /// the returned guard is dropped. The types of a compiled program can only be inspected
+    /// while the engine it was compiled with is entered, and using types outside of any guard,
+    /// or the types of an engine which can't be seen from the entered one, panics.
+    pub fn enter(&self) -> EnteredTypeEngine {
+        ENTERED.with(|entered| entered.borrow_mut().push(self.clone()));
+        EnteredTypeEngine {
+            _not_send: PhantomData,
+        }
+    }
+
+    /// The engine last entered on this thread.
+    pub(crate) fn current() -> TypeEngine {
+        ENTERED.with(|entered| {
+            entered
+                .borrow()
+                .last()
+                .cloned()
+                .expect("types can only be used while a type engine is entered")
+        })
+    }
+}
+
+/// Restores the engine that was entered before, when dropped.
+#[must_use]
+pub struct EnteredTypeEngine {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for EnteredTypeEngine {
+    fn drop(&mut self) {
+        ENTERED.with(|entered| entered.borrow_mut().pop());
+    }
 }
 
 #[derive(Debug, Default)]
 pub(crate) struct Engine {
-    slab: ConcurrentSlab<TypeInfo>,
+    types: RwLock<HashMap<TypeId, TypeInfo>>,
+    /// The engines whose types can be seen from this one. Where this engine has changed one of
+    /// their types, its own copy is kept in `types`.
+    parents: RwLock<Vec<TypeEngine>>,
 }
 
 impl Engine {
     pub fn insert_type(&self, ty: TypeInfo) -> TypeId {
-        self.slab.insert(ty)
+        let id = NEXT_TYPE_ID.fetch_add(1, Ordering::Relaxed);
+        self.types.write().unwrap().insert(id, ty);
+        id
+    }
+
+    fn get(&self, id: TypeId) -> TypeInfo {
+        self.try_get(id)
+            .unwrap_or_else(|| panic!("type {} does not belong to this type engine", id))
+    }
+
+    fn try_get(&self, id: TypeId) -> Option<TypeInfo> {
+        let ty = self.types.read().unwrap().get(&id).cloned();
+        ty.or_else(|| self.try_get_from_parents(id))
+    }
+
+    fn try_get_from_parents(&self, id: TypeId) -> Option<TypeInfo> {
+        self.parents
+            .read()
+            .unwrap()
+            .iter()
+            .find_map(|parent| parent.0.try_get(id))
+    }
+
+    /// Replaces the type `id` with `new_value` if it is still `prev_value`, otherwise returns
+    /// what it is now. The types of parent engines are replaced by a copy in this one.
+    fn replace(&self, id: TypeId, prev_value: &TypeInfo, new_value: TypeInfo) -> Option<TypeInfo> {
+        let mut types = self.types.write().unwrap();
+        let actual_prev_value = match types.get(&id) {
+            Some(ty) => ty.clone(),
+            None => self
+                .try_get_from_parents(id)
+                .unwrap_or_else(|| panic!("type {} does not belong to this type engine", id)),
+        };
+        if actual_prev_value != *prev_value {
+            return Some(actual_prev_value);
+        }
+        types.insert(id, new_value);
+        None
     }
 
     pub fn look_up_type_id_raw(&self, id: TypeId) -> TypeInfo {
-        self.slab.get(id)
+        self.get(id)
     }
 
     pub fn look_up_type_id(&self, id: TypeId) -> TypeInfo {
-        match self.slab.get(id) {
-            TypeInfo::Ref(other) => self.look_up_type_id(other),
+        match self.get(id) {
+            TypeInfo::Ref(other) | TypeInfo::Alias { ty: other, .. } => self.look_up_type_id(other),
+            ty => ty,
+        }
+    }
+
+    /// Like [Engine::look_up_type_id], but stops at type aliases so that diagnostics can refer
+    /// to a type by the name it was written with.
+    pub fn look_up_type_id_for_display(&self, id: TypeId) -> TypeInfo {
+        match self.get(id) {
+            TypeInfo::Ref(other) => self.look_up_type_id_for_display(other),
             ty => ty,
         }
     }
@@ -39,7 +174,7 @@ impl Engine {
         span: &Span,
     ) -> Result<Vec<CompileWarning>, TypeError> {
         use TypeInfo::*;
-        match (self.slab.get(received), self.slab.get(expected)) {
+        match (self.get(received), self.get(expected)) {
             // If the types are exactly the same, we are done.
             (received_info, expected_info) if received_info == expected_info => Ok(vec![]),
 
@@ -47,20 +182,43 @@ impl Engine {
             (Ref(received), _) => self.unify(received, expected, span),
             (_, Ref(expected)) => self.unify(received, expected, span),
 
+            // Type aliases are interchangeable with the type they refer to. If there is an error
+            // then we want to report it in terms of the alias, not the aliased type.
+            (
+                Alias {
+                    ty: received_ty, ..
+                },
+                _,
+            ) => self
+                .unify(received_ty, expected, span)
+                .map_err(|_| TypeError::MismatchedType {
+                    expected: expected.friendly_type_str(),
+                    received: received.friendly_type_str(),
+                    help_text: Default::default(),
+                    span: span.clone(),
+                }),
+            (
+                _,
+                Alias {
+                    ty: expected_ty, ..
+                },
+            ) => self
+                .unify(received, expected_ty, span)
+                .map_err(|_| TypeError::MismatchedType {
+                    expected: expected.friendly_type_str(),
+                    received: received.friendly_type_str(),
+                    help_text: Default::default(),
+                    span: span.clone(),
+                }),
+
             // When we don't know anything about either term, assume that
             // they match and make the one we know nothing about reference the
             // one we may know something about
-            (Unknown, _) => match self
-                .slab
-                .replace(received, &Unknown, TypeInfo::Ref(expected))
-            {
+            (Unknown, _) => match self.replace(received, &Unknown, TypeInfo::Ref(expected)) {
                 None => Ok(vec![]),
                 Some(_) => self.unify(received, expected, span),
             },
-            (_, Unknown) => match self
-                .slab
-                .replace(expected, &Unknown, TypeInfo::Ref(received))
-            {
+            (_, Unknown) => match self.replace(expected, &Unknown, TypeInfo::Ref(received)) {
                 None => Ok(vec![]),
                 Some(_) => self.unify(received, expected, span),
             },
@@ -93,32 +251,31 @@ impl Engine {
                 };
 
                 // Cast the expected type to the received type.
-                self.slab
-                    .replace(received, received_info, expected_info.clone());
+                self.replace(received, received_info, expected_info.clone());
                 Ok(warn)
             }
 
             (ref received_info @ UnknownGeneric { .. }, _) => {
-                self.slab
-                    .replace(received, received_info, TypeInfo::Ref(expected));
+                self.replace(received, received_info, TypeInfo::Ref(expected));
                 Ok(vec![])
             }
 
             (_, ref expected_info @ UnknownGeneric { .. }) => {
-                self.slab
-                    .replace(expected, expected_info, TypeInfo::Ref(received));
+                self.replace(expected, expected_info, TypeInfo::Ref(received));
                 Ok(vec![])
             }
 
             // if the types, once their ids have been looked up, are the same, we are done
             (
                 Struct {
-                    fields: a_fields, ..
+                    name: a_name,
+                    fields: a_fields,
                 },
                 Struct {
-                    fields: b_fields, ..
+                    name: b_name,
+                    fields: b_fields,
                 },
-            ) if {
+            ) if a_name == b_name && a_fields.len() == b_fields.len() && {
                 let a_fields = a_fields.iter().map(|x| x.r#type);
                 let b_fields = b_fields.iter().map(|x| x.r#type);
 
@@ -149,13 +306,13 @@ impl Engine {
             }
 
             (Numeric, expected_info @ UnsignedInteger(_)) => {
-                match self.slab.replace(received, &Numeric, expected_info) {
+                match self.replace(received, &Numeric, expected_info) {
                     None => Ok(vec![]),
                     Some(_) => self.unify(received, expected, span),
                 }
             }
             (received_info @ UnsignedInteger(_), Numeric) => {
-                match self.slab.replace(expected, &Numeric, received_info) {
+                match self.replace(expected, &Numeric, received_info) {
                     None => Ok(vec![]),
                     Some(_) => self.unify(received, expected, span),
                 }
@@ -166,12 +323,22 @@ impl Engine {
                 // If there was an error then we want to report the array types as mismatching, not
                 // the elem types.
                 .map_err(|_| TypeError::MismatchedType {
-                    expected,
-                    received,
+                    expected: expected.friendly_type_str(),
+                    received: received.friendly_type_str(),
                     help_text: Default::default(),
                     span: span.clone(),
                 }),
 
+            (Vector(a_elem), Vector(b_elem)) => {
+                self.unify(a_elem, b_elem, span)
+                    .map_err(|_| TypeError::MismatchedType {
+                        expected: expected.friendly_type_str(),
+                        received: received.friendly_type_str(),
+                        help_text: Default::default(),
+                        span: span.clone(),
+                    })
+            }
+
             // When unifying complex types, we must check their sub-types. This
             // can be trivially implemented for tuples, sum types, etc.
             // (List(a_item), List(b_item)) => self.unify(a_item, b_item),
@@ -185,8 +352,8 @@ impl Engine {
                 (TypeInfo::ErrorRecovery, _) => Ok(vec![]),
                 (_, TypeInfo::ErrorRecovery) => Ok(vec![]),
                 _ => Err(TypeError::MismatchedType {
-                    expected,
-                    received,
+                    expected: expected.friendly_type_str(),
+                    received: received.friendly_type_str(),
                     help_text: Default::default(),
                     span: span.clone(),
                 }),
@@ -226,15 +393,19 @@ impl Engine {
 }
 
 pub fn insert_type(ty: TypeInfo) -> TypeId {
-    TYPE_ENGINE.insert_type(ty)
+    TypeEngine::current().0.insert_type(ty)
 }
 
 pub(crate) fn look_up_type_id(id: TypeId) -> TypeInfo {
-    TYPE_ENGINE.look_up_type_id(id)
+    TypeEngine::current().0.look_up_type_id(id)
+}
+
+pub(crate) fn look_up_type_id_for_display(id: TypeId) -> TypeInfo {
+    TypeEngine::current().0.look_up_type_id_for_display(id)
 }
 
 pub(crate) fn look_up_type_id_raw(id: TypeId) -> TypeInfo {
-    TYPE_ENGINE.look_up_type_id_raw(id)
+    TypeEngine::current().0.look_up_type_id_raw(id)
 }
 
 pub fn unify_with_self(
@@ -243,11 +414,13 @@ pub fn unify_with_self(
     self_type: TypeId,
     span: &Span,
 ) -> Result<Vec<CompileWarning>, TypeError> {
-    TYPE_ENGINE.unify_with_self(a, b, self_type, span)
+    TypeEngine::current()
+        .0
+        .unify_with_self(a, b, self_type, span)
 }
 
 pub fn resolve_type(id: TypeId, error_span: &Span) -> Result<TypeInfo, TypeError> {
-    TYPE_ENGINE.resolve_type(id, error_span)
+    TypeEngine::current().0.resolve_type(id, error_span)
 }
 
 fn numeric_cast_compat(new_size: IntegerBits, old_size: IntegerBits) -> NumericCastCompatResult {
@@ -274,3 +447,31 @@ enum NumericCastCompatResult {
     Compatible,
     CastableWithWarning(Warning),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "does not belong to this type engine")]
+    fn types_of_an_engine_which_is_not_entered_panic() {
+        let engine = TypeEngine::new();
+        let id = {
+            let _entered = engine.enter();
+            insert_type(TypeInfo::Boolean)
+        };
+        {
+            let _entered = engine.enter();
+            assert_eq!(look_up_type_id(id), TypeInfo::Boolean);
+        }
+
+        let _entered = TypeEngine::new().enter();
+        look_up_type_id(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "while a type engine is entered")]
+    fn types_can_not_be_created_outside_of_an_engine() {
+        insert_type(TypeInfo::Boolean);
+    }
+}
@@ -52,6 +52,13 @@ pub enum TypeInfo {
     /// until the semantic analysis stage.
     Custom {
         name: Ident,
+        type_arguments: Vec<TypeInfo>,
+    },
+    /// A type referred to through a type alias. This behaves exactly like the type it refers to,
+    /// but keeps the alias name around so that diagnostics can use it.
+    Alias {
+        name: Ident,
+        ty: TypeId,
     },
     SelfType,
     Byte,
@@ -64,6 +71,11 @@ pub enum TypeInfo {
     ErrorRecovery,
     // Static, constant size arrays.
     Array(TypeId, usize),
+    /// A growable, heap allocated vector, `Vec<T>`.
+    Vector(TypeId),
+    /// A heap allocated string of any length, `String`. Unlike `str[N]` its length is not part of
+    /// its type.
+    DynamicString,
 }
 
 impl Default for TypeInfo {
@@ -82,7 +94,7 @@ impl TypeInfo {
             _ => {
                 let span = Span {
                     span: input.as_span(),
-                    path: config.map(|config| config.dir_of_code.clone()),
+                    path: config.map(|config| config.path()),
                 };
                 let errors = vec![CompileError::Internal(
                     "Unexpected token while parsing type.",
@@ -91,7 +103,45 @@ impl TypeInfo {
                 return err(vec![], errors);
             }
         }
-        Self::parse_from_pair_inner(input.into_inner().next().unwrap(), config)
+        let mut inner = input.into_inner();
+        let type_info = Self::parse_from_pair_inner(inner.next().unwrap(), config);
+        match inner.next() {
+            Some(type_arguments) => type_info.flat_map(|type_info| {
+                Self::parse_type_arguments(type_info, type_arguments, config)
+            }),
+            None => type_info,
+        }
+    }
+
+    /// Attaches the type arguments in `Foo<A, B>` to the custom type `Foo`. `Vec<T>` is parsed like
+    /// a custom type too, and only resolved to the built in vector type if no declaration in scope
+    /// is named `Vec`. Type arguments on any other kind of type have no meaning and are ignored.
+    fn parse_type_arguments(
+        type_info: TypeInfo,
+        type_arguments: Pair<Rule>,
+        config: Option<&BuildConfig>,
+    ) -> CompileResult<Self> {
+        let mut warnings = vec![];
+        let mut errors = vec![];
+        let type_info = match type_info {
+            TypeInfo::Custom { name, .. } => {
+                let mut type_arguments_buf = vec![];
+                for type_argument in type_arguments.into_inner() {
+                    type_arguments_buf.push(check!(
+                        TypeInfo::parse_from_pair(type_argument, config),
+                        TypeInfo::ErrorRecovery,
+                        warnings,
+                        errors
+                    ));
+                }
+                TypeInfo::Custom {
+                    name,
+                    type_arguments: type_arguments_buf,
+                }
+            }
+            other => other,
+        };
+        ok(type_info, warnings, errors)
     }
 
     fn parse_from_pair_inner(
@@ -102,7 +152,7 @@ impl TypeInfo {
         let mut errors = vec![];
         let span = Span {
             span: input.as_span(),
-            path: config.map(|config| config.dir_of_code.clone()),
+            path: config.map(|config| config.path()),
         };
         let type_info = match input.as_rule() {
             Rule::str_type => {
@@ -126,6 +176,7 @@ impl TypeInfo {
                 "Contract" => TypeInfo::Contract,
                 _other => TypeInfo::Custom {
                     name: Ident::new(span),
+                    type_arguments: vec![],
                 },
             },
             Rule::array_type => {
@@ -236,7 +287,10 @@ impl TypeInfo {
             }
             .into(),
             Boolean => "bool".into(),
-            Custom { name } => format!("unresolved {}", name.as_str()),
+            Custom { name, .. } => format!("unresolved {}", name.as_str()),
+            Alias { name, ty } => {
+                format!("{} (alias of {})", name.as_str(), ty.friendly_type_str())
+            }
             Ref(id) => format!("T{} ({})", id, (*id).friendly_type_str()),
             Tuple(fields) => {
                 let field_strs = fields
@@ -265,6 +319,8 @@ impl TypeInfo {
                 format!("contract caller {}", abi_name.suffix)
             }
             Array(elem_ty, count) => format!("[{}; {}]", elem_ty.friendly_type_str(), count),
+            Vector(elem_ty) => format!("Vec<{}>", elem_ty.friendly_type_str()),
+            DynamicString => "String".into(),
         }
     }
 
@@ -282,7 +338,8 @@ impl TypeInfo {
             }
             .into(),
             Boolean => "bool".into(),
-            Custom { name } => format!("unresolved {}", name.as_str()),
+            Custom { name, .. } => format!("unresolved {}", name.as_str()),
+            Alias { ty, .. } => ty.json_abi_str(),
             Ref(id) => format!("T{} ({})", id, (*id).json_abi_str()),
             Tuple(fields) => {
                 let field_strs = fields
@@ -307,6 +364,8 @@ impl TypeInfo {
                 format!("contract caller {}", abi_name.suffix)
             }
             Array(elem_ty, count) => format!("[{}; {}]", elem_ty.json_abi_str(), count),
+            Vector(elem_ty) => format!("Vec<{}>", elem_ty.json_abi_str()),
+            DynamicString => "String".into(),
         }
     }
 
@@ -326,6 +385,17 @@ impl TypeInfo {
                 .into()
             }
             Boolean => "bool".into(),
+            DynamicString => "String".into(),
+            Vector(elem_ty) => {
+                let elem_name = match resolve_type(*elem_ty, error_msg_span) {
+                    Err(e) => return err(vec![], vec![e.into()]),
+                    Ok(ty) => ty.to_selector_name(error_msg_span),
+                };
+                match elem_name.value {
+                    Some(elem_name) => format!("Vec<{}>", elem_name),
+                    None => return elem_name,
+                }
+            }
 
             Tuple(fields) => {
                 let field_names = {
@@ -408,6 +478,7 @@ impl TypeInfo {
         };
         ok(name, vec![], vec![])
     }
+
     /// Calculates the stack size of this type, to be used when allocating stack memory for it.
     pub(crate) fn size_in_words(&self, err_span: &Span) -> Result<u64, CompileError> {
         match self {
@@ -462,10 +533,16 @@ impl TypeInfo {
                 ty: self.friendly_type_str(),
                 span: err_span.clone(),
             }),
-            TypeInfo::Ref(id) => look_up_type_id(*id).size_in_words(err_span),
+            TypeInfo::Ref(id) | TypeInfo::Alias { ty: id, .. } => {
+                look_up_type_id(*id).size_in_words(err_span)
+            }
             TypeInfo::Array(elem_ty, count) => {
                 Ok(look_up_type_id(*elem_ty).size_in_words(err_span)? * *count as u64)
             }
+            // The pointer to the heap buffer, its capacity and its length.
+            TypeInfo::Vector(_) => Ok(3),
+            // The same header as a vector, with the capacity and length counted in bytes.
+            TypeInfo::DynamicString => Ok(3),
         }
     }
     pub(crate) fn is_copy_type(&self) -> bool {
@@ -563,7 +640,12 @@ impl TypeInfo {
             }
             TypeInfo::UnknownGeneric { name, .. } => {
                 for (param, ty_id) in mapping.iter() {
-                    if param.name == (TypeInfo::Custom { name: name.clone() }) {
+                    if param.name
+                        == (TypeInfo::Custom {
+                            name: name.clone(),
+                            type_arguments: vec![],
+                        })
+                    {
                         return Some(*ty_id);
                     }
                 }
@@ -601,9 +683,13 @@ impl TypeInfo {
                     name: name.clone(),
                 }))
             }
+            TypeInfo::Alias { ty, .. } => look_up_type_id(*ty).matches_type_parameter(mapping),
             TypeInfo::Array(ary_ty_id, count) => look_up_type_id(*ary_ty_id)
                 .matches_type_parameter(mapping)
                 .map(|matching_id| insert_type(TypeInfo::Array(matching_id, *count))),
+            TypeInfo::Vector(elem_ty) => look_up_type_id(*elem_ty)
+                .matches_type_parameter(mapping)
+                .map(|matching_id| insert_type(TypeInfo::Vector(matching_id))),
             TypeInfo::Tuple(fields) => {
                 let mut new_fields = Vec::new();
                 let mut index = 0;
@@ -644,6 +730,7 @@ impl TypeInfo {
             | B256
             | Numeric
             | Contract
+            | DynamicString
             | ErrorRecovery => None,
         }
     }
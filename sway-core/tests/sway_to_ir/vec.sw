@@ -0,0 +1,17 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn main() -> u64 {
+    let mut points = ~Vec::new();
+    points.push(Point { x: 1, y: 2 });
+    let p = points.pop();
+
+    let mut numbers: Vec<u64> = ~Vec::new();
+    numbers.push(p.y);
+    let first = 0;
+    numbers.get(first)
+}
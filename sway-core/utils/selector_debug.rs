@@ -25,6 +25,8 @@ fn main() {
     let fn_decl = opt.fn_decl;
     let mut warnings = vec![];
     let mut errors = vec![];
+    let type_engine = TypeEngine::new();
+    let _type_engine = type_engine.enter();
 
     let parsed_fn_decl = SwayParser::parse(Rule::fn_decl, Arc::from(fn_decl));
     let mut parsed_fn_decl = match parsed_fn_decl {
@@ -46,7 +48,8 @@ fn main() {
         build_config: &mut BuildConfig::root_from_file_name_and_manifest_path(
             Default::default(),
             Default::default(),
-        ),
+        )
+        .type_engine(type_engine.clone()),
         dead_code_graph: &mut Default::default(),
         mode: Mode::ImplAbiFn,
         dependency_graph: &mut Default::default(),
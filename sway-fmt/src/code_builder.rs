@@ -108,6 +108,24 @@ impl CodeBuilder {
                                 _ => code_line.append_with_whitespace("/ "),
                             }
                         }
+                        // attributes, such as `#[test]`, go on a line of their own
+                        '#' if matches!(iter.peek(), Some((_, '['))) => {
+                            if let Some(end) = line[current_index..].find(']') {
+                                let attribute = line[current_index..=current_index + end]
+                                    .chars()
+                                    .filter(|c| !c.is_whitespace())
+                                    .collect::<String>();
+                                code_line.push_str(&attribute);
+                                self.complete_and_add_line(code_line);
+
+                                let rest = &line[current_index + end + 1..];
+                                return self.move_rest_to_new_line(
+                                    rest,
+                                    rest.chars().enumerate().peekable(),
+                                );
+                            }
+                            code_line.push_char('#');
+                        }
                         '%' => code_line.append_with_whitespace("% "),
                         '^' => code_line.append_with_whitespace("^ "),
                         '!' => code_line.append_with_whitespace("!"),
@@ -21,6 +21,8 @@ use sway_types::ident::Ident;
 
 #[derive(Debug, Clone)]
 pub enum Instruction {
+    /// Allocate room for `count` values of a type on the heap, returning a pointer to it.
+    Alloc { ty: Type, count: Value },
     /// An opaque list of ASM instructions passed directly to codegen.
     AsmBlock(AsmBlock, Vec<AsmArg>),
     /// An unconditional jump.
@@ -45,6 +47,12 @@ pub enum Instruction {
         ty: Aggregate,
         indices: Vec<u64>,
     },
+    /// Offset a pointer to the element at an index, treating it as an array of a type.
+    GetElementPointer {
+        ptr_val: Value,
+        ty: Type,
+        index_val: Value,
+    },
     /// Return a pointer as a value.
     GetPointer(Pointer),
     /// Writing a specific value to an array.
@@ -63,12 +71,27 @@ pub enum Instruction {
     },
     /// Read a value from a memory pointer.
     Load(Pointer),
+    /// Read a value of a type through a pointer value.
+    LoadFrom { ptr_val: Value, ty: Type },
+    /// Copy `count` values of a type from one pointer value to another.
+    MemCopy {
+        dst_val: Value,
+        src_val: Value,
+        ty: Type,
+        count: Value,
+    },
     /// Choose a value from a list depending on the preceding block.
     Phi(Vec<(Block, Value)>),
     /// Return from a function.
     Ret(Value, Type),
     /// Write a value to a memory pointer.
     Store { ptr: Pointer, stored_val: Value },
+    /// Write a value of a type through a pointer value.
+    StoreTo {
+        ptr_val: Value,
+        ty: Type,
+        stored_val: Value,
+    },
 }
 
 impl Instruction {
@@ -78,11 +101,14 @@ impl Instruction {
     /// `Ret` do not have a type.
     pub fn get_type(&self, context: &Context) -> Option<Type> {
         match self {
+            Instruction::Alloc { .. } => Some(Type::Pointer),
             Instruction::AsmBlock(asm_block, _) => asm_block.get_type(context),
             Instruction::Call(function, _) => Some(context.functions[function.0].return_type),
             Instruction::ExtractElement { ty, .. } => ty.get_elem_type(context),
             Instruction::ExtractValue { ty, indices, .. } => ty.get_field_type(context, indices),
+            Instruction::GetElementPointer { .. } => Some(Type::Pointer),
             Instruction::Load(ptr) => Some(context.pointers[ptr.0].ty),
+            Instruction::LoadFrom { ty, .. } => Some(*ty),
             Instruction::Phi(_alts) => {
                 unimplemented!("phi get type -- I think we should put the type in the enum.")
             }
@@ -98,7 +124,9 @@ impl Instruction {
             // These write values but don't return one.  If we're explicit we could return Unit.
             Instruction::InsertElement { .. } => None,
             Instruction::InsertValue { .. } => None,
+            Instruction::MemCopy { .. } => None,
             Instruction::Store { .. } => None,
+            Instruction::StoreTo { .. } => None,
         }
     }
 
@@ -110,6 +138,11 @@ impl Instruction {
                 Type::Struct(aggregate) => Some(*aggregate),
                 _otherwise => None,
             },
+            Instruction::LoadFrom { ty, .. } => match ty {
+                Type::Array(aggregate) => Some(*aggregate),
+                Type::Struct(aggregate) => Some(*aggregate),
+                _otherwise => None,
+            },
             Instruction::ExtractElement { ty, .. } => {
                 ty.get_elem_type(context).and_then(|ty| match ty {
                     Type::Array(nested_aggregate) => Some(nested_aggregate),
@@ -139,6 +172,7 @@ impl Instruction {
             }
         };
         match self {
+            Instruction::Alloc { count, .. } => replace(count),
             Instruction::AsmBlock(_, args) => args.iter_mut().for_each(|asm_arg| {
                 asm_arg
                     .initializer
@@ -148,6 +182,12 @@ impl Instruction {
             Instruction::Branch(_) => (),
             Instruction::Call(_, args) => args.iter_mut().for_each(replace),
             Instruction::ConditionalBranch { cond_value, .. } => replace(cond_value),
+            Instruction::GetElementPointer {
+                ptr_val, index_val, ..
+            } => {
+                replace(ptr_val);
+                replace(index_val);
+            }
             Instruction::GetPointer(_) => (),
             Instruction::InsertElement {
                 array,
@@ -173,11 +213,30 @@ impl Instruction {
             }
             Instruction::ExtractValue { aggregate, .. } => replace(aggregate),
             Instruction::Load(_) => (),
+            Instruction::LoadFrom { ptr_val, .. } => replace(ptr_val),
+            Instruction::MemCopy {
+                dst_val,
+                src_val,
+                count,
+                ..
+            } => {
+                replace(dst_val);
+                replace(src_val);
+                replace(count);
+            }
             Instruction::Phi(pairs) => pairs.iter_mut().for_each(|(_, val)| replace(val)),
             Instruction::Ret(ret_val, _) => replace(ret_val),
             Instruction::Store { stored_val, .. } => {
                 replace(stored_val);
             }
+            Instruction::StoreTo {
+                ptr_val,
+                stored_val,
+                ..
+            } => {
+                replace(ptr_val);
+                replace(stored_val);
+            }
         }
     }
 }
@@ -233,6 +292,14 @@ impl<'a> InstructionInserter<'a> {
     // XXX maybe these should return result, in case they get bad args?
     //
 
+    pub fn alloc(self, ty: Type, count: Value) -> Value {
+        let alloc_val = Value::new_instruction(self.context, Instruction::Alloc { ty, count });
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(alloc_val);
+        alloc_val
+    }
+
     /// Append a new [`Instruction::AsmBlock`] from `args` and a `body`.
     pub fn asm_block(
         self,
@@ -326,6 +393,21 @@ impl<'a> InstructionInserter<'a> {
         extract_value_val
     }
 
+    pub fn get_elem_ptr(self, ptr_val: Value, ty: Type, index_val: Value) -> Value {
+        let get_elem_ptr_val = Value::new_instruction(
+            self.context,
+            Instruction::GetElementPointer {
+                ptr_val,
+                ty,
+                index_val,
+            },
+        );
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(get_elem_ptr_val);
+        get_elem_ptr_val
+    }
+
     pub fn get_ptr(self, ptr: Pointer) -> Value {
         let get_ptr_val = Value::new_instruction(self.context, Instruction::GetPointer(ptr));
         self.context.blocks[self.block.0]
@@ -386,6 +468,30 @@ impl<'a> InstructionInserter<'a> {
         load_val
     }
 
+    pub fn load_from(self, ptr_val: Value, ty: Type) -> Value {
+        let load_val = Value::new_instruction(self.context, Instruction::LoadFrom { ptr_val, ty });
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(load_val);
+        load_val
+    }
+
+    pub fn mem_copy(self, dst_val: Value, src_val: Value, ty: Type, count: Value) -> Value {
+        let copy_val = Value::new_instruction(
+            self.context,
+            Instruction::MemCopy {
+                dst_val,
+                src_val,
+                ty,
+                count,
+            },
+        );
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(copy_val);
+        copy_val
+    }
+
     pub fn ret(self, value: Value, ty: Type) -> Value {
         let ret_val = Value::new_instruction(self.context, Instruction::Ret(value, ty));
         self.context.blocks[self.block.0].instructions.push(ret_val);
@@ -400,4 +506,19 @@ impl<'a> InstructionInserter<'a> {
             .push(store_val);
         store_val
     }
+
+    pub fn store_to(self, ptr_val: Value, ty: Type, stored_val: Value) -> Value {
+        let store_val = Value::new_instruction(
+            self.context,
+            Instruction::StoreTo {
+                ptr_val,
+                ty,
+                stored_val,
+            },
+        );
+        self.context.blocks[self.block.0]
+            .instructions
+            .push(store_val);
+        store_val
+    }
 }
@@ -8,6 +8,9 @@
 //!
 //! [`Type::Contract`] and [`Type::ContractCaller`] are both Sway specific types.
 //!
+//! [`Type::Pointer`] is the address of a value outside of the function's locals, such as on the
+//! heap.  It is untyped; the instructions which read and write through it say what they access.
+//!
 //! [`Aggregate`] is an abstract collection of [`Type`]s used for structs, unions and arrays,
 //! though see below for future improvements around splitting arrays into a different construct.
 
@@ -23,6 +26,7 @@ pub enum Type {
     Array(Aggregate),
     Union(Aggregate),
     Struct(Aggregate),
+    Pointer,
 
     Contract,
     ContractCaller(AbiInstance),
@@ -58,6 +62,7 @@ impl Type {
                 let agg_content = &context.aggregates[agg.0];
                 format!("{{ {} }}", sep_types_str(agg_content, ", "))
             }
+            Type::Pointer => "ptr".into(),
             Type::Contract => "contract".into(),
             Type::ContractCaller(_) => "TODO CONTRACT CALLER".into(),
         }
@@ -193,6 +193,7 @@ fn inline_instruction(
     // restructure instructions somehow, so we don't need a persistent `&Context` to access them.
     if let ValueContent::Instruction(old_ins) = context.values[instruction.0].clone() {
         let new_ins = match old_ins {
+            Instruction::Alloc { ty, count } => new_block.ins(context).alloc(ty, map_value(count)),
             Instruction::AsmBlock(asm, args) => {
                 let new_args = args
                     .iter()
@@ -239,6 +240,13 @@ fn inline_instruction(
             } => new_block
                 .ins(context)
                 .extract_value(map_value(aggregate), ty, indices),
+            Instruction::GetElementPointer {
+                ptr_val,
+                ty,
+                index_val,
+            } => new_block
+                .ins(context)
+                .get_elem_ptr(map_value(ptr_val), ty, map_value(index_val)),
             Instruction::GetPointer(ptr) => new_block.ins(context).get_ptr(map_ptr(ptr)),
             Instruction::InsertElement {
                 array,
@@ -263,6 +271,20 @@ fn inline_instruction(
                 indices,
             ),
             Instruction::Load(ptr) => new_block.ins(context).load(map_ptr(ptr)),
+            Instruction::LoadFrom { ptr_val, ty } => {
+                new_block.ins(context).load_from(map_value(ptr_val), ty)
+            }
+            Instruction::MemCopy {
+                dst_val,
+                src_val,
+                ty,
+                count,
+            } => new_block.ins(context).mem_copy(
+                map_value(dst_val),
+                map_value(src_val),
+                ty,
+                map_value(count),
+            ),
             // We convert `ret` to `br post_block` and add the returned value as a phi value.
             Instruction::Ret(val, _) => new_block
                 .ins(context)
@@ -270,6 +292,13 @@ fn inline_instruction(
             Instruction::Store { ptr, stored_val } => new_block
                 .ins(context)
                 .store(map_ptr(ptr), map_value(stored_val)),
+            Instruction::StoreTo {
+                ptr_val,
+                ty,
+                stored_val,
+            } => new_block
+                .ins(context)
+                .store_to(map_value(ptr_val), ty, map_value(stored_val)),
 
             // NOTE: We're not translating the phi value yet, since this is the single instance of
             // use of a value which may not be mapped yet -- a branch from a subsequent block,
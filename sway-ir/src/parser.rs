@@ -88,21 +88,31 @@ mod ir_builder {
                 }
 
             rule operation() -> IrAstOperation
-                = op_asm()
+                = op_alloc()
+                / op_asm()
                 / op_branch()
                 / op_call()
                 / op_cbr()
                 / op_const()
                 / op_extract_element()
                 / op_extract_value()
+                / op_get_elem_ptr()
                 / op_get_ptr()
                 / op_insert_element()
                 / op_insert_value()
+                / op_load_from()
                 / op_load()
+                / op_mem_copy()
                 / op_phi()
                 / op_ret()
+                / op_store_to()
                 / op_store()
 
+            rule op_alloc() -> IrAstOperation
+                = "alloc" _ ty:ast_ty() comma() count:id() {
+                    IrAstOperation::Alloc(ty, count)
+                }
+
             rule op_asm() -> IrAstOperation
                 = "asm" _ "(" _ args:(asm_arg() ** comma()) ")" _ ret:asm_ret()? "{" _
                     ops:asm_op()*
@@ -140,6 +150,11 @@ mod ir_builder {
                     IrAstOperation::ExtractValue(name, ty, idcs)
                 }
 
+            rule op_get_elem_ptr() -> IrAstOperation
+                = "get_elem_ptr" _ ptr:id() comma() ty:ast_ty() comma() idx:id() {
+                    IrAstOperation::GetElemPtr(ptr, ty, idx)
+                }
+
             rule op_get_ptr() -> IrAstOperation
                 = "get_ptr" _ ("mut" _)? "ptr" _ ty:ast_ty() name:id() {
                     IrAstOperation::GetPtr(name)
@@ -155,11 +170,21 @@ mod ir_builder {
                     IrAstOperation::InsertValue(aval, ty, ival, idcs)
                 }
 
+            rule op_load_from() -> IrAstOperation
+                = "load_from" _ ptr:id() comma() ty:ast_ty() {
+                    IrAstOperation::LoadFrom(ptr, ty)
+                }
+
             rule op_load() -> IrAstOperation
                 = "load" _ ("mut" _)? "ptr" _ ast_ty() src:id() {
                     IrAstOperation::Load(src)
                 }
 
+            rule op_mem_copy() -> IrAstOperation
+                = "mem_copy" _ dst:id() comma() src:id() comma() ty:ast_ty() comma() count:id() {
+                    IrAstOperation::MemCopy(dst, src, ty, count)
+                }
+
             rule op_phi() -> IrAstOperation
                 = "phi" _ "(" _ pairs:((bl:id() ":" _ vn:id() { (bl, vn) }) ** comma()) ")" _ {
                     IrAstOperation::Phi(pairs)
@@ -170,6 +195,11 @@ mod ir_builder {
                     IrAstOperation::Ret(ty, vn)
                 }
 
+            rule op_store_to() -> IrAstOperation
+                = "store_to" _ vn:id() comma() ptr:id() comma() ty:ast_ty() {
+                    IrAstOperation::StoreTo(vn, ptr, ty)
+                }
+
             rule op_store() -> IrAstOperation
                 = "store" _ dst:id() comma() ("mut" _)? "ptr" _ ast_ty() vn:id() {
                     IrAstOperation::Store(dst, vn)
@@ -194,7 +224,7 @@ mod ir_builder {
                 }
 
             rule asm_op() -> IrAstAsmOp
-                = name:id_id() args:asm_op_arg()* imm:asm_op_arg_imm()? {
+                = name:asm_id() args:asm_op_arg()* imm:asm_op_arg_imm()? _ {
                     IrAstAsmOp {
                         name,
                         args,
@@ -203,10 +233,19 @@ mod ir_builder {
                 }
 
             rule asm_op_arg() -> Ident
-                = !asm_op_arg_imm() arg:id_id() {
+                = !asm_op_arg_imm() arg:asm_id() {
                     arg
                 }
 
+            // Each asm op is on its own line, so its args mustn't run on to the next one.
+            rule asm_id() -> Ident
+                = !ast_ty() id:$(id_char0() id_char()*) [' ' | '\t']* {
+                    Ident::new(Span {
+                        span: pest::Span::new(id.into(), 0, id.len()).unwrap(),
+                        path: None,
+                    })
+                }
+
             rule asm_op_arg_imm() -> Ident
                 = imm:$("i" d:decimal()) {
                     Ident::new(Span {
@@ -269,6 +308,7 @@ mod ir_builder {
                 / "bool" _ { IrAstTy::Bool }
                 / "u64" _ { IrAstTy::U64 }
                 / "b256" _ { IrAstTy::B256 }
+                / "ptr" !id_char() _ { IrAstTy::Pointer }
                 / "string" _ "<" _ sz:decimal() ">" _ { IrAstTy::String(sz) }
                 / array_ty()
                 / enum_ty()
@@ -378,6 +418,7 @@ mod ir_builder {
 
     #[derive(Debug)]
     enum IrAstOperation {
+        Alloc(IrAstTy, String),
         Asm(
             Vec<(Ident, Option<IrAstAsmArgInit>)>,
             Option<Ident>,
@@ -389,13 +430,17 @@ mod ir_builder {
         Const(IrAstConstValue),
         ExtractElement(String, IrAstTy, String),
         ExtractValue(String, IrAstTy, Vec<u64>),
+        GetElemPtr(String, IrAstTy, String),
         GetPtr(String),
         InsertElement(String, IrAstTy, String, String),
         InsertValue(String, IrAstTy, String, Vec<u64>),
         Load(String),
+        LoadFrom(String, IrAstTy),
+        MemCopy(String, String, IrAstTy, String),
         Phi(Vec<(String, String)>),
         Ret(IrAstTy, String),
         Store(String, String),
+        StoreTo(String, String, IrAstTy),
     }
 
     #[derive(Debug)]
@@ -480,6 +525,7 @@ mod ir_builder {
         U64,
         B256,
         String(u64),
+        Pointer,
         Array(Box<IrAstTy>, u64),
         Union(Vec<IrAstTy>),
         Struct(Vec<IrAstTy>),
@@ -493,6 +539,7 @@ mod ir_builder {
                 IrAstTy::U64 => Type::Uint(64),
                 IrAstTy::B256 => Type::B256,
                 IrAstTy::String(n) => Type::String(*n),
+                IrAstTy::Pointer => Type::Pointer,
                 IrAstTy::Array(..) => Type::Array(self.to_ir_aggregate_type(context)),
                 IrAstTy::Union(_) => Type::Union(self.to_ir_aggregate_type(context)),
                 IrAstTy::Struct(_) => Type::Struct(self.to_ir_aggregate_type(context)),
@@ -601,6 +648,10 @@ mod ir_builder {
         let block = named_blocks.get(&ir_block.label).unwrap();
         for ins in ir_block.instructions {
             let ins_val = match ins.op {
+                IrAstOperation::Alloc(ty, count) => {
+                    let ty = ty.to_ir_type(context);
+                    block.ins(context).alloc(ty, *val_map.get(&count).unwrap())
+                }
                 IrAstOperation::Asm(args, return_name, ops) => {
                     let args = args
                         .into_iter()
@@ -670,6 +721,14 @@ mod ir_builder {
                         .ins(context)
                         .extract_value(*val_map.get(&val).unwrap(), ir_ty, idcs)
                 }
+                IrAstOperation::GetElemPtr(ptr, ty, idx) => {
+                    let ty = ty.to_ir_type(context);
+                    block.ins(context).get_elem_ptr(
+                        *val_map.get(&ptr).unwrap(),
+                        ty,
+                        *val_map.get(&idx).unwrap(),
+                    )
+                }
                 IrAstOperation::GetPtr(src_name) => {
                     block.ins(context).get_ptr(*ptr_map.get(&src_name).unwrap())
                 }
@@ -694,6 +753,21 @@ mod ir_builder {
                 IrAstOperation::Load(src_name) => {
                     block.ins(context).load(*ptr_map.get(&src_name).unwrap())
                 }
+                IrAstOperation::LoadFrom(ptr, ty) => {
+                    let ty = ty.to_ir_type(context);
+                    block
+                        .ins(context)
+                        .load_from(*val_map.get(&ptr).unwrap(), ty)
+                }
+                IrAstOperation::MemCopy(dst, src, ty, count) => {
+                    let ty = ty.to_ir_type(context);
+                    block.ins(context).mem_copy(
+                        *val_map.get(&dst).unwrap(),
+                        *val_map.get(&src).unwrap(),
+                        ty,
+                        *val_map.get(&count).unwrap(),
+                    )
+                }
                 IrAstOperation::Phi(pairs) => {
                     for (block_name, val_name) in pairs {
                         block.add_phi(
@@ -714,6 +788,14 @@ mod ir_builder {
                     *ptr_map.get(&ptr_name).unwrap(),
                     *val_map.get(&stored_val_name).unwrap(),
                 ),
+                IrAstOperation::StoreTo(stored_val_name, ptr, ty) => {
+                    let ty = ty.to_ir_type(context);
+                    block.ins(context).store_to(
+                        *val_map.get(&ptr).unwrap(),
+                        ty,
+                        *val_map.get(&stored_val_name).unwrap(),
+                    )
+                }
             };
             ins.value_name.map(|vn| val_map.insert(vn, ins_val));
         }
@@ -245,6 +245,13 @@ fn instruction_to_doc<'a>(
 ) -> Doc {
     match &context.values[ins_value.0] {
         ValueContent::Instruction(instruction) => match instruction {
+            Instruction::Alloc { ty, count } => maybe_constant_to_doc(context, namer, count)
+                .append(Doc::text_line(format!(
+                    "{} = alloc {}, {}",
+                    namer.name(context, ins_value),
+                    ty.as_string(context),
+                    namer.name(context, count),
+                ))),
             Instruction::AsmBlock(asm, args) => {
                 asm_block_to_doc(context, namer, ins_value, asm, args)
             }
@@ -322,6 +329,17 @@ fn instruction_to_doc<'a>(
                     Doc::Comma,
                 )),
             ),
+            Instruction::GetElementPointer {
+                ptr_val,
+                ty,
+                index_val,
+            } => maybe_constant_to_doc(context, namer, index_val).append(Doc::text_line(format!(
+                "{} = get_elem_ptr {}, {}, {}",
+                namer.name(context, ins_value),
+                namer.name(context, ptr_val),
+                ty.as_string(context),
+                namer.name(context, index_val),
+            ))),
             Instruction::GetPointer(ptr) => {
                 let name = block
                     .get_function(context)
@@ -383,6 +401,24 @@ fn instruction_to_doc<'a>(
                     ptr.as_string(context, name)
                 ))
             }
+            Instruction::LoadFrom { ptr_val, ty } => Doc::text_line(format!(
+                "{} = load_from {}, {}",
+                namer.name(context, ins_value),
+                namer.name(context, ptr_val),
+                ty.as_string(context),
+            )),
+            Instruction::MemCopy {
+                dst_val,
+                src_val,
+                ty,
+                count,
+            } => maybe_constant_to_doc(context, namer, count).append(Doc::text_line(format!(
+                "mem_copy {}, {}, {}, {}",
+                namer.name(context, dst_val),
+                namer.name(context, src_val),
+                ty.as_string(context),
+                namer.name(context, count),
+            ))),
             Instruction::Phi(pairs) => {
                 if pairs.is_empty() {
                     Doc::Empty
@@ -424,6 +460,16 @@ fn instruction_to_doc<'a>(
                     ptr.as_string(context, name),
                 )))
             }
+            Instruction::StoreTo {
+                ptr_val,
+                ty,
+                stored_val,
+            } => maybe_constant_to_doc(context, namer, stored_val).append(Doc::text_line(format!(
+                "store_to {}, {}, {}",
+                namer.name(context, stored_val),
+                namer.name(context, ptr_val),
+                ty.as_string(context),
+            ))),
         },
         _ => unreachable!("Unexpected non instruction for block contents."),
     }
@@ -73,6 +73,7 @@ impl Context {
     ) -> Result<(), String> {
         if let ValueContent::Instruction(instruction) = instruction {
             match instruction {
+                Instruction::Alloc { ty, count } => self.verify_alloc(ty, count)?,
                 Instruction::AsmBlock(asm, args) => self.verify_asm_block(asm, args)?,
                 Instruction::Branch(block) => self.verify_br(block)?,
                 Instruction::Call(func, args) => self.verify_call(func, args)?,
@@ -91,6 +92,11 @@ impl Context {
                     ty,
                     indices,
                 } => self.verify_extract_value(aggregate, ty, indices)?,
+                Instruction::GetElementPointer {
+                    ptr_val,
+                    ty,
+                    index_val,
+                } => self.verify_get_elem_ptr(ptr_val, ty, index_val)?,
                 Instruction::GetPointer(ptr) => self.verify_get_ptr(ptr)?,
                 Instruction::InsertElement {
                     array,
@@ -105,9 +111,21 @@ impl Context {
                     indices,
                 } => self.verify_insert_values(aggregate, ty, value, indices)?,
                 Instruction::Load(ptr) => self.verify_load(ptr)?,
+                Instruction::LoadFrom { ptr_val, ty } => self.verify_load_from(ptr_val, ty)?,
+                Instruction::MemCopy {
+                    dst_val,
+                    src_val,
+                    ty,
+                    count,
+                } => self.verify_mem_copy(dst_val, src_val, ty, count)?,
                 Instruction::Phi(pairs) => self.verify_phi(&pairs[..])?,
                 Instruction::Ret(val, ty) => self.verify_ret(function, val, ty)?,
                 Instruction::Store { ptr, stored_val } => self.verify_store(ptr, stored_val)?,
+                Instruction::StoreTo {
+                    ptr_val,
+                    ty,
+                    stored_val,
+                } => self.verify_store_to(ptr_val, ty, stored_val)?,
             }
         } else {
             unreachable!("Verify instruction is not an instruction.");
@@ -115,6 +133,10 @@ impl Context {
         Ok(())
     }
 
+    fn verify_alloc(&self, _ty: &Type, count: &Value) -> Result<(), String> {
+        self.verify_uint_value(count, "Alloc count")
+    }
+
     fn verify_asm_block(&self, _asm: &AsmBlock, _args: &[AsmArg]) -> Result<(), String> {
         Ok(())
     }
@@ -164,6 +186,16 @@ impl Context {
         Ok(())
     }
 
+    fn verify_get_elem_ptr(
+        &self,
+        ptr_val: &Value,
+        _ty: &Type,
+        index_val: &Value,
+    ) -> Result<(), String> {
+        self.verify_ptr_value(ptr_val, "Get element pointer base")?;
+        self.verify_uint_value(index_val, "Get element pointer index")
+    }
+
     fn verify_get_ptr(&self, _ptr: &Pointer) -> Result<(), String> {
         // XXX get_ptr() shouldn't exist in the final IR?
         Ok(())
@@ -195,6 +227,22 @@ impl Context {
         Ok(())
     }
 
+    fn verify_load_from(&self, ptr_val: &Value, _ty: &Type) -> Result<(), String> {
+        self.verify_ptr_value(ptr_val, "Load from pointer")
+    }
+
+    fn verify_mem_copy(
+        &self,
+        _dst_val: &Value,
+        _src_val: &Value,
+        _ty: &Type,
+        count: &Value,
+    ) -> Result<(), String> {
+        // The source or destination may be the address of a local aggregate rather than a
+        // pointer value, so only the count is checked.
+        self.verify_uint_value(count, "Memory copy count")
+    }
+
     fn verify_phi(&self, pairs: &[(Block, Value)]) -> Result<(), String> {
         let label_set = std::collections::HashSet::<&String>::from_iter(
             pairs.iter().map(|(block, _)| &(self.blocks[block.0].label)),
@@ -234,4 +282,36 @@ impl Context {
         Ok(())
         //}
     }
+
+    fn verify_store_to(
+        &self,
+        ptr_val: &Value,
+        _ty: &Type,
+        _stored_val: &Value,
+    ) -> Result<(), String> {
+        self.verify_ptr_value(ptr_val, "Store to pointer")
+    }
+
+    // Values whose types are unknown, such as phis or asm block results, are given the benefit of
+    // the doubt.
+    fn verify_ptr_value(&self, val: &Value, what: &str) -> Result<(), String> {
+        match self.value_type(val) {
+            Some(Type::Pointer) | None => Ok(()),
+            Some(_) => Err(format!("{} must be a pointer.", what)),
+        }
+    }
+
+    fn verify_uint_value(&self, val: &Value, what: &str) -> Result<(), String> {
+        match self.value_type(val) {
+            Some(Type::Uint(_)) | None => Ok(()),
+            Some(_) => Err(format!("{} must be an integer.", what)),
+        }
+    }
+
+    fn value_type(&self, val: &Value) -> Option<Type> {
+        match &self.values[val.0] {
+            ValueContent::Instruction(Instruction::Phi(_)) => None,
+            _otherwise => val.get_type(self),
+        }
+    }
 }
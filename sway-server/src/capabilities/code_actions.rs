@@ -0,0 +1,255 @@
+use super::diagnostic::{error_to_diagnostic, warning_to_diagnostic};
+use crate::core::session::Session;
+use lspower::lsp::{
+    CodeActionOrCommand, CodeActionParams, CodeActionResponse, Diagnostic, Position, Range,
+    TextEdit,
+};
+use std::sync::Arc;
+use sway_core::{
+    error::Warning, import_paths, CompileError, CompileWarning, Namespace, NamespaceRef,
+};
+use sway_types::span::Span;
+
+pub fn get_code_actions(
+    session: Arc<Session>,
+    params: CodeActionParams,
+) -> Option<CodeActionResponse> {
+    let actions = session.get_code_actions(&params.text_document.uri, params.range)?;
+
+    Some(
+        actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect(),
+    )
+}
+
+/// A fix for a warning or an error, worked out when the document is checked.
+#[derive(Debug, Clone)]
+pub struct QuickFix {
+    pub title: String,
+    pub diagnostic: Diagnostic,
+    pub action: FixAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum FixAction {
+    /// Renames the item named at `position`, everywhere it is used.
+    Rename {
+        position: Position,
+        new_name: String,
+    },
+    /// Edits the document the diagnostic is in.
+    Edit(Vec<TextEdit>),
+}
+
+/// The fixes for the warnings and errors found in the file at `file_name`, whose text is `text`.
+/// Items that can't be found are looked for in the modules of `namespace`, and in the `modules`
+/// the project includes with `dep`.
+pub fn get_quick_fixes(
+    warnings: &[CompileWarning],
+    errors: &[CompileError],
+    file_name: &str,
+    text: &str,
+    namespace: NamespaceRef,
+    modules: &[(String, Namespace)],
+) -> Vec<QuickFix> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut fixes = vec![];
+
+    for warning in warnings
+        .iter()
+        .filter(|warning| warning.path() == file_name)
+    {
+        let diagnostic = warning_to_diagnostic(warning);
+        if let Some((name, new_name)) = warning.warning_content.suggested_name() {
+            fixes.push(QuickFix {
+                title: format!("Rename to `{}`", new_name),
+                diagnostic,
+                action: FixAction::Rename {
+                    position: get_start_position(name.span()),
+                    new_name,
+                },
+            });
+            continue;
+        }
+        match warning.warning_content {
+            Warning::DeadDeclaration
+            | Warning::DeadFunctionDeclaration
+            | Warning::DeadStructDeclaration => fixes.push(QuickFix {
+                title: "Remove unused declaration".into(),
+                diagnostic,
+                action: FixAction::Edit(vec![TextEdit::new(
+                    get_removal_range(&warning.span, &lines),
+                    String::new(),
+                )]),
+            }),
+            _ => (),
+        }
+    }
+
+    for error in errors.iter().filter(|error| error.path() == file_name) {
+        if let Some(name) = error.unresolved_name() {
+            let position = get_import_position(&lines);
+            for path in import_paths(namespace, modules, name) {
+                let path = path.join("::");
+                fixes.push(QuickFix {
+                    title: format!("Import `{}`", path),
+                    diagnostic: error_to_diagnostic(error),
+                    action: FixAction::Edit(vec![TextEdit::new(
+                        Range::new(position, position),
+                        format!("use {};\n", path),
+                    )]),
+                });
+            }
+        }
+        if let CompileError::MissingInterfaceSurfaceMethods {
+            signatures, span, ..
+        } = error
+        {
+            fixes.push(QuickFix {
+                title: "Add missing functions".into(),
+                diagnostic: error_to_diagnostic(error),
+                action: FixAction::Edit(vec![get_stubs_edit(signatures, span, &lines)]),
+            });
+        }
+    }
+
+    fixes
+}
+
+fn get_start_position(span: &Span) -> Position {
+    let (line, col) = span.start_pos().line_col();
+    Position::new(line as u32 - 1, col as u32 - 1)
+}
+
+fn get_end_position(span: &Span) -> Position {
+    let (line, col) = span.end_pos().line_col();
+    Position::new(line as u32 - 1, col as u32 - 1)
+}
+
+/// The range to delete to remove the declaration at `span`, along with the `;` after it. If the
+/// declaration has lines of its own, they are removed entirely.
+fn get_removal_range(span: &Span, lines: &[&str]) -> Range {
+    let start = get_start_position(span);
+    let mut end = get_end_position(span);
+    let end_line = lines.get(end.line as usize).copied().unwrap_or_default();
+    let after = chars_from(end_line, end.character);
+    if after.starts_with(';') {
+        end.character += 1;
+    }
+
+    let start_line = lines.get(start.line as usize).copied().unwrap_or_default();
+    let is_alone = chars_before(start_line, start.character).trim().is_empty()
+        && chars_from(end_line, end.character).trim().is_empty();
+    if is_alone {
+        // don't leave two blank lines where the declaration was
+        let is_blank =
+            |line: u32| matches!(lines.get(line as usize), Some(line) if line.trim().is_empty());
+        let after_blank = (start.line == 0 || is_blank(start.line - 1)) && is_blank(end.line + 1);
+        let end_line = end.line + 1 + after_blank as u32;
+        Range::new(Position::new(start.line, 0), Position::new(end_line, 0))
+    } else {
+        Range::new(start, end)
+    }
+}
+
+/// Where a new `use` statement goes: after the last `use` or `dep` statement, or after the line
+/// declaring the kind of program if there are none.
+fn get_import_position(lines: &[&str]) -> Position {
+    let last_import = lines.iter().rposition(|line| {
+        let line = line.trim_start();
+        line.starts_with("use ") || line.starts_with("dep ")
+    });
+    match last_import {
+        Some(line) => Position::new(line as u32 + 1, 0),
+        None => Position::new(1, 0),
+    }
+}
+
+/// Inserts a function for each of `signatures` at the end of the implementation at `span`. Each
+/// one reverts until it is filled in, which lets it compile whatever it returns.
+fn get_stubs_edit(signatures: &[String], span: &Span, lines: &[&str]) -> TextEdit {
+    let impl_start = get_start_position(span);
+    let impl_line = lines
+        .get(impl_start.line as usize)
+        .copied()
+        .unwrap_or_default();
+    let base_indent: String = impl_line
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    let indent = format!("{}    ", base_indent);
+    let stubs = signatures
+        .iter()
+        .map(|signature| {
+            let body = match get_return_type(signature) {
+                Some(return_type) => format!("asm() {{ rvrt zero; zero: {} }}", return_type),
+                None => "asm() { rvrt zero; }".to_string(),
+            };
+            format!(
+                "{indent}{} {{\n{indent}    {}\n{indent}}}\n",
+                signature,
+                body,
+                indent = indent
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // the span ends just after the closing brace
+    let end = get_end_position(span);
+    let brace = Position::new(end.line, end.character.saturating_sub(1));
+    let brace_line = lines.get(brace.line as usize).copied().unwrap_or_default();
+    if chars_before(brace_line, brace.character).trim().is_empty() {
+        let previous_line = brace
+            .line
+            .checked_sub(1)
+            .and_then(|line| lines.get(line as usize))
+            .copied()
+            .unwrap_or_default();
+        // keep a blank line between the new functions and the ones already there
+        let separator = if previous_line.trim_end().ends_with('{') {
+            ""
+        } else {
+            "\n"
+        };
+        TextEdit::new(
+            Range::new(Position::new(brace.line, 0), Position::new(brace.line, 0)),
+            format!("{}{}", separator, stubs),
+        )
+    } else {
+        TextEdit::new(
+            Range::new(brace, brace),
+            format!("\n{}{}", stubs, base_indent),
+        )
+    }
+}
+
+/// The type after the `->` of a function signature, unless it returns `()`.
+fn get_return_type(signature: &str) -> Option<&str> {
+    let mut depth = 0;
+    for (index, c) in signature.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => {
+                return signature[index + 1..]
+                    .trim()
+                    .strip_prefix("->")
+                    .map(str::trim)
+                    .filter(|return_type| *return_type != "()");
+            }
+            ')' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+fn chars_before(line: &str, character: u32) -> String {
+    line.chars().take(character as usize).collect()
+}
+
+fn chars_from(line: &str, character: u32) -> String {
+    line.chars().skip(character as usize).collect()
+}
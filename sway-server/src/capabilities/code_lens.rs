@@ -0,0 +1,65 @@
+use crate::{
+    core::{session::Session, token::Token, token_type::TokenType},
+    utils::lsp_helpers::make_range_end_inclusive,
+};
+use lspower::{
+    lsp::{CodeLens, CodeLensParams, Command, MessageType},
+    Client,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use sway_core::{source_map::SourceMap, AbiSelector};
+
+/// The command run when a selector's lens is clicked, with the selector as its only argument.
+/// The server handles it itself, through [copy_selector], so that it works in any client.
+pub const COPY_SELECTOR_COMMAND: &str = "sway.copySelector";
+
+pub fn get_code_lenses(session: Arc<Session>, params: CodeLensParams) -> Option<Vec<CodeLens>> {
+    session.get_code_lenses(&params.text_document.uri)
+}
+
+/// Runs [COPY_SELECTOR_COMMAND]. The server can't reach the clipboard, so the selector is shown
+/// to the user in a message they can copy it from, and returned to the client.
+pub async fn copy_selector(client: &Client, arguments: Vec<Value>) -> Option<Value> {
+    let selector = match arguments.as_slice() {
+        [Value::String(selector)] => selector.clone(),
+        _ => return None,
+    };
+    client
+        .show_message(MessageType::INFO, format!("selector: {}", selector))
+        .await;
+    Some(Value::String(selector))
+}
+
+/// A lens above the name of an ABI method showing its selector, along with the number of
+/// instructions it compiles to if a `source_map` of the compiled contract is given.
+pub fn to_code_lens(abi_selector: &AbiSelector, source_map: Option<&SourceMap>) -> CodeLens {
+    let selector: String = abi_selector
+        .selector
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let selector = format!("0x{}", selector);
+
+    let mut title = format!("selector: {}", selector);
+    if let Some(source_map) = source_map {
+        let count = source_map.instructions_within(&abi_selector.span);
+        let noun = if count == 1 {
+            "instruction"
+        } else {
+            "instructions"
+        };
+        title.push_str(&format!(" | {} {}", count, noun));
+    }
+
+    let token = Token::from_ident(&abi_selector.name, TokenType::Function);
+    CodeLens {
+        range: make_range_end_inclusive(token.range),
+        command: Some(Command::new(
+            title,
+            COPY_SELECTOR_COMMAND.into(),
+            Some(vec![Value::String(selector)]),
+        )),
+        data: None,
+    }
+}
@@ -7,24 +7,76 @@ pub fn get_completion(
     params: CompletionParams,
 ) -> Option<CompletionResponse> {
     let url = params.text_document_position.text_document.uri;
+    let position = params.text_document_position.position;
 
     session
-        .get_completion_items(&url)
+        .get_completion_items(&url, position)
         .map(CompletionResponse::Array)
 }
 
-pub fn to_completion_items(tokens: &[Token]) -> Vec<CompletionItem> {
-    let mut completion_items = vec![];
+/// What the name being typed at the cursor follows, which decides the items it may be.
+#[derive(Debug, PartialEq)]
+pub enum CompletionContext {
+    /// A field or method, after the `.` following a receiver whose last character is at the
+    /// given byte offset of the text.
+    Member { receiver_end: usize },
+    /// An item of the module, enum or struct at the path before a `::`.
+    Path(Vec<String>),
+    /// Any item that can be named where the cursor is.
+    Name,
+}
 
-    for token in tokens {
-        if token.is_initial_declaration() {
-            let item = CompletionItem {
-                label: token.name.clone(),
-                kind: get_kind(&token.token_type),
-                ..Default::default()
+/// Finds the context of the name being typed at the end of `text`, which is the text of the
+/// document up to the cursor.
+pub fn completion_context(text: &str) -> CompletionContext {
+    let text = text.trim_end_matches(is_ident_char);
+
+    if let Some(receiver) = text.strip_suffix('.') {
+        if receiver.ends_with(is_ident_char) {
+            return CompletionContext::Member {
+                receiver_end: receiver.len() - 1,
             };
-            completion_items.push(item);
         }
+        return CompletionContext::Name;
+    }
+
+    let mut path = vec![];
+    let mut rest = text;
+    while let Some(before_separator) = rest.strip_suffix("::") {
+        let before_segment = before_separator.trim_end_matches(is_ident_char);
+        let segment = &before_separator[before_segment.len()..];
+        if segment.is_empty() {
+            break;
+        }
+        path.insert(0, segment.to_string());
+        rest = before_segment;
+    }
+    if path.is_empty() {
+        CompletionContext::Name
+    } else {
+        CompletionContext::Path(path)
+    }
+}
+
+pub fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+pub fn to_completion_items<'a>(tokens: impl Iterator<Item = &'a Token>) -> Vec<CompletionItem> {
+    let mut completion_items: Vec<CompletionItem> = vec![];
+
+    for token in tokens {
+        // an item declared in this document may also be visible through its namespace
+        if completion_items.iter().any(|item| item.label == token.name) {
+            continue;
+        }
+        let item = CompletionItem {
+            label: token.name.clone(),
+            kind: get_kind(&token.token_type),
+            detail: Some(token.detail.clone()).filter(|detail| !detail.is_empty()),
+            ..Default::default()
+        };
+        completion_items.push(item);
     }
 
     completion_items
@@ -32,14 +84,15 @@ pub fn to_completion_items(tokens: &[Token]) -> Vec<CompletionItem> {
 
 fn get_kind(token_type: &TokenType) -> Option<CompletionItemKind> {
     match token_type {
-        TokenType::Enum => Some(CompletionItemKind::ENUM),
-        TokenType::FunctionDeclaration(_) | &TokenType::FunctionApplication => {
-            Some(CompletionItemKind::FUNCTION)
-        }
         TokenType::Library => Some(CompletionItemKind::MODULE),
-        TokenType::Struct(_) => Some(CompletionItemKind::STRUCT),
-        TokenType::Variable(_) => Some(CompletionItemKind::VARIABLE),
-        TokenType::Trait(_) => Some(CompletionItemKind::INTERFACE),
-        _ => None,
+        TokenType::Variable | TokenType::Parameter => Some(CompletionItemKind::VARIABLE),
+        TokenType::Constant => Some(CompletionItemKind::CONSTANT),
+        TokenType::Function => Some(CompletionItemKind::FUNCTION),
+        TokenType::Struct => Some(CompletionItemKind::STRUCT),
+        TokenType::Field => Some(CompletionItemKind::FIELD),
+        TokenType::Enum => Some(CompletionItemKind::ENUM),
+        TokenType::Variant => Some(CompletionItemKind::ENUM_MEMBER),
+        TokenType::Trait | TokenType::Abi => Some(CompletionItemKind::INTERFACE),
+        TokenType::TypeAlias => Some(CompletionItemKind::CLASS),
     }
 }
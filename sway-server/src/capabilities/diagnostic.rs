@@ -1,52 +1,136 @@
-use lsp::{Diagnostic, DiagnosticSeverity, Position, Range};
+use std::collections::HashMap;
+
+use lsp::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position, Range, Url,
+};
 use lspower::lsp::{self};
 
 use sway_core::{CompileError, CompileWarning};
 
+/// Diagnostics to publish, keyed by the file they belong to.
+pub type DiagnosticMap = HashMap<Url, Vec<Diagnostic>>;
+
 pub fn get_diagnostics(
     warnings: Vec<CompileWarning>,
     errors: Vec<CompileError>,
 ) -> Vec<Diagnostic> {
     let errors: Vec<Diagnostic> = errors
         .iter()
-        .map(|error| {
-            let range = get_range(&WarningOrError::Error(error));
-            Diagnostic {
-                range,
-                severity: Some(DiagnosticSeverity::ERROR),
-                message: error.to_friendly_error_string(),
-                ..Default::default()
-            }
-        })
+        .map(|error| to_diagnostic(&WarningOrError::Error(error)))
         .collect();
 
     let warnings: Vec<Diagnostic> = warnings
         .iter()
-        .map(|warning| {
-            let range = get_range(&WarningOrError::Warning(warning));
-            Diagnostic {
-                range,
-                severity: Some(DiagnosticSeverity::WARNING),
-                message: warning.to_friendly_warning_string(),
-                ..Default::default()
-            }
-        })
+        .map(|warning| to_diagnostic(&WarningOrError::Warning(warning)))
         .collect();
 
     vec![warnings, errors].into_iter().flatten().collect()
 }
 
+/// Groups the warnings and errors found while checking the document at `url` by the file they
+/// are in, using `resolve_path` to find the file a span path refers to. Errors in other files,
+/// such as the modules the document depends on, are also summarized on the document itself, so
+/// that it is clear why it failed to compile.
+pub fn get_diagnostics_by_file(
+    url: &Url,
+    warnings: Vec<CompileWarning>,
+    errors: Vec<CompileError>,
+    resolve_path: impl Fn(&str) -> Option<Url>,
+) -> DiagnosticMap {
+    let mut diagnostics = DiagnosticMap::new();
+    diagnostics.insert(url.clone(), vec![]);
+
+    let warnings_and_errors = warnings
+        .iter()
+        .map(WarningOrError::Warning)
+        .chain(errors.iter().map(WarningOrError::Error));
+    for warning_or_error in warnings_and_errors {
+        let file_url = resolve_path(&warning_or_error.path()).unwrap_or_else(|| url.clone());
+        diagnostics
+            .entry(file_url)
+            .or_default()
+            .push(to_diagnostic(&warning_or_error));
+    }
+
+    let summaries: Vec<Diagnostic> = diagnostics
+        .iter()
+        .filter(|(file_url, _)| *file_url != url)
+        .filter_map(|(file_url, file_diagnostics)| summarize_errors(file_url, file_diagnostics))
+        .collect();
+    if let Some(document_diagnostics) = diagnostics.get_mut(url) {
+        document_diagnostics.extend(summaries);
+    }
+
+    diagnostics
+}
+
+pub fn warning_to_diagnostic(warning: &CompileWarning) -> Diagnostic {
+    to_diagnostic(&WarningOrError::Warning(warning))
+}
+
+pub fn error_to_diagnostic(error: &CompileError) -> Diagnostic {
+    to_diagnostic(&WarningOrError::Error(error))
+}
+
+fn summarize_errors(file_url: &Url, diagnostics: &[Diagnostic]) -> Option<Diagnostic> {
+    let related_information: Vec<DiagnosticRelatedInformation> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+        .map(|diagnostic| DiagnosticRelatedInformation {
+            location: Location::new(file_url.clone(), diagnostic.range),
+            message: diagnostic.message.clone(),
+        })
+        .collect();
+    if related_information.is_empty() {
+        return None;
+    }
+
+    let file_name = file_url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_else(|| file_url.path());
+    Some(Diagnostic {
+        range: Range::default(),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("sway".into()),
+        message: format!("`{}` has errors", file_name),
+        related_information: Some(related_information),
+        ..Default::default()
+    })
+}
+
+fn to_diagnostic(warning_or_error: &WarningOrError<'_>) -> Diagnostic {
+    let (severity, message) = match warning_or_error {
+        WarningOrError::Error(error) => {
+            (DiagnosticSeverity::ERROR, error.to_friendly_error_string())
+        }
+        WarningOrError::Warning(warning) => (
+            DiagnosticSeverity::WARNING,
+            warning.to_friendly_warning_string(),
+        ),
+    };
+
+    Diagnostic {
+        range: get_range(warning_or_error),
+        severity: Some(severity),
+        source: Some("sway".into()),
+        message,
+        ..Default::default()
+    }
+}
+
 fn get_range(warning_or_error: &WarningOrError<'_>) -> Range {
     let (start, end) = match warning_or_error {
         WarningOrError::Error(error) => error.line_col(),
         WarningOrError::Warning(warning) => warning.line_col(),
     };
 
+    // lines and columns from the compiler start at 1
     let start_line = start.line as u32 - 1;
-    let start_character = start.col as u32;
+    let start_character = start.col as u32 - 1;
 
     let end_line = end.line as u32 - 1;
-    let end_character = end.col as u32;
+    let end_character = end.col as u32 - 1;
 
     Range {
         start: Position::new(start_line, start_character),
@@ -58,3 +142,12 @@ enum WarningOrError<'s> {
     Warning(&'s CompileWarning),
     Error(&'s CompileError),
 }
+
+impl WarningOrError<'_> {
+    fn path(&self) -> String {
+        match self {
+            WarningOrError::Error(error) => error.path(),
+            WarningOrError::Warning(warning) => warning.path(),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::core::session::Session;
+use lspower::lsp::{DocumentLink, DocumentLinkParams, Position, Range, Url};
+use std::path::Path;
+use std::sync::Arc;
+use sway_utils::constants::SWAY_EXTENSION;
+
+pub fn get_document_links(
+    session: Arc<Session>,
+    params: DocumentLinkParams,
+) -> Option<Vec<DocumentLink>> {
+    session.get_document_links(&params.text_document.uri)
+}
+
+/// Links from the path of each `dep` statement in `text` to the file it includes. The paths are
+/// relative to `dir`, the directory of the document. Files that don't exist aren't linked.
+pub fn to_document_links(text: &str, dir: &Path) -> Vec<DocumentLink> {
+    let mut links = vec![];
+
+    for (line, line_text) in (0..).zip(text.lines()) {
+        let indent = line_text.len() - line_text.trim_start().len();
+        let after_keyword = match line_text[indent..].strip_prefix("dep") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest,
+            _ => continue,
+        };
+        let path_start = line_text.len() - after_keyword.trim_start().len();
+        let path_len = line_text[path_start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '/'))
+            .unwrap_or(line_text.len() - path_start);
+        let include_path = &line_text[path_start..path_start + path_len];
+        if include_path.is_empty() {
+            continue;
+        }
+
+        let file_path = dir.join(include_path).with_extension(SWAY_EXTENSION);
+        if !file_path.is_file() {
+            continue;
+        }
+        let start = line_text[..path_start].encode_utf16().count() as u32;
+        let end = start + include_path.encode_utf16().count() as u32;
+        links.push(DocumentLink {
+            range: Range::new(Position::new(line, start), Position::new(line, end)),
+            target: Url::from_file_path(&file_path).ok(),
+            tooltip: Some(file_path.to_string_lossy().into_owned()),
+            data: None,
+        });
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn dep_statements_link_to_existing_files() {
+        let dir =
+            std::env::temp_dir().join(format!("sway-server-document-link-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("lib.sw"), "library lib;\n").unwrap();
+        fs::write(dir.join("nested").join("inner.sw"), "library inner;\n").unwrap();
+        let text = "library test;\ndep lib;\n  dep nested/inner;\ndep missing;\n// dep lib;\n";
+
+        let links: Vec<_> = to_document_links(text, &dir)
+            .into_iter()
+            .map(|link| (link.range, link.target))
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            links,
+            [
+                (
+                    Range::new(Position::new(1, 4), Position::new(1, 7)),
+                    Url::from_file_path(dir.join("lib.sw")).ok(),
+                ),
+                (
+                    Range::new(Position::new(2, 6), Position::new(2, 18)),
+                    Url::from_file_path(dir.join("nested").join("inner.sw")).ok(),
+                ),
+            ]
+        );
+    }
+}
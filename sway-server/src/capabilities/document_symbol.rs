@@ -1,4 +1,7 @@
-use crate::core::{session::Session, token::Token, token_type::TokenType};
+use crate::{
+    core::{session::Session, token::Token, token_type::TokenType},
+    utils::lsp_helpers::make_range_end_inclusive,
+};
 use lspower::lsp::{DocumentSymbolResponse, Location, SymbolInformation, SymbolKind, Url};
 use std::sync::Arc;
 
@@ -21,11 +24,11 @@ pub fn to_symbol_information(tokens: &[Token], url: Url) -> Vec<SymbolInformatio
 
 #[allow(warnings)]
 // TODO: the "deprecated: None" field is deprecated according to this library
-fn create_symbol_info(token: &Token, url: Url) -> SymbolInformation {
+pub fn create_symbol_info(token: &Token, url: Url) -> SymbolInformation {
     SymbolInformation {
         name: token.name.clone(),
         kind: get_kind(&token.token_type),
-        location: Location::new(url, token.range),
+        location: Location::new(url, make_range_end_inclusive(token.range)),
         tags: None,
         container_name: None,
         deprecated: None,
@@ -34,12 +37,15 @@ fn create_symbol_info(token: &Token, url: Url) -> SymbolInformation {
 
 fn get_kind(token_type: &TokenType) -> SymbolKind {
     match token_type {
-        TokenType::Enum => SymbolKind::ENUM,
-        TokenType::FunctionDeclaration(_) | &TokenType::FunctionApplication => SymbolKind::FUNCTION,
         TokenType::Library => SymbolKind::MODULE,
-        TokenType::Struct(_) => SymbolKind::STRUCT,
-        TokenType::Variable(_) => SymbolKind::VARIABLE,
-        TokenType::Trait(_) => SymbolKind::INTERFACE,
-        TokenType::Reassignment => SymbolKind::OPERATOR,
+        TokenType::Variable | TokenType::Parameter => SymbolKind::VARIABLE,
+        TokenType::Constant => SymbolKind::CONSTANT,
+        TokenType::Function => SymbolKind::FUNCTION,
+        TokenType::Struct => SymbolKind::STRUCT,
+        TokenType::Field => SymbolKind::FIELD,
+        TokenType::Enum => SymbolKind::ENUM,
+        TokenType::Variant => SymbolKind::ENUM_MEMBER,
+        TokenType::Trait | TokenType::Abi => SymbolKind::INTERFACE,
+        TokenType::TypeAlias => SymbolKind::CLASS,
     }
 }
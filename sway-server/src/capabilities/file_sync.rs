@@ -1,9 +1,13 @@
 use crate::core::session::Session;
 use lspower::lsp::{FileChangeType, FileEvent};
+use std::path::Path;
 use std::sync::Arc;
 
+/// Forgets deleted documents, and the projects whose manifests, lock files or dependencies
+/// changed on disk.
 pub fn handle_watched_files(session: Arc<Session>, events: Vec<FileEvent>) {
     for event in events {
+        session.invalidate_projects(Path::new(event.uri.path()));
         if event.typ == FileChangeType::DELETED {
             let _ = session.remove_document(&event.uri);
         }
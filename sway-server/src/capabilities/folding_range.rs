@@ -0,0 +1,173 @@
+use crate::core::session::Session;
+use lspower::lsp::{FoldingRange, FoldingRangeKind, FoldingRangeParams};
+use std::sync::Arc;
+
+pub fn get_folding_ranges(
+    session: Arc<Session>,
+    params: FoldingRangeParams,
+) -> Option<Vec<FoldingRange>> {
+    session.get_folding_ranges(&params.text_document.uri)
+}
+
+/// The parts of `text` that can be folded: blocks spanning several lines, block comments, runs
+/// of line comments and groups of `use` statements.
+pub fn to_folding_ranges(text: &str) -> Vec<FoldingRange> {
+    let mut ranges = get_block_ranges(text);
+    ranges.extend(get_line_ranges(text));
+
+    // a `use` statement spanning several lines is both a block and a group of imports
+    ranges.sort_by_key(|range| (range.start_line, range.kind.is_none()));
+    ranges.dedup_by_key(|range| range.start_line);
+    ranges
+}
+
+/// The blocks between braces and the block comments of `text`. Braces inside of strings and
+/// comments are skipped. The line of a closing brace is left out of its block, so that it stays
+/// visible when the block is folded.
+fn get_block_ranges(text: &str) -> Vec<FoldingRange> {
+    let mut ranges = vec![];
+    let mut open_braces = vec![];
+    let mut line = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\n' => line += 1,
+                        // an escaped newline is still counted by the arm above
+                        '\\' if chars.peek() != Some(&'\n') => {
+                            chars.next();
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let start_line = line;
+                let mut previous = None;
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    } else if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    previous = Some(c);
+                }
+                if line > start_line {
+                    ranges.push(new_range(start_line, line, Some(FoldingRangeKind::Comment)));
+                }
+            }
+            '{' => open_braces.push(line),
+            '}' => {
+                if let Some(start_line) = open_braces.pop() {
+                    if line > start_line + 1 {
+                        ranges.push(new_range(start_line, line - 1, None));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    ranges
+}
+
+/// The runs of lines holding only line comments, and the groups of consecutive `use` statements.
+fn get_line_ranges(text: &str) -> Vec<FoldingRange> {
+    let mut ranges = vec![];
+    let mut comments: Option<(u32, u32)> = None;
+    let mut imports: Option<(u32, u32)> = None;
+    let mut in_use_statement = false;
+
+    for (line, line_text) in (0..).zip(text.lines()) {
+        let line_text = line_text.trim_start();
+
+        if line_text.starts_with("//") {
+            comments = Some((comments.map_or(line, |(start, _)| start), line));
+        } else if let Some((start, end)) = comments.take() {
+            push_if_multiline(&mut ranges, start, end, FoldingRangeKind::Comment);
+        }
+
+        if in_use_statement || line_text.starts_with("use ") {
+            imports = Some((imports.map_or(line, |(start, _)| start), line));
+            in_use_statement = !line_text.contains(';');
+        } else if let Some((start, end)) = imports.take() {
+            push_if_multiline(&mut ranges, start, end, FoldingRangeKind::Imports);
+        }
+    }
+    if let Some((start, end)) = comments {
+        push_if_multiline(&mut ranges, start, end, FoldingRangeKind::Comment);
+    }
+    if let Some((start, end)) = imports {
+        push_if_multiline(&mut ranges, start, end, FoldingRangeKind::Imports);
+    }
+
+    ranges
+}
+
+fn push_if_multiline(
+    ranges: &mut Vec<FoldingRange>,
+    start_line: u32,
+    end_line: u32,
+    kind: FoldingRangeKind,
+) {
+    if end_line > start_line {
+        ranges.push(new_range(start_line, end_line, Some(kind)));
+    }
+}
+
+fn new_range(start_line: u32, end_line: u32, kind: Option<FoldingRangeKind>) -> FoldingRange {
+    FoldingRange {
+        start_line,
+        end_line,
+        kind,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_comments_and_imports_fold() {
+        let text = r#"script;
+
+use std::{
+    chain::log_u64,
+    constants::ZERO,
+};
+
+// first line
+// second line
+fn main() {
+    let s = "{";
+    /* a
+       b */
+    log_u64(ZERO);
+}
+"#;
+        let ranges: Vec<_> = to_folding_ranges(text)
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind))
+            .collect();
+
+        assert_eq!(
+            ranges,
+            [
+                // the braces of the `use` statement are folded with the rest of it
+                (2, 5, Some(FoldingRangeKind::Imports)),
+                (7, 8, Some(FoldingRangeKind::Comment)),
+                // the brace in the string doesn't open a block
+                (9, 13, None),
+                (11, 12, Some(FoldingRangeKind::Comment)),
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use crate::core::{session::Session, token::Token};
-use lspower::lsp::{GotoDefinitionParams, GotoDefinitionResponse, Location, Url};
+use crate::{core::session::Session, utils::lsp_helpers::make_range_end_inclusive};
+use lspower::lsp::{GotoDefinitionParams, GotoDefinitionResponse, Location, Range, Url};
 
 pub fn go_to_definition(
     session: Arc<Session>,
@@ -13,6 +13,6 @@ pub fn go_to_definition(
     session.get_token_definition_response(url, position)
 }
 
-pub fn to_definition_response(url: Url, token: &Token) -> GotoDefinitionResponse {
-    GotoDefinitionResponse::Scalar(Location::new(url, token.range))
+pub fn to_definition_response(url: Url, range: Range) -> GotoDefinitionResponse {
+    GotoDefinitionResponse::Scalar(Location::new(url, make_range_end_inclusive(range)))
 }
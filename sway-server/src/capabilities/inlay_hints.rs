@@ -0,0 +1,50 @@
+//! `textDocument/inlayHint` is newer than the version of `lsp-types` that `lspower` uses, so its
+//! messages are defined here and the request is handled through `request_else`.
+use crate::core::session::Session;
+use lspower::lsp::{Position, Range, TextDocumentIdentifier};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use sway_core::InlayHintKind;
+
+pub const INLAY_HINT_METHOD: &str = "textDocument/inlayHint";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHintParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHint {
+    pub position: Position,
+    pub label: String,
+    /// `1` for a type and `2` for a parameter.
+    pub kind: u32,
+    pub padding_left: bool,
+    pub padding_right: bool,
+}
+
+pub fn get_inlay_hints(session: Arc<Session>, params: InlayHintParams) -> Option<Vec<InlayHint>> {
+    session.get_inlay_hints(&params.text_document.uri, params.range)
+}
+
+pub fn to_inlay_hint(hint: &sway_core::InlayHint) -> InlayHint {
+    // a type follows the name of its variable, and a parameter name comes before its argument
+    let (line, col) = match hint.kind {
+        InlayHintKind::Type => hint.span.end_pos().line_col(),
+        InlayHintKind::Parameter => hint.span.start_pos().line_col(),
+    };
+
+    InlayHint {
+        position: Position::new(line as u32 - 1, col as u32 - 1),
+        label: hint.label.clone(),
+        kind: match hint.kind {
+            InlayHintKind::Type => 1,
+            InlayHintKind::Parameter => 2,
+        },
+        padding_left: false,
+        padding_right: hint.kind == InlayHintKind::Parameter,
+    }
+}
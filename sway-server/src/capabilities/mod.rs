@@ -1,11 +1,19 @@
+pub mod code_actions;
+pub mod code_lens;
 pub mod completion;
 pub mod diagnostic;
+pub mod document_link;
 pub mod document_symbol;
 pub mod file_sync;
+pub mod folding_range;
 pub mod formatting;
 pub mod go_to;
 pub mod highlight;
 pub mod hover;
+pub mod inlay_hints;
+pub mod references;
 pub mod rename;
 pub mod semantic_tokens;
+pub mod signature_help;
 pub mod text_sync;
+pub mod workspace_symbol;
@@ -0,0 +1,22 @@
+use crate::{core::session::Session, utils::lsp_helpers::make_range_end_inclusive};
+use lspower::lsp::{Location, ReferenceParams};
+use std::path::Path;
+use std::sync::Arc;
+
+pub fn get_references(session: Arc<Session>, params: ReferenceParams) -> Option<Vec<Location>> {
+    let url = params.text_document_position.text_document.uri;
+    let position = params.text_document_position.position;
+    let include_declaration = params.context.include_declaration;
+
+    let definition = session.get_definition(&url, position)?;
+    let locations = session
+        .get_references(&url, &definition)
+        .into_iter()
+        .filter(|(url, token)| {
+            include_declaration || !token.is_declaration_in(Path::new(url.path()))
+        })
+        .map(|(url, token)| Location::new(url, make_range_end_inclusive(token.range)))
+        .collect();
+
+    Some(locations)
+}
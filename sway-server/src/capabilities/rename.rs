@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::Arc};
 use lspower::lsp::{self, WorkspaceEdit};
 
 use crate::{
-    core::{session::Session, token::Token, token_type::TokenType},
+    core::{session::Session, token_type::TokenType},
     utils::lsp_helpers::make_range_end_inclusive,
 };
 
@@ -12,25 +12,28 @@ pub fn rename(session: Arc<Session>, params: lsp::RenameParams) -> Option<lsp::W
     let url = params.text_document_position.text_document.uri;
     let position = params.text_document_position.position;
 
-    match session.documents.get(url.path()) {
-        Some(ref document) => {
-            if let Some(token) = document.get_token_at_position(position) {
-                if let Some(tokens) = document.get_all_tokens_by_single_name(&token.name) {
-                    // todo: currently only supports single file rename
-                    let edits = prepare_token_rename(&tokens, new_name);
-                    let mut map_of_changes = HashMap::new();
-                    map_of_changes.insert(url.clone(), edits);
+    get_rename_edit(&session, &url, position, new_name)
+}
 
-                    Some(WorkspaceEdit::new(map_of_changes))
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        }
-        _ => None,
+/// Renames the item named at `position` in the document at `url` everywhere it is used.
+pub fn get_rename_edit(
+    session: &Session,
+    url: &lsp::Url,
+    position: lsp::Position,
+    new_name: String,
+) -> Option<lsp::WorkspaceEdit> {
+    let definition = session.get_definition(url, position)?;
+    if !session.is_editable(url, &definition) {
+        return None;
     }
+
+    let mut map_of_changes: HashMap<lsp::Url, Vec<lsp::TextEdit>> = HashMap::new();
+    for (url, token) in session.get_references(url, &definition) {
+        let edit = lsp::TextEdit::new(make_range_end_inclusive(token.range), new_name.clone());
+        map_of_changes.entry(url).or_default().push(edit);
+    }
+
+    Some(WorkspaceEdit::new(map_of_changes))
 }
 
 pub fn prepare_rename(
@@ -39,27 +42,22 @@ pub fn prepare_rename(
 ) -> Option<lsp::PrepareRenameResponse> {
     let url = params.text_document.uri;
 
-    match session.documents.get(url.path()) {
+    let (range, placeholder) = match session.documents.get(url.path()) {
         Some(ref document) => {
-            if let Some(token) = document.get_token_at_position(params.position) {
-                match token.token_type {
-                    TokenType::Library | TokenType::Reassignment => None,
-                    _ => Some(lsp::PrepareRenameResponse::RangeWithPlaceholder {
-                        range: token.range,
-                        placeholder: token.name.clone(),
-                    }),
-                }
-            } else {
-                None
+            let token = document.get_token_at_position(params.position)?;
+            match token.token_type {
+                TokenType::Library => return None,
+                _ => (make_range_end_inclusive(token.range), token.name.clone()),
             }
         }
-        _ => None,
+        _ => return None,
+    };
+
+    // only items declared in the project or one of its path dependencies can be renamed
+    let definition = session.get_definition(&url, params.position)?;
+    if !session.is_editable(&url, &definition) {
+        return None;
     }
-}
 
-fn prepare_token_rename(tokens: &[&Token], new_name: String) -> Vec<lsp::TextEdit> {
-    tokens
-        .iter()
-        .map(|token| lsp::TextEdit::new(make_range_end_inclusive(token.range), new_name.clone()))
-        .collect()
+    Some(lsp::PrepareRenameResponse::RangeWithPlaceholder { range, placeholder })
 }
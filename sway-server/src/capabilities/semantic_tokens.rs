@@ -72,23 +72,29 @@ fn create_semantic_token(next_token: &Token, prev_token: Option<&Token>) -> Sema
 }
 
 // these values should reflect indexes in `token_types`
+static CLASS: u32 = 0;
 static FUNCTION: u32 = 1;
 static LIBRARY: u32 = 3;
+static PARAMETER: u32 = 5;
 static VARIABLE: u32 = 9;
 static ENUM: u32 = 10;
 static STRUCT: u32 = 11;
 static TRAIT: u32 = 12;
+static PROPERTY: u32 = 13;
+static ENUM_MEMBER: u32 = 14;
 
 fn get_type(token_type: &TokenType) -> u32 {
     match token_type {
-        TokenType::FunctionDeclaration(_) | &TokenType::FunctionApplication => FUNCTION,
+        TokenType::Function => FUNCTION,
         TokenType::Library => LIBRARY,
-        TokenType::Variable(_) => VARIABLE,
+        TokenType::Variable | TokenType::Constant => VARIABLE,
+        TokenType::Parameter => PARAMETER,
         TokenType::Enum => ENUM,
-        TokenType::Struct(_) => STRUCT,
-        TokenType::Trait(_) => TRAIT,
-        // currently we return `variable` type as default
-        _ => VARIABLE,
+        TokenType::Variant => ENUM_MEMBER,
+        TokenType::Struct => STRUCT,
+        TokenType::Field => PROPERTY,
+        TokenType::Trait | TokenType::Abi => TRAIT,
+        TokenType::TypeAlias => CLASS,
     }
 }
 
@@ -107,6 +113,8 @@ pub fn get_semantic_tokens() -> Option<SemanticTokensServerCapabilities> {
         SemanticTokenType::ENUM,           // 10
         SemanticTokenType::STRUCT,         // 11
         SemanticTokenType::INTERFACE,      // 12
+        SemanticTokenType::PROPERTY,       // 13
+        SemanticTokenType::ENUM_MEMBER,    // 14
     ];
 
     let token_modifiers: Vec<SemanticTokenModifier> = vec![
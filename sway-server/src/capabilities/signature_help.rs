@@ -0,0 +1,108 @@
+use super::completion::{completion_context, is_ident_char, CompletionContext};
+use crate::core::{session::Session, token::Token};
+use lspower::lsp::{
+    ParameterInformation, ParameterLabel, SignatureHelp, SignatureHelpParams, SignatureInformation,
+};
+use std::sync::Arc;
+
+pub fn get_signature_help(
+    session: Arc<Session>,
+    params: SignatureHelpParams,
+) -> Option<SignatureHelp> {
+    let url = params.text_document_position_params.text_document.uri;
+    let position = params.text_document_position_params.position;
+
+    session.get_signature_help(&url, position)
+}
+
+/// The call whose arguments are being typed.
+#[derive(Debug, PartialEq)]
+pub struct Call {
+    /// The name of the function being called.
+    pub name: String,
+    /// What the name of the function follows, such as the receiver of a method.
+    pub context: CompletionContext,
+    /// The index of the argument the cursor is in.
+    pub active_parameter: u32,
+}
+
+/// Finds the call whose arguments end `text`, which is the text of the document up to the cursor,
+/// by looking back for the `(` that hasn't been closed yet.
+pub fn find_call(text: &str) -> Option<Call> {
+    let mut depth = 0;
+    let mut active_parameter = 0;
+    let mut open_paren = None;
+    for (index, c) in text.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                open_paren = Some(index);
+                break;
+            }
+            // the cursor is in a block or an array, not in a list of arguments
+            '[' | '{' | ';' => return None,
+            ',' if depth == 0 => active_parameter += 1,
+            _ => {}
+        }
+    }
+
+    let before_paren = text[..open_paren?].trim_end();
+    let before_name = before_paren.trim_end_matches(is_ident_char);
+    let name = &before_paren[before_name.len()..];
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(Call {
+        name: name.to_string(),
+        context: completion_context(before_name),
+        active_parameter,
+    })
+}
+
+pub fn to_signature_help(token: &Token, active_parameter: u32, is_method: bool) -> SignatureHelp {
+    // the receiver of a method call is passed as its `self` parameter
+    let skips_self = is_method
+        && matches!(token.parameters.first(), Some(parameter) if parameter.starts_with("self:"));
+    let active_parameter = active_parameter + skips_self as u32;
+
+    let signature = SignatureInformation {
+        label: token.detail.clone(),
+        documentation: None,
+        parameters: Some(get_parameter_labels(token)),
+        active_parameter: Some(active_parameter),
+    };
+
+    SignatureHelp {
+        signatures: vec![signature],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    }
+}
+
+/// Points each parameter at where it is written in the signature, so that the client can
+/// highlight the active one even if two of them read the same.
+fn get_parameter_labels(token: &Token) -> Vec<ParameterInformation> {
+    let mut offset = token.detail.find('(').map_or(0, |index| index + 1);
+    let mut parameters = vec![];
+    for parameter in &token.parameters {
+        let label = match token.detail[offset..].find(parameter.as_str()) {
+            Some(index) => {
+                let start = offset + index;
+                offset = start + parameter.len();
+                ParameterLabel::LabelOffsets([
+                    token.detail[..start].encode_utf16().count() as u32,
+                    token.detail[..offset].encode_utf16().count() as u32,
+                ])
+            }
+            None => ParameterLabel::Simple(parameter.clone()),
+        };
+        parameters.push(ParameterInformation {
+            label,
+            documentation: None,
+        });
+    }
+
+    parameters
+}
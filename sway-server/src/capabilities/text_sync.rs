@@ -1,18 +1,21 @@
 use std::sync::Arc;
 
 use lspower::lsp::{
-    Diagnostic, DidChangeTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    DidChangeTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams, Url,
 };
 
-use crate::core::{
-    document::{DocumentError, TextDocument},
-    session::Session,
+use crate::{
+    capabilities::diagnostic::DiagnosticMap,
+    core::{
+        document::{DocumentError, TextDocument},
+        session::Session,
+    },
 };
 
 pub fn handle_open_file(
     session: Arc<Session>,
     params: &DidOpenTextDocumentParams,
-) -> Vec<Diagnostic> {
+) -> DiagnosticMap {
     let path = params.text_document.uri.path();
 
     if !session.contains_sway_file(&params.text_document.uri) {
@@ -21,35 +24,39 @@ pub fn handle_open_file(
         }
     }
 
-    match session.parse_document(path) {
-        Ok(diagnostics) => diagnostics,
-        Err(DocumentError::FailedToParse(diagnostics)) => diagnostics,
-        _ => vec![],
-    }
+    session.parse_document(path).unwrap_or_default()
 }
 
 pub fn handle_change_file(
     session: Arc<Session>,
     params: DidChangeTextDocumentParams,
 ) -> Result<(), DocumentError> {
-    session.update_text_document(&params.text_document.uri, params.content_changes)
+    session.update_text_document(
+        &params.text_document.uri,
+        params.text_document.version,
+        params.content_changes,
+    )
+}
+
+/// Checks a document after it was changed to `version`, unless it has been changed again since,
+/// in which case the check for the newer version makes this one redundant.
+pub fn handle_changed_file_check(
+    session: Arc<Session>,
+    url: &Url,
+    version: i32,
+) -> Option<DiagnosticMap> {
+    if session.get_document_version(url) != Some(version) {
+        return None;
+    }
+
+    session.parse_document(url.path()).ok()
 }
 
 pub fn handle_save_file(
     session: Arc<Session>,
     params: &DidSaveTextDocumentParams,
-) -> Option<Vec<Diagnostic>> {
+) -> DiagnosticMap {
     let path = params.text_document.uri.path();
 
-    match session.parse_document(path) {
-        Ok(diagnostics) => {
-            if diagnostics.is_empty() {
-                None
-            } else {
-                Some(diagnostics)
-            }
-        }
-        Err(DocumentError::FailedToParse(diagnostics)) => Some(diagnostics),
-        _ => None,
-    }
+    session.parse_document(path).unwrap_or_default()
 }
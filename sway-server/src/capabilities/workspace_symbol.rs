@@ -0,0 +1,98 @@
+use super::document_symbol::create_symbol_info;
+use crate::{
+    core::{session::Session, token::Token, token_type::TokenType},
+    utils::lsp_helpers::make_range_end_inclusive,
+};
+use lspower::lsp::{SymbolInformation, Url, WorkspaceSymbolParams};
+use std::sync::Arc;
+
+pub fn get_workspace_symbols(
+    session: Arc<Session>,
+    params: WorkspaceSymbolParams,
+) -> Option<Vec<SymbolInformation>> {
+    Some(session.get_workspace_symbols(&params.query))
+}
+
+/// Whether a token declaring an item of this type is listed by a workspace symbol search.
+pub fn is_workspace_symbol(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Struct
+            | TokenType::Enum
+            | TokenType::Trait
+            | TokenType::Abi
+            | TokenType::Function
+            | TokenType::Constant
+    )
+}
+
+/// The symbols matching `query` among `declarations`, each declared in the document at the given
+/// url, best matches first.
+pub fn to_workspace_symbols(
+    query: &str,
+    declarations: Vec<(Url, Token)>,
+) -> Vec<SymbolInformation> {
+    let mut matches: Vec<_> = declarations
+        .into_iter()
+        .filter_map(|(url, token)| {
+            let score = fuzzy_match(query, &token.name)?;
+            Some((score, url, token))
+        })
+        .collect();
+    matches.sort_by(
+        |(score, url, token), (other_score, other_url, other_token)| {
+            (score, &token.name, url.as_str(), token.range.start).cmp(&(
+                other_score,
+                &other_token.name,
+                other_url.as_str(),
+                other_token.range.start,
+            ))
+        },
+    );
+
+    matches
+        .into_iter()
+        .map(|(_, url, token)| {
+            let mut symbol = create_symbol_info(&token, url);
+            symbol.location.range = make_range_end_inclusive(token.range);
+            symbol
+        })
+        .collect()
+}
+
+/// Whether the characters of `query` appear in `name` in the same order, ignoring case. If they
+/// do, the score is how many characters of `name` were skipped before and between them, so that
+/// names starting with the query, and then names containing it, come first.
+fn fuzzy_match(query: &str, name: &str) -> Option<usize> {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase).enumerate();
+    let mut score = 0;
+    let mut next_index = 0;
+    for query_char in query.chars().flat_map(char::to_lowercase) {
+        let (index, _) = name_chars.find(|(_, name_char)| *name_char == query_char)?;
+        score += index - next_index;
+        next_index = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_match_names_holding_their_characters_in_order() {
+        // a subsequence, scored by the characters skipped before and between the matches
+        assert_eq!(fuzzy_match("tfr", "transfer"), Some(5));
+        assert_eq!(fuzzy_match("trans", "transfer"), Some(0));
+        assert_eq!(fuzzy_match("fer", "transfer"), Some(5));
+        // case doesn't matter, on either side
+        assert_eq!(fuzzy_match("mystruct", "MyStruct"), Some(0));
+        assert_eq!(fuzzy_match("MS", "my_struct"), Some(2));
+        // an empty query matches everything
+        assert_eq!(fuzzy_match("", "anything"), Some(0));
+        // the characters must all appear, and in the same order
+        assert_eq!(fuzzy_match("rt", "transfer"), None);
+        assert_eq!(fuzzy_match("transfers", "transfer"), None);
+    }
+}
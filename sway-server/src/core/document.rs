@@ -1,23 +1,49 @@
-use super::token::Token;
+use super::project::Project;
+use super::token::{tokens_from_untyped_nodes, Token};
 use super::token_type::TokenType;
-use crate::{capabilities, core::token::traverse_node};
-use lspower::lsp::{Diagnostic, Position, Range, TextDocumentContentChangeEvent};
+use crate::capabilities::{
+    self, code_actions::QuickFix, diagnostic::DiagnosticMap, inlay_hints::InlayHint,
+};
+use lspower::lsp::{CodeLens, Position, Range, TextDocumentContentChangeEvent, Url};
 use ropey::Rope;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
-use sway_core::{parse, TreeType};
+use sway_core::{
+    parse, type_engine::EnteredTypeEngine, AbiSelector, CompileAstResult, CompileError,
+    CompileWarning, NamespaceRef, TreeType, TypeEngine,
+};
 
 #[derive(Debug)]
 pub struct TextDocument {
     #[allow(dead_code)]
     language_id: String,
-    #[allow(dead_code)]
     version: i32,
     uri: String,
     content: Rope,
     tokens: Vec<Token>,
+    /// The items that can be named at the top level of this document, including those declared
+    /// in other modules.
+    declarations: Vec<Token>,
+    /// The root namespace from the last successful compilation, for looking up the members of
+    /// types and the items of modules.
+    namespace: Option<NamespaceRef>,
+    /// The engine holding the types of `namespace` and of the tokens.
+    type_engine: Option<TypeEngine>,
+    inlay_hints: Vec<InlayHint>,
+    /// The selectors of the ABI methods implemented in this document, from the last successful
+    /// compilation.
+    abi_selectors: Vec<AbiSelector>,
+    /// The lenses showing `abi_selectors`, once they have been worked out. Counting the
+    /// instructions of each method takes a compilation to bytecode, so it is only done once for
+    /// each successful compilation.
+    code_lenses: Option<Vec<CodeLens>>,
+    /// The fixes for the warnings and errors from the last check of this document.
+    fixes: Vec<QuickFix>,
+    /// The files that were given diagnostics by the last check of this document, so they can be
+    /// cleared once they are fixed.
+    diagnosed_files: HashSet<Url>,
     lines: HashMap<u32, Vec<usize>>,
-    values: HashMap<String, Vec<usize>>,
 }
 
 impl TextDocument {
@@ -29,8 +55,15 @@ impl TextDocument {
                 uri: path.into(),
                 content: Rope::from_str(&content),
                 tokens: vec![],
+                declarations: vec![],
+                namespace: None,
+                type_engine: None,
+                inlay_hints: vec![],
+                abi_selectors: vec![],
+                code_lenses: None,
+                fixes: vec![],
+                diagnosed_files: HashSet::new(),
                 lines: HashMap::new(),
-                values: HashMap::new(),
             }),
             Err(_) => Err(DocumentError::DocumentNotFound),
         }
@@ -51,48 +84,125 @@ impl TextDocument {
         None
     }
 
-    pub fn get_all_tokens_by_single_name(&self, name: &str) -> Option<Vec<&Token>> {
-        if let Some(indices) = self.values.get(name) {
-            let tokens = indices.iter().map(|index| &self.tokens[*index]).collect();
-            Some(tokens)
-        } else {
-            None
-        }
+    /// The token named `name` whose last character is at `position`. Text typed since the last
+    /// successful compilation has no tokens, so the closest token with that name before
+    /// `position` is used instead.
+    pub fn get_token_ending_at(&self, name: &str, position: Position) -> Option<&Token> {
+        let at_position = self.lines.get(&position.line).and_then(|indices| {
+            indices
+                .iter()
+                .map(|index| &self.tokens[*index])
+                .find(|token| token.range.end == position && token.name == name)
+        });
+
+        at_position.or_else(|| {
+            self.tokens
+                .iter()
+                .rev()
+                .filter(|token| token.range.end < position)
+                .find(|token| token.name == name)
+        })
     }
 
-    pub fn get_declared_token(&self, name: &str) -> Option<&Token> {
-        if let Some(indices) = self.values.get(name) {
-            for index in indices {
-                let token = &self.tokens[*index];
-                if token.is_initial_declaration() {
-                    return Some(token);
-                }
-            }
-        }
-        None
+    pub fn is_declaration(&self, token: &Token) -> bool {
+        token.is_declaration_in(Path::new(&self.uri))
     }
 
     pub fn get_tokens(&self) -> &Vec<Token> {
         &self.tokens
     }
 
+    pub fn get_declarations(&self) -> &Vec<Token> {
+        &self.declarations
+    }
+
+    pub fn get_namespace(&self) -> Option<NamespaceRef> {
+        self.namespace
+    }
+
+    /// Enters the type engine of the last successful compilation, so that the types of the
+    /// namespace and the tokens can be looked up.
+    pub fn enter_type_engine(&self) -> Option<EnteredTypeEngine> {
+        self.type_engine.as_ref().map(TypeEngine::enter)
+    }
+
+    pub fn get_inlay_hints(&self) -> &Vec<InlayHint> {
+        &self.inlay_hints
+    }
+
+    pub fn get_abi_selectors(&self) -> &Vec<AbiSelector> {
+        &self.abi_selectors
+    }
+
+    pub fn get_code_lenses(&self) -> Option<&Vec<CodeLens>> {
+        self.code_lenses.as_ref()
+    }
+
+    pub fn set_code_lenses(&mut self, code_lenses: Vec<CodeLens>) {
+        self.code_lenses = Some(code_lenses);
+    }
+
+    pub fn get_fixes(&self) -> &Vec<QuickFix> {
+        &self.fixes
+    }
+
     pub fn get_uri(&self) -> &str {
         &self.uri
     }
 
-    pub fn parse(&mut self) -> Result<Vec<Diagnostic>, DocumentError> {
-        self.clear_tokens();
-        self.clear_hash_maps();
+    /// Type checks the document as part of `project`, or only parses it if it doesn't belong to
+    /// one or its project couldn't be loaded. If it fails to compile, the tokens from the last
+    /// successful compilation are kept.
+    pub fn parse(&mut self, project: Option<&Project>) -> Result<DiagnosticMap, DocumentError> {
+        let result = match project {
+            Some(project) => self.compile_tokens_from_text(project),
+            None => self.parse_tokens_from_text(),
+        };
 
-        match self.parse_tokens_from_text() {
-            Ok((tokens, diagnostics)) => {
+        match result {
+            Ok(Parsed {
+                tokens,
+                declarations,
+                namespace,
+                type_engine,
+                inlay_hints,
+                abi_selectors,
+                fixes,
+                diagnostics,
+            }) => {
+                self.clear_tokens();
+                self.clear_hash_maps();
                 self.store_tokens(tokens);
+                self.declarations = declarations;
+                self.namespace = namespace;
+                self.type_engine = type_engine;
+                self.inlay_hints = inlay_hints;
+                self.abi_selectors = abi_selectors;
+                self.code_lenses = None;
+                self.fixes = fixes;
                 Ok(diagnostics)
             }
-            Err(diagnostics) => Err(DocumentError::FailedToParse(diagnostics)),
+            Err(Failed { diagnostics, fixes }) => {
+                self.fixes = fixes;
+                Err(DocumentError::FailedToParse(diagnostics))
+            }
         }
     }
 
+    /// Replaces the files recorded as having diagnostics from this document, returning the
+    /// previous ones.
+    pub fn replace_diagnosed_files(&mut self, files: HashSet<Url>) -> HashSet<Url> {
+        std::mem::replace(&mut self.diagnosed_files, files)
+    }
+
+    pub fn get_version(&self) -> i32 {
+        self.version
+    }
+
+    pub fn set_version(&mut self, version: i32) {
+        self.version = version;
+    }
+
     pub fn apply_change(&mut self, change: &TextDocumentContentChangeEvent) {
         let edit = self.build_edit(change);
 
@@ -103,48 +213,163 @@ impl TextDocument {
     pub fn get_text(&self) -> String {
         self.content.to_string()
     }
+
+    /// The text of the document up to `position`.
+    pub fn get_text_before(&self, position: Position) -> String {
+        let index = self
+            .position_to_index(position)
+            .min(self.content.len_chars());
+        self.content.slice(..index).to_string()
+    }
 }
 
 // private methods
 impl TextDocument {
-    fn parse_tokens_from_text(&self) -> Result<(Vec<Token>, Vec<Diagnostic>), Vec<Diagnostic>> {
+    fn compile_tokens_from_text(&self, project: &Project) -> ParseResult {
+        let path = Path::new(&self.uri);
+        let text: Arc<str> = Arc::from(self.get_text());
+        let file_name = project.file_name(path);
+        let file_name = file_name.to_string_lossy();
+        let url = self.get_url();
+        let resolve_path = |span_path: &str| {
+            project
+                .resolve_span_path(Path::new(span_path))
+                .and_then(|path| Url::from_file_path(path).ok())
+        };
+
+        let type_engine = project.new_type_engine();
+        let compiled = project.compile(path, text.clone(), &type_engine);
+        // items that can't be found are looked for in the modules from the last successful
+        // compilation, and in every module the project's files include with `dep`
+        let get_fixes = |warnings: &[CompileWarning], errors: &[CompileError]| {
+            let (namespace, type_engine) = match (self.namespace, &self.type_engine) {
+                (Some(namespace), Some(type_engine)) => (namespace, type_engine.clone()),
+                _ => (project.dependency_namespace(), project.new_type_engine()),
+            };
+            let modules = project.included_modules(path);
+            let _type_engine = type_engine.enter();
+            capabilities::code_actions::get_quick_fixes(
+                warnings, errors, &file_name, &text, namespace, &modules,
+            )
+        };
+
+        match compiled {
+            CompileAstResult::Success {
+                parse_tree,
+                tree_type,
+                warnings,
+            } => {
+                let _type_engine = type_engine.enter();
+                let mut tokens = vec![];
+                if let TreeType::Library { name } = tree_type {
+                    tokens.push(Token::from_ident(&name, TokenType::Library));
+                }
+                tokens.extend(
+                    parse_tree
+                        .symbols()
+                        .iter()
+                        .filter(|symbol| symbol.ident.span().path() == file_name)
+                        .map(|symbol| Token::from_symbol(symbol, project)),
+                );
+                // Names are collected in the order they are resolved, which isn't always the
+                // order they are written in.
+                tokens.sort_by_key(|token| (token.range.start.line, token.range.start.character));
+                tokens.dedup_by_key(|token| token.range);
+
+                let declarations = parse_tree
+                    .visible_declarations()
+                    .iter()
+                    .map(|symbol| Token::from_symbol(symbol, project))
+                    .collect();
+
+                let inlay_hints = parse_tree
+                    .inlay_hints()
+                    .iter()
+                    .filter(|hint| hint.span.path() == file_name)
+                    .map(capabilities::inlay_hints::to_inlay_hint)
+                    .collect();
+
+                let abi_selectors = parse_tree
+                    .abi_selectors()
+                    .value
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|selector| selector.span.path() == file_name)
+                    .collect();
+
+                Ok(Parsed {
+                    tokens,
+                    declarations,
+                    namespace: Some(parse_tree.namespace_ref()),
+                    type_engine: Some(type_engine.clone()),
+                    inlay_hints,
+                    abi_selectors,
+                    fixes: get_fixes(&warnings, &[]),
+                    diagnostics: capabilities::diagnostic::get_diagnostics_by_file(
+                        &url,
+                        warnings,
+                        vec![],
+                        resolve_path,
+                    ),
+                })
+            }
+            CompileAstResult::Failure { warnings, errors } => Err(Failed {
+                fixes: get_fixes(&warnings, &errors),
+                diagnostics: capabilities::diagnostic::get_diagnostics_by_file(
+                    &url,
+                    warnings,
+                    errors,
+                    resolve_path,
+                ),
+            }),
+        }
+    }
+
+    fn parse_tokens_from_text(&self) -> ParseResult {
         let text = Arc::from(self.get_text());
         let parsed_result = parse(text, None);
+        let diagnostics = DiagnosticMap::from([(
+            self.get_url(),
+            capabilities::diagnostic::get_diagnostics(parsed_result.warnings, parsed_result.errors),
+        )]);
         match parsed_result.value {
-            None => Err(capabilities::diagnostic::get_diagnostics(
-                parsed_result.warnings,
-                parsed_result.errors,
-            )),
+            None => Err(Failed {
+                diagnostics,
+                fixes: vec![],
+            }),
             Some(value) => {
                 let mut tokens = vec![];
-
                 if let TreeType::Library { name } = value.tree_type {
-                    // TODO
-                    // Is library name necessary to store for the LSP?
-                    let token = Token::from_ident(&name, TokenType::Library);
-                    tokens.push(token);
-                };
-                for node in value.tree.root_nodes {
-                    traverse_node(node, &mut tokens);
+                    tokens.push(Token::from_ident(&name, TokenType::Library));
                 }
-
-                Ok((
+                tokens.extend(tokens_from_untyped_nodes(
+                    &value.tree.root_nodes,
+                    Path::new(&self.uri),
+                ));
+                tokens.sort_by_key(|token| (token.range.start.line, token.range.start.character));
+                Ok(Parsed {
                     tokens,
-                    capabilities::diagnostic::get_diagnostics(
-                        parsed_result.warnings,
-                        parsed_result.errors,
-                    ),
-                ))
+                    declarations: vec![],
+                    namespace: None,
+                    type_engine: None,
+                    inlay_hints: vec![],
+                    abi_selectors: vec![],
+                    fixes: vec![],
+                    diagnostics,
+                })
             }
         }
     }
 
+    fn get_url(&self) -> Url {
+        Url::from_file_path(&self.uri).expect("documents are stored by their absolute path")
+    }
+
     fn store_tokens(&mut self, tokens: Vec<Token>) {
         self.tokens = Vec::with_capacity(tokens.len());
 
         for (index, token) in tokens.into_iter().enumerate() {
             let line = token.get_line_start();
-            let token_name = token.name.clone();
 
             // insert to tokens
             self.tokens.push(token);
@@ -158,22 +383,11 @@ impl TextDocument {
                     self.lines.insert(line, vec![index]);
                 }
             }
-
-            // insert index into hashmap for names
-            match self.values.get_mut(&token_name) {
-                Some(v) => {
-                    v.push(index);
-                }
-                None => {
-                    self.values.insert(token_name, vec![index]);
-                }
-            }
         }
     }
 
     fn clear_hash_maps(&mut self) {
         self.lines = HashMap::new();
-        self.values = HashMap::new();
     }
 
     fn clear_tokens(&mut self) {
@@ -236,6 +450,26 @@ impl TextDocument {
     }
 }
 
+/// What a successful compilation of a document found.
+struct Parsed {
+    tokens: Vec<Token>,
+    declarations: Vec<Token>,
+    namespace: Option<NamespaceRef>,
+    type_engine: Option<TypeEngine>,
+    inlay_hints: Vec<InlayHint>,
+    abi_selectors: Vec<AbiSelector>,
+    fixes: Vec<QuickFix>,
+    diagnostics: DiagnosticMap,
+}
+
+/// What a failed compilation of a document found.
+struct Failed {
+    diagnostics: DiagnosticMap,
+    fixes: Vec<QuickFix>,
+}
+
+type ParseResult = Result<Parsed, Failed>;
+
 #[derive(Debug)]
 struct EditText<'text> {
     start_index: usize,
@@ -245,7 +479,7 @@ struct EditText<'text> {
 
 #[derive(Debug)]
 pub enum DocumentError {
-    FailedToParse(Vec<Diagnostic>),
+    FailedToParse(DiagnosticMap),
     DocumentNotFound,
     DocumentAlreadyStored,
 }
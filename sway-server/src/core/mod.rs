@@ -1,4 +1,5 @@
 pub mod document;
+pub mod project;
 pub mod session;
 pub(crate) mod token;
 pub(crate) mod token_type;
@@ -0,0 +1,213 @@
+use forc_pkg::{
+    find_file_name, find_main_path, get_main_file,
+    graph::{Package, Source},
+    lock::find_lock_dir,
+    Lock, PackageGraph,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sway_core::{
+    compile_to_ast, compile_to_bytecode, create_module, module_cache::ModuleCache,
+    source_map::SourceMap, BuildConfig, BytecodeCompilationResult, CompileAstResult, Namespace,
+    NamespaceRef, NamespaceWrapper, TypeEngine, TypedParseTree,
+};
+
+/// A Sway package on disk. The namespaces of its dependencies are compiled once, when the project
+/// is loaded, so that each of its documents can be type checked against them as it changes.
+#[derive(Debug)]
+pub struct Project {
+    manifest_dir: PathBuf,
+    dependencies: Vec<(String, NamespaceRef)>,
+    /// The types of the dependencies, which the engine of each compilation is layered on.
+    type_engine: TypeEngine,
+    /// The manifest directories of this package and of every package it depends on. The paths in
+    /// spans are relative to the parent of one of these.
+    package_dirs: Vec<PathBuf>,
+    /// The manifest directories of this package and of the packages it depends on by path, whose
+    /// files may be edited by renames. Packages fetched from GitHub are left alone.
+    editable_dirs: Vec<PathBuf>,
+    /// The modules included by earlier compilations of this package's files, so that only the
+    /// files which have changed are type checked again.
+    module_cache: ModuleCache,
+}
+
+impl Project {
+    /// Loads the package in `manifest_dir`, compiling each of its dependencies once. Dependencies
+    /// hosted on GitHub are only found if `forc` has already downloaded them, at the commit in the
+    /// lock file; the language server never fetches anything itself.
+    pub fn load(manifest_dir: &Path) -> Result<Self, String> {
+        let (lock_dir, _) = find_lock_dir(manifest_dir)?;
+        let lock = Lock::read(&lock_dir)?;
+        let graph = PackageGraph::load(manifest_dir, &lock)?;
+        let mut project = Project {
+            manifest_dir: manifest_dir.to_path_buf(),
+            dependencies: vec![],
+            type_engine: TypeEngine::new(),
+            package_dirs: vec![manifest_dir.to_path_buf()],
+            editable_dirs: vec![manifest_dir.to_path_buf()],
+            module_cache: ModuleCache::new(),
+        };
+
+        // packages reached by path from this one can be edited, those downloaded can't
+        let mut editable = vec![false; graph.packages().len()];
+        for &index in graph.compile_order().iter().rev() {
+            let package = &graph.packages()[index];
+            editable[index] |= graph.is_root(index);
+            for (_, dependency) in &package.dependencies {
+                let is_path = graph.packages()[*dependency].source == Source::Path;
+                editable[*dependency] |= editable[index] && is_path;
+            }
+        }
+
+        let mut compiled = HashMap::new();
+        for &index in graph.compile_order() {
+            let package = &graph.packages()[index];
+            let namespace = create_module();
+            for (dependency_name, dependency) in &package.dependencies {
+                namespace.insert_module_ref(dependency_name.clone(), compiled[dependency]);
+            }
+            if graph.is_root(index) {
+                project.dependencies = package
+                    .dependencies
+                    .iter()
+                    .map(|(name, dependency)| (name.clone(), compiled[dependency]))
+                    .collect();
+                break;
+            }
+
+            project.package_dirs.push(package.manifest_dir.clone());
+            if editable[index] {
+                project.editable_dirs.push(package.manifest_dir.clone());
+            }
+            let namespace = compile_dependency(&project, package, namespace)?;
+            compiled.insert(index, namespace);
+        }
+        Ok(project)
+    }
+
+    /// Type checks the file at `path`, which belongs to this project, with the given text. Its
+    /// types are created in `type_engine`, which should come from [Project::new_type_engine].
+    pub fn compile(
+        &self,
+        path: &Path,
+        text: Arc<str>,
+        type_engine: &TypeEngine,
+    ) -> CompileAstResult {
+        compile_to_ast(
+            text,
+            self.dependency_namespace(),
+            &self.build_config(path, type_engine),
+            &mut HashMap::new(),
+        )
+    }
+
+    /// Compiles the file at `path` with the given text all the way to bytecode, recording the
+    /// source code each instruction came from in `source_map`.
+    pub fn compile_to_bytecode(
+        &self,
+        path: &Path,
+        text: Arc<str>,
+        source_map: &mut SourceMap,
+    ) -> BytecodeCompilationResult {
+        compile_to_bytecode(
+            text,
+            self.dependency_namespace(),
+            self.build_config(path, &self.new_type_engine()),
+            &mut HashMap::new(),
+            source_map,
+        )
+    }
+
+    /// A type engine for a compilation of one of this project's files, which can see the types
+    /// of its dependencies. It is freed along with the compilation's results.
+    pub fn new_type_engine(&self) -> TypeEngine {
+        self.type_engine.child()
+    }
+
+    fn build_config(&self, path: &Path, type_engine: &TypeEngine) -> BuildConfig {
+        let file_name = self.file_name(path);
+        let dir_of_code = path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.manifest_dir.clone());
+        BuildConfig::root_from_file_name_and_manifest_path(file_name, self.manifest_dir.clone())
+            .dir_of_code(dir_of_code)
+            .module_cache(self.module_cache.for_compilation())
+            .type_engine(type_engine.clone())
+    }
+
+    /// A new namespace holding only the dependencies of this project, for a file to be compiled in.
+    pub fn dependency_namespace(&self) -> NamespaceRef {
+        let namespace = create_module();
+        for (dependency_name, dependency_namespace) in &self.dependencies {
+            namespace.insert_module_ref(dependency_name.clone(), *dependency_namespace);
+        }
+        namespace
+    }
+
+    /// The modules the project's files include with `dep`, by their names, which the file at
+    /// `path` could import items from.
+    pub fn included_modules(&self, path: &Path) -> Vec<(String, Namespace)> {
+        self.module_cache.importable_modules(path)
+    }
+
+    /// The path of the file at `path` as it appears in spans, which is relative to the parent of
+    /// the manifest directory.
+    pub fn file_name(&self, path: &Path) -> PathBuf {
+        match self.manifest_dir.parent() {
+            Some(parent) => path.strip_prefix(parent).unwrap_or(path).to_path_buf(),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// Turns the path of a span, in this package or one of its dependencies, back into the path
+    /// of the file on disk.
+    pub fn resolve_span_path(&self, span_path: &Path) -> Option<PathBuf> {
+        self.package_dirs
+            .iter()
+            .filter_map(|dir| dir.parent())
+            .map(|parent| parent.join(span_path))
+            .find(|path| path.is_file())
+    }
+
+    pub fn package_dirs(&self) -> &[PathBuf] {
+        &self.package_dirs
+    }
+
+    pub fn editable_dirs(&self) -> &[PathBuf] {
+        &self.editable_dirs
+    }
+
+    pub fn is_editable(&self, path: &Path) -> bool {
+        self.editable_dirs.iter().any(|dir| path.starts_with(dir))
+    }
+}
+
+/// Compiles a dependency of the project, whose own dependencies are in `namespace`, and returns
+/// the namespace it exports.
+fn compile_dependency(
+    project: &Project,
+    package: &Package,
+    namespace: NamespaceRef,
+) -> Result<NamespaceRef, String> {
+    let main_path = find_main_path(&package.manifest_dir, &package.manifest);
+    let file_name = find_file_name(&package.manifest_dir, &main_path)?;
+    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+        file_name.to_path_buf(),
+        package.manifest_dir.clone(),
+    )
+    .type_engine(project.type_engine.clone());
+    let main_file = get_main_file(&package.manifest, &package.manifest_dir)?;
+
+    match compile_to_ast(main_file, namespace, &build_config, &mut HashMap::new()) {
+        CompileAstResult::Success { parse_tree, .. } => match *parse_tree {
+            TypedParseTree::Library { namespace, .. } => Ok(namespace),
+            _ => Err(format!("Dependency {:?} is not a library.", package.name())),
+        },
+        CompileAstResult::Failure { .. } => Err(format!(
+            "Dependency {:?} failed to compile.",
+            package.name()
+        )),
+    }
+}
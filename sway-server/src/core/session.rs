@@ -1,21 +1,47 @@
-use super::document::{DocumentError, TextDocument};
+use super::{
+    document::{DocumentError, TextDocument},
+    project::Project,
+    token::{Definition, Token},
+    token_type::TokenType,
+};
 use crate::{
-    capabilities::{self, formatting::get_format_text_edits},
+    capabilities::{
+        self,
+        code_actions::FixAction,
+        completion::{completion_context, is_ident_char, CompletionContext},
+        diagnostic::DiagnosticMap,
+        formatting::get_format_text_edits,
+        inlay_hints::InlayHint,
+        workspace_symbol::{is_workspace_symbol, to_workspace_symbols},
+    },
     sway_config::SwayConfig,
+    utils::lsp_helpers::make_range_end_inclusive,
 };
 use dashmap::DashMap;
 use lspower::lsp::{
-    CompletionItem, Diagnostic, GotoDefinitionResponse, Position, Range, SemanticToken,
-    SymbolInformation, TextDocumentContentChangeEvent, TextEdit, Url,
+    CodeAction, CodeActionKind, CodeLens, CompletionItem, DocumentLink, FoldingRange,
+    GotoDefinitionResponse, Position, Range, SemanticToken, SignatureHelp, SymbolInformation,
+    TextDocumentContentChangeEvent, TextEdit, Url, WorkspaceEdit,
 };
 use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, LockResult, RwLock};
+use sway_core::{items_at_path, members_of_type, source_map::SourceMap, BytecodeCompilationResult};
+use sway_utils::{
+    constants::{LOCK_FILE_NAME, MANIFEST_FILE_NAME},
+    find_manifest_dir, get_sway_files,
+};
 
 pub type Documents = DashMap<String, TextDocument>;
+/// The projects loaded so far, by their manifest directories, or nothing for those that failed
+/// to load.
+pub type Projects = DashMap<PathBuf, Option<Arc<Project>>>;
 
 #[derive(Debug)]
 pub struct Session {
     pub documents: Documents,
+    pub projects: Projects,
     pub config: RwLock<SwayConfig>,
 }
 
@@ -23,6 +49,7 @@ impl Session {
     pub fn new() -> Self {
         Session {
             documents: DashMap::new(),
+            projects: DashMap::new(),
             config: RwLock::new(SwayConfig::default()),
         }
     }
@@ -52,11 +79,59 @@ impl Session {
         }
     }
 
-    pub fn parse_document(&self, path: &str) -> Result<Vec<Diagnostic>, DocumentError> {
-        match self.documents.get_mut(path) {
-            Some(ref mut document) => document.parse(),
-            _ => Err(DocumentError::DocumentNotFound),
+    /// Checks the document at `path` and returns the diagnostics to publish for every file the
+    /// check reported on. Files that had diagnostics from the previous check of this document
+    /// but no longer do are given an empty list, so that the client clears them.
+    pub fn parse_document(&self, path: &str) -> Result<DiagnosticMap, DocumentError> {
+        let project = self.get_project(Path::new(path));
+        let mut document = self
+            .documents
+            .get_mut(path)
+            .ok_or(DocumentError::DocumentNotFound)?;
+
+        let mut diagnostics = match document.parse(project.as_deref()) {
+            Ok(diagnostics) | Err(DocumentError::FailedToParse(diagnostics)) => diagnostics,
+            Err(err) => return Err(err),
+        };
+        let diagnosed_files = diagnostics
+            .iter()
+            .filter(|(_, file_diagnostics)| !file_diagnostics.is_empty())
+            .map(|(url, _)| url.clone())
+            .collect();
+        for url in document.replace_diagnosed_files(diagnosed_files) {
+            diagnostics.entry(url).or_default();
+        }
+
+        Ok(diagnostics)
+    }
+
+    // Project
+    /// The project the file at `path` belongs to, loading it the first time one of its files is
+    /// parsed. Files outside of a project, or in one whose dependencies can't be compiled, are
+    /// only parsed.
+    ///
+    /// A project that fails to load isn't tried again until one of the files it is loaded from
+    /// changes, so that its dependencies aren't compiled again on every keystroke.
+    fn get_project(&self, path: &Path) -> Option<Arc<Project>> {
+        let manifest_dir = find_manifest_dir(path)?;
+        if let Some(project) = self.projects.get(&manifest_dir) {
+            return project.clone();
         }
+        let project = Project::load(&manifest_dir).ok().map(Arc::new);
+        self.projects.insert(manifest_dir, project.clone());
+        project
+    }
+
+    /// Forgets the projects a change to the file at `path` may affect, so that they are loaded
+    /// again the next time one of their files is parsed.
+    pub fn invalidate_projects(&self, path: &Path) {
+        self.projects.retain(|manifest_dir, project| {
+            !affects_project(
+                path,
+                manifest_dir,
+                project.as_ref().map(|project| project.package_dirs()),
+            )
+        });
     }
 
     pub fn contains_sway_file(&self, url: &Url) -> bool {
@@ -66,6 +141,7 @@ impl Session {
     pub fn update_text_document(
         &self,
         url: &Url,
+        version: i32,
         changes: Vec<TextDocumentContentChangeEvent>,
     ) -> Result<(), DocumentError> {
         match self.documents.get_mut(url.path()) {
@@ -73,28 +149,127 @@ impl Session {
                 changes.iter().for_each(|change| {
                     document.apply_change(change);
                 });
+                document.set_version(version);
                 Ok(())
             }
             _ => Err(DocumentError::DocumentNotFound),
         }
     }
 
+    pub fn get_document_version(&self, url: &Url) -> Option<i32> {
+        self.documents
+            .get(url.path())
+            .map(|document| document.get_version())
+    }
+
+    /// Makes sure every file of the packages in `dirs` has been parsed, so that the items they
+    /// name can be searched for, even if they haven't been opened.
+    fn load_documents(&self, dirs: &[PathBuf]) {
+        for dir in dirs {
+            for file_path in get_sway_files(dir.clone()) {
+                if let Some(path) = file_path.to_str() {
+                    if self.documents.contains_key(path) {
+                        continue;
+                    }
+                    if let Ok(text_document) = TextDocument::build_from_path(path) {
+                        let _ = self.store_document(text_document);
+                        let _ = self.parse_document(path);
+                    }
+                }
+            }
+        }
+    }
+
     // Token
     pub fn get_token_ranges(&self, url: &Url, position: Position) -> Option<Vec<Range>> {
-        if let Some(document) = self.documents.get(url.path()) {
-            if let Some(token) = document.get_token_at_position(position) {
-                let result = document
-                    .get_all_tokens_by_single_name(&token.name)
-                    .unwrap()
+        let document = self.documents.get(url.path())?;
+        let token = document.get_token_at_position(position)?;
+        let ranges = match &token.definition {
+            Some(definition) => document
+                .get_tokens()
+                .iter()
+                .filter(|token| token.definition.as_ref() == Some(definition))
+                .map(|token| make_range_end_inclusive(token.range))
+                .collect(),
+            None => vec![make_range_end_inclusive(token.range)],
+        };
+
+        Some(ranges)
+    }
+
+    pub fn get_definition(&self, url: &Url, position: Position) -> Option<Definition> {
+        self.documents
+            .get(url.path())?
+            .get_token_at_position(position)?
+            .definition
+            .clone()
+    }
+
+    /// Every token naming the item declared at `definition`, in the project the document at `url`
+    /// belongs to and in its path dependencies, along with the document it is in.
+    pub fn get_references(&self, url: &Url, definition: &Definition) -> Vec<(Url, Token)> {
+        if let Some(project) = self.get_project(Path::new(url.path())) {
+            self.load_documents(project.editable_dirs());
+        }
+
+        let mut references = vec![];
+        for document in self.documents.iter() {
+            let document_url = match Url::from_file_path(document.key()) {
+                Ok(url) => url,
+                Err(_) => continue,
+            };
+            references.extend(
+                document
+                    .get_tokens()
                     .iter()
-                    .map(|token| token.range)
-                    .collect();
+                    .filter(|token| token.definition.as_ref() == Some(definition))
+                    .map(|token| (document_url.clone(), token.clone())),
+            );
+        }
 
-                return Some(result);
-            }
+        references
+    }
+
+    /// The items declared in every loaded project, and in the packages they depend on, whose
+    /// names match `query`.
+    pub fn get_workspace_symbols(&self, query: &str) -> Vec<SymbolInformation> {
+        // loading documents may load more projects, so the list is copied out first
+        let projects: Vec<Arc<Project>> = self
+            .projects
+            .iter()
+            .filter_map(|project| project.value().clone())
+            .collect();
+        for project in projects {
+            self.load_documents(project.package_dirs());
         }
 
-        None
+        let mut declarations = vec![];
+        for document in self.documents.iter() {
+            let document_url = match Url::from_file_path(document.key()) {
+                Ok(url) => url,
+                Err(_) => continue,
+            };
+            declarations.extend(
+                document
+                    .get_tokens()
+                    .iter()
+                    .filter(|token| {
+                        is_workspace_symbol(&token.token_type) && document.is_declaration(token)
+                    })
+                    .map(|token| (document_url.clone(), token.clone())),
+            );
+        }
+
+        to_workspace_symbols(query, declarations)
+    }
+
+    /// Whether the files declaring and using the item at `definition` may be edited from the
+    /// document at `url`: the item must be declared in its project or in a path dependency.
+    pub fn is_editable(&self, url: &Url, definition: &Definition) -> bool {
+        match self.get_project(Path::new(url.path())) {
+            Some(project) => project.is_editable(&definition.path),
+            None => definition.path == Path::new(url.path()),
+        }
     }
 
     pub fn get_token_definition_response(
@@ -102,39 +277,97 @@ impl Session {
         url: Url,
         position: Position,
     ) -> Option<GotoDefinitionResponse> {
-        let key = url.path();
-
-        if let Some(document) = self.documents.get(key) {
-            if let Some(token) = document.get_token_at_position(position) {
-                if token.is_initial_declaration() {
-                    return Some(capabilities::go_to::to_definition_response(url, token));
-                } else {
-                    for document_ref in &self.documents {
-                        if let Some(declared_token) = document_ref.get_declared_token(&token.name) {
-                            return match Url::from_file_path(document_ref.key()) {
-                                Ok(url) => Some(capabilities::go_to::to_definition_response(
-                                    url,
-                                    declared_token,
-                                )),
-                                Err(_) => None,
-                            };
-                        }
+        let document = self.documents.get(url.path())?;
+        let definition = document
+            .get_token_at_position(position)?
+            .definition
+            .as_ref()?;
+        let url = Url::from_file_path(&definition.path).ok()?;
+        Some(capabilities::go_to::to_definition_response(
+            url,
+            definition.range,
+        ))
+    }
+
+    pub fn get_completion_items(
+        &self,
+        url: &Url,
+        position: Position,
+    ) -> Option<Vec<CompletionItem>> {
+        let project = self.get_project(Path::new(url.path()));
+        let document = self.documents.get(url.path())?;
+        let text = document.get_text_before(position);
+        let context = completion_context(&text);
+        let tokens = get_tokens_in_context(&document, project.as_deref(), &text, &context)?;
+
+        Some(capabilities::completion::to_completion_items(tokens.iter()))
+    }
+
+    pub fn get_signature_help(&self, url: &Url, position: Position) -> Option<SignatureHelp> {
+        let project = self.get_project(Path::new(url.path()));
+        let document = self.documents.get(url.path())?;
+        let text = document.get_text_before(position);
+        let call = capabilities::signature_help::find_call(&text)?;
+        let tokens = get_tokens_in_context(&document, project.as_deref(), &text, &call.context)?;
+        let function = tokens
+            .iter()
+            .find(|token| token.token_type == TokenType::Function && token.name == call.name)?;
+
+        Some(capabilities::signature_help::to_signature_help(
+            function,
+            call.active_parameter,
+            matches!(call.context, CompletionContext::Member { .. }),
+        ))
+    }
+
+    /// The quick fixes for the diagnostics in `range` of the document at `url`.
+    pub fn get_code_actions(&self, url: &Url, range: Range) -> Option<Vec<CodeAction>> {
+        // renames look through every document, so the fixes are copied out of this one first
+        let fixes: Vec<_> = self
+            .documents
+            .get(url.path())?
+            .get_fixes()
+            .iter()
+            .filter(|fix| {
+                fix.diagnostic.range.start <= range.end && range.start <= fix.diagnostic.range.end
+            })
+            .cloned()
+            .collect();
+
+        let actions = fixes
+            .into_iter()
+            .filter_map(|fix| {
+                let edit = match fix.action {
+                    FixAction::Rename { position, new_name } => {
+                        capabilities::rename::get_rename_edit(self, url, position, new_name)?
                     }
-                }
-            }
-        }
+                    FixAction::Edit(edits) => {
+                        WorkspaceEdit::new(HashMap::from([(url.clone(), edits)]))
+                    }
+                };
+                Some(CodeAction {
+                    title: fix.title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![fix.diagnostic]),
+                    edit: Some(edit),
+                    ..Default::default()
+                })
+            })
+            .collect();
 
-        None
+        Some(actions)
     }
 
-    pub fn get_completion_items(&self, url: &Url) -> Option<Vec<CompletionItem>> {
-        if let Some(document) = self.documents.get(url.path()) {
-            return Some(capabilities::completion::to_completion_items(
-                document.get_tokens(),
-            ));
-        }
+    pub fn get_inlay_hints(&self, url: &Url, range: Range) -> Option<Vec<InlayHint>> {
+        let document = self.documents.get(url.path())?;
+        let hints = document
+            .get_inlay_hints()
+            .iter()
+            .filter(|hint| range.start <= hint.position && hint.position <= range.end)
+            .cloned()
+            .collect();
 
-        None
+        Some(hints)
     }
 
     pub fn get_semantic_tokens(&self, url: &Url) -> Option<Vec<SemanticToken>> {
@@ -158,6 +391,67 @@ impl Session {
         None
     }
 
+    /// The lenses above the ABI methods implemented in the document at `url`, as of its last
+    /// successful compilation. The text of that compilation is compiled to bytecode the first
+    /// time they are asked for, to count the instructions of each method; if that fails, only the
+    /// selectors are shown.
+    pub fn get_code_lenses(&self, url: &Url) -> Option<Vec<CodeLens>> {
+        let path = Path::new(url.path());
+        let project = self.get_project(path)?;
+        // the document isn't locked while the contract is compiled, so that it can be edited
+        let selectors = {
+            let document = self.documents.get(url.path())?;
+            if let Some(code_lenses) = document.get_code_lenses() {
+                return Some(code_lenses.clone());
+            }
+            document.get_abi_selectors().clone()
+        };
+
+        let code_lenses: Vec<_> = match selectors.first() {
+            // the spans of the selectors hold the text they were compiled from
+            Some(selector) => {
+                let text: Arc<str> = Arc::from(selector.span.input());
+                let mut source_map = SourceMap::new();
+                let source_map = match project.compile_to_bytecode(path, text, &mut source_map) {
+                    BytecodeCompilationResult::Success { .. } => Some(&source_map),
+                    _ => None,
+                };
+                selectors
+                    .iter()
+                    .map(|selector| capabilities::code_lens::to_code_lens(selector, source_map))
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        // the document may have been compiled again in the meantime, from another text
+        let mut document = self.documents.get_mut(url.path())?;
+        let compiled_text = document
+            .get_abi_selectors()
+            .first()
+            .map(|selector| selector.span.input());
+        if compiled_text == selectors.first().map(|selector| selector.span.input()) {
+            document.set_code_lenses(code_lenses.clone());
+        }
+        Some(code_lenses)
+    }
+
+    pub fn get_folding_ranges(&self, url: &Url) -> Option<Vec<FoldingRange>> {
+        let document = self.documents.get(url.path())?;
+        Some(capabilities::folding_range::to_folding_ranges(
+            &document.get_text(),
+        ))
+    }
+
+    pub fn get_document_links(&self, url: &Url) -> Option<Vec<DocumentLink>> {
+        let document = self.documents.get(url.path())?;
+        let dir = Path::new(url.path()).parent()?;
+        Some(capabilities::document_link::to_document_links(
+            &document.get_text(),
+            dir,
+        ))
+    }
+
     pub fn format_text(&self, url: &Url) -> Option<Vec<TextEdit>> {
         if let Some(document) = self.documents.get(url.path()) {
             match self.config.read() {
@@ -172,3 +466,114 @@ impl Session {
         }
     }
 }
+
+/// The items that can be named in `context`, which was found in `text`, the text of `document` up
+/// to the cursor. Members and paths are looked up in the namespace from the last successful
+/// compilation of the document.
+fn get_tokens_in_context(
+    document: &TextDocument,
+    project: Option<&Project>,
+    text: &str,
+    context: &CompletionContext,
+) -> Option<Vec<Token>> {
+    let _type_engine = document.enter_type_engine();
+    let symbols = match context {
+        CompletionContext::Member { receiver_end } => {
+            let name_start = text[..*receiver_end].trim_end_matches(is_ident_char).len();
+            let name = &text[name_start..=*receiver_end];
+            let receiver = document.get_token_ending_at(name, position_at(text, *receiver_end))?;
+            members_of_type(document.get_namespace()?, receiver.type_id?)
+        }
+        CompletionContext::Path(path) => {
+            let path: Vec<&str> = path.iter().map(String::as_str).collect();
+            items_at_path(document.get_namespace()?, &path)
+        }
+        CompletionContext::Name => {
+            let declared_tokens = document
+                .get_tokens()
+                .iter()
+                .filter(|token| document.is_declaration(token));
+            return Some(
+                declared_tokens
+                    .chain(document.get_declarations())
+                    .cloned()
+                    .collect(),
+            );
+        }
+    };
+    let project = project?;
+
+    Some(
+        symbols
+            .iter()
+            .map(|symbol| Token::from_symbol(symbol, project))
+            .collect(),
+    )
+}
+
+/// The position of the character at byte `offset` of `text`, which starts at the beginning of the
+/// document.
+/// Whether a change to the file at `path` may change what the project in `manifest_dir` loads,
+/// given the `package_dirs` of the project and its dependencies if it was loaded: the manifest
+/// or lock file of the project, of the workspace it is in or of a package it depends on, or the
+/// sources of one of those packages. Which packages a project that failed to load depends on
+/// isn't known, so it is affected by any manifest or lock file, and by the files in its own
+/// directory.
+fn affects_project(path: &Path, manifest_dir: &Path, package_dirs: Option<&[PathBuf]>) -> bool {
+    let is_manifest = matches!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some(MANIFEST_FILE_NAME | LOCK_FILE_NAME)
+    );
+    match package_dirs {
+        Some(package_dirs) => {
+            let mut dependency_dirs = package_dirs
+                .iter()
+                .filter(|dir| dir.as_path() != manifest_dir);
+            let in_workspace = matches!(path.parent(), Some(dir) if manifest_dir.starts_with(dir));
+            (is_manifest && in_workspace) || dependency_dirs.any(|dir| path.starts_with(dir))
+        }
+        None => is_manifest || path.starts_with(manifest_dir),
+    }
+}
+
+fn position_at(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(line as u32, character as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_are_affected_by_their_manifests_and_dependencies() {
+        let manifest_dir = Path::new("/work/app");
+        let package_dirs = [
+            PathBuf::from("/work/app"),
+            PathBuf::from("/work/lib"),
+            PathBuf::from("/home/user/.forc/git/checkouts/std-abc/123"),
+        ];
+        let loaded =
+            |path: &str| affects_project(Path::new(path), manifest_dir, Some(&package_dirs));
+
+        assert!(loaded("/work/app/Forc.toml"));
+        assert!(loaded("/work/app/Forc.lock"));
+        // the lock file of the workspace the project is a member of
+        assert!(loaded("/work/Forc.lock"));
+        assert!(loaded("/work/lib/Forc.toml"));
+        assert!(loaded("/work/lib/src/lib.sw"));
+        // the project's own files are compiled again as they change, without loading it again
+        assert!(!loaded("/work/app/src/main.sw"));
+        assert!(!loaded("/work/other/Forc.toml"));
+        assert!(!loaded("/work/other/src/main.sw"));
+
+        let failed = |path: &str| affects_project(Path::new(path), manifest_dir, None);
+        assert!(failed("/work/other/Forc.toml"));
+        assert!(failed("/work/app/src/main.sw"));
+        assert!(!failed("/work/other/src/main.sw"));
+    }
+}
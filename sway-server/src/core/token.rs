@@ -1,10 +1,10 @@
-use super::token_type::{get_trait_details, TokenType, VariableDetails};
-use crate::{
-    core::token_type::{get_function_details, get_struct_details},
-    utils::common::extract_var_body,
-};
+use super::{project::Project, token_type::TokenType};
 use lspower::lsp::{Position, Range};
-use sway_core::{AstNode, AstNodeContent, Declaration, Expression, VariableDeclaration};
+use std::path::{Path, PathBuf};
+use sway_core::{
+    type_engine::TypeId, AstNode, AstNodeContent, CodeBlock, Declaration, Expression, Symbol,
+    WhileLoop,
+};
 use sway_types::{ident::Ident, span::Span};
 
 #[derive(Debug, Clone)]
@@ -14,6 +14,21 @@ pub struct Token {
     pub name: String,
     pub line_start: u32,
     pub length: u32,
+    /// The type of the value this token names, or the return type of a function.
+    pub type_id: Option<TypeId>,
+    /// The declaration of the item this token names, on a single line, for hovers and
+    /// completions.
+    pub detail: String,
+    /// The parameters of a function, each as `name: type`.
+    pub parameters: Vec<String>,
+    pub definition: Option<Definition>,
+}
+
+/// Where the item a token names was declared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub path: PathBuf,
+    pub range: Range,
 }
 
 impl Token {
@@ -26,156 +41,243 @@ impl Token {
             token_type,
             line_start: range.start.line,
             length: range.end.character - range.start.character + 1,
+            type_id: None,
+            detail: String::new(),
+            parameters: vec![],
+            definition: None,
         }
     }
 
-    pub fn is_within_character_range(&self, character: u32) -> bool {
-        let range = self.range;
-        character >= range.start.character && character <= range.end.character
+    pub fn from_ident(ident: &Ident, token_type: TokenType) -> Self {
+        Token::new(ident.span(), ident.as_str().into(), token_type)
     }
 
-    pub fn is_same_type(&self, other_token: &Token) -> bool {
-        if other_token.token_type == self.token_type {
-            true
-        } else {
-            matches!(
-                (&other_token.token_type, &self.token_type),
-                (
-                    TokenType::FunctionApplication,
-                    TokenType::FunctionDeclaration(_)
-                ) | (
-                    TokenType::FunctionDeclaration(_),
-                    TokenType::FunctionApplication
-                ),
-            )
+    pub fn from_symbol(symbol: &Symbol, project: &Project) -> Self {
+        let definition = symbol.declaration.as_ref().and_then(|declaration| {
+            let span = declaration.span();
+            let path = project.resolve_span_path(Path::new(&span.path()))?;
+            Some(Definition {
+                path,
+                range: get_range_from_span(span),
+            })
+        });
+
+        Token {
+            type_id: symbol.type_id,
+            detail: symbol.detail.clone(),
+            parameters: symbol.parameters.clone(),
+            definition,
+            ..Token::from_ident(&symbol.ident, symbol.kind.into())
         }
     }
 
-    pub fn get_line_start(&self) -> u32 {
-        self.line_start
+    pub fn is_within_character_range(&self, character: u32) -> bool {
+        let range = self.range;
+        character >= range.start.character && character <= range.end.character
     }
 
-    pub fn from_variable(var_dec: &VariableDeclaration) -> Self {
-        let ident = &var_dec.name;
-        let name = ident.as_str();
-        let var_body = extract_var_body(var_dec);
-
-        Token::new(
-            ident.span(),
-            name.into(),
-            TokenType::Variable(VariableDetails {
-                is_mutable: var_dec.is_mutable,
-                var_body,
-            }),
-        )
+    pub fn get_line_start(&self) -> u32 {
+        self.line_start
     }
 
-    pub fn from_ident(ident: &Ident, token_type: TokenType) -> Self {
-        Token::new(ident.span(), ident.as_str().into(), token_type)
+    /// Whether this token is where the item it names was declared, in the document at `path`.
+    pub fn is_declaration_in(&self, path: &Path) -> bool {
+        match &self.definition {
+            Some(definition) => definition.path == path && definition.range == self.range,
+            None => false,
+        }
     }
+}
 
-    pub fn from_span(span: Span, token_type: TokenType) -> Self {
-        Token::new(&span, span.as_str().into(), token_type)
+/// The tokens of a document which has only been parsed, because the project it belongs to
+/// couldn't be loaded. Without types, each name that is used is taken to refer to the last
+/// declaration of that name in the document before it, or failing that to the first one after.
+pub fn tokens_from_untyped_nodes(nodes: &[AstNode], path: &Path) -> Vec<Token> {
+    let mut tokens = vec![];
+    for node in nodes {
+        traverse_node(node, &mut tokens);
     }
 
-    pub fn is_initial_declaration(&self) -> bool {
-        !matches!(
-            self.token_type,
-            TokenType::Reassignment | TokenType::FunctionApplication
-        )
+    let declarations: Vec<Token> = tokens
+        .iter()
+        .filter(|token| token.definition.is_some())
+        .map(|token| Token {
+            definition: Some(Definition {
+                path: path.to_path_buf(),
+                range: token.range,
+            }),
+            ..token.clone()
+        })
+        .collect();
+    for token in &mut tokens {
+        let same_name = declarations
+            .iter()
+            .filter(|declaration| declaration.name == token.name);
+        let declaration = same_name
+            .clone()
+            .rev()
+            .find(|declaration| declaration.range.start <= token.range.start)
+            .or_else(|| same_name.clone().next());
+        if let Some(declaration) = declaration {
+            token.token_type = declaration.token_type;
+            token.detail = declaration.detail.clone();
+            token.definition = declaration.definition.clone();
+        }
     }
+    tokens
 }
 
-pub fn traverse_node(node: AstNode, tokens: &mut Vec<Token>) {
-    match node.content {
-        AstNodeContent::Declaration(dec) => handle_declaration(dec, tokens),
-        AstNodeContent::Expression(exp) => handle_expression(exp, tokens),
-        // TODO
-        // handle other content types
+/// Adds a token for each declaration and use of a name in `node`. The definitions of the
+/// declarations are filled in by [tokens_from_untyped_nodes], so they are only marked here.
+fn traverse_node(node: &AstNode, tokens: &mut Vec<Token>) {
+    match &node.content {
+        AstNodeContent::Declaration(declaration) => handle_declaration(declaration, tokens),
+        AstNodeContent::Expression(expression)
+        | AstNodeContent::ImplicitReturnExpression(expression) => {
+            handle_expression(expression, tokens)
+        }
+        AstNodeContent::ReturnStatement(statement) => handle_expression(&statement.expr, tokens),
+        AstNodeContent::WhileLoop(WhileLoop { condition, body }) => {
+            handle_expression(condition, tokens);
+            handle_code_block(body, tokens);
+        }
         _ => {}
-    };
+    }
 }
 
-fn handle_declaration(declaration: Declaration, tokens: &mut Vec<Token>) {
+fn handle_declaration(declaration: &Declaration, tokens: &mut Vec<Token>) {
+    let mut declare = |name: &Ident, token_type, detail: String| {
+        tokens.push(Token {
+            detail,
+            definition: Some(Definition {
+                path: PathBuf::new(),
+                range: get_range_from_span(name.span()),
+            }),
+            ..Token::from_ident(name, token_type)
+        })
+    };
     match declaration {
         Declaration::VariableDeclaration(variable) => {
-            tokens.push(Token::from_variable(&variable));
-            handle_expression(variable.body, tokens);
-        }
-        Declaration::FunctionDeclaration(func_dec) => {
-            let ident = &func_dec.name;
-            let token = Token::from_ident(
-                ident,
-                TokenType::FunctionDeclaration(get_function_details(&func_dec)),
+            let keyword = if variable.is_mutable {
+                "let mut"
+            } else {
+                "let"
+            };
+            let name = &variable.name;
+            declare(
+                name,
+                TokenType::Variable,
+                format!("{} {}", keyword, name.as_str()),
             );
-            tokens.push(token);
-
-            for node in func_dec.body.contents {
-                traverse_node(node, tokens);
-            }
+            handle_expression(&variable.body, tokens);
         }
-        Declaration::Reassignment(reassignment) => {
-            let token_type = TokenType::Reassignment;
-            let token = match *reassignment.lhs {
-                // a reassignment's lhs can _only_ be a variable expression or
-                // struct field a subfield expression
-                Expression::SubfieldExpression { span, .. } => Token::from_span(span, token_type),
-                Expression::VariableExpression { name, .. } => Token::from_ident(&name, token_type),
-                _ => {
-                    unreachable!("any other reassignment lhs is invalid and cannot be constructed.")
-                }
-            };
-            tokens.push(token);
-
-            handle_expression(reassignment.rhs, tokens);
+        Declaration::ConstantDeclaration(constant) => {
+            let name = &constant.name;
+            declare(
+                name,
+                TokenType::Constant,
+                format!("const {}", name.as_str()),
+            );
+            handle_expression(&constant.value, tokens);
         }
-
-        Declaration::TraitDeclaration(trait_dec) => {
-            let ident = &trait_dec.name;
-            let token = Token::from_ident(ident, TokenType::Trait(get_trait_details(&trait_dec)));
-            tokens.push(token);
-
-            // todo
-            // traverse methods: Vec<FunctionDeclaration<'sc>> field as well ?
+        Declaration::FunctionDeclaration(function) => {
+            let name = &function.name;
+            declare(name, TokenType::Function, format!("fn {}", name.as_str()));
+            handle_code_block(&function.body, tokens);
+        }
+        Declaration::TraitDeclaration(declaration) => {
+            let name = &declaration.name;
+            declare(name, TokenType::Trait, format!("trait {}", name.as_str()));
+        }
+        Declaration::StructDeclaration(declaration) => {
+            let name = &declaration.name;
+            declare(name, TokenType::Struct, format!("struct {}", name.as_str()));
         }
-        Declaration::StructDeclaration(struct_dec) => {
-            let ident = &struct_dec.name;
-            let token =
-                Token::from_ident(ident, TokenType::Struct(get_struct_details(&struct_dec)));
-            tokens.push(token);
+        Declaration::EnumDeclaration(declaration) => {
+            let name = &declaration.name;
+            declare(name, TokenType::Enum, format!("enum {}", name.as_str()));
         }
-        Declaration::EnumDeclaration(enum_dec) => {
-            let ident = enum_dec.name;
-            let token = Token::from_ident(&ident, TokenType::Enum);
-            tokens.push(token);
+        Declaration::TypeAliasDeclaration(declaration) => {
+            let name = &declaration.name;
+            declare(
+                name,
+                TokenType::TypeAlias,
+                format!("type {}", name.as_str()),
+            );
+        }
+        Declaration::Reassignment(reassignment) => {
+            handle_expression(&reassignment.lhs, tokens);
+            handle_expression(&reassignment.rhs, tokens);
         }
         _ => {}
-    };
+    }
 }
 
-fn handle_expression(exp: Expression, tokens: &mut Vec<Token>) {
-    match exp {
-        Expression::CodeBlock { span: _, contents } => {
-            let nodes = contents.contents;
-
-            for node in nodes {
-                traverse_node(node, tokens);
+fn handle_expression(expression: &Expression, tokens: &mut Vec<Token>) {
+    match expression {
+        Expression::VariableExpression { name, .. } => {
+            tokens.push(Token::from_ident(name, TokenType::Variable));
+        }
+        Expression::FunctionApplication {
+            name, arguments, ..
+        } => {
+            tokens.push(Token::from_ident(&name.suffix, TokenType::Function));
+            for argument in arguments {
+                handle_expression(argument, tokens);
             }
         }
-        Expression::FunctionApplication { name, .. } => {
-            let ident = name.suffix;
-            let token = Token::from_ident(&ident, TokenType::FunctionApplication);
-            tokens.push(token);
-
-            // TODO
-            // perform a for/in on arguments ?
+        Expression::MethodApplication { arguments, .. }
+        | Expression::DelineatedPath {
+            args: arguments, ..
+        }
+        | Expression::Tuple {
+            fields: arguments, ..
+        }
+        | Expression::Array {
+            contents: arguments,
+            ..
+        } => {
+            for argument in arguments {
+                handle_expression(argument, tokens);
+            }
+        }
+        Expression::StructExpression { struct_name, .. } => {
+            tokens.push(Token::from_ident(struct_name, TokenType::Struct));
+        }
+        Expression::LazyOperator { lhs, rhs, .. } => {
+            handle_expression(lhs, tokens);
+            handle_expression(rhs, tokens);
+        }
+        Expression::SubfieldExpression { prefix, .. } | Expression::TupleIndex { prefix, .. } => {
+            handle_expression(prefix, tokens)
+        }
+        Expression::ArrayIndex { prefix, index, .. } => {
+            handle_expression(prefix, tokens);
+            handle_expression(index, tokens);
+        }
+        Expression::CodeBlock { contents, .. } => handle_code_block(contents, tokens),
+        Expression::IfExp {
+            condition,
+            then,
+            r#else,
+            ..
+        } => {
+            handle_expression(condition, tokens);
+            handle_expression(then, tokens);
+            if let Some(r#else) = r#else {
+                handle_expression(r#else, tokens);
+            }
         }
-        // TODO
-        // handle other expressions
         _ => {}
     }
 }
 
+fn handle_code_block(code_block: &CodeBlock, tokens: &mut Vec<Token>) {
+    for node in &code_block.contents {
+        traverse_node(node, tokens);
+    }
+}
+
 fn get_range_from_span(span: &Span) -> Range {
     let start = span.start_pos().line_col();
     let end = span.end_pos().line_col();
@@ -191,3 +293,54 @@ fn get_range_from_span(span: &Span) -> Range {
         end: Position::new(end_line, end_character),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn names_in_untyped_nodes_refer_to_their_declarations() {
+        let source = r#"script;
+
+struct Point {
+    x: u64,
+}
+
+fn double(x: u64) -> u64 {
+    x * 2
+}
+
+fn main() -> u64 {
+    let mut total = 1;
+    total = double(total);
+    total
+}
+"#;
+        let path = Path::new("/project/src/main.sw");
+        let parsed = sway_core::parse(Arc::from(source), None).value.unwrap();
+        let tokens = tokens_from_untyped_nodes(&parsed.tree.root_nodes, path);
+
+        let total: Vec<_> = tokens
+            .iter()
+            .filter(|token| token.name == "total")
+            .collect();
+        assert_eq!(total.len(), 4);
+        let declaration = total[0].definition.clone().unwrap();
+        assert_eq!(declaration.path, path);
+        assert_eq!(declaration.range, total[0].range);
+        assert!(total
+            .iter()
+            .all(|token| token.definition.as_ref() == Some(&declaration)
+                && token.detail == "let mut total"));
+
+        let double: Vec<_> = tokens
+            .iter()
+            .filter(|token| token.name == "double")
+            .collect();
+        assert_eq!(double.len(), 2);
+        assert_eq!(double[1].token_type, TokenType::Function);
+        assert_eq!(double[1].definition, double[0].definition);
+        assert!(tokens.iter().any(|token| token.name == "Point"));
+    }
+}
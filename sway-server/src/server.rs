@@ -1,4 +1,4 @@
-use crate::capabilities;
+use crate::capabilities::{self, diagnostic::DiagnosticMap};
 use crate::core::{
     document::{DocumentError, TextDocument},
     session::Session,
@@ -9,7 +9,12 @@ use lsp::{
 };
 use lspower::{jsonrpc, lsp, Client, LanguageServer};
 use std::sync::Arc;
+use std::time::Duration;
 use sway_utils::helpers::{find_manifest_dir, get_sway_files};
+/// How long to wait after a document changes before checking it again, so that it isn't
+/// recompiled on every keystroke.
+const DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(300);
+
 #[derive(Debug)]
 pub struct Backend {
     pub client: Client,
@@ -47,6 +52,14 @@ impl Backend {
     }
 }
 
+async fn publish_diagnostics(client: &Client, diagnostics: DiagnosticMap) {
+    for (url, file_diagnostics) in diagnostics {
+        client
+            .publish_diagnostics(url, file_diagnostics, None)
+            .await;
+    }
+}
+
 #[lspower::async_trait]
 impl LanguageServer for Backend {
     async fn initialize(&self, params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
@@ -73,9 +86,16 @@ impl LanguageServer for Backend {
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(lsp::CompletionOptions {
                     resolve_provider: Some(false),
-                    trigger_characters: None,
+                    trigger_characters: Some(vec![".".into(), ":".into()]),
                     ..Default::default()
                 }),
+                signature_help_provider: Some(lsp::SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".into(), ",".into()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: lsp::WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                }),
                 rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
                     prepare_provider: Some(true),
                     work_done_progress_options: lsp::WorkDoneProgressOptions {
@@ -83,11 +103,32 @@ impl LanguageServer for Backend {
                     },
                 })),
                 execute_command_provider: Some(lsp::ExecuteCommandOptions {
-                    commands: vec![],
+                    commands: vec![capabilities::code_lens::COPY_SELECTOR_COMMAND.into()],
                     ..Default::default()
                 }),
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
+                    lsp::CodeActionOptions {
+                        code_action_kinds: Some(vec![lsp::CodeActionKind::QUICKFIX]),
+                        work_done_progress_options: lsp::WorkDoneProgressOptions {
+                            work_done_progress: None,
+                        },
+                        resolve_provider: Some(false),
+                    },
+                )),
+                code_lens_provider: Some(lsp::CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
                 document_highlight_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: lsp::WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                }),
                 ..lsp::ServerCapabilities::default()
             },
         })
@@ -95,6 +136,46 @@ impl LanguageServer for Backend {
 
     // LSP-Server Lifecycle
     async fn initialized(&self, _: lsp::InitializedParams) {
+        // `ServerCapabilities` has no field for inlay hints yet, so they are registered here
+        let inlay_hints = lsp::Registration {
+            id: capabilities::inlay_hints::INLAY_HINT_METHOD.into(),
+            method: capabilities::inlay_hints::INLAY_HINT_METHOD.into(),
+            register_options: Some(serde_json::json!({
+                "documentSelector": [{ "language": "sway" }],
+            })),
+        };
+        if self
+            .client
+            .register_capability(vec![inlay_hints])
+            .await
+            .is_err()
+        {
+            self.log_info_message("Inlay hints are not supported by the client")
+                .await;
+        }
+
+        // projects are loaded again when their manifests, lock files or dependencies change
+        let watched_files = lsp::Registration {
+            id: "workspace/didChangeWatchedFiles".into(),
+            method: "workspace/didChangeWatchedFiles".into(),
+            register_options: Some(serde_json::json!({
+                "watchers": [
+                    { "globPattern": "**/Forc.toml" },
+                    { "globPattern": "**/Forc.lock" },
+                    { "globPattern": "**/*.sw" },
+                ],
+            })),
+        };
+        if self
+            .client
+            .register_capability(vec![watched_files])
+            .await
+            .is_err()
+        {
+            self.log_info_message("Watching files is not supported by the client")
+                .await;
+        }
+
         self.log_info_message("Server initialized").await;
     }
 
@@ -106,29 +187,33 @@ impl LanguageServer for Backend {
     // Document Handlers
     async fn did_open(&self, params: lsp::DidOpenTextDocumentParams) {
         let diagnostics = capabilities::text_sync::handle_open_file(self.session.clone(), &params);
-
-        if !diagnostics.is_empty() {
-            self.client
-                .publish_diagnostics(params.text_document.uri, diagnostics, None)
-                .await;
-        }
+        publish_diagnostics(&self.client, diagnostics).await;
     }
 
     async fn did_change(&self, params: lsp::DidChangeTextDocumentParams) {
-        let _ = capabilities::text_sync::handle_change_file(self.session.clone(), params);
+        let url = params.text_document.uri.clone();
+        let version = params.text_document.version;
+        if capabilities::text_sync::handle_change_file(self.session.clone(), params).is_err() {
+            return;
+        }
+
+        let session = self.session.clone();
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(DIAGNOSTICS_DEBOUNCE).await;
+            let diagnostics = tokio::task::spawn_blocking(move || {
+                capabilities::text_sync::handle_changed_file_check(session, &url, version)
+            })
+            .await;
+            if let Ok(Some(diagnostics)) = diagnostics {
+                publish_diagnostics(&client, diagnostics).await;
+            }
+        });
     }
 
     async fn did_save(&self, params: lsp::DidSaveTextDocumentParams) {
-        let url = params.text_document.uri.clone();
-        self.client.publish_diagnostics(url, vec![], None).await;
-
-        if let Some(diagnostics) =
-            capabilities::text_sync::handle_save_file(self.session.clone(), &params)
-        {
-            self.client
-                .publish_diagnostics(params.text_document.uri, diagnostics, None)
-                .await;
-        }
+        let diagnostics = capabilities::text_sync::handle_save_file(self.session.clone(), &params);
+        publish_diagnostics(&self.client, diagnostics).await;
     }
 
     async fn did_change_watched_files(&self, params: lsp::DidChangeWatchedFilesParams) {
@@ -155,6 +240,16 @@ impl LanguageServer for Backend {
         ))
     }
 
+    async fn signature_help(
+        &self,
+        params: lsp::SignatureHelpParams,
+    ) -> jsonrpc::Result<Option<lsp::SignatureHelp>> {
+        Ok(capabilities::signature_help::get_signature_help(
+            self.session.clone(),
+            params,
+        ))
+    }
+
     async fn document_symbol(
         &self,
         params: lsp::DocumentSymbolParams,
@@ -165,6 +260,36 @@ impl LanguageServer for Backend {
         ))
     }
 
+    async fn symbol(
+        &self,
+        params: lsp::WorkspaceSymbolParams,
+    ) -> jsonrpc::Result<Option<Vec<lsp::SymbolInformation>>> {
+        Ok(capabilities::workspace_symbol::get_workspace_symbols(
+            self.session.clone(),
+            params,
+        ))
+    }
+
+    async fn folding_range(
+        &self,
+        params: lsp::FoldingRangeParams,
+    ) -> jsonrpc::Result<Option<Vec<lsp::FoldingRange>>> {
+        Ok(capabilities::folding_range::get_folding_ranges(
+            self.session.clone(),
+            params,
+        ))
+    }
+
+    async fn document_link(
+        &self,
+        params: lsp::DocumentLinkParams,
+    ) -> jsonrpc::Result<Option<Vec<lsp::DocumentLink>>> {
+        Ok(capabilities::document_link::get_document_links(
+            self.session.clone(),
+            params,
+        ))
+    }
+
     async fn semantic_tokens_full(
         &self,
         params: lsp::SemanticTokensParams,
@@ -195,6 +320,72 @@ impl LanguageServer for Backend {
         ))
     }
 
+    async fn references(
+        &self,
+        params: lsp::ReferenceParams,
+    ) -> jsonrpc::Result<Option<Vec<lsp::Location>>> {
+        Ok(capabilities::references::get_references(
+            self.session.clone(),
+            params,
+        ))
+    }
+
+    async fn request_else(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> jsonrpc::Result<Option<serde_json::Value>> {
+        match method {
+            capabilities::inlay_hints::INLAY_HINT_METHOD => {
+                let params = params
+                    .and_then(|params| serde_json::from_value(params).ok())
+                    .ok_or_else(|| jsonrpc::Error::invalid_params("expected InlayHintParams"))?;
+                let hints =
+                    capabilities::inlay_hints::get_inlay_hints(self.session.clone(), params);
+                Ok(hints.map(|hints| serde_json::json!(hints)))
+            }
+            _ => Err(jsonrpc::Error::method_not_found()),
+        }
+    }
+
+    async fn code_action(
+        &self,
+        params: lsp::CodeActionParams,
+    ) -> jsonrpc::Result<Option<lsp::CodeActionResponse>> {
+        Ok(capabilities::code_actions::get_code_actions(
+            self.session.clone(),
+            params,
+        ))
+    }
+
+    async fn code_lens(
+        &self,
+        params: lsp::CodeLensParams,
+    ) -> jsonrpc::Result<Option<Vec<lsp::CodeLens>>> {
+        // counting the instructions of each method compiles the contract, which takes a while
+        let session = self.session.clone();
+        let code_lenses = tokio::task::spawn_blocking(move || {
+            capabilities::code_lens::get_code_lenses(session, params)
+        })
+        .await;
+        Ok(code_lenses.ok().flatten())
+    }
+
+    async fn execute_command(
+        &self,
+        params: lsp::ExecuteCommandParams,
+    ) -> jsonrpc::Result<Option<serde_json::Value>> {
+        match params.command.as_str() {
+            capabilities::code_lens::COPY_SELECTOR_COMMAND => {
+                capabilities::code_lens::copy_selector(&self.client, params.arguments)
+                    .await
+                    .map(Some)
+                    .ok_or_else(|| jsonrpc::Error::invalid_params("expected a selector"))
+            }
+            _ => Err(jsonrpc::Error::method_not_found()),
+        }
+    }
+
     async fn formatting(
         &self,
         params: lsp::DocumentFormattingParams,
@@ -1,3 +1 @@
-pub(crate) mod common;
-pub(crate) mod function;
 pub(crate) mod lsp_helpers;
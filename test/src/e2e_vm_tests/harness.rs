@@ -29,6 +29,7 @@ pub(crate) fn deploy_contract(file_name: &str) -> ContractId {
             print_ir: false,
             binary_outfile: None,
             debug_outfile: None,
+            release: false,
             offline_mode: false,
             silent_mode: !verbose,
         }))
@@ -60,12 +61,14 @@ pub(crate) fn runs_on_node(file_name: &str, contract_ids: &[fuel_tx::ContractId]
         use_ir,
         binary_outfile: None,
         debug_outfile: None,
+        release: false,
         print_finalized_asm: false,
         print_intermediate_asm: false,
         print_ir: false,
         silent_mode: !verbose,
         pretty_print: false,
         contract: Some(contracts),
+        preload_contracts: None,
     };
     tokio::runtime::Runtime::new()
         .unwrap()
@@ -131,6 +134,7 @@ pub(crate) fn compile_to_bytes(file_name: &str) -> Result<Vec<u8>, String> {
         print_ir: false,
         binary_outfile: None,
         debug_outfile: None,
+        release: false,
         offline_mode: false,
         silent_mode: !verbose,
     })
@@ -84,6 +84,15 @@ pub fn run(filter_regex: Option<regex::Regex>) {
         ("funcs_with_generic_types", ProgramState::Return(1)), // true
         ("supertraits_1", ProgramState::Return(1)),
         ("supertraits_2", ProgramState::Return(1)),
+        ("type_alias", ProgramState::Return(1)), // true
+        ("tuple_structs", ProgramState::Return(1)), // true
+        ("struct_destructuring", ProgramState::Return(1)), // true
+        ("vec", ProgramState::Return(1)), // true
+        ("vec_get_out_of_bounds", ProgramState::Revert(1)),
+        ("user_defined_vec", ProgramState::Return(1)), // true
+        ("arithmetic_overflow", ProgramState::Revert(1)),
+        ("string", ProgramState::Return(1)), // true
+        ("string_abi_impl", ProgramState::Revert(0)),
     ];
 
     let mut number_of_tests_run = positive_project_names.iter().fold(0, |acc, (name, res)| {
@@ -117,6 +126,7 @@ pub fn run(filter_regex: Option<regex::Regex>) {
         "name_shadowing",
         "match_expressions_wrong_struct",
         "match_expressions_enums",
+        "match_expressions_tuple_struct_arity",
         "pure_calls_impure",
         "nested_impure",
         "predicate_calls_impure",
@@ -130,6 +140,12 @@ pub fn run(filter_regex: Option<regex::Regex>) {
         "supertrait_dup_methods_2",
         "missing_supertrait",
         "missing_supertrait_impl",
+        "let_refutable_pattern",
+        "let_tuple_struct_arity",
+        "recursive_type_alias",
+        "type_alias_type_arguments",
+        "vec_push_to_immutable",
+        "string_from_non_str",
     ];
     number_of_tests_run += negative_project_names.iter().fold(0, |acc, name| {
         if filter(name) {
@@ -150,6 +166,7 @@ pub fn run(filter_regex: Option<regex::Regex>) {
         ("contract_abi_impl", "contract_call"),
         ("balance_test_contract", "bal_opcode"),
         ("test_fuel_coin_contract", "token_ops_test"),
+        ("string_abi_impl", "string_contract_call"),
     ];
 
     let total_number_of_tests = positive_project_names.len()
@@ -0,0 +1,7 @@
+script;
+
+// arithmetic is checked by default, so this reverts instead of wrapping around to 0.
+fn main() -> u64 {
+    let max = 18446744073709551615;
+    max + 1
+}
@@ -0,0 +1,11 @@
+script;
+
+enum Shape {
+    Square: u64,
+    Circle: u64,
+}
+
+fn main() -> u64 {
+    let Shape::Square(side) = Shape::Square(5);
+    side
+}
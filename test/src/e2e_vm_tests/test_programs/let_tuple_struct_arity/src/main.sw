@@ -0,0 +1,9 @@
+script;
+
+struct Pair(u64, bool);
+
+fn main() -> u64 {
+    let Pair(a) = Pair(7, true);
+    let Pair(b, c, d) = Pair(7, true);
+    a + b
+}
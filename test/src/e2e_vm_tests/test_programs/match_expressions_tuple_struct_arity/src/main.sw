@@ -0,0 +1,16 @@
+script;
+
+struct Pair(u64, bool);
+
+fn main() -> u64 {
+    let p = Pair(7, true);
+    let x = match p {
+        Pair(a) => { a },
+        _ => { 0 },
+    };
+    let y = match p {
+        Pair(a, true, c) => { a },
+        _ => { 0 },
+    };
+    x + y
+}
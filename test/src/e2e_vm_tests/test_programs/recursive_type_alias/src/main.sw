@@ -0,0 +1,15 @@
+script;
+
+// A -> A
+type A = A;
+
+// B -> C -> B
+type B = C;
+type C = B;
+
+// An alias can't contain itself either.
+type List = (u64, List);
+
+fn main() -> u64 {
+    0
+}
@@ -0,0 +1,25 @@
+script;
+
+fn first_byte(s: String) -> u8 {
+    s.byte_at(0)
+}
+
+fn main() -> bool {
+    let empty = ~String::new();
+    let short = ~String::from("fuel");
+    let long = ~String::from("a longer string!");
+    let joined = short.concat(long);
+    let joined_again = ~String::from("fuela longer string!");
+
+    // Concatenating with an empty string must not change anything.
+    let unchanged = empty.concat(short);
+
+    empty.len() == 0 && short.len() == 4 && long.len() == 16 && joined.len() == 20
+        && first_byte(short) == 102 && short.byte_at(3) == 108 && long.byte_at(2) == 108
+        && long.byte_at(15) == 33
+        // Bytes on either side of the boundary between the two strings and between words.
+        && joined.byte_at(3) == 108 && joined.byte_at(4) == 97 && joined.byte_at(5) == 32
+        && joined.byte_at(7) == 111 && joined.byte_at(8) == 110 && joined.byte_at(19) == 33
+        && joined == joined_again && joined != long && unchanged == short
+        && empty == ~String::new()
+}
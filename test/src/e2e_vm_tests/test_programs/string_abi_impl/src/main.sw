@@ -0,0 +1,33 @@
+contract;
+// this file tests passing heap types to and returning them from a contract
+
+abi StringContract {
+    fn greet(gas: u64, coin: u64, asset_id: b256, name: String) -> String;
+    fn lengths(gas: u64, coin: u64, asset_id: b256, words: Vec<String>) -> Vec<u64>;
+    fn repeat(gas: u64, coin: u64, asset_id: b256, word: String) -> Vec<String>;
+}
+
+impl StringContract for Contract {
+    fn greet(gas: u64, coin: u64, asset_id: b256, name: String) -> String {
+        let greeting = ~String::from("hello, ");
+        greeting.concat(name)
+    }
+
+    fn lengths(gas: u64, coin: u64, asset_id: b256, words: Vec<String>) -> Vec<u64> {
+        let mut lengths = ~Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            let word = words.get(i);
+            lengths.push(word.len());
+            i = i + 1;
+        }
+        lengths
+    }
+
+    fn repeat(gas: u64, coin: u64, asset_id: b256, word: String) -> Vec<String> {
+        let mut words = ~Vec::new();
+        words.push(word);
+        words.push(word.concat(word));
+        words
+    }
+}
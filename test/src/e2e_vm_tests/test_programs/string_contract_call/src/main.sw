@@ -0,0 +1,31 @@
+script;
+// this file tests passing heap types to and returning them from a contract call
+
+abi StringContract {
+    fn greet(gas: u64, coin: u64, asset_id: b256, name: String) -> String;
+    fn lengths(gas: u64, coin: u64, asset_id: b256, words: Vec<String>) -> Vec<u64>;
+    fn repeat(gas: u64, coin: u64, asset_id: b256, word: String) -> Vec<String>;
+}
+
+fn main() -> bool {
+    let caller = abi(StringContract, 0xaabba3de9513405a0eb74c3f6d7392ea3cda6f0caabed93a2572141ae6c9bb93);
+    let asset_id = 0x0000000000000000000000000000000000000000000000000000000000000000;
+
+    let greeting = caller.greet(10000, 0, asset_id, ~String::from("fuel"));
+
+    let mut words = ~Vec::new();
+    words.push(~String::from("a"));
+    words.push(~String::from("longer than a word"));
+    words.push(~String::new());
+    let mut lengths = caller.lengths(10000, 0, asset_id, words);
+    // The returned vector is in the heap of the script, so it can be pushed to.
+    lengths.push(7);
+
+    let repeated = caller.repeat(10000, 0, asset_id, ~String::from("ab"));
+    let first = repeated.get(0);
+    let second = repeated.get(1);
+
+    greeting == ~String::from("hello, fuel") && lengths.len() == 4 && lengths.get(0) == 1
+        && lengths.get(1) == 18 && lengths.get(2) == 0 && lengths.get(3) == 7
+        && repeated.len() == 2 && first == ~String::from("ab") && second == ~String::from("abab")
+}
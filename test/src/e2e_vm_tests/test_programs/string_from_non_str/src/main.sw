@@ -0,0 +1,6 @@
+script;
+
+fn main() -> u64 {
+    let s = ~String::from(42);
+    s.len()
+}
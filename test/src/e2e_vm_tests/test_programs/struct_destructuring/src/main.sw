@@ -0,0 +1,23 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+struct Line {
+    start: Point,
+    end: Point,
+}
+
+struct Meters(u64);
+
+fn main() -> bool {
+    let p = Point { x: 1, y: 2 };
+    let Point { x, y: py } = p;
+    let (Point { x: ax, y: ay }, n) = (Point { x: 3, y: 4 }, 5);
+    let Meters(m) = Meters(6);
+    let l = Line { start: Point { x: 7, y: 8 }, end: Point { x: 9, y: 10 } };
+    let Line { start: Point { x: sx, y: sy }, end } = l;
+    x == 1 && py == 2 && ax == 3 && ay == 4 && n == 5 && m == 6 && sx == 7 && sy == 8 && end.y == 10
+}
@@ -0,0 +1,25 @@
+script;
+
+struct Meters(u64);
+struct Pair(u64, bool);
+struct Marker;
+
+fn add(a: Meters, b: Meters) -> Meters {
+    Meters(a.0 + b.0)
+}
+
+fn is_marker(m: Marker) -> bool {
+    true
+}
+
+fn main() -> bool {
+    let m = Meters(40);
+    let n = add(m, Meters(2));
+    let p = Pair(7, true);
+    let x = match p {
+        Pair(a, true) => { a },
+        _ => { 0 },
+    };
+    let marker = Marker;
+    n.0 == 42 && x == 7 && p.1 && is_marker(marker)
+}
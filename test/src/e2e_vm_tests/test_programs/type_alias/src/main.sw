@@ -0,0 +1,32 @@
+script;
+
+dep types;
+
+use types::{Inner, Key};
+
+type Balances = [u64; 4];
+type Entry = (Count, u64, b256);
+type Pair<T> = (T, T);
+type Count = u64;
+
+struct Wrapper {
+    pair: Pair<u64>,
+    count: Count,
+}
+
+fn total(balances: Balances) -> Count {
+    balances[0] + balances[1] + balances[2] + balances[3]
+}
+
+fn sum(p: Pair<u64>) -> u64 {
+    p.0 + p.1
+}
+
+fn main() -> bool {
+    let b: Balances = [1, 2, 3, 4];
+    let e: Entry = (5, 6, 0x0000000000000000000000000000000000000000000000000000000000000000);
+    let k: Key = Inner { id: 7 };
+    let p: Pair<u64> = (9, 10);
+    let w = Wrapper { pair: p, count: 8 };
+    total(b) == 10 && e.0 + e.1 == 11 && k.id == 7 && w.count == 8 && sum(p) == 19
+}
@@ -0,0 +1,7 @@
+library types;
+
+pub struct Inner {
+    id: u64,
+}
+
+pub type Key = Inner;
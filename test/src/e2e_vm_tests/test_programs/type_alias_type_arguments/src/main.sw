@@ -0,0 +1,11 @@
+script;
+
+type Pair<T> = (T, T);
+type Count = u64;
+
+fn main() -> u64 {
+    let too_many: Pair<u64, u64> = (1, 2);
+    let too_few: Pair = (1, 2);
+    let unexpected: Count<u64> = 3;
+    0
+}
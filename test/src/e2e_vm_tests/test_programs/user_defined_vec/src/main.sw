@@ -0,0 +1,25 @@
+script;
+
+// A declaration named `Vec` takes the place of the built in vector type.
+struct Vec {
+    first: u64,
+    second: u64,
+}
+
+impl Vec {
+    fn new(first: u64, second: u64) -> Vec {
+        Vec {
+            first: first,
+            second: second,
+        }
+    }
+
+    fn sum(self) -> u64 {
+        self.first + self.second
+    }
+}
+
+fn main() -> bool {
+    let v: Vec = ~Vec::new(40, 2);
+    v.sum() == 42
+}
@@ -0,0 +1,66 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn last_of(v: Vec<u64>) -> u64 {
+    v.get(v.len() - 1)
+}
+
+fn b256_eq(a: b256, b: b256) -> bool {
+    asm(r1: a, r2: b, r3, r4: 32) {
+        meq r3 r1 r2 r4;
+        r3: bool
+    }
+}
+
+fn main() -> bool {
+    // Push enough to make the vector grow past its initial capacity a couple of times.
+    let mut numbers: Vec<u64> = ~Vec::new();
+    let mut i = 0;
+    while i < 10 {
+        numbers.push(i);
+        i = i + 1;
+    }
+    let last = numbers.pop();
+
+    let mut sum = 0;
+    i = 0;
+    while i < numbers.len() {
+        sum = sum + numbers.get(i);
+        i = i + 1;
+    }
+
+    let mut points = ~Vec::new();
+    points.push(Point { x: 1, y: 2 });
+    points.push(Point { x: 3, y: 4 });
+    let p = points.get(1);
+
+    let mut hashes: Vec<b256> = ~Vec::new();
+    hashes.push(0x0000000000000000000000000000000000000000000000000000000000000001);
+
+    let mut flags = ~Vec::new();
+    flags.push(true);
+
+    let mut total = 0;
+    for n in numbers {
+        total = total + n;
+    }
+    let mut xs = 0;
+    for point in points {
+        for n in numbers {
+            xs = xs + point.x;
+        }
+    }
+    let empty: Vec<u64> = ~Vec::new();
+    for n in empty {
+        total = total + 100;
+    }
+
+    last == 9 && numbers.len() == 9 && sum == 36 && last_of(numbers) == 8
+        && p.x == 3 && p.y == 4 && points.len() == 2
+        && b256_eq(hashes.get(0), 0x0000000000000000000000000000000000000000000000000000000000000001)
+        && flags.get(0) && total == 36 && xs == 36
+}
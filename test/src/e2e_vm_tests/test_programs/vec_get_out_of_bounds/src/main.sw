@@ -0,0 +1,7 @@
+script;
+
+fn main() -> u64 {
+    let mut v: Vec<u64> = ~Vec::new();
+    v.push(1);
+    v.get(1)
+}